@@ -0,0 +1,55 @@
+//! Benchmarks of the hot paths of the conversions, run with `cargo bench --bench hot_paths`.
+//!
+//! 変換の主要な経路のベンチマーク。`cargo bench --bench hot_paths`で実行する。
+
+use std::hint::black_box;
+
+use coordinate_transformer::{jpr2ll, ll2jpr, ll2pixel, llz2xyz, pixel2ll, pixel_resolution, JprOrigin, ZoomLv};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn points() -> Vec<(f64, f64)> {
+    (0..1000)
+        .map(|i| {
+            let t = i as f64 / 1000.;
+            ((138. + 4. * t).to_radians(), (34. + 4. * t).to_radians())
+        })
+        .collect()
+}
+
+fn bench_jpr(c: &mut Criterion) {
+    let points = points();
+    let jpr = points.iter().map(|&ll| ll2jpr(ll, JprOrigin::Nine)).collect::<Vec<_>>();
+
+    c.bench_function("ll2jpr", |b| {
+        b.iter(|| points.iter().map(|&ll| ll2jpr(black_box(ll), JprOrigin::Nine)).collect::<Vec<_>>())
+    });
+    c.bench_function("jpr2ll", |b| {
+        b.iter(|| jpr.iter().map(|&yx| jpr2ll(black_box(yx), JprOrigin::Nine)).collect::<Vec<_>>())
+    });
+}
+
+fn bench_pixel(c: &mut Criterion) {
+    let points = points();
+    let pixels = points.iter().map(|&ll| ll2pixel(ll, ZoomLv::Lv17)).collect::<Vec<_>>();
+
+    c.bench_function("ll2pixel", |b| {
+        b.iter(|| points.iter().map(|&ll| ll2pixel(black_box(ll), ZoomLv::Lv17)).collect::<Vec<_>>())
+    });
+    c.bench_function("pixel2ll", |b| {
+        b.iter(|| pixels.iter().map(|&p| pixel2ll(black_box(p), ZoomLv::Lv17)).collect::<Vec<_>>())
+    });
+    c.bench_function("pixel_resolution", |b| {
+        b.iter(|| points.iter().map(|&(_, lat)| pixel_resolution(black_box(lat), ZoomLv::Lv17)).collect::<Vec<_>>())
+    });
+}
+
+fn bench_xyz(c: &mut Criterion) {
+    let points = points();
+
+    c.bench_function("llz2xyz", |b| {
+        b.iter(|| points.iter().map(|&ll| llz2xyz(black_box(ll), 50.)).collect::<Vec<_>>())
+    });
+}
+
+criterion_group!(benches, bench_jpr, bench_pixel, bench_xyz);
+criterion_main!(benches);
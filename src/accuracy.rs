@@ -0,0 +1,167 @@
+//! Selection of the trade-off between speed and accuracy
+//!
+//! 速度と精度の兼ね合いの選択
+
+use std::sync::OnceLock;
+
+use crate::fast::FastMercator;
+use crate::math;
+use crate::{jpr2ll, ll2jpr, ll2pixel, pixel_resolution, JprOrigin, ZoomLv};
+
+/// Enumerated type representing the accuracy of a conversion.
+/// Where a conversion has no implementation for an accuracy, the next more accurate one is used.
+///
+/// 変換の精度を表す列挙型。
+/// ある精度の実装を持たない変換では、次に精度の高いものを用いる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Accuracy {
+    /// Approximation with lookup tables (see `FastMercator`), for real-time rendering
+    ///
+    /// ルックアップテーブルによる近似(`FastMercator`を参照)。リアルタイム描画向け
+    Fast,
+    /// The functions of this crate as they are
+    ///
+    /// このクレートの関数そのまま
+    #[default]
+    Standard,
+    /// Refines the inverse conversions iteratively so that they are consistent with the forward conversions, for survey-grade processing
+    ///
+    /// 逆変換を反復的に改良して順変換と整合させる。測量向け
+    Precise,
+}
+
+fn fast_mercator() -> &'static FastMercator {
+    static FAST_MERCATOR: OnceLock<FastMercator> = OnceLock::new();
+    FAST_MERCATOR.get_or_init(|| {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("building the lookup table of FastMercator");
+        FastMercator::new()
+    })
+}
+
+/// Same as `ll2pixel` with the accuracy selected.
+/// `Fast` uses a lookup table built on the first call and shared afterwards.
+///
+/// 精度を選択できる`ll2pixel`。
+/// `Fast`では最初の呼び出しで作成し、以後共有するルックアップテーブルを用いる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::accuracy::{ll2pixel_with_accuracy, Accuracy};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let (x, y) = ll2pixel_with_accuracy(ll, ZoomLv::Lv17, Accuracy::Fast);
+/// ```
+pub fn ll2pixel_with_accuracy(ll: (f64, f64), zoom: ZoomLv, accuracy: Accuracy) -> (u32, u32) {
+    match accuracy {
+        Accuracy::Fast => fast_mercator().ll2pixel(ll, zoom),
+        Accuracy::Standard | Accuracy::Precise => ll2pixel(ll, zoom),
+    }
+}
+
+/// Same as `pixel_resolution` with the accuracy selected.
+///
+/// 精度を選択できる`pixel_resolution`。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::accuracy::{pixel_resolution_with_accuracy, Accuracy};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let resolution = pixel_resolution_with_accuracy(36_f64.to_radians(), ZoomLv::Lv17, Accuracy::Fast);
+/// ```
+pub fn pixel_resolution_with_accuracy(lat: f64, zoom: ZoomLv, accuracy: Accuracy) -> f64 {
+    match accuracy {
+        Accuracy::Fast => fast_mercator().pixel_resolution(lat, zoom),
+        Accuracy::Standard | Accuracy::Precise => pixel_resolution(lat, zoom),
+    }
+}
+
+/// Same as `jpr2ll` with the accuracy selected.
+/// `Precise` refines the result with Newton's method so that converting it back with `ll2jpr` reproduces the input within 1e-9 m.
+/// Within the zones the series alone is already consistent to about 1e-9 m, so this matters mainly far from the central meridian.
+///
+/// 精度を選択できる`jpr2ll`。
+/// `Precise`では、結果を`ll2jpr`で戻したときに入力を1e-9m以内で再現するようにニュートン法で改良する。
+/// 適用区域内では級数のみで既に約1e-9mの整合性があるため、主に中央子午線から遠い場合に意味を持つ。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::accuracy::{jpr2ll_with_accuracy, Accuracy};
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let (long, lat) = jpr2ll_with_accuracy((22694.980, 11573.375), JprOrigin::Nine, Accuracy::Precise);
+/// ```
+pub fn jpr2ll_with_accuracy(yx: (f64, f64), origin: JprOrigin, accuracy: Accuracy) -> (f64, f64) {
+    let ll = jpr2ll(yx, origin);
+
+    match accuracy {
+        Accuracy::Fast | Accuracy::Standard => ll,
+        Accuracy::Precise => refine_jpr2ll(yx, ll, origin),
+    }
+}
+
+/// Refines (longitude, latitude) with Newton's method using a numerical Jacobian of `ll2jpr`
+///
+/// `ll2jpr`の数値的なヤコビ行列を用いたニュートン法で(経度, 緯度)を改良する
+fn refine_jpr2ll(yx: (f64, f64), ll: (f64, f64), origin: JprOrigin) -> (f64, f64) {
+    const H: f64 = 1e-7; // 数値微分の刻み(rad, 約0.6m)
+
+    let (mut long, mut lat) = ll;
+
+    for _ in 0..3 {
+        let (y, x) = ll2jpr((long, lat), origin);
+        let (dy, dx) = (yx.0 - y, yx.1 - x);
+
+        if math::hypot(dy, dx) < 1e-9 {
+            break;
+        }
+
+        let (y_l1, x_l1) = ll2jpr((long + H, lat), origin);
+        let (y_l0, x_l0) = ll2jpr((long - H, lat), origin);
+        let (y_p1, x_p1) = ll2jpr((long, lat + H), origin);
+        let (y_p0, x_p0) = ll2jpr((long, lat - H), origin);
+
+        // ヤコビ行列 [[dy/dλ, dy/dφ], [dx/dλ, dx/dφ]]
+        let j11 = (y_l1 - y_l0) / (2. * H);
+        let j12 = (y_p1 - y_p0) / (2. * H);
+        let j21 = (x_l1 - x_l0) / (2. * H);
+        let j22 = (x_p1 - x_p0) / (2. * H);
+        let det = j11 * j22 - j12 * j21;
+
+        long += (j22 * dy - j12 * dx) / det;
+        lat += (-j21 * dy + j11 * dx) / det;
+    }
+
+    (long, lat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precise_jpr2ll_is_consistent_with_ll2jpr() {
+        // 級数の打ち切り誤差が大きくなる、中央子午線から遠い点
+        let yx = (2500000., 0.);
+
+        let (y, x) = ll2jpr(jpr2ll_with_accuracy(yx, JprOrigin::Nine, Accuracy::Standard), JprOrigin::Nine);
+        assert!((y - yx.0).hypot(x - yx.1) > 1e-9);
+
+        let (y, x) = ll2jpr(jpr2ll_with_accuracy(yx, JprOrigin::Nine, Accuracy::Precise), JprOrigin::Nine);
+        assert!((y - yx.0).hypot(x - yx.1) < 1e-9);
+    }
+
+    #[test]
+    fn fast_ll2pixel_is_close() {
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        let (x, y) = ll2pixel_with_accuracy(ll, ZoomLv::Lv21, Accuracy::Fast);
+        let (ex, ey) = ll2pixel_with_accuracy(ll, ZoomLv::Lv21, Accuracy::Standard);
+
+        assert!(x.abs_diff(ex) <= 1 && y.abs_diff(ey) <= 1);
+    }
+}
@@ -0,0 +1,257 @@
+//! Conversion of Arrow `Float64Array` columns, e.g. in DataFusion or Polars pipelines.
+//! The input columns are read in place without copying, and each conversion produces new columns.
+//! Longitude and latitude are expressed in the arc degree method, as in the rest of this crate. A row is null in the output if it is null in any input column.
+//!
+//! DataFusionやPolarsのパイプラインなどにおけるArrowの`Float64Array`の列の変換。
+//! 入力の列は複製せずにそのまま読み込まれ、各変換は新しい列を生成する。
+//! 経度と緯度は、このクレートの他の部分と同様に弧度法で表す。いずれかの入力の列でnullである行は、出力でもnullとなる。
+
+use std::fmt::{Display, Formatter};
+
+use arrow_array::{Array, Float64Array};
+use arrow_buffer::NullBuffer;
+
+use crate::jpr_ll::{jpr2ll, ll2jpr, JprOrigin};
+use crate::xyz_ll::{llz2xyz, xyz2llz};
+
+/// Error that occurs while converting Arrow columns
+///
+/// Arrowの列の変換中に発生するエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrowInteropError {
+    /// The input columns have different lengths
+    ///
+    /// 入力の列の長さが異なる
+    LengthMismatch {
+        /// Length of the first column
+        ///
+        /// 最初の列の長さ
+        expected: usize,
+        /// Length of the mismatching column
+        ///
+        /// 長さが一致しない列の長さ
+        actual: usize,
+    },
+}
+
+impl Display for ArrowInteropError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrowInteropError::LengthMismatch { expected, actual } => {
+                write!(f, "column length mismatch: expected {}, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArrowInteropError {}
+
+/// Checks that all columns have the same length and returns the union of their null buffers
+///
+/// 全ての列が同じ長さであることを確認し、それらのnullのバッファの和を返す
+fn check_columns(columns: &[&Float64Array]) -> Result<Option<NullBuffer>, ArrowInteropError> {
+    let expected = columns[0].len();
+
+    columns.iter().try_fold(None, |nulls, column| {
+        if column.len() != expected {
+            return Err(ArrowInteropError::LengthMismatch {
+                expected,
+                actual: column.len(),
+            });
+        }
+        Ok(NullBuffer::union(nulls.as_ref(), column.nulls()))
+    })
+}
+
+/// Returns whether the row is null.
+/// The values of a null row are unspecified (usually 0) and must not be passed to the scalar functions, some of which do not terminate on such input.
+///
+/// 行がnullかどうかを返す。
+/// nullの行の値は不定(通常は0)であり、そのような入力で終了しないものもあるため、スカラーの関数に渡してはならない。
+fn is_null(nulls: &Option<NullBuffer>, i: usize) -> bool {
+    nulls.as_ref().is_some_and(|nulls| nulls.is_null(i))
+}
+
+/// Function to convert columns of longitude and latitude expressed in the arc degree method to columns (y, x) of plane rectangular coordinates, following `ll2jpr`
+///
+/// `ll2jpr`に従って、弧度法で表された経度と緯度の列を平面直角座標の列(y, x)に変換する関数
+///
+/// # Examples
+///
+/// ```
+/// use arrow_array::Float64Array;
+/// use coordinate_transformer::arrow_interop::ll2jpr_arrow;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let long = Float64Array::from(vec![139.7649308_f64.to_radians()]);
+/// let lat = Float64Array::from(vec![35.6812405_f64.to_radians()]);
+///
+/// let (y, x) = ll2jpr_arrow(&long, &lat, JprOrigin::Nine).unwrap();
+/// ```
+pub fn ll2jpr_arrow(
+    long: &Float64Array,
+    lat: &Float64Array,
+    origin: JprOrigin,
+) -> Result<(Float64Array, Float64Array), ArrowInteropError> {
+    let nulls = check_columns(&[long, lat])?;
+
+    let (y, x): (Vec<_>, Vec<_>) = long
+        .values()
+        .iter()
+        .zip(lat.values().iter())
+        .enumerate()
+        .map(|(i, (&long, &lat))| if is_null(&nulls, i) { (0., 0.) } else { ll2jpr((long, lat), origin) })
+        .unzip();
+
+    Ok((
+        Float64Array::new(y.into(), nulls.clone()),
+        Float64Array::new(x.into(), nulls),
+    ))
+}
+
+/// Function to convert columns (y, x) of plane rectangular coordinates to columns of longitude and latitude expressed in the arc degree method, following `jpr2ll`
+///
+/// `jpr2ll`に従って、平面直角座標の列(y, x)を弧度法で表された経度と緯度の列に変換する関数
+pub fn jpr2ll_arrow(
+    y: &Float64Array,
+    x: &Float64Array,
+    origin: JprOrigin,
+) -> Result<(Float64Array, Float64Array), ArrowInteropError> {
+    let nulls = check_columns(&[y, x])?;
+
+    let (long, lat): (Vec<_>, Vec<_>) = y
+        .values()
+        .iter()
+        .zip(x.values().iter())
+        .enumerate()
+        .map(|(i, (&y, &x))| if is_null(&nulls, i) { (0., 0.) } else { jpr2ll((y, x), origin) })
+        .unzip();
+
+    Ok((
+        Float64Array::new(long.into(), nulls.clone()),
+        Float64Array::new(lat.into(), nulls),
+    ))
+}
+
+/// Function to convert columns of longitude and latitude expressed in the arc degree method and height to columns (x, y, z) of the Cartesian coordinate system (EPSG:4979), following `llz2xyz`
+///
+/// `llz2xyz`に従って、弧度法で表された経度と緯度、高さの列を直交座標系(EPSG:4979)の列(x, y, z)に変換する関数
+///
+/// # Examples
+///
+/// ```
+/// use arrow_array::{Array, Float64Array};
+/// use coordinate_transformer::arrow_interop::llz2xyz_arrow;
+///
+/// let long = Float64Array::from(vec![Some(140_f64.to_radians()), None]);
+/// let lat = Float64Array::from(vec![36_f64.to_radians(), 36_f64.to_radians()]);
+/// let height = Float64Array::from(vec![100., 100.]);
+///
+/// let (x, y, z) = llz2xyz_arrow(&long, &lat, &height).unwrap();
+///
+/// assert!(x.is_valid(0) && x.is_null(1));
+/// ```
+pub fn llz2xyz_arrow(
+    long: &Float64Array,
+    lat: &Float64Array,
+    height: &Float64Array,
+) -> Result<(Float64Array, Float64Array, Float64Array), ArrowInteropError> {
+    let nulls = check_columns(&[long, lat, height])?;
+    let len = long.len();
+    let (mut xs, mut ys, mut zs) = (Vec::with_capacity(len), Vec::with_capacity(len), Vec::with_capacity(len));
+
+    let rows = long.values().iter().zip(lat.values().iter()).zip(height.values().iter());
+    for (i, ((&long, &lat), &height)) in rows.enumerate() {
+        let (x, y, z) = if is_null(&nulls, i) { (0., 0., 0.) } else { llz2xyz((long, lat), height) };
+        xs.push(x);
+        ys.push(y);
+        zs.push(z);
+    }
+
+    Ok((
+        Float64Array::new(xs.into(), nulls.clone()),
+        Float64Array::new(ys.into(), nulls.clone()),
+        Float64Array::new(zs.into(), nulls),
+    ))
+}
+
+/// Function to convert columns (x, y, z) of the Cartesian coordinate system (EPSG:4979) to columns of longitude and latitude expressed in the arc degree method and height, following `xyz2llz`
+///
+/// `xyz2llz`に従って、直交座標系(EPSG:4979)の列(x, y, z)を弧度法で表された経度と緯度、高さの列に変換する関数
+pub fn xyz2llz_arrow(
+    x: &Float64Array,
+    y: &Float64Array,
+    z: &Float64Array,
+) -> Result<(Float64Array, Float64Array, Float64Array), ArrowInteropError> {
+    let nulls = check_columns(&[x, y, z])?;
+    let len = x.len();
+    let (mut longs, mut lats, mut heights) = (Vec::with_capacity(len), Vec::with_capacity(len), Vec::with_capacity(len));
+
+    let rows = x.values().iter().zip(y.values().iter()).zip(z.values().iter());
+    for (i, ((&x, &y), &z)) in rows.enumerate() {
+        // nullの行の値は(0, 0, 0)となっていることが多く、xyz2llzの反復が収束しないため変換しない
+        let ((long, lat), height) = if is_null(&nulls, i) { ((0., 0.), 0.) } else { xyz2llz((x, y, z)) };
+        longs.push(long);
+        lats.push(lat);
+        heights.push(height);
+    }
+
+    Ok((
+        Float64Array::new(longs.into(), nulls.clone()),
+        Float64Array::new(lats.into(), nulls.clone()),
+        Float64Array::new(heights.into(), nulls),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrow_conversions_match_scalar_functions() {
+        let long = Float64Array::from(vec![139.7649308_f64.to_radians(), 140_f64.to_radians()]);
+        let lat = Float64Array::from(vec![Some(35.6812405_f64.to_radians()), None]);
+
+        let (y, x) = ll2jpr_arrow(&long, &lat, JprOrigin::Nine).unwrap();
+        assert_eq!((y.value(0), x.value(0)), ll2jpr((long.value(0), lat.value(0)), JprOrigin::Nine));
+        assert!(y.is_null(1) && x.is_null(1));
+
+        let (long2, lat2) = jpr2ll_arrow(&y, &x, JprOrigin::Nine).unwrap();
+        assert_eq!((long2.value(0), lat2.value(0)), jpr2ll((y.value(0), x.value(0)), JprOrigin::Nine));
+        assert_eq!(long2.null_count(), 1);
+
+        let height = Float64Array::from(vec![10., 20.]);
+        let (x, y, z) = llz2xyz_arrow(&long, &lat, &height).unwrap();
+        let (_, _, height2) = xyz2llz_arrow(&x, &y, &z).unwrap();
+        assert!((height2.value(0) - 10.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn null_rows_are_not_converted() {
+        // 全ての列でnullの行は値のバッファに(0, 0, 0)を持つ
+        let x = Float64Array::from(vec![Some(-3957446.631), None]);
+        let y = Float64Array::from(vec![Some(3320692.008), None]);
+        let z = Float64Array::from(vec![Some(3728250.454), None]);
+
+        let (long, lat, height) = xyz2llz_arrow(&x, &y, &z).unwrap();
+        assert!(long.is_valid(0) && long.is_null(1) && lat.is_null(1) && height.is_null(1));
+
+        let (x, y, z) = llz2xyz_arrow(&long, &lat, &height).unwrap();
+        assert!(x.is_null(1) && y.is_null(1) && z.is_null(1));
+
+        let (y, x) = ll2jpr_arrow(&long, &lat, JprOrigin::Nine).unwrap();
+        let (long, _) = jpr2ll_arrow(&y, &x, JprOrigin::Nine).unwrap();
+        assert!(long.is_valid(0) && long.is_null(1));
+    }
+
+    #[test]
+    fn length_mismatch_is_rejected() {
+        let long = Float64Array::from(vec![0., 0.]);
+        let lat = Float64Array::from(vec![0.]);
+
+        assert_eq!(
+            ll2jpr_arrow(&long, &lat, JprOrigin::Nine),
+            Err(ArrowInteropError::LengthMismatch { expected: 2, actual: 1 })
+        );
+    }
+}
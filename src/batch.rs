@@ -0,0 +1,546 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::pixel_ll::check_pixel_domain;
+use crate::{jpr2ll, ll2jpr, ll2pixel, pixel2ll, JprOrigin, ZoomLv};
+
+/// Structure representing statistics accumulated during a batch conversion
+///
+/// 一括変換中に集計した統計を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct BatchReport {
+    /// Number of converted points
+    ///
+    /// 変換した点の数
+    pub count: usize,
+    /// Range ((minimum, minimum), (maximum, maximum)) of the finite input coordinates
+    ///
+    /// 有限な入力座標の範囲((最小, 最小), (最大, 最大))
+    pub input_bounds: Option<((f64, f64), (f64, f64))>,
+    /// Range ((minimum, minimum), (maximum, maximum)) of the finite output coordinates
+    ///
+    /// 有限な出力座標の範囲((最小, 最小), (最大, 最大))
+    pub output_bounds: Option<((f64, f64), (f64, f64))>,
+    /// Number of points outside the domain of the conversion
+    ///
+    /// 変換の定義域外にある点の数
+    pub out_of_domain: usize,
+    /// Maximum round-trip error among the sampled points, in the units of the plane or pixel coordinates
+    ///
+    /// 抽出した点における往復変換誤差の最大値(平面直角座標またはピクセル座標の単位)
+    pub max_round_trip_error: f64,
+}
+
+fn extend(bounds: Option<((f64, f64), (f64, f64))>, p: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+    if !p.0.is_finite() || !p.1.is_finite() {
+        return bounds;
+    }
+
+    Some(match bounds {
+        Some(((min_a, min_b), (max_a, max_b))) => (
+            (min_a.min(p.0), min_b.min(p.1)),
+            (max_a.max(p.0), max_b.max(p.1)),
+        ),
+        None => (p, p),
+    })
+}
+
+fn is_sampled(i: usize, sample_step: usize) -> bool {
+    sample_step != 0 && i.is_multiple_of(sample_step)
+}
+
+/// Emits the counts of a finished batch conversion as a tracing event
+///
+/// 終了した一括変換の点の数をtracingのイベントとして出力する
+#[cfg(feature = "tracing")]
+fn trace_report(count: usize, out_of_domain: usize) {
+    tracing::debug!(count, out_of_domain, "batch conversion finished");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn trace_report(_: usize, _: usize) {}
+
+/// Converts (longitude, latitude) expressed in the arc degree method to plane rectangular coordinates (y, x) in bulk.
+///
+/// 弧度法で表された(経度, 緯度)を平面直角座標(y, x)に一括変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::ll2jpr_batch;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let yx = ll2jpr_batch(&[(140_f64.to_radians(), 36_f64.to_radians())], JprOrigin::Nine);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn ll2jpr_batch(points: &[(f64, f64)], origin: JprOrigin) -> Vec<(f64, f64)> {
+    points.iter().map(|&ll| ll2jpr(ll, origin)).collect()
+}
+
+/// Same as `ll2jpr_batch`, and also returns a report of the conversion.
+/// Points outside the area of the origin are counted as out of domain, and every `sample_step`-th point (none if 0) is checked for round-trip error.
+///
+/// `ll2jpr_batch`と同じ変換を行い、変換の報告も返す。
+/// 原点の適用区域外の点を定義域外として数え、`sample_step`点ごと(0なら行わない)に往復変換誤差を確認する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::ll2jpr_batch_with_report;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let (yx, report) = ll2jpr_batch_with_report(&[(140_f64.to_radians(), 36_f64.to_radians())], JprOrigin::Nine, 100);
+/// assert_eq!(report.out_of_domain, 0);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn ll2jpr_batch_with_report(points: &[(f64, f64)], origin: JprOrigin, sample_step: usize) -> (Vec<(f64, f64)>, BatchReport) {
+    let mut report = BatchReport::default();
+
+    let result = points
+        .iter()
+        .enumerate()
+        .map(|(i, &ll)| {
+            let yx = ll2jpr(ll, origin);
+
+            report.count += 1;
+            report.input_bounds = extend(report.input_bounds, ll);
+            report.output_bounds = extend(report.output_bounds, yx);
+
+            if !origin.contains(ll) || !yx.0.is_finite() || !yx.1.is_finite() {
+                report.out_of_domain += 1;
+            } else if is_sampled(i, sample_step) {
+                let (y, x) = ll2jpr(jpr2ll(yx, origin), origin);
+                let error = ((y - yx.0).powi(2) + (x - yx.1).powi(2)).sqrt();
+                report.max_round_trip_error = report.max_round_trip_error.max(error);
+            }
+
+            yx
+        })
+        .collect();
+
+    trace_report(report.count, report.out_of_domain);
+
+    (result, report)
+}
+
+/// Structure representing statistics of a batch conversion with per-point origin selection
+///
+/// 点ごとに原点を選ぶ一括変換の統計を表す構造体
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AutoBatchReport {
+    /// Number of converted points
+    ///
+    /// 変換した点の数
+    pub count: usize,
+    /// Origin used for each point, in the order of the input
+    ///
+    /// 各点に用いた原点(入力の順)
+    pub zones: Vec<JprOrigin>,
+    /// Number of points converted with each origin
+    ///
+    /// 原点ごとの変換した点の数
+    pub zone_counts: BTreeMap<JprOrigin, usize>,
+    /// Number of points outside the areas of all origins, which are converted with the origin whose central meridian is nearest
+    ///
+    /// 全ての原点の適用区域外にある点の数。これらは中央子午線が最も近い原点で変換される
+    pub out_of_domain: usize,
+}
+
+/// Converts (longitude, latitude) expressed in the arc degree method to plane rectangular coordinates (y, x) in bulk, selecting the origin for each point with `JprOrigin::best_zone`.
+/// Returns the converted coordinates and a report with the origin used for each point and the counts per origin.
+///
+/// 弧度法で表された(経度, 緯度)を、点ごとに`JprOrigin::best_zone`で原点を選んで平面直角座標(y, x)に一括変換する。
+/// 変換した座標と、各点に用いた原点および原点ごとの点の数を含む報告を返す。
+///
+/// # Examples
+///
+/// Convert points in Tokyo and Fukuoka at once.
+///
+/// 東京と福岡の点を一度に変換する。
+///
+/// ```
+/// use coordinate_transformer::batch::ll2jpr_auto_batch;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let (yx, report) = ll2jpr_auto_batch(&[
+/// (139.7671_f64.to_radians(), 35.6812_f64.to_radians()),
+/// (130.4017_f64.to_radians(), 33.5902_f64.to_radians()),
+/// ]);
+///
+/// assert_eq!(report.zones, vec![JprOrigin::Nine, JprOrigin::Two]);
+/// assert_eq!(report.zone_counts[&JprOrigin::Nine], 1);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len())))]
+pub fn ll2jpr_auto_batch(points: &[(f64, f64)]) -> (Vec<(f64, f64)>, AutoBatchReport) {
+    let mut report = AutoBatchReport::default();
+    let all = (1..=19).filter_map(|num| JprOrigin::parse(num).ok()).collect::<Vec<_>>();
+
+    let result = points
+        .iter()
+        .map(|&ll| {
+            let origin = JprOrigin::best_zone(ll).unwrap_or_else(|| {
+                report.out_of_domain += 1;
+                JprOrigin::nearest(all.iter().copied(), ll).unwrap()
+            });
+
+            report.count += 1;
+            report.zones.push(origin);
+            *report.zone_counts.entry(origin).or_default() += 1;
+
+            ll2jpr(ll, origin)
+        })
+        .collect();
+
+    trace_report(report.count, report.out_of_domain);
+
+    (result, report)
+}
+
+/// Converts plane rectangular coordinates (y, x) to (longitude, latitude) expressed in the arc degree method in bulk.
+///
+/// 平面直角座標(y, x)を弧度法で表された(経度, 緯度)に一括変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::jpr2ll_batch;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let ll = jpr2ll_batch(&[(22694.980, 11573.375)], JprOrigin::Nine);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn jpr2ll_batch(points: &[(f64, f64)], origin: JprOrigin) -> Vec<(f64, f64)> {
+    points.iter().map(|&yx| jpr2ll(yx, origin)).collect()
+}
+
+/// Same as `jpr2ll_batch`, and also returns a report of the conversion.
+/// Points converted outside the area of the origin are counted as out of domain, and every `sample_step`-th point (none if 0) is checked for round-trip error.
+///
+/// `jpr2ll_batch`と同じ変換を行い、変換の報告も返す。
+/// 原点の適用区域外に変換された点を定義域外として数え、`sample_step`点ごと(0なら行わない)に往復変換誤差を確認する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::jpr2ll_batch_with_report;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let (ll, report) = jpr2ll_batch_with_report(&[(22694.980, 11573.375)], JprOrigin::Nine, 1);
+/// assert!(report.max_round_trip_error < 1e-6);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn jpr2ll_batch_with_report(points: &[(f64, f64)], origin: JprOrigin, sample_step: usize) -> (Vec<(f64, f64)>, BatchReport) {
+    let mut report = BatchReport::default();
+
+    let result = points
+        .iter()
+        .enumerate()
+        .map(|(i, &yx)| {
+            let ll = jpr2ll(yx, origin);
+
+            report.count += 1;
+            report.input_bounds = extend(report.input_bounds, yx);
+            report.output_bounds = extend(report.output_bounds, ll);
+
+            if !origin.contains(ll) || !ll.0.is_finite() || !ll.1.is_finite() {
+                report.out_of_domain += 1;
+            } else if is_sampled(i, sample_step) {
+                let (y, x) = ll2jpr(ll, origin);
+                let error = ((y - yx.0).powi(2) + (x - yx.1).powi(2)).sqrt();
+                report.max_round_trip_error = report.max_round_trip_error.max(error);
+            }
+
+            ll
+        })
+        .collect();
+
+    trace_report(report.count, report.out_of_domain);
+
+    (result, report)
+}
+
+/// Converts (longitude, latitude) expressed in the arc degree method to pixel coordinates (x, y) in bulk.
+///
+/// 弧度法で表された(経度, 緯度)をピクセル座標(x, y)に一括変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::ll2pixel_batch;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let pixels = ll2pixel_batch(&[(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians())], ZoomLv::Lv21);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?zoom)))]
+pub fn ll2pixel_batch(points: &[(f64, f64)], zoom: ZoomLv) -> Vec<(u32, u32)> {
+    points.iter().map(|&ll| ll2pixel(ll, zoom)).collect()
+}
+
+/// Same as `ll2pixel_batch`, and also returns a report of the conversion.
+/// Points rejected by `try_ll2pixel`, beyond the latitude limit of Web Mercator or with the longitude outside -π to π, are counted as out of domain, and every `sample_step`-th point (none if 0) is checked for round-trip error in pixels.
+///
+/// `ll2pixel_batch`と同じ変換を行い、変換の報告も返す。
+/// `try_ll2pixel`が受け付けない、Webメルカトルの緯度の限界を超える点や経度が-πからπの範囲外の点を定義域外として数え、`sample_step`点ごと(0なら行わない)にピクセル単位の往復変換誤差を確認する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::ll2pixel_batch_with_report;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let (pixels, report) = ll2pixel_batch_with_report(&[(0., 89_f64.to_radians())], ZoomLv::Lv21, 1);
+/// assert_eq!(report.out_of_domain, 1);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?zoom)))]
+pub fn ll2pixel_batch_with_report(points: &[(f64, f64)], zoom: ZoomLv, sample_step: usize) -> (Vec<(u32, u32)>, BatchReport) {
+    let mut report = BatchReport::default();
+
+    let result = points
+        .iter()
+        .enumerate()
+        .map(|(i, &ll)| {
+            let pixel = ll2pixel(ll, zoom);
+
+            report.count += 1;
+            report.input_bounds = extend(report.input_bounds, ll);
+            report.output_bounds = extend(report.output_bounds, (pixel.0 as f64, pixel.1 as f64));
+
+            if check_pixel_domain(ll).is_err() {
+                report.out_of_domain += 1;
+            } else if is_sampled(i, sample_step) {
+                let (x, y) = ll2pixel(pixel2ll(pixel, zoom), zoom);
+                let error = ((x as f64 - pixel.0 as f64).powi(2) + (y as f64 - pixel.1 as f64).powi(2)).sqrt();
+                report.max_round_trip_error = report.max_round_trip_error.max(error);
+            }
+
+            pixel
+        })
+        .collect();
+
+    trace_report(report.count, report.out_of_domain);
+
+    (result, report)
+}
+
+/// Number of points converted between calls of the progress callback and checks of the cancellation token
+///
+/// 進捗のコールバックの呼び出しと取り消しのトークンの確認の間に変換する点の数
+const PROGRESS_INTERVAL: usize = 1024;
+
+/// Token to cancel a batch conversion from another thread, e.g. from UI or job-control code.
+/// Clones share the same state, so a clone can be kept by the canceller while another is passed to the conversion.
+///
+/// UIやジョブ制御のコードなど、別のスレッドから一括変換を取り消すためのトークン。
+/// 複製は同じ状態を共有するため、取り消す側が複製を保持し、別の複製を変換に渡すことができる。
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    ///
+    /// 取り消されていないトークンを作成する
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the cancellation of the conversions holding this token
+    ///
+    /// このトークンを持つ変換の取り消しを要求する
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the cancellation has been requested
+    ///
+    /// 取り消しが要求されたかどうかを返す
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Error returned when a batch conversion is cancelled, holding the number of points converted before the cancellation
+///
+/// 一括変換が取り消された場合に返されるエラー。取り消しまでに変換した点の数を持つ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchCancelled {
+    /// Number of points converted before the cancellation
+    ///
+    /// 取り消しまでに変換した点の数
+    pub done: usize,
+}
+
+impl Display for BatchCancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "batch conversion cancelled after {} points", self.done)
+    }
+}
+
+impl std::error::Error for BatchCancelled {}
+
+/// Converts the points with `f`, calling `progress` with (points done, total) and checking `cancel` every `PROGRESS_INTERVAL` points
+///
+/// 点を`f`で変換し、`PROGRESS_INTERVAL`点ごとに`progress`を(変換した点の数, 総数)で呼び出し、`cancel`を確認する
+fn convert_with_progress<I: Copy, O>(
+    points: &[I],
+    f: impl Fn(I) -> O,
+    mut progress: Option<&mut dyn FnMut(usize, usize)>,
+    cancel: Option<&CancellationToken>,
+) -> Result<Vec<O>, BatchCancelled> {
+    let total = points.len();
+    let mut result = Vec::with_capacity(total);
+
+    for chunk in points.chunks(PROGRESS_INTERVAL) {
+        if cancel.is_some_and(CancellationToken::is_cancelled) {
+            return Err(BatchCancelled { done: result.len() });
+        }
+
+        result.extend(chunk.iter().map(|&p| f(p)));
+
+        if let Some(progress) = progress.as_mut() {
+            progress(result.len(), total);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Same as `ll2jpr_batch`, with an optional progress callback called with (points done, total) and an optional cancellation token.
+/// The callback is called and the token is checked every 1024 points, and `BatchCancelled` is returned once the cancellation is requested.
+///
+/// `ll2jpr_batch`と同じ変換を行う。(変換した点の数, 総数)で呼び出される進捗のコールバックと取り消しのトークンを任意で指定できる。
+/// コールバックの呼び出しとトークンの確認は1024点ごとに行われ、取り消しが要求されると`BatchCancelled`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::{ll2jpr_batch_with_progress, CancellationToken};
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let points = vec![(140_f64.to_radians(), 36_f64.to_radians()); 3000];
+/// let token = CancellationToken::new();
+/// let mut last = (0, 0);
+///
+/// let yx = ll2jpr_batch_with_progress(&points, JprOrigin::Nine, Some(&mut |done, total| last = (done, total)), Some(&token)).unwrap();
+///
+/// assert_eq!(yx.len(), 3000);
+/// assert_eq!(last, (3000, 3000));
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn ll2jpr_batch_with_progress(
+    points: &[(f64, f64)],
+    origin: JprOrigin,
+    progress: Option<&mut dyn FnMut(usize, usize)>,
+    cancel: Option<&CancellationToken>,
+) -> Result<Vec<(f64, f64)>, BatchCancelled> {
+    convert_with_progress(points, |ll| ll2jpr(ll, origin), progress, cancel)
+}
+
+/// Same as `jpr2ll_batch`, with an optional progress callback and cancellation token as in `ll2jpr_batch_with_progress`
+///
+/// `jpr2ll_batch`と同じ変換を行う。`ll2jpr_batch_with_progress`と同様に進捗のコールバックと取り消しのトークンを任意で指定できる
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?origin)))]
+pub fn jpr2ll_batch_with_progress(
+    points: &[(f64, f64)],
+    origin: JprOrigin,
+    progress: Option<&mut dyn FnMut(usize, usize)>,
+    cancel: Option<&CancellationToken>,
+) -> Result<Vec<(f64, f64)>, BatchCancelled> {
+    convert_with_progress(points, |yx| jpr2ll(yx, origin), progress, cancel)
+}
+
+/// Same as `ll2pixel_batch`, with an optional progress callback and cancellation token as in `ll2jpr_batch_with_progress`
+///
+/// `ll2pixel_batch`と同じ変換を行う。`ll2jpr_batch_with_progress`と同様に進捗のコールバックと取り消しのトークンを任意で指定できる
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(points = points.len(), ?zoom)))]
+pub fn ll2pixel_batch_with_progress(
+    points: &[(f64, f64)],
+    zoom: ZoomLv,
+    progress: Option<&mut dyn FnMut(usize, usize)>,
+    cancel: Option<&CancellationToken>,
+) -> Result<Vec<(u32, u32)>, BatchCancelled> {
+    convert_with_progress(points, |ll| ll2pixel(ll, zoom), progress, cancel)
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn ll2jpr_batch_with_report_works() {
+        let points = [
+            (140_f64.to_radians(), 36_f64.to_radians()),
+            (139.5_f64.to_radians(), 35.5_f64.to_radians()),
+            (130_f64.to_radians(), 33_f64.to_radians()),
+        ];
+
+        let (yx, report) = ll2jpr_batch_with_report(&points, JprOrigin::Nine, 1);
+
+        assert_eq!(yx, ll2jpr_batch(&points, JprOrigin::Nine));
+        assert_eq!(report.count, 3);
+        assert_eq!(report.out_of_domain, 1);
+        assert!(report.max_round_trip_error < 1e-6);
+
+        let ((min_long, min_lat), (max_long, max_lat)) = report.input_bounds.unwrap();
+        assert_close_to(min_long.to_degrees(), 130., 9);
+        assert_close_to(min_lat.to_degrees(), 33., 9);
+        assert_close_to(max_long.to_degrees(), 140., 9);
+        assert_close_to(max_lat.to_degrees(), 36., 9);
+    }
+
+    #[test]
+    fn ll2jpr_auto_batch_works() {
+        let points = [
+            (139.7671_f64.to_radians(), 35.6812_f64.to_radians()),
+            (139.5_f64.to_radians(), 35.5_f64.to_radians()),
+            (141.3544_f64.to_radians(), 43.0621_f64.to_radians()),
+            (0., 0.),
+            // 鳥島はIX系の区域内にある
+            (140.303_f64.to_radians(), 30.483_f64.to_radians()),
+        ];
+
+        let (yx, report) = ll2jpr_auto_batch(&points);
+
+        assert_eq!(yx[0], ll2jpr(points[0], JprOrigin::Nine));
+        assert_eq!(report.zones[2], JprOrigin::Twelve);
+        assert_eq!(report.count, 5);
+        assert_eq!(report.zone_counts[&JprOrigin::Nine], 3);
+        assert_eq!(report.zone_counts.values().sum::<usize>(), 5);
+        assert_eq!(report.out_of_domain, 1);
+    }
+
+    #[test]
+    fn ll2pixel_batch_with_report_works() {
+        let points = [(0., 0.), (0., 86_f64.to_radians()), (f64::NAN, 0.), (4., 0.)];
+
+        let (_, report) = ll2pixel_batch_with_report(&points, ZoomLv::Lv10, 1);
+
+        assert_eq!(report.count, 4);
+        assert_eq!(report.max_round_trip_error, 0.);
+        assert_eq!(report.input_bounds, Some(((0., 0.), (4., 86_f64.to_radians()))));
+
+        // 定義域外の点の数はtry_ll2pixelが受け付けない点の数と一致する
+        let rejected = points.iter().filter(|&&ll| crate::try_ll2pixel(ll, ZoomLv::Lv10).is_err()).count();
+        assert_eq!(report.out_of_domain, 3);
+        assert_eq!(report.out_of_domain, rejected);
+    }
+
+    #[test]
+    fn batch_with_progress_reports_and_cancels() {
+        let points = vec![(140_f64.to_radians(), 36_f64.to_radians()); 2500];
+        let mut calls = Vec::new();
+
+        let yx = ll2jpr_batch_with_progress(&points, JprOrigin::Nine, Some(&mut |done, total| calls.push((done, total))), None).unwrap();
+        assert_eq!(yx, ll2jpr_batch(&points, JprOrigin::Nine));
+        assert_eq!(calls, vec![(1024, 2500), (2048, 2500), (2500, 2500)]);
+
+        // 最初のまとまりの後に取り消す
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let result = jpr2ll_batch_with_progress(&points, JprOrigin::Nine, Some(&mut |_, _| canceller.cancel()), Some(&token));
+        assert_eq!(result, Err(BatchCancelled { done: 1024 }));
+    }
+}
@@ -0,0 +1,281 @@
+//! Memoization of conversions with a bounded least recently used (LRU) cache.
+//! Vertex-heavy data often repeats identical coordinates, e.g. on the borders shared by polygons, and the cache converts each of them only once.
+//!
+//! 容量に上限のあるLRU(最も長く使われていないものから破棄する)キャッシュによる変換のメモ化。
+//! 頂点の多いデータでは、ポリゴンが共有する境界などで同一の座標が繰り返し現れることが多く、キャッシュによってそれぞれを一度だけ変換できる。
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Trait for inputs of conversions that can be used as keys of the cache.
+/// Floating point values are compared by their bits, so only identical inputs hit the cache.
+///
+/// キャッシュのキーとして使用できる変換の入力のトレイト。
+/// 浮動小数点数はビットで比較されるため、同一の入力のみがキャッシュに当たる。
+pub trait CacheKey {
+    /// Type of the key
+    ///
+    /// キーの型
+    type Key: Hash + Eq + Clone;
+
+    /// Returns the key of the input
+    ///
+    /// 入力のキーを返す
+    fn cache_key(&self) -> Self::Key;
+}
+
+impl CacheKey for (f64, f64) {
+    type Key = (u64, u64);
+
+    fn cache_key(&self) -> Self::Key {
+        (self.0.to_bits(), self.1.to_bits())
+    }
+}
+
+impl CacheKey for (f64, f64, f64) {
+    type Key = (u64, u64, u64);
+
+    fn cache_key(&self) -> Self::Key {
+        (self.0.to_bits(), self.1.to_bits(), self.2.to_bits())
+    }
+}
+
+impl CacheKey for ((f64, f64), f64) {
+    type Key = ((u64, u64), u64);
+
+    fn cache_key(&self) -> Self::Key {
+        (self.0.cache_key(), self.1.to_bits())
+    }
+}
+
+impl CacheKey for (u32, u32) {
+    type Key = (u32, u32);
+
+    fn cache_key(&self) -> Self::Key {
+        *self
+    }
+}
+
+/// Index representing the absence of an entry in the linked list
+///
+/// 連結リストにおいてエントリがないことを表すインデックス
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Entry<K, O> {
+    key: K,
+    value: O,
+    prev: usize,
+    next: usize,
+}
+
+/// Structure wrapping a conversion function with a bounded LRU cache of its results.
+/// When the cache is full, the result used least recently is evicted.
+///
+/// 変換関数を、その結果の容量に上限のあるLRUキャッシュで包む構造体。
+/// キャッシュが満杯のときは、最も長く使われていない結果が破棄される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::cache::CachedProjector;
+/// use coordinate_transformer::jpr_ll::{ll2jpr, JprOrigin};
+///
+/// let mut projector = CachedProjector::new(|ll| ll2jpr(ll, JprOrigin::Nine), 1024);
+///
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let first = projector.project(ll);
+/// let second = projector.project(ll);
+///
+/// assert_eq!(first, second);
+/// assert_eq!((projector.hits(), projector.misses()), (1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedProjector<I: CacheKey, O, F> {
+    f: F,
+    capacity: usize,
+    map: HashMap<I::Key, usize>,
+    entries: Vec<Entry<I::Key, O>>,
+    // 最も最近使われたエントリと最も長く使われていないエントリ
+    head: usize,
+    tail: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl<I, O, F> CachedProjector<I, O, F>
+where
+    I: CacheKey + Copy,
+    O: Clone,
+    F: Fn(I) -> O,
+{
+    /// Create a new cache holding at most `capacity` results of `f`.
+    /// With a capacity of 0, every input is converted without caching.
+    ///
+    /// `f`の結果を最大`capacity`個保持するキャッシュを新しく作成する。
+    /// 容量が0の場合、全ての入力はキャッシュされずに変換される。
+    pub fn new(f: F, capacity: usize) -> Self {
+        Self {
+            f,
+            capacity,
+            map: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: NONE,
+            tail: NONE,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Convert the input, returning the cached result if the same input was converted recently
+    ///
+    /// 入力を変換する。同じ入力が最近変換されていれば、キャッシュされた結果を返す
+    pub fn project(&mut self, input: I) -> O {
+        let key = input.cache_key();
+
+        if let Some(&index) = self.map.get(&key) {
+            self.hits += 1;
+            self.detach(index);
+            self.push_front(index);
+            return self.entries[index].value.clone();
+        }
+
+        self.misses += 1;
+        let value = (self.f)(input);
+        if self.capacity == 0 {
+            return value;
+        }
+
+        let index = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                key: key.clone(),
+                value: value.clone(),
+                prev: NONE,
+                next: NONE,
+            });
+            self.entries.len() - 1
+        } else {
+            // 最も長く使われていないエントリを再利用する
+            let index = self.tail;
+            self.detach(index);
+            self.map.remove(&self.entries[index].key);
+            self.entries[index].key = key.clone();
+            self.entries[index].value = value.clone();
+            index
+        };
+
+        self.map.insert(key, index);
+        self.push_front(index);
+
+        value
+    }
+
+    /// Returns the number of inputs whose result was found in the cache
+    ///
+    /// 結果がキャッシュに見つかった入力の数を返す
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of inputs that were converted
+    ///
+    /// 変換された入力の数を返す
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of cached results
+    ///
+    /// キャッシュされた結果の数を返す
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the cache is empty
+    ///
+    /// キャッシュが空かどうかを返す
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Remove all cached results and reset the counts
+    ///
+    /// キャッシュされた結果を全て削除し、回数を初期化する
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.entries.clear();
+        self.head = NONE;
+        self.tail = NONE;
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    fn detach(&mut self, index: usize) {
+        let (prev, next) = (self.entries[index].prev, self.entries[index].next);
+
+        match prev {
+            NONE => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, index: usize) {
+        self.entries[index].prev = NONE;
+        self.entries[index].next = self.head;
+
+        match self.head {
+            NONE => self.tail = index,
+            head => self.entries[head].prev = index,
+        }
+        self.head = index;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn cached_projector_evicts_least_recently_used() {
+        let calls = Cell::new(0);
+        let mut projector = CachedProjector::new(
+            |(x, y): (u32, u32)| {
+                calls.set(calls.get() + 1);
+                x + y
+            },
+            2,
+        );
+
+        assert_eq!(projector.project((1, 2)), 3);
+        assert_eq!(projector.project((3, 4)), 7);
+        // (1, 2)を使うことで(3, 4)が最も長く使われていないものとなる
+        assert_eq!(projector.project((1, 2)), 3);
+        assert_eq!(projector.project((5, 6)), 11);
+        assert_eq!(projector.len(), 2);
+
+        assert_eq!(projector.project((1, 2)), 3);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(projector.project((3, 4)), 7);
+        assert_eq!(calls.get(), 4);
+        assert_eq!((projector.hits(), projector.misses()), (2, 4));
+
+        projector.clear();
+        assert!(projector.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity_does_not_cache() {
+        let mut projector = CachedProjector::new(|xyz: (f64, f64, f64)| xyz.0, 0);
+
+        projector.project((1., 2., 3.));
+        projector.project((1., 2., 3.));
+        assert_eq!((projector.hits(), projector.misses()), (0, 2));
+        assert!(projector.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+//! Ellipsoid and projection parameters used by the conversions of this crate, so that downstream code can share the same values.
+//! GRS80 is the ellipsoid of JGD2000/JGD2011 and of the plane rectangular coordinate system, and WGS84 is that of the Cartesian coordinate system (EPSG:4979) in `xyz_ll`.
+//!
+//! このクレートの変換で用いる楕円体と投影のパラメータ。下流のコードが同じ値を共有できるようにする。
+//! GRS80はJGD2000/JGD2011及び平面直角座標系の楕円体であり、WGS84は`xyz_ll`の直交座標系(EPSG:4979)の楕円体である。
+
+use std::f64::consts::PI;
+
+/// Semi-major axis of GRS80 (m)
+///
+/// GRS80の長半径(m)
+pub const GRS80_SEMI_MAJOR_AXIS: f64 = 6378137.;
+
+/// Inverse flattening of GRS80
+///
+/// GRS80の逆扁平率
+pub const GRS80_INVERSE_FLATTENING: f64 = 298.257222101;
+
+/// Flattening of GRS80
+///
+/// GRS80の扁平率
+pub const GRS80_FLATTENING: f64 = 1. / GRS80_INVERSE_FLATTENING;
+
+/// Square of the first eccentricity of GRS80
+///
+/// GRS80の第一離心率の2乗
+pub const GRS80_ECCENTRICITY_SQUARED: f64 = GRS80_FLATTENING * (2. - GRS80_FLATTENING);
+
+/// First eccentricity of GRS80
+///
+/// GRS80の第一離心率
+pub const GRS80_ECCENTRICITY: f64 = 0.0818191910428158;
+
+/// Semi-major axis of WGS84 (m)
+///
+/// WGS84の長半径(m)
+pub const WGS84_SEMI_MAJOR_AXIS: f64 = 6378137.;
+
+/// Inverse flattening of WGS84
+///
+/// WGS84の逆扁平率
+pub const WGS84_INVERSE_FLATTENING: f64 = 298.257223563;
+
+/// Flattening of WGS84
+///
+/// WGS84の扁平率
+pub const WGS84_FLATTENING: f64 = 1. / WGS84_INVERSE_FLATTENING;
+
+/// Square of the first eccentricity of WGS84
+///
+/// WGS84の第一離心率の2乗
+pub const WGS84_ECCENTRICITY_SQUARED: f64 = WGS84_FLATTENING * (2. - WGS84_FLATTENING);
+
+/// First eccentricity of WGS84
+///
+/// WGS84の第一離心率
+pub const WGS84_ECCENTRICITY: f64 = 0.0818191908426215;
+
+/// Scale factor on the central meridian of the plane rectangular coordinate system (m0)
+///
+/// 平面直角座標系の中央子午線上の縮尺係数(m0)
+pub const JPR_SCALE_FACTOR: f64 = 0.9999;
+
+/// Radius of the sphere used by Web Mercator (m)
+///
+/// Webメルカトルで用いる球の半径(m)
+pub const WEB_MERCATOR_RADIUS: f64 = 6378137.;
+
+/// Latitude limit of Web Mercator expressed in the degree method.
+///
+/// 度数法で表したWebメルカトルの緯度の限界。
+pub const MAX_MERCATOR_LAT_DEG: f64 = 85.05112878;
+
+/// Latitude limit of Web Mercator expressed in the arc degree method.
+///
+/// 弧度法で表したWebメルカトルの緯度の限界。
+pub const MAX_MERCATOR_LAT: f64 = MAX_MERCATOR_LAT_DEG * PI / 180.;
+
+/// Ground resolution of a pixel at the equator at Zoom level 0 of 256 px tiles (m/px)
+///
+/// 256pxのタイルのZoomレベル0における赤道上のピクセルの地上解像度(m/px)
+pub const ZOOM0_RESOLUTION: f64 = 2. * PI * WEB_MERCATOR_RADIUS / 256.;
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn eccentricities_match_flattening() {
+        assert_close_to(GRS80_ECCENTRICITY * GRS80_ECCENTRICITY, GRS80_ECCENTRICITY_SQUARED, 15);
+        assert_close_to(WGS84_ECCENTRICITY * WGS84_ECCENTRICITY, WGS84_ECCENTRICITY_SQUARED, 15);
+        assert_close_to(ZOOM0_RESOLUTION, 156543.03392804097, 8);
+    }
+}
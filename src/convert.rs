@@ -0,0 +1,279 @@
+//! Generic conversion between the coordinate structures
+//! `ConvertTo<T>` is implemented among `LL`, `LLZ`, `JPR`, `Pixel`, `XYZ` and `Voxel`, so that generic code can be written over e.g. "anything convertible to pixel coordinates".
+//! The information required for the conversion, such as the origin or Zoom level, is passed as `Context`.
+//!
+//! 座標を表す構造体間の汎用的な変換
+//! `ConvertTo<T>`は`LL`、`LLZ`、`JPR`、`Pixel`、`XYZ`、`Voxel`の間で実装されているため、「ピクセル座標に変換できるもの」などに対する汎用的なコードを書ける。
+//! 原点やZoomレベルなど変換に必要な情報は`Context`として渡す。
+
+use crate::{JprOrigin, Pixel, Voxel, ZoomLv, JPR, LL, LLZ, XYZ};
+
+/// Trait for converting to the coordinate structure `T`
+///
+/// 座標を表す構造体`T`に変換するトレイト
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::convert::ConvertTo;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::{Pixel, JPR, LL};
+///
+/// fn tiles<C: ConvertTo<Pixel, Context = ZoomLv>>(coords: &[C], zoom: ZoomLv) -> Vec<(u32, u32)> {
+///     coords.iter().map(|c| c.convert_to(zoom).to_tuple()).map(|(x, y)| (x / 256, y / 256)).collect()
+/// }
+///
+/// let ll = [LL::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians())];
+/// let jpr = [JPR::new(22694.980, 11573.375, JprOrigin::Nine)];
+///
+/// let a = tiles(&ll, ZoomLv::Lv10);
+/// let b = tiles(&jpr, ZoomLv::Lv10);
+/// ```
+pub trait ConvertTo<T> {
+    /// Information required for the conversion
+    ///
+    /// 変換に必要な情報
+    type Context;
+
+    /// Convert to `T`
+    ///
+    /// `T`に変換する
+    fn convert_to(&self, context: Self::Context) -> T;
+}
+
+impl ConvertTo<JPR> for LL {
+    type Context = JprOrigin;
+
+    fn convert_to(&self, origin: JprOrigin) -> JPR {
+        self.to_jpr(origin)
+    }
+}
+
+impl ConvertTo<Pixel> for LL {
+    type Context = ZoomLv;
+
+    fn convert_to(&self, zoom: ZoomLv) -> Pixel {
+        self.to_pixel(zoom)
+    }
+}
+
+/// The context is the altitude (m)
+///
+/// 情報は標高(m)
+impl ConvertTo<XYZ> for LL {
+    type Context = f64;
+
+    fn convert_to(&self, altitude: f64) -> XYZ {
+        self.to_xyz(altitude)
+    }
+}
+
+/// The context is the altitude (m)
+///
+/// 情報は標高(m)
+impl ConvertTo<LLZ> for LL {
+    type Context = f64;
+
+    fn convert_to(&self, altitude: f64) -> LLZ {
+        let (long, lat) = self.to_tuple();
+        LLZ::new(long, lat, altitude)
+    }
+}
+
+impl ConvertTo<LL> for LLZ {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LL {
+        self.to_ll()
+    }
+}
+
+impl ConvertTo<JPR> for LLZ {
+    type Context = JprOrigin;
+
+    fn convert_to(&self, origin: JprOrigin) -> JPR {
+        self.to_ll().to_jpr(origin)
+    }
+}
+
+impl ConvertTo<Pixel> for LLZ {
+    type Context = ZoomLv;
+
+    fn convert_to(&self, zoom: ZoomLv) -> Pixel {
+        self.to_ll().to_pixel(zoom)
+    }
+}
+
+impl ConvertTo<XYZ> for LLZ {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> XYZ {
+        self.to_xyz()
+    }
+}
+
+/// The context is the Zoom level and the resolution of the height (m)
+///
+/// 情報はZoomレベルと高さの分解能(m)
+impl ConvertTo<Voxel> for LLZ {
+    type Context = (ZoomLv, f64);
+
+    fn convert_to(&self, (zoom, resolution): (ZoomLv, f64)) -> Voxel {
+        let (x, y) = self.to_ll().to_pixel(zoom).to_tuple();
+        Voxel::new(x, y, (self.altitude() / resolution) as u32, resolution, zoom)
+    }
+}
+
+impl ConvertTo<LL> for JPR {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LL {
+        self.to_ll()
+    }
+}
+
+impl ConvertTo<Pixel> for JPR {
+    type Context = ZoomLv;
+
+    fn convert_to(&self, zoom: ZoomLv) -> Pixel {
+        self.to_pixel(zoom)
+    }
+}
+
+/// The context is the altitude (m)
+///
+/// 情報は標高(m)
+impl ConvertTo<XYZ> for JPR {
+    type Context = f64;
+
+    fn convert_to(&self, altitude: f64) -> XYZ {
+        self.to_xyz(altitude)
+    }
+}
+
+impl ConvertTo<LL> for Pixel {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LL {
+        self.to_ll()
+    }
+}
+
+impl ConvertTo<JPR> for Pixel {
+    type Context = JprOrigin;
+
+    fn convert_to(&self, origin: JprOrigin) -> JPR {
+        self.to_jpr(origin)
+    }
+}
+
+/// The context is the altitude (m)
+///
+/// 情報は標高(m)
+impl ConvertTo<XYZ> for Pixel {
+    type Context = f64;
+
+    fn convert_to(&self, altitude: f64) -> XYZ {
+        self.to_xyz(altitude)
+    }
+}
+
+impl ConvertTo<LL> for XYZ {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LL {
+        self.to_ll()
+    }
+}
+
+impl ConvertTo<LLZ> for XYZ {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LLZ {
+        let (ll, altitude) = self.to_ll_with_altitude();
+        let (long, lat) = ll.to_tuple();
+        LLZ::new(long, lat, altitude)
+    }
+}
+
+impl ConvertTo<JPR> for XYZ {
+    type Context = JprOrigin;
+
+    fn convert_to(&self, origin: JprOrigin) -> JPR {
+        self.to_jpr(origin)
+    }
+}
+
+impl ConvertTo<Pixel> for XYZ {
+    type Context = ZoomLv;
+
+    fn convert_to(&self, zoom: ZoomLv) -> Pixel {
+        self.to_pixel(zoom)
+    }
+}
+
+impl ConvertTo<LL> for Voxel {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LL {
+        self.to_ll()
+    }
+}
+
+impl ConvertTo<LLZ> for Voxel {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> LLZ {
+        let (ll, altitude) = self.to_ll_with_altitude();
+        let (long, lat) = ll.to_tuple();
+        LLZ::new(long, lat, altitude)
+    }
+}
+
+impl ConvertTo<JPR> for Voxel {
+    type Context = JprOrigin;
+
+    fn convert_to(&self, origin: JprOrigin) -> JPR {
+        self.to_jpr(origin)
+    }
+}
+
+impl ConvertTo<Pixel> for Voxel {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> Pixel {
+        self.to_pixel()
+    }
+}
+
+impl ConvertTo<XYZ> for Voxel {
+    type Context = ();
+
+    fn convert_to(&self, _: ()) -> XYZ {
+        self.to_xyz()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_pixel<C: ConvertTo<Pixel, Context = ZoomLv>>(coord: &C, zoom: ZoomLv) -> (u32, u32) {
+        coord.convert_to(zoom).to_tuple()
+    }
+
+    #[test]
+    fn convert_to_works() {
+        let ll = LL::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        let llz: LLZ = ll.convert_to(100.);
+        let xyz: XYZ = llz.convert_to(());
+
+        assert_eq!(to_pixel(&ll, ZoomLv::Lv21), (476868027, 211407949));
+        assert_eq!(to_pixel(&llz, ZoomLv::Lv21), (476868027, 211407949));
+        assert_eq!(to_pixel(&xyz, ZoomLv::Lv21), (476868027, 211407949));
+
+        let voxel: Voxel = llz.convert_to((ZoomLv::Lv21, 1.));
+        assert_eq!(voxel.to_tuple(), (476868027, 211407949, 100));
+    }
+}
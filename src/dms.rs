@@ -0,0 +1,386 @@
+//! Formatting and parsing of angles in degrees, minutes and seconds (度分秒).
+//!
+//! 度分秒による角度の書式化と解析。
+
+use std::fmt::{Display, Formatter};
+
+/// Splits the absolute value of an angle expressed in the arc degree method into (degrees, minutes, seconds scaled by 10^decimals).
+/// The seconds are rounded first, so that rounding up to 60 seconds carries into the minutes and degrees.
+///
+/// 弧度法で表された角度の絶対値を(度, 分, 10^decimals倍した秒)に分ける。
+/// 先に秒を丸めるため、60秒への切り上げは分と度に繰り上がる。
+pub(crate) fn split_dms(angle: f64, decimals: usize) -> (u64, u64, u64) {
+    let scale = 10_u64.pow(decimals as u32);
+    let total = (angle.to_degrees().abs() * 3600. * scale as f64).round() as u64;
+
+    let seconds = total % (60 * scale);
+    let minutes = total / (60 * scale) % 60;
+    let degrees = total / (3600 * scale);
+
+    (degrees, minutes, seconds)
+}
+
+fn format_seconds(seconds: u64, decimals: usize, width: usize) -> String {
+    let scale = 10_u64.pow(decimals as u32);
+
+    if decimals == 0 {
+        format!("{:0width$}", seconds, width = width)
+    } else {
+        format!(
+            "{:0width$}.{:0decimals$}",
+            seconds / scale,
+            seconds % scale,
+            width = width,
+            decimals = decimals
+        )
+    }
+}
+
+/// Function to format (longitude, latitude) expressed in the arc degree method in the Japanese notation such as `北緯35度40分52.5秒 東経139度45分53.8秒`, with `decimals` decimal places of seconds.
+///
+/// 弧度法で表された(経度, 緯度)を、秒を小数点以下`decimals`桁として`北緯35度40分52.5秒 東経139度45分53.8秒`のような日本語の表記に書式化する関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::dms::format_dms_ja;
+///
+/// let text = format_dms_ja((139.764944_f64.to_radians(), 35.681250_f64.to_radians()), 1);
+///
+/// assert_eq!(text, "北緯35度40分52.5秒 東経139度45分53.8秒");
+/// ```
+pub fn format_dms_ja(ll: (f64, f64), decimals: usize) -> String {
+    let (long, lat) = ll;
+
+    let part = |angle: f64, positive: &str, negative: &str| {
+        let (d, m, s) = split_dms(angle, decimals);
+        let hemisphere = if angle < 0. { negative } else { positive };
+        format!("{}{}度{}分{}秒", hemisphere, d, m, format_seconds(s, decimals, 1))
+    };
+
+    format!("{} {}", part(lat, "北緯", "南緯"), part(long, "東経", "西経"))
+}
+
+/// Function to format an angle expressed in the arc degree method in the packed DDMMSS.ss notation used in some government CSV files, with `decimals` decimal places of seconds.
+/// Minutes and seconds are padded to two digits, and negative angles are prefixed with `-`.
+///
+/// 弧度法で表された角度を、一部の行政機関のCSVファイルで用いられる詰めたDDMMSS.ss表記に、秒を小数点以下`decimals`桁として書式化する関数。
+/// 分と秒は2桁に揃えられ、負の角度には`-`が前置される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::dms::format_ddmmss;
+///
+/// assert_eq!(format_ddmmss(35.343403_f64.to_radians(), 2), "352036.25");
+/// assert_eq!(format_ddmmss(139.764944_f64.to_radians(), 0), "1394554");
+/// ```
+pub fn format_ddmmss(angle: f64, decimals: usize) -> String {
+    let (d, m, s) = split_dms(angle, decimals);
+    let sign = if angle < 0. && (d, m, s) != (0, 0, 0) { "-" } else { "" };
+
+    format!("{}{}{:02}{}", sign, d, m, format_seconds(s, decimals, 2))
+}
+
+/// Enumerated type representing errors when parsing degrees, minutes and seconds
+///
+/// 度分秒の解析時のエラーを表す列挙型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DmsParseError {
+    /// The text is not in a supported notation
+    ///
+    /// 対応する表記ではない
+    InvalidFormat(String),
+    /// The minutes or seconds are 60 or more, or the angle is outside ±90 degrees of latitude or ±180 degrees of longitude
+    ///
+    /// 分または秒が60以上であるか、角度が緯度±90度または経度±180度の範囲外である
+    OutOfRange(String),
+}
+
+impl Display for DmsParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DmsParseError::InvalidFormat(s) => write!(f, "invalid DMS notation: {}", s),
+            DmsParseError::OutOfRange(s) => write!(f, "DMS value out of range: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for DmsParseError {}
+
+/// Axis indicated by a hemisphere marker
+///
+/// 半球の記号が示す軸
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Lat,
+    Long,
+}
+
+/// Converts full-width alphanumerics, symbols and spaces to their ASCII forms
+///
+/// 全角の英数字、記号、空白をASCIIに変換する
+fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap(),
+            '\u{3000}' => ' ',
+            '\u{2212}' | '\u{2010}' | '\u{2013}' | '\u{2014}' => '-',
+            c => c,
+        })
+        .collect()
+}
+
+/// Builds an angle expressed in the arc degree method from degrees, minutes and seconds, checking the range of the minutes and seconds
+///
+/// 度、分、秒から弧度法で表された角度を作る。分と秒の範囲を確認する
+fn from_dms(degrees: f64, minutes: f64, seconds: f64, negative: bool, text: &str) -> Result<f64, DmsParseError> {
+    if minutes >= 60. || seconds >= 60. {
+        return Err(DmsParseError::OutOfRange(text.to_string()));
+    }
+
+    let angle = (degrees + minutes / 60. + seconds / 3600.).to_radians();
+    Ok(if negative { -angle } else { angle })
+}
+
+/// Parses one angle, returning it with the axis indicated by its hemisphere marker
+///
+/// 1つの角度を解析し、半球の記号が示す軸とともに返す
+fn parse_angle(text: &str) -> Result<(f64, Option<Axis>), DmsParseError> {
+    let err = || DmsParseError::InvalidFormat(text.to_string());
+    let normalized = normalize(text);
+    let mut rest = normalized.trim();
+
+    let mut negative = false;
+    let mut axis = None;
+    for (marker, sign, a) in [
+        ("北緯", false, Axis::Lat),
+        ("南緯", true, Axis::Lat),
+        ("東経", false, Axis::Long),
+        ("西経", true, Axis::Long),
+        ("N", false, Axis::Lat),
+        ("S", true, Axis::Lat),
+        ("E", false, Axis::Long),
+        ("W", true, Axis::Long),
+    ] {
+        if let Some(r) = rest.strip_prefix(marker) {
+            (rest, negative, axis) = (r.trim_start(), sign, Some(a));
+            break;
+        }
+    }
+    if axis.is_none() {
+        if let Some(r) = rest.strip_prefix('-') {
+            (rest, negative) = (r, true);
+        }
+    }
+
+    // 数値と単位の組を度、分、秒の順に読む
+    let mut values = [0_f64; 3];
+    let mut unit = 0;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        if end == 0 {
+            return Err(err());
+        }
+        let value = rest[..end].parse::<f64>().map_err(|_| err())?;
+        rest = rest[end..].trim_start();
+
+        let next = [("度", 0), ("°", 0), ("分", 1), ("′", 1), ("'", 1), ("秒", 2), ("″", 2), ("\"", 2)]
+            .into_iter()
+            .find_map(|(marker, u)| rest.strip_prefix(marker).map(|r| (r, u)));
+
+        let u = match next {
+            Some((r, u)) => {
+                rest = r.trim_start();
+                u
+            }
+            // 単位のない数値は、度の位置にあるものだけを度とみなす
+            None if unit == 0 && rest.is_empty() => 0,
+            None => return Err(err()),
+        };
+        if u < unit {
+            return Err(err());
+        }
+        values[u] = value;
+        unit = u + 1;
+    }
+    if unit == 0 {
+        return Err(err());
+    }
+
+    let angle = from_dms(values[0], values[1], values[2], negative, text)?;
+    Ok((angle, axis))
+}
+
+fn check_range(angle: f64, axis: Axis, text: &str) -> Result<f64, DmsParseError> {
+    let limit = match axis {
+        Axis::Lat => 90_f64,
+        Axis::Long => 180_f64,
+    };
+
+    if angle.abs() > limit.to_radians() {
+        return Err(DmsParseError::OutOfRange(text.to_string()));
+    }
+    Ok(angle)
+}
+
+/// Function to parse an angle in degrees, minutes and seconds into the arc degree method.
+/// Full-width characters, the markers 度/分/秒 and °/′/″, and the hemisphere prefixes 北緯/南緯/東経/西経 (or N/S/E/W) are accepted.
+///
+/// 度分秒の角度を解析して弧度法に変換する関数。
+/// 全角文字、度/分/秒および°/′/″の記号、北緯/南緯/東経/西経(またはN/S/E/W)の半球の接頭辞に対応する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::dms::parse_dms;
+///
+/// let lat = parse_dms("北緯３５度４０分５２．５秒").unwrap();
+///
+/// assert!((lat.to_degrees() - 35.68125).abs() < 1e-9);
+/// ```
+pub fn parse_dms(text: &str) -> Result<f64, DmsParseError> {
+    let (angle, axis) = parse_angle(text)?;
+    check_range(angle, axis.unwrap_or(Axis::Long), text)
+}
+
+/// Function to parse a pair of latitude and longitude in degrees, minutes and seconds, such as `北緯35度40分52.5秒 東経139度45分53.8秒`, into (longitude, latitude) expressed in the arc degree method.
+/// The two angles are separated by spaces, `,` or `、`. With hemisphere markers they may be in either order; without them the latitude comes first.
+///
+/// `北緯35度40分52.5秒 東経139度45分53.8秒`のような度分秒の緯度と経度の組を解析し、弧度法で表された(経度, 緯度)に変換する関数。
+/// 2つの角度は空白、`,`、`、`で区切る。半球の記号があればどちらの順でもよく、なければ緯度が先となる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::dms::{format_dms_ja, parse_dms_ja};
+///
+/// let ll = parse_dms_ja("北緯35度40分52.5秒 東経139度45分53.8秒").unwrap();
+///
+/// assert_eq!(format_dms_ja(ll, 1), "北緯35度40分52.5秒 東経139度45分53.8秒");
+/// ```
+pub fn parse_dms_ja(text: &str) -> Result<(f64, f64), DmsParseError> {
+    let normalized = normalize(text);
+    let parts = normalized
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '、')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>();
+
+    // 「北緯 35度」のように記号と数値の間に空白がある場合に対応するため、記号だけの部分は次の部分と結合する
+    let mut angles = vec![];
+    let mut pending = String::new();
+    for part in parts {
+        pending.push_str(part);
+        if pending.chars().any(|c| c.is_ascii_digit()) {
+            angles.push(std::mem::take(&mut pending));
+        }
+    }
+
+    let [first, second] = angles.as_slice() else {
+        return Err(DmsParseError::InvalidFormat(text.to_string()));
+    };
+    let (a, axis_a) = parse_angle(first)?;
+    let (b, axis_b) = parse_angle(second)?;
+
+    let (lat, long) = match (axis_a, axis_b) {
+        (Some(Axis::Long), _) | (_, Some(Axis::Lat)) if axis_a != axis_b => (b, a),
+        (Some(x), Some(y)) if x == y => return Err(DmsParseError::InvalidFormat(text.to_string())),
+        _ => (a, b),
+    };
+
+    Ok((check_range(long, Axis::Long, text)?, check_range(lat, Axis::Lat, text)?))
+}
+
+/// Function to parse an angle in the packed DDMMSS.sss notation, as used in 位置参照情報 and cadastral CSV files, into the arc degree method.
+/// The last two digits of the integer part are the seconds, the two before them the minutes, and the rest the degrees.
+///
+/// 位置参照情報や地籍のCSVファイルで用いられる詰めたDDMMSS.sss表記の角度を解析して弧度法に変換する関数。
+/// 整数部の下2桁が秒、その上の2桁が分、残りが度となる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::dms::{format_ddmmss, parse_ddmmss};
+///
+/// let lat = parse_ddmmss("352036.25").unwrap();
+///
+/// assert_eq!(format_ddmmss(lat, 2), "352036.25");
+/// ```
+pub fn parse_ddmmss(text: &str) -> Result<f64, DmsParseError> {
+    let err = || DmsParseError::InvalidFormat(text.to_string());
+    let normalized = normalize(text);
+    let trimmed = normalized.trim();
+
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if integer.len() < 5
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(err());
+    }
+
+    let split = integer.len() - 4;
+    let degrees = integer[..split].parse::<f64>().map_err(|_| err())?;
+    let minutes = integer[split..split + 2].parse::<f64>().map_err(|_| err())?;
+    let seconds = format!("{}.{}0", &integer[split + 2..], fraction).parse::<f64>().map_err(|_| err())?;
+
+    let angle = from_dms(degrees, minutes, seconds, negative, text)?;
+    check_range(angle, Axis::Long, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_dms_ja_works() {
+        assert_eq!(
+            format_dms_ja((-58.3816_f64.to_radians(), -34.6037_f64.to_radians()), 0),
+            "南緯34度36分13秒 西経58度22分54秒"
+        );
+
+        // 59.96秒は小数点以下1桁で60.0秒となるため分に繰り上がる
+        let lat = (35. + 59. / 60. + 59.96 / 3600_f64).to_radians();
+        assert_eq!(format_dms_ja((0., lat), 1), "北緯36度0分0.0秒 東経0度0分0.0秒");
+    }
+
+    #[test]
+    fn parse_dms_works() {
+        let expected = (139.764944_f64.to_radians(), 35.68125_f64.to_radians());
+        let close = |(long, lat): (f64, f64)| (long - expected.0).abs() < 1e-6 && (lat - expected.1).abs() < 1e-9;
+
+        assert!(close(parse_dms_ja("東経139度45分53.8秒、北緯35度40分52.5秒").unwrap()));
+        assert!(close(parse_dms_ja("北緯 35°40′52.5″ 東経 139°45′53.8″").unwrap()));
+        assert!(close(parse_dms_ja("35度40分52.5秒,139度45分53.8秒").unwrap()));
+        assert!(close(parse_dms_ja("Ｎ３５°４０\'５２．５\"　Ｅ１３９°４５\'５３．８\"").unwrap()));
+
+        assert_eq!(parse_dms("西経58度").unwrap(), -58_f64.to_radians());
+        assert_eq!(parse_dms("-12.5").unwrap(), -12.5_f64.to_radians());
+
+        assert!(matches!(parse_dms("35度60分"), Err(DmsParseError::OutOfRange(_))));
+        assert!(matches!(parse_dms("35分40度"), Err(DmsParseError::InvalidFormat(_))));
+        assert!(matches!(parse_dms_ja("北緯95度 東経139度"), Err(DmsParseError::OutOfRange(_))));
+        assert!(matches!(parse_dms_ja("北緯35度 北緯36度"), Err(DmsParseError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn parse_ddmmss_works() {
+        assert_eq!(parse_ddmmss("1394554"), parse_dms("139度45分54秒"));
+        assert_eq!(format_ddmmss(parse_ddmmss("-10203.5").unwrap(), 1), "-10203.5");
+        assert_eq!(format_ddmmss(parse_ddmmss("３５２０３６．２５０").unwrap(), 3), "352036.250");
+
+        assert!(matches!(parse_ddmmss("3520"), Err(DmsParseError::InvalidFormat(_))));
+        assert!(matches!(parse_ddmmss("356036"), Err(DmsParseError::OutOfRange(_))));
+    }
+
+    #[test]
+    fn format_ddmmss_works() {
+        assert_eq!(format_ddmmss((-(1. + 2. / 60. + 3.5 / 3600_f64)).to_radians(), 1), "-10203.5");
+        assert_eq!(format_ddmmss(0., 2), "00000.00");
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt::{Display, Formatter};
+
+use crate::pixel_ll::{ll2pixel_f64, world_size_px, ZoomLv};
+use crate::structure::{voxel_resolution, Voxel};
+
+/// Number of pixels on a side of an elevation tile
+///
+/// 標高タイルの一辺のピクセル数
+const TILE_PX: u32 = 256;
+
+/// Function to decode the elevation (m) of one pixel of a GSI PNG elevation tile.
+/// Returns `None` for the no-data value (R, G, B) = (128, 0, 0).
+///
+/// 地理院のPNG形式の標高タイルの1ピクセルの標高(m)を復号する関数。
+/// 無効値(R, G, B) = (128, 0, 0)の場合は`None`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::elevation::gsi_png_elevation;
+///
+/// assert_eq!(gsi_png_elevation((0, 3, 232)), Some(10.));
+/// assert_eq!(gsi_png_elevation((128, 0, 0)), None);
+/// ```
+pub fn gsi_png_elevation(rgb: (u8, u8, u8)) -> Option<f64> {
+    let (r, g, b) = rgb;
+    let x = ((r as i32) << 16) + ((g as i32) << 8) + b as i32;
+
+    match x {
+        0x800000 => None,
+        x if x < 0x800000 => Some(x as f64 * 0.01),
+        x => Some((x - 0x1000000) as f64 * 0.01),
+    }
+}
+
+/// Function to return the elevation (m) at (longitude, latitude) expressed in the arc degree method by bilinear interpolation between the centers of the four surrounding pixels.
+/// `tiles` returns the elevation tile at the Zoom level `zoom` for tile coordinates, so that points near a tile boundary are interpolated across neighboring tiles; the x coordinate wraps around the antimeridian.
+/// Pixels without data or in tiles for which `tiles` returns `None` are left out and the remaining weights are renormalized. Returns `None` if none of the four pixels has data.
+///
+/// 弧度法で表された(経度, 緯度)の標高(m)を、周囲の4つのピクセルの中心の間の双線形補間で返す関数。
+/// `tiles`はタイル座標に対してZoomレベル`zoom`の標高タイルを返し、タイルの境界付近の点は隣接するタイルをまたいで補間される。x座標は180度経線で折り返す。
+/// 値を持たないピクセル、および`tiles`が`None`を返すタイルのピクセルは除外され、残りの重みが正規化される。4つのピクセルのいずれも値を持たない場合は`None`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::elevation::{elevation_at, ElevationTile};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let tile = ElevationTile::new((0, 0), ZoomLv::Lv0, vec![Some(10.); 256 * 256]).unwrap();
+///
+/// let elevation = elevation_at((139_f64.to_radians(), 35_f64.to_radians()), ZoomLv::Lv0, |_| Some(&tile));
+///
+/// assert_eq!(elevation, Some(10.));
+/// ```
+pub fn elevation_at<'a, F>(ll: (f64, f64), zoom: ZoomLv, mut tiles: F) -> Option<f64>
+where
+    F: FnMut((u32, u32)) -> Option<&'a ElevationTile>,
+{
+    let world = world_size_px(zoom) as i64;
+    let (px, py) = ll2pixel_f64(ll, zoom);
+
+    // 値はピクセルの中心にあるとみなす
+    let (u, v) = (px - 0.5, py - 0.5);
+    let (x0, y0) = (u.floor(), v.floor());
+    let (fx, fy) = (u - x0, v - y0);
+
+    let mut sum = 0.;
+    let mut weight_sum = 0.;
+    for (dx, dy, weight) in [
+        (0, 0, (1. - fx) * (1. - fy)),
+        (1, 0, fx * (1. - fy)),
+        (0, 1, (1. - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        let x = (x0 as i64 + dx).rem_euclid(world) as u32;
+        let y = (y0 as i64 + dy).clamp(0, world - 1) as u32;
+
+        let value = tiles((x / TILE_PX, y / TILE_PX))
+            .filter(|tile| tile.zoom == zoom)
+            .and_then(|tile| tile.get(x % TILE_PX, y % TILE_PX));
+
+        if let Some(value) = value {
+            sum += value * weight;
+            weight_sum += weight;
+        }
+    }
+
+    (weight_sum > 0.).then(|| sum / weight_sum)
+}
+
+/// Enumerated type representing errors when building an elevation tile
+///
+/// 標高タイルを作成する際のエラーを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElevationError {
+    /// The number of values does not match the size of the tile
+    ///
+    /// 値の数がタイルの大きさと一致しない
+    InvalidLength {
+        /// Number of values of the tile
+        ///
+        /// タイルの値の数
+        expected: usize,
+        /// Number of values given
+        ///
+        /// 与えられた値の数
+        actual: usize,
+    },
+    /// The value at the row and column (counted from 0) is neither a number nor the no-data value
+    ///
+    /// 行と列(0から数える)の値が数値でも無効値でもない
+    InvalidValue {
+        /// Row (counted from 0)
+        ///
+        /// 行(0から数える)
+        row: usize,
+        /// Column (counted from 0)
+        ///
+        /// 列(0から数える)
+        column: usize,
+    },
+}
+
+impl Display for ElevationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElevationError::InvalidLength { expected, actual } => {
+                write!(f, "expected {} values, but got {}", expected, actual)
+            }
+            ElevationError::InvalidValue { row, column } => {
+                write!(f, "invalid value at row {}, column {}", row, column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ElevationError {}
+
+/// Structure representing the decoded elevation values (m) of a 256 x 256 pixel tile, in row-major order from the upper left
+///
+/// 256 x 256ピクセルのタイルの復号された標高(m)を、左上から行優先の順で保持する構造体
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ElevationTile {
+    tile: (u32, u32),
+    zoom: ZoomLv,
+    values: Vec<Option<f64>>,
+}
+
+impl ElevationTile {
+    /// Create a new elevation tile from decoded values.
+    /// Returns an error if the number of values is not 256 x 256.
+    ///
+    /// 復号された値から標高タイルを新しく作成する。
+    /// 値の数が256 x 256でない場合はエラーを返す。
+    pub fn new(tile: (u32, u32), zoom: ZoomLv, values: Vec<Option<f64>>) -> Result<Self, ElevationError> {
+        let expected = (TILE_PX * TILE_PX) as usize;
+        if values.len() != expected {
+            return Err(ElevationError::InvalidLength {
+                expected,
+                actual: values.len(),
+            });
+        }
+
+        Ok(Self { tile, zoom, values })
+    }
+
+    /// Create a new elevation tile from the RGB bytes of a decoded GSI PNG elevation tile
+    ///
+    /// 復号された地理院のPNG形式の標高タイルのRGBのバイト列から標高タイルを新しく作成する
+    pub fn from_gsi_png_rgb(tile: (u32, u32), zoom: ZoomLv, rgb: &[u8]) -> Result<Self, ElevationError> {
+        let expected = (TILE_PX * TILE_PX * 3) as usize;
+        if rgb.len() != expected {
+            return Err(ElevationError::InvalidLength {
+                expected,
+                actual: rgb.len(),
+            });
+        }
+
+        let values = rgb
+            .chunks_exact(3)
+            .map(|p| gsi_png_elevation((p[0], p[1], p[2])))
+            .collect();
+
+        Self::new(tile, zoom, values)
+    }
+
+    /// Create a new elevation tile from the text of a GSI TXT elevation tile, which has 256 lines of 256 comma-separated values with `e` for no data.
+    /// Returns an error if the number of lines or of values in a line is not 256.
+    ///
+    /// 地理院のTXT形式の標高タイルの文字列から標高タイルを新しく作成する。この形式は256行のカンマ区切りの256個の値からなり、無効値は`e`で表される。
+    /// 行数または1行の値の数が256でない場合はエラーを返す。
+    pub fn from_gsi_txt(tile: (u32, u32), zoom: ZoomLv, text: &str) -> Result<Self, ElevationError> {
+        let rows = text.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>();
+        if rows.len() != TILE_PX as usize {
+            return Err(ElevationError::InvalidLength {
+                expected: TILE_PX as usize,
+                actual: rows.len(),
+            });
+        }
+
+        let mut values = Vec::with_capacity((TILE_PX * TILE_PX) as usize);
+        for (row, line) in rows.iter().enumerate() {
+            let start = values.len();
+
+            for (column, value) in line.split(',').enumerate() {
+                let value = match value.trim() {
+                    "e" => None,
+                    v => Some(v.parse::<f64>().map_err(|_| ElevationError::InvalidValue { row, column })?),
+                };
+                values.push(value);
+            }
+
+            if values.len() - start != TILE_PX as usize {
+                return Err(ElevationError::InvalidLength {
+                    expected: TILE_PX as usize,
+                    actual: values.len() - start,
+                });
+            }
+        }
+
+        Self::new(tile, zoom, values)
+    }
+
+    /// Returns the tile coordinates
+    ///
+    /// タイル座標を返す
+    pub fn tile(&self) -> (u32, u32) {
+        self.tile
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom
+    }
+
+    /// Returns the elevation (m) at the pixel (x, y) within the tile, or `None` for no data
+    ///
+    /// タイル内のピクセル(x, y)の標高(m)を返す。無効値の場合は`None`を返す
+    pub fn get(&self, x: u32, y: u32) -> Option<f64> {
+        assert!(x < TILE_PX && y < TILE_PX, "pixel out of tile");
+        self.values[(y * TILE_PX + x) as usize]
+    }
+
+    /// Returns the elevation (m) at (longitude, latitude) expressed in the arc degree method by bilinear interpolation within this tile only, following `elevation_at`
+    ///
+    /// `elevation_at`に従って、このタイルのみを用いた双線形補間で弧度法で表された(経度, 緯度)の標高(m)を返す
+    pub fn elevation_at(&self, ll: (f64, f64)) -> Option<f64> {
+        elevation_at(ll, self.zoom, |tile| (tile == self.tile).then_some(self))
+    }
+
+    /// Returns the vertical resolution (m) of the voxels, which is the pixel resolution at the latitude of the tile center
+    ///
+    /// ボクセルの鉛直方向の分解能(m)を返す。これはタイルの中心の緯度におけるピクセルの分解能である
+    pub fn voxel_resolution(&self) -> f64 {
+        let (tx, ty) = self.tile;
+        voxel_resolution((tx * TILE_PX, ty * TILE_PX), self.zoom)
+    }
+
+    /// Returns the voxel of the ground surface of each pixel with data.
+    /// Negative elevations are clamped to 0 because voxel heights are unsigned.
+    ///
+    /// 値を持つ各ピクセルの地表面のボクセルを返す。
+    /// ボクセルの高さは符号なしのため、負の標高は0に丸められる。
+    pub fn to_voxels(&self) -> Vec<Voxel> {
+        let resolution = self.voxel_resolution();
+
+        self.surface()
+            .map(|(x, y, z)| Voxel::new(x, y, z, resolution, self.zoom))
+            .collect()
+    }
+
+    /// Returns the voxel columns from height 0 up to the ground surface of each pixel with data
+    ///
+    /// 値を持つ各ピクセルについて、高さ0から地表面までのボクセルの柱を返す
+    pub fn to_voxel_columns(&self) -> impl Iterator<Item=Voxel> + '_ {
+        let resolution = self.voxel_resolution();
+
+        self.surface()
+            .flat_map(move |(x, y, top)| (0..=top).map(move |z| Voxel::new(x, y, z, resolution, self.zoom)))
+    }
+
+    /// Returns (x, y, z) of the ground surface in voxel coordinates
+    ///
+    /// ボクセル座標における地表面の(x, y, z)を返す
+    fn surface(&self) -> impl Iterator<Item=(u32, u32, u32)> + '_ {
+        let resolution = self.voxel_resolution();
+        let (tx, ty) = self.tile;
+
+        self.values.iter().enumerate().filter_map(move |(i, value)| {
+            let h = (*value)?;
+            let (x, y) = (i as u32 % TILE_PX, i as u32 / TILE_PX);
+            let z = (h.max(0.) / resolution) as u32;
+            Some((tx * TILE_PX + x, ty * TILE_PX + y, z))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::pixel_ll::pixel2ll_f64;
+
+    use super::*;
+
+    #[test]
+    fn gsi_png_elevation_works() {
+        assert_eq!(gsi_png_elevation((0, 0, 1)), Some(0.01));
+        assert_eq!(gsi_png_elevation((255, 255, 255)), Some(-0.01));
+        assert_eq!(gsi_png_elevation((128, 0, 0)), None);
+    }
+
+    #[test]
+    fn elevation_tile_to_voxels_works() {
+        // 富士山付近のZoomレベル14のタイル
+        let mut values = vec![None; 256 * 256];
+        values[0] = Some(3776.);
+        values[1] = Some(-5.);
+        let tile = ElevationTile::new((14525, 6478), ZoomLv::Lv14, values).unwrap();
+
+        let resolution = tile.voxel_resolution();
+        let voxels = tile.to_voxels();
+        assert_eq!(voxels.len(), 2);
+        assert_eq!(voxels[0].to_tuple(), (14525 * 256, 6478 * 256, (3776. / resolution) as u32));
+        assert_eq!(voxels[1].to_tuple().2, 0);
+
+        let (_, altitude) = voxels[0].to_ll_with_altitude();
+        assert!((3776. - altitude).abs() < resolution);
+
+        let columns = tile.to_voxel_columns().count();
+        assert_eq!(columns, (3776. / resolution) as usize + 1 + 1);
+
+        assert_eq!(
+            ElevationTile::from_gsi_png_rgb((0, 0), ZoomLv::Lv0, &[0; 3]),
+            Err(ElevationError::InvalidLength {
+                expected: 256 * 256 * 3,
+                actual: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn elevation_at_stitches_tiles() {
+        // 値が世界全体でのピクセルのx座標と等しい、Zoomレベル1の上段の2つのタイル
+        let gradient = |tx: u32| {
+            let values = (0..256 * 256).map(|i| Some((tx * 256 + i % 256) as f64)).collect();
+            ElevationTile::new((tx, 0), ZoomLv::Lv1, values).unwrap()
+        };
+        let tiles = [gradient(0), gradient(1)];
+        let lookup = |(tx, ty): (u32, u32)| tiles.iter().find(|t| t.tile() == (tx, ty));
+
+        // タイルの境界上の点は両側のピクセルの中間の値となる
+        let boundary = pixel2ll_f64((256., 100.5), ZoomLv::Lv1);
+        assert_close_to(elevation_at(boundary, ZoomLv::Lv1, lookup).unwrap(), 255.5, 9);
+
+        let inner = pixel2ll_f64((10.25, 100.5), ZoomLv::Lv1);
+        assert_close_to(elevation_at(inner, ZoomLv::Lv1, lookup).unwrap(), 9.75, 9);
+
+        // 隣接するタイルがない場合は、このタイルの値のみで補間する
+        assert_close_to(tiles[0].elevation_at(boundary).unwrap(), 255., 9);
+        assert_eq!(elevation_at(boundary, ZoomLv::Lv1, |_| None), None);
+    }
+
+    #[test]
+    fn from_gsi_txt_works() {
+        let mut rows = vec![vec!["e"; 256].join(","); 256];
+        rows[0] = std::iter::once("12.34").chain(std::iter::repeat_n("-0.5", 255)).collect::<Vec<_>>().join(",");
+        let text = rows.join("\r\n") + "\r\n";
+
+        let tile = ElevationTile::from_gsi_txt((0, 0), ZoomLv::Lv14, &text).unwrap();
+        assert_eq!(tile.get(0, 0), Some(12.34));
+        assert_eq!(tile.get(255, 0), Some(-0.5));
+        assert_eq!(tile.get(0, 1), None);
+
+        rows[3] = rows[3].replacen("e", "x", 2);
+        assert_eq!(
+            ElevationTile::from_gsi_txt((0, 0), ZoomLv::Lv14, &rows.join("\n")),
+            Err(ElevationError::InvalidValue { row: 3, column: 0 })
+        );
+        assert_eq!(
+            ElevationTile::from_gsi_txt((0, 0), ZoomLv::Lv14, &rows[..10].join("\n")),
+            Err(ElevationError::InvalidLength {
+                expected: 256,
+                actual: 10,
+            })
+        );
+    }
+}
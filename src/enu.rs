@@ -0,0 +1,221 @@
+//! Local east-north-up (ENU) frames on the GRS80 ellipsoid and the matrices placing them in 3D scenes.
+//! Matrices are `[[f64; 4]; 4]` in row-major order (`m[row][column]`) acting on column vectors, so the translation is in the last column.
+//!
+//! GRS80楕円体上の局所的な東-北-上(ENU)座標系と、それを3次元のシーンに配置する行列。
+//! 行列は列ベクトルに作用する行優先(`m[行][列]`)の`[[f64; 4]; 4]`であり、平行移動は最後の列にある。
+
+use crate::math;
+use crate::structure::LLZ;
+use crate::xyz_ll::llz2xyz;
+
+/// Returns the unit vectors (east, north, up) of the ENU frame at (longitude, latitude) expressed in the arc degree method, in the Cartesian coordinate system (EPSG:4979)
+///
+/// 弧度法で表された(経度, 緯度)におけるENU座標系の単位ベクトル(東, 北, 上)を直交座標系(EPSG:4979)で返す
+fn enu_basis(ll: (f64, f64)) -> [[f64; 3]; 3] {
+    let (sin_long, cos_long) = math::sin_cos(ll.0);
+    let (sin_lat, cos_lat) = math::sin_cos(ll.1);
+
+    [
+        [-sin_long, cos_long, 0.],
+        [-sin_lat * cos_long, -sin_lat * sin_long, cos_lat],
+        [cos_lat * cos_long, cos_lat * sin_long, sin_lat],
+    ]
+}
+
+/// Function to return the matrix transforming the local ENU frame (east, north, up in meters) at `origin` to the Cartesian coordinate system (EPSG:4979), e.g. for the `transform` of 3D Tiles
+///
+/// `origin`における局所的なENU座標系(東, 北, 上、メートル単位)を直交座標系(EPSG:4979)に変換する行列を返す関数。3D Tilesの`transform`などに用いる
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::enu::east_north_up_to_ecef_matrix;
+/// use coordinate_transformer::structure::LLZ;
+/// use coordinate_transformer::xyz_ll::llz2xyz;
+///
+/// let origin = LLZ::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians(), 40.);
+///
+/// let m = east_north_up_to_ecef_matrix(&origin);
+/// let (x, y, z) = llz2xyz(origin.to_tuple().0, 40.);
+///
+/// assert_eq!([m[0][3], m[1][3], m[2][3]], [x, y, z]);
+/// ```
+pub fn east_north_up_to_ecef_matrix(origin: &LLZ) -> [[f64; 4]; 4] {
+    let (ll, altitude) = origin.to_tuple();
+    let basis = enu_basis(ll);
+    let (x, y, z) = llz2xyz(ll, altitude);
+    let translation = [x, y, z];
+
+    let mut m = [[0.; 4]; 4];
+    for (row, m_row) in m.iter_mut().take(3).enumerate() {
+        for (column, axis) in basis.iter().enumerate() {
+            m_row[column] = axis[row];
+        }
+        m_row[3] = translation[row];
+    }
+    m[3][3] = 1.;
+
+    m
+}
+
+/// Function to return the matrix transforming the Cartesian coordinate system (EPSG:4979) to the local ENU frame (east, north, up in meters) at `origin`, the inverse of `east_north_up_to_ecef_matrix`
+///
+/// 直交座標系(EPSG:4979)を`origin`における局所的なENU座標系(東, 北, 上、メートル単位)に変換する行列を返す関数。`east_north_up_to_ecef_matrix`の逆行列
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::enu::ecef_to_east_north_up_matrix;
+/// use coordinate_transformer::structure::LLZ;
+/// use coordinate_transformer::xyz_ll::llz2xyz;
+///
+/// let origin = LLZ::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians(), 40.);
+/// let (x, y, z) = llz2xyz(origin.to_tuple().0, 140.);
+///
+/// let m = ecef_to_east_north_up_matrix(&origin);
+/// let up = m[2][0] * x + m[2][1] * y + m[2][2] * z + m[2][3];
+///
+/// assert!((up - 100.).abs() < 1e-6);
+/// ```
+pub fn ecef_to_east_north_up_matrix(origin: &LLZ) -> [[f64; 4]; 4] {
+    let (ll, altitude) = origin.to_tuple();
+    let basis = enu_basis(ll);
+    let (x, y, z) = llz2xyz(ll, altitude);
+
+    // 回転の逆は転置であり、平行移動は回転した原点を打ち消す
+    let mut m = [[0.; 4]; 4];
+    for (m_row, axis) in m.iter_mut().zip(basis.iter()) {
+        m_row[..3].copy_from_slice(axis);
+        m_row[3] = -(axis[0] * x + axis[1] * y + axis[2] * z);
+    }
+    m[3][3] = 1.;
+
+    m
+}
+
+/// Returns the product `a` · `b` of matrices
+///
+/// 行列の積`a` · `b`を返す
+fn multiply(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    let mut m = [[0.; 4]; 4];
+    for (m_row, a_row) in m.iter_mut().zip(a.iter()) {
+        for (column, value) in m_row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a_row[k] * b[k][column]).sum();
+        }
+    }
+
+    m
+}
+
+/// Function to return the matrix placing y-up glTF content modelled in meters in the local ENU frame at `origin` into a y-up glTF or three.js scene whose world coordinates are the Cartesian coordinates (EPSG:4979).
+/// The content axes are x = east, y = up and z = south, and the scene axes are x = X, y = Z and z = -Y of EPSG:4979, as when a z-up Earth is shown in a y-up viewer. Both are right-handed.
+/// glTF `node.matrix` and three.js `Matrix4.fromArray` take the column-major order, see `matrix_to_column_major`.
+///
+/// `origin`における局所的なENU座標系でメートル単位でモデル化されたy-upのglTFのコンテンツを、直交座標系(EPSG:4979)を世界座標とするy-upのglTFまたはthree.jsのシーンに配置する行列を返す関数。
+/// コンテンツの軸はx = 東、y = 上、z = 南であり、シーンの軸はz-upの地球をy-upのビューアで表示する場合と同様にEPSG:4979のx = X、y = Z、z = -Yである。いずれも右手系である。
+/// glTFの`node.matrix`とthree.jsの`Matrix4.fromArray`は列優先の順をとるため、`matrix_to_column_major`を参照。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::enu::enu_to_gltf_matrix;
+/// use coordinate_transformer::structure::LLZ;
+///
+/// let origin = LLZ::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians(), 40.);
+///
+/// let m = enu_to_gltf_matrix(&origin);
+///
+/// // 行列式は1(回転は鏡映を含まない)
+/// let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+/// - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+/// + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+/// assert!((det - 1.).abs() < 1e-12);
+/// ```
+pub fn enu_to_gltf_matrix(origin: &LLZ) -> [[f64; 4]; 4] {
+    // EPSG:4979の(X, Y, Z)をシーンの(X, Z, -Y)に入れ替える
+    const ECEF_TO_SCENE: [[f64; 4]; 4] = [[1., 0., 0., 0.], [0., 0., 1., 0.], [0., -1., 0., 0.], [0., 0., 0., 1.]];
+    // コンテンツの(東, 上, 南)をENUの(東, 北, 上)に入れ替える
+    const CONTENT_TO_ENU: [[f64; 4]; 4] = [[1., 0., 0., 0.], [0., 0., -1., 0.], [0., 1., 0., 0.], [0., 0., 0., 1.]];
+
+    multiply(&multiply(&ECEF_TO_SCENE, &east_north_up_to_ecef_matrix(origin)), &CONTENT_TO_ENU)
+}
+
+/// Function to convert a row-major matrix to the column-major array taken by glTF `node.matrix`, three.js `Matrix4.fromArray` and 3D Tiles `transform`
+///
+/// 行優先の行列を、glTFの`node.matrix`、three.jsの`Matrix4.fromArray`、3D Tilesの`transform`がとる列優先の配列に変換する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::enu::matrix_to_column_major;
+///
+/// let m = [[1., 0., 0., 5.], [0., 1., 0., 6.], [0., 0., 1., 7.], [0., 0., 0., 1.]];
+///
+/// assert_eq!(matrix_to_column_major(&m)[12..15], [5., 6., 7.]);
+/// ```
+pub fn matrix_to_column_major(m: &[[f64; 4]; 4]) -> [f64; 16] {
+    let mut array = [0.; 16];
+    for (i, value) in array.iter_mut().enumerate() {
+        *value = m[i % 4][i / 4];
+    }
+
+    array
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    fn apply(m: &[[f64; 4]; 4], p: [f64; 3]) -> [f64; 3] {
+        [0, 1, 2].map(|row| m[row][0] * p[0] + m[row][1] * p[1] + m[row][2] * p[2] + m[row][3])
+    }
+
+    #[test]
+    fn enu_to_gltf_matrix_places_content() {
+        let origin = LLZ::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians(), 40.);
+        let (ll, altitude) = origin.to_tuple();
+        let m = enu_to_gltf_matrix(&origin);
+        let to_scene = |(x, y, z): (f64, f64, f64)| [x, z, -y];
+
+        // 原点はシーンのEPSG:4979の位置に置かれる
+        let scene_origin = to_scene(llz2xyz(ll, altitude));
+        for (a, b) in apply(&m, [0., 0., 0.]).iter().zip(scene_origin) {
+            assert_close_to(*a, b, 6);
+        }
+
+        // コンテンツの+yは鉛直上方、-zは北
+        let above = to_scene(llz2xyz(ll, altitude + 100.));
+        for (a, b) in apply(&m, [0., 100., 0.]).iter().zip(above) {
+            assert_close_to(*a, b, 6);
+        }
+        let north = apply(&m, [0., 0., -1.]);
+        let [_, n, _] = enu_basis(ll);
+        for (a, b) in north.iter().zip(scene_origin).zip(to_scene((n[0], n[1], n[2]))) {
+            assert_close_to(*a.0 - a.1, b, 6);
+        }
+    }
+
+    #[test]
+    fn ecef_to_east_north_up_matrix_is_inverse() {
+        let origin = LLZ::new(-70_f64.to_radians(), -33_f64.to_radians(), 500.);
+        let product = multiply(&ecef_to_east_north_up_matrix(&origin), &east_north_up_to_ecef_matrix(&origin));
+
+        for (row, product_row) in product.iter().enumerate() {
+            for (column, &value) in product_row.iter().enumerate() {
+                let expected = if row == column { 1. } else { 0. };
+                assert_close_to(value, expected, 9);
+            }
+        }
+
+        // 東に10m進んだ点はENUで(10, 0, 0)付近となる
+        let m = ecef_to_east_north_up_matrix(&origin);
+        let [east, _, _] = enu_basis(origin.to_tuple().0);
+        let (x, y, z) = llz2xyz(origin.to_tuple().0, 500.);
+        let enu = apply(&m, [x + 10. * east[0], y + 10. * east[1], z + 10. * east[2]]);
+        assert_close_to(enu[0], 10., 6);
+        assert_close_to(enu[1], 0., 6);
+        assert_close_to(enu[2], 0., 6);
+    }
+}
@@ -0,0 +1,134 @@
+//! Epochs of coordinates expressed as decimal years, and coordinates tagged with them.
+//! Coordinates of the epoch of observation move with the crust, so the epoch is kept together with the coordinate values to make conversions between epochs explicit.
+//!
+//! 十進年で表した座標の元期・観測時などのエポックと、それを付けた座標。
+//! 観測時の座標は地殻とともに動くため、エポックを座標値とともに保持して、エポック間の変換を明示的にする。
+
+use std::fmt::{Display, Formatter};
+
+/// Structure representing an epoch expressed as a decimal year, e.g. 2015.5 for the beginning of July 2015
+///
+/// 十進年で表したエポックを表す構造体。例えば2015年7月初めは2015.5
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::epoch::Epoch;
+///
+/// let observed = Epoch::new(2015.5);
+/// let target = Epoch::new(2024.);
+///
+/// assert_eq!(observed.years_until(target), 8.5);
+/// assert_eq!(observed.to_string(), "2015.5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Epoch(f64);
+
+impl Epoch {
+    /// Create an epoch from a decimal year
+    ///
+    /// 十進年からエポックを作成する
+    pub fn new(year: f64) -> Self {
+        Self(year)
+    }
+
+    /// Create an epoch from the year and the day of the year counted from 1, dividing the year into 365 or 366 days
+    ///
+    /// 年と1から数えた年の通日からエポックを作成する。1年は365日または366日に分ける
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::epoch::Epoch;
+    ///
+    /// assert_eq!(Epoch::from_day_of_year(2024, 1), Epoch::new(2024.));
+    /// assert_eq!(Epoch::from_day_of_year(2023, 183).year(), 2023. + 182. / 365.);
+    /// ```
+    pub fn from_day_of_year(year: i32, day: u32) -> Self {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days = if leap { 366. } else { 365. };
+
+        Self(year as f64 + (day as f64 - 1.) / days)
+    }
+
+    /// Returns the decimal year
+    ///
+    /// 十進年を返す
+    pub fn year(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns the years from this epoch to `other`, negative if `other` is earlier
+    ///
+    /// このエポックから`other`までの年数を返す。`other`の方が前の場合は負になる
+    pub fn years_until(&self, other: Epoch) -> f64 {
+        other.0 - self.0
+    }
+}
+
+impl Display for Epoch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Structure representing coordinate values tagged with the epoch they refer to
+///
+/// 参照するエポックを付けた座標値を表す構造体
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::epoch::{Epoch, Epoched};
+///
+/// let point = Epoched::new(((140_f64.to_radians(), 36_f64.to_radians()), 50.), Epoch::new(2015.));
+///
+/// assert_eq!(point.epoch, Epoch::new(2015.));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Epoched<T> {
+    /// Coordinate values
+    ///
+    /// 座標値
+    pub coordinate: T,
+    /// Epoch of the coordinate values
+    ///
+    /// 座標値のエポック
+    pub epoch: Epoch,
+}
+
+impl<T> Epoched<T> {
+    /// Create coordinate values tagged with the epoch
+    ///
+    /// エポックを付けた座標値を作成する
+    pub fn new(coordinate: T, epoch: Epoch) -> Self {
+        Self { coordinate, epoch }
+    }
+
+    /// Convert the coordinate values with `f`, keeping the epoch
+    ///
+    /// エポックを保ったまま、座標値を`f`で変換する
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Epoched<U> {
+        Epoched::new(f(self.coordinate), self.epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_works() {
+        assert_eq!(Epoch::from_day_of_year(2024, 366).year(), 2024. + 365. / 366.);
+        assert_eq!(Epoch::from_day_of_year(1900, 59), Epoch::new(1900. + 58. / 365.));
+        assert_eq!(Epoch::new(2024.).years_until(Epoch::new(2015.)), -9.);
+        assert!(Epoch::new(2015.) < Epoch::new(2024.));
+    }
+
+    #[test]
+    fn epoched_map_keeps_epoch() {
+        let point = Epoched::new((1., 2.), Epoch::new(2011.));
+
+        assert_eq!(point.map(|(a, b)| a + b), Epoched::new(3., Epoch::new(2011.)));
+    }
+}
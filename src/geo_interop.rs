@@ -0,0 +1,142 @@
+//! Reprojection of `geo` geometries
+//! Each function returns a closure that can be passed to `geo::MapCoords::map_coords`, so that an entire `geo::Geometry` can be reprojected at once.
+//! Longitude and latitude are expressed in the degree method as x = longitude and y = latitude, following the convention of `geo`.
+//! Plane rectangular coordinates are expressed as x = y (easting) and y = x (northing) so that the axes match those of `geo`.
+//!
+//! `geo`のジオメトリの再投影
+//! 各関数は`geo::MapCoords::map_coords`に渡せるクロージャを返すため、`geo::Geometry`全体を一度に再投影できる。
+//! 緯経度は`geo`の慣習に従い、x = 経度、y = 緯度として度数法で表す。
+//! 平面直角座標は軸を`geo`に合わせるため、x = y(東方向)、y = x(北方向)として表す。
+
+use geo::Coord;
+
+use crate::pixel_ll::{ll2pixel_f64, pixel2ll_f64};
+use crate::{jpr2ll, ll2jpr, JprOrigin, ZoomLv};
+
+/// Returns a closure converting plane rectangular coordinates to longitude and latitude.
+///
+/// 平面直角座標を緯経度に変換するクロージャを返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::geo_interop::jpr_to_ll;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use geo::{polygon, MapCoords};
+///
+/// let polygon = polygon![(x: 0., y: 0.), (x: 1000., y: 0.), (x: 1000., y: 1000.)];
+/// let ll = polygon.map_coords(jpr_to_ll(JprOrigin::Nine));
+/// ```
+pub fn jpr_to_ll(origin: JprOrigin) -> impl Fn(Coord<f64>) -> Coord<f64> + Copy {
+    move |coord| {
+        let (long, lat) = jpr2ll((coord.x, coord.y), origin);
+        Coord {
+            x: long.to_degrees(),
+            y: lat.to_degrees(),
+        }
+    }
+}
+
+/// Returns a closure converting longitude and latitude to plane rectangular coordinates.
+///
+/// 緯経度を平面直角座標に変換するクロージャを返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::geo_interop::ll_to_jpr;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use geo::{point, MapCoords};
+///
+/// let point = point!(x: 139.7649308, y: 35.6812405);
+/// let jpr = point.map_coords(ll_to_jpr(JprOrigin::Nine));
+/// ```
+pub fn ll_to_jpr(origin: JprOrigin) -> impl Fn(Coord<f64>) -> Coord<f64> + Copy {
+    move |coord| {
+        let (y, x) = ll2jpr((coord.x.to_radians(), coord.y.to_radians()), origin);
+        Coord { x: y, y: x }
+    }
+}
+
+/// Returns a closure converting longitude and latitude to fractional pixel coordinates.
+///
+/// 緯経度を小数のピクセル座標に変換するクロージャを返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::geo_interop::ll_to_pixel;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use geo::{line_string, MapCoords};
+///
+/// let line = line_string![(x: 139., y: 35.), (x: 140., y: 36.)];
+/// let pixels = line.map_coords(ll_to_pixel(ZoomLv::Lv10));
+/// ```
+pub fn ll_to_pixel(zoom: ZoomLv) -> impl Fn(Coord<f64>) -> Coord<f64> + Copy {
+    move |coord| {
+        let (x, y) = ll2pixel_f64((coord.x.to_radians(), coord.y.to_radians()), zoom);
+        Coord { x, y }
+    }
+}
+
+/// Returns a closure converting fractional pixel coordinates to longitude and latitude.
+///
+/// 小数のピクセル座標を緯経度に変換するクロージャを返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::geo_interop::pixel_to_ll;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use geo::{point, MapCoords};
+///
+/// let ll = point!(x: 128., y: 128.).map_coords(pixel_to_ll(ZoomLv::Lv0));
+/// ```
+pub fn pixel_to_ll(zoom: ZoomLv) -> impl Fn(Coord<f64>) -> Coord<f64> + Copy {
+    move |coord| {
+        let (long, lat) = pixel2ll_f64((coord.x, coord.y), zoom);
+        Coord {
+            x: long.to_degrees(),
+            y: lat.to_degrees(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+    use geo::{polygon, Geometry, MapCoords, MultiPolygon};
+
+    use super::*;
+
+    #[test]
+    fn geometry_round_trip_works() {
+        let multi_polygon = MultiPolygon::new(vec![
+            polygon![(x: 139., y: 35.), (x: 140., y: 35.), (x: 140., y: 36.)],
+            polygon![(x: 139.5, y: 35.5), (x: 139.6, y: 35.5), (x: 139.6, y: 35.6)],
+        ]);
+        let geometry = Geometry::MultiPolygon(multi_polygon.clone());
+
+        let jpr = geometry.map_coords(ll_to_jpr(JprOrigin::Nine));
+        let ll = jpr.map_coords(jpr_to_ll(JprOrigin::Nine));
+
+        let Geometry::MultiPolygon(result) = ll else {
+            panic!("geometry type changed");
+        };
+        for (p, q) in multi_polygon.iter().zip(result.iter()) {
+            for (a, b) in p.exterior().coords().zip(q.exterior().coords()) {
+                assert_close_to(a.x, b.x, 9);
+                assert_close_to(a.y, b.y, 9);
+            }
+        }
+    }
+
+    #[test]
+    fn ll_to_jpr_axes_work() {
+        let coord = ll_to_jpr(JprOrigin::Nine)(Coord { x: 139.8333333333333, y: 36. });
+        let (y, x) = ll2jpr((139.8333333333333_f64.to_radians(), 36_f64.to_radians()), JprOrigin::Nine);
+
+        assert_close_to(coord.x, y, 9);
+        assert_close_to(coord.y, x, 9);
+    }
+}
@@ -0,0 +1,278 @@
+use std::f64::consts::PI;
+
+use crate::consts::{GRS80_FLATTENING, GRS80_SEMI_MAJOR_AXIS};
+use crate::math;
+use crate::precision::meters_per_radian;
+
+/// Computes the point reached by travelling `distance_m` (m) from (longitude, latitude) expressed in the arc degree method along the geodesic with the initial `bearing`.
+/// The bearing is expressed in the arc degree method, measured clockwise from north.
+/// The calculation is performed on the GRS80 ellipsoid using Vincenty's direct formula.
+/// Returns NaN if any of the inputs is NaN or infinite.
+///
+/// 弧度法で表された(経度, 緯度)から、初期方位角`bearing`の測地線に沿って`distance_m`(m)進んだ地点を求める。
+/// 方位角は弧度法で表し、北から時計回りに測る。
+/// 計算はGRS80楕円体上でVincentyの順解法を用いて行う。
+/// 入力のいずれかがNaNまたは無限大の場合はNaNを返す。
+///
+/// # Examples
+///
+/// Calculate the point 1 km north of a given point.
+///
+/// ある地点から北に1km進んだ地点を求める。
+///
+/// ```
+/// use coordinate_transformer::geodesic::destination;
+///
+/// let (long, lat) = destination(
+/// (140_f64.to_radians(), 36_f64.to_radians()),
+/// 0_f64.to_radians(),
+/// 1000.,
+/// );
+/// ```
+pub fn destination(ll: (f64, f64), bearing: f64, distance_m: f64) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    // 反復が収束しないため、有限でない入力は計算しない
+    if !(long.is_finite() && lat.is_finite() && bearing.is_finite() && distance_m.is_finite()) {
+        return (f64::NAN, f64::NAN);
+    }
+
+    const A: f64 = GRS80_SEMI_MAJOR_AXIS;
+    const F: f64 = GRS80_FLATTENING;
+    const B: f64 = A * (1. - F); // 短半径
+
+    let (sin_alpha1, cos_alpha1) = math::sin_cos(bearing);
+
+    // 化成緯度
+    let tan_u1 = (1. - F) * math::tan(lat);
+    let cos_u1 = 1. / (1. + tan_u1 * tan_u1).sqrt();
+    let sin_u1 = tan_u1 * cos_u1;
+
+    let sigma1 = math::atan2(tan_u1, cos_alpha1);
+    let sin_alpha = cos_u1 * sin_alpha1;
+    let cos2_alpha = 1. - sin_alpha * sin_alpha;
+
+    let u2 = cos2_alpha * (A * A - B * B) / (B * B);
+    let a_ = 1. + u2 / 16384. * (4096. + u2 * (-768. + u2 * (320. - 175. * u2)));
+    let b_ = u2 / 1024. * (256. + u2 * (-128. + u2 * (74. - 47. * u2)));
+
+    let mut sigma = distance_m / (B * a_);
+    let mut cos_2sigma_m;
+    let mut sin_sigma;
+    let mut cos_sigma;
+
+    // 順解法の反復は通常数回で収束する
+    for _ in 0..32 {
+        cos_2sigma_m = math::cos(2. * sigma1 + sigma);
+        (sin_sigma, cos_sigma) = math::sin_cos(sigma);
+
+        let delta_sigma = b_
+            * sin_sigma
+            * (cos_2sigma_m
+            + b_ / 4.
+            * (cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)
+            - b_ / 6.
+            * cos_2sigma_m
+            * (-3. + 4. * sin_sigma * sin_sigma)
+            * (-3. + 4. * cos_2sigma_m * cos_2sigma_m)));
+
+        let next_sigma = distance_m / (B * a_) + delta_sigma;
+
+        if (sigma - next_sigma).abs() < 1e-12 {
+            sigma = next_sigma;
+            break;
+        }
+        sigma = next_sigma;
+    }
+
+    cos_2sigma_m = math::cos(2. * sigma1 + sigma);
+    (sin_sigma, cos_sigma) = math::sin_cos(sigma);
+
+    let tmp = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+    let lat2 = math::atan2(
+        sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1,
+        (1. - F) * (sin_alpha * sin_alpha + tmp * tmp).sqrt(),
+    );
+
+    let lambda = math::atan2(sin_sigma * sin_alpha1, cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+    let c = F / 16. * cos2_alpha * (4. + F * (4. - 3. * cos2_alpha));
+    let l = lambda
+        - (1. - c)
+        * F
+        * sin_alpha
+        * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)));
+
+    // 経度を-π〜πに正規化する
+    let long2 = (long + l + PI).rem_euclid(2. * PI) - PI;
+
+    (long2, lat2)
+}
+
+/// Moves (longitude, latitude) expressed in the arc degree method by `east_m` (m) to the east and `north_m` (m) to the north.
+/// The offset is scaled by the radii of curvature of GRS80 at the point, treating the surface as flat, so it is valid for offsets small compared to the radius of the earth (up to a few km).
+///
+/// 弧度法で表された(経度, 緯度)を東に`east_m`(m)、北に`north_m`(m)移動させる。
+/// 移動量はその地点におけるGRS80の曲率半径で換算し、地表を平面とみなすため、地球の半径に比べて小さい移動量(数km以内)で有効である。
+///
+/// # Examples
+///
+/// Move a point 3 m north.
+///
+/// ある地点を北に3m移動させる。
+///
+/// ```
+/// use coordinate_transformer::geodesic::offset_by_meters;
+///
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let (long, lat) = offset_by_meters(ll, 0., 3.);
+///
+/// assert_eq!(long, ll.0);
+/// assert!(lat > ll.1);
+/// ```
+pub fn offset_by_meters(ll: (f64, f64), east_m: f64, north_m: f64) -> (f64, f64) {
+    let (long, lat) = ll;
+    let (long_m, lat_m) = meters_per_radian(lat);
+
+    let long2 = long + east_m / long_m;
+
+    ((long2 + PI).rem_euclid(2. * PI) - PI, lat + north_m / lat_m)
+}
+
+/// Returns the offset (east (m), north (m)) from `origin` to `ll`, both (longitude, latitude) expressed in the arc degree method.
+/// This is the inverse of `offset_by_meters` and is valid for the same small offsets.
+///
+/// 共に弧度法で表された(経度, 緯度)である`origin`から`ll`への移動量(東(m), 北(m))を返す。
+/// `offset_by_meters`の逆変換であり、同じく小さい移動量で有効である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::geodesic::{offset_by_meters, offset_in_meters};
+///
+/// let origin = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let (east, north) = offset_in_meters(origin, offset_by_meters(origin, 12., -5.));
+///
+/// assert!((east - 12.).abs() < 1e-6);
+/// assert!((north + 5.).abs() < 1e-6);
+/// ```
+pub fn offset_in_meters(origin: (f64, f64), ll: (f64, f64)) -> (f64, f64) {
+    let (long_m, lat_m) = meters_per_radian(origin.1);
+    let d_long = (ll.0 - origin.0 + PI).rem_euclid(2. * PI) - PI;
+
+    (d_long * long_m, (ll.1 - origin.1) * lat_m)
+}
+
+/// Function to return a polygon approximating the geodesic circle of `radius_m` (m) around `center` (longitude, latitude) expressed in the arc degree method, e.g. for buffering points before rasterizing to tiles or converting to JPR.
+/// The `n_vertices` vertices are at the ellipsoidal distance `radius_m` from the center following `destination`, at bearings equally spaced from north.
+/// The ring runs counter-clockwise as the exterior rings of GeoJSON and is closed. Panics if `n_vertices` is less than 3.
+///
+/// 弧度法で表された(経度, 緯度)の`center`を中心とする半径`radius_m`(m)の測地線円を近似する多角形を返す関数。タイルへのラスタ化や平面直角座標への変換の前に点を緩衝する場合などに用いる。
+/// `n_vertices`個の頂点は、北から等間隔の方位角で、`destination`に従って中心から楕円体上の距離`radius_m`にある。
+/// 環はGeoJSONの外側の環と同様に反時計回りであり、閉じている。`n_vertices`が3未満の場合はパニックする。
+///
+/// # Examples
+///
+/// Buffer Tokyo Station by 500 m.
+///
+/// 東京駅を500m緩衝する。
+///
+/// ```
+/// use coordinate_transformer::geodesic::geodesic_circle;
+///
+/// let ring = geodesic_circle((139.7671_f64.to_radians(), 35.6812_f64.to_radians()), 500., 64);
+///
+/// assert_eq!(ring.len(), 65);
+/// assert_eq!(ring.first(), ring.last());
+/// ```
+pub fn geodesic_circle(center: (f64, f64), radius_m: f64, n_vertices: usize) -> Vec<(f64, f64)> {
+    assert!(n_vertices >= 3, "a polygon needs at least 3 vertices");
+
+    let mut ring = (0..n_vertices)
+        .map(|i| destination(center, -2. * PI * i as f64 / n_vertices as f64, radius_m))
+        .collect::<Vec<_>>();
+    ring.push(ring[0]);
+
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn destination_works() {
+        // Flinders Peak から Buninyong への計算例
+        let (long, lat) = destination(
+            (
+                (144. + 25. / 60. + 29.5244 / 3600_f64).to_radians(),
+                -(37. + 57. / 60. + 3.7203 / 3600_f64).to_radians(),
+            ),
+            (306. + 52. / 60. + 5.37 / 3600_f64).to_radians(),
+            54972.271,
+        );
+
+        assert_close_to(long.to_degrees(), 143. + 55. / 60. + 35.3839 / 3600., 7);
+        assert_close_to(lat.to_degrees(), -(37. + 39. / 60. + 10.1561 / 3600.), 7);
+    }
+
+    #[test]
+    fn destination_wraps_longitude() {
+        let (long, lat) = destination((179.99_f64.to_radians(), 0.), 90_f64.to_radians(), 10000.);
+
+        assert!(long < 0.);
+        assert_close_to(lat, 0., 9);
+    }
+
+    #[test]
+    fn destination_returns_nan_for_non_finite_input() {
+        let ll = (140_f64.to_radians(), 36_f64.to_radians());
+
+        for (ll, bearing, distance_m) in [((f64::NAN, ll.1), 0., 1000.), (ll, f64::NAN, 1000.), (ll, 0., f64::INFINITY)] {
+            let (long, lat) = destination(ll, bearing, distance_m);
+            assert!(long.is_nan() && lat.is_nan());
+        }
+        assert!(geodesic_circle(ll, f64::NAN, 8).iter().all(|(long, lat)| long.is_nan() && lat.is_nan()));
+    }
+
+    #[test]
+    fn offset_by_meters_works() {
+        let origin = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+
+        // 測地線による計算と小さい移動量ではほぼ一致する
+        let (long, lat) = offset_by_meters(origin, 0., 100.);
+        let (expected_long, expected_lat) = destination(origin, 0., 100.);
+        assert_close_to(long, expected_long, 12);
+        assert_close_to(lat, expected_lat, 9);
+
+        let (long, lat) = offset_by_meters(origin, 100., 0.);
+        let (expected_long, expected_lat) = destination(origin, 90_f64.to_radians(), 100.);
+        assert_close_to(long, expected_long, 9);
+        assert_close_to(lat, expected_lat, 9);
+
+        // 180度経線をまたいでも往復で元に戻る
+        let near_antimeridian = (179.99999_f64.to_radians(), 0.);
+        let moved = offset_by_meters(near_antimeridian, 5., 0.);
+        assert!(moved.0 < 0.);
+        let (east, north) = offset_in_meters(near_antimeridian, moved);
+        assert_close_to(east, 5., 6);
+        assert_close_to(north, 0., 9);
+    }
+
+    #[test]
+    fn geodesic_circle_keeps_radius() {
+        let center = (139.7671_f64.to_radians(), 35.6812_f64.to_radians());
+        let ring = geodesic_circle(center, 100., 8);
+
+        for &ll in &ring {
+            let (east, north) = offset_in_meters(center, ll);
+            assert_close_to((east * east + north * north).sqrt(), 100., 2);
+        }
+
+        // 北から西回り(反時計回り)に進む
+        let (_, north) = offset_in_meters(center, ring[0]);
+        let (east, _) = offset_in_meters(center, ring[2]);
+        assert!(north > 0. && east < 0.);
+    }
+}
@@ -0,0 +1,488 @@
+//! High-level conversion configured once with the horizontal datums, the vertical datums, the geoid, the semi-dynamic correction, the crustal velocity and the target projection, so that the chain of individual conversions is assembled in the right order.
+//! This crate has no geoid model, correction parameters or velocities of its own, so they are given by the caller as functions of (longitude, latitude), e.g. interpolating the grids published by GSI.
+//!
+//! 水平基準、鉛直基準、ジオイド、セミ・ダイナミック補正、地殻変動の速度、変換先の投影を一度設定しておく高水準の変換。個々の変換の連鎖を正しい順序で組み立てる。
+//! このクレートはジオイドモデルや補正パラメータ、速度を持たないため、それらは国土地理院が公開するグリッドを補間するなどして、呼び出し側が(経度, 緯度)の関数として与える。
+
+use std::fmt::{Display, Formatter};
+
+use crate::epoch::{Epoch, Epoched};
+use crate::jpr_ll::{ll2jpr, JprOrigin};
+use crate::transverse_mercator::TransverseMercator;
+use crate::utm::{ll2utm, Hemisphere};
+use crate::vertical_datum::{convert_height, VerticalDatum};
+use crate::xyz_ll::llz2xyz;
+
+/// Number of iterations to invert the semi-dynamic correction, whose parameters are given at the coordinates of the reference epoch
+///
+/// 元期の座標で与えられるセミ・ダイナミック補正のパラメータを逆に適用する際の反復回数
+const INVERSE_ITERATIONS: usize = 3;
+
+/// Function returning the geoid height (m) at (longitude, latitude) expressed in the arc degree method, or `None` outside the model
+///
+/// 弧度法で表された(経度, 緯度)におけるジオイド高(m)を返す関数。モデルの範囲外では`None`を返す
+type GeoidFn = Box<dyn Fn((f64, f64)) -> Option<f64> + Send + Sync>;
+
+/// Function returning the semi-dynamic correction (longitude, latitude, height) at (longitude, latitude) of the reference epoch, or `None` outside the parameters
+///
+/// 元期の(経度, 緯度)におけるセミ・ダイナミック補正量(経度, 緯度, 高さ)を返す関数。パラメータの範囲外では`None`を返す
+type CorrectionFn = Box<dyn Fn((f64, f64)) -> Option<(f64, f64, f64)> + Send + Sync>;
+
+/// Function returning the velocity (longitude, latitude, height) per year at (longitude, latitude), or `None` outside the model
+///
+/// (経度, 緯度)における1年あたりの速度(経度, 緯度, 高さ)を返す関数。モデルの範囲外では`None`を返す
+type VelocityFn = Box<dyn Fn((f64, f64)) -> Option<(f64, f64, f64)> + Send + Sync>;
+
+/// Enumerated type representing the horizontal datum of geodetic coordinates
+///
+/// 測地座標の水平基準を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum GeodeticDatum {
+    /// JGD2011 at its reference epoch, the datum of published survey results
+    ///
+    /// 元期のJGD2011。公共測量の成果の基準
+    #[default]
+    Jgd2011,
+    /// JGD2011 at the epoch of observation, e.g. GNSS positions, related to the reference epoch by the semi-dynamic correction
+    ///
+    /// 観測時(今期)のJGD2011。GNSSによる位置など。元期とはセミ・ダイナミック補正で関係付けられる
+    Jgd2011Observed,
+}
+
+/// Enumerated type representing the coordinate system of the converted points
+///
+/// 変換後の点の座標系を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum GeodeticProjection {
+    /// (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)
+    LongLat,
+    /// Plane rectangular coordinates (y, x) of the origin
+    ///
+    /// 原点に対する平面直角座標(y, x)
+    Jpr(JprOrigin),
+    /// Projected coordinates (y, x) of a Transverse Mercator projection
+    ///
+    /// 横メルカトル図法による投影座標(y, x)
+    TransverseMercator(TransverseMercator),
+    /// UTM coordinates (easting, northing) of the zone and hemisphere
+    ///
+    /// ゾーンと半球におけるUTM座標(東距, 北距)
+    Utm(u8, Hemisphere),
+    /// Cartesian coordinates (x, y, z) (EPSG:4979), always with the ellipsoidal height
+    ///
+    /// 直交座標系(EPSG:4979)の座標(x, y, z)。常に楕円体高を用いる
+    Cartesian,
+}
+
+/// Enumerated type representing errors when converting a point with `GeodeticConverter`
+///
+/// `GeodeticConverter`で点を変換する際のエラーを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum GeodeticConvertError {
+    /// The geoid height is needed but not configured or not available at (longitude, latitude)
+    ///
+    /// ジオイド高が必要だが、設定されていないか、(経度, 緯度)で得られない
+    GeoidUnavailable {
+        /// (Longitude, latitude) of the point expressed in the arc degree method
+        ///
+        /// 弧度法で表された点の(経度, 緯度)
+        ll: (f64, f64),
+    },
+    /// The semi-dynamic correction is needed but not configured or not available at (longitude, latitude)
+    ///
+    /// セミ・ダイナミック補正が必要だが、設定されていないか、(経度, 緯度)で得られない
+    CorrectionUnavailable {
+        /// (Longitude, latitude) of the point expressed in the arc degree method
+        ///
+        /// 弧度法で表された点の(経度, 緯度)
+        ll: (f64, f64),
+    },
+    /// The velocity is needed to move a point between epochs but not configured or not available at (longitude, latitude)
+    ///
+    /// 点をエポック間で移すために速度が必要だが、設定されていないか、(経度, 緯度)で得られない
+    VelocityUnavailable {
+        /// (Longitude, latitude) of the point expressed in the arc degree method
+        ///
+        /// 弧度法で表された点の(経度, 緯度)
+        ll: (f64, f64),
+    },
+}
+
+impl Display for GeodeticConvertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeodeticConvertError::GeoidUnavailable { ll } => {
+                write!(f, "geoid height is not available at ({}, {})", ll.0, ll.1)
+            }
+            GeodeticConvertError::CorrectionUnavailable { ll } => {
+                write!(f, "semi-dynamic correction is not available at ({}, {})", ll.0, ll.1)
+            }
+            GeodeticConvertError::VelocityUnavailable { ll } => {
+                write!(f, "velocity is not available at ({}, {})", ll.0, ll.1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeodeticConvertError {}
+
+/// Structure converting ((longitude, latitude), height) expressed in the arc degree method from a source datum to the target datums and projection.
+/// The point is first moved between the horizontal datums with the semi-dynamic correction, then its height is converted between the vertical datums with the geoid height, and finally it is projected.
+/// The output is (y, x, height) for plane rectangular and Transverse Mercator coordinates, (easting, northing, height) for UTM, (longitude, latitude, height) for `GeodeticProjection::LongLat` and (x, y, z) for Cartesian coordinates.
+///
+/// 弧度法で表された((経度, 緯度), 高さ)を変換元の基準から変換先の基準と投影に変換する構造体。
+/// 点はまずセミ・ダイナミック補正で水平基準の間を移され、次にジオイド高で高さが鉛直基準の間で変換され、最後に投影される。
+/// 出力は、平面直角座標と横メルカトル図法では(y, x, 高さ)、UTMでは(東距, 北距, 高さ)、`GeodeticProjection::LongLat`では(経度, 緯度, 高さ)、直交座標では(x, y, z)である。
+///
+/// # Examples
+///
+/// Convert a GNSS position with the ellipsoidal height to plane rectangular coordinates of zone IX at the reference epoch with the elevation above T.P.
+///
+/// 楕円体高を持つGNSSによる位置を、元期のIX系の平面直角座標とT.P.からの標高に変換する。
+///
+/// ```
+/// use coordinate_transformer::geodetic_converter::{GeodeticConverter, GeodeticDatum, GeodeticProjection};
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::vertical_datum::VerticalDatum;
+///
+/// let converter = GeodeticConverter::new(GeodeticProjection::Jpr(JprOrigin::Nine))
+/// .with_datums(GeodeticDatum::Jgd2011Observed, GeodeticDatum::Jgd2011)
+/// .with_vertical_datums(VerticalDatum::Ellipsoidal, VerticalDatum::Tp)
+/// .with_geoid(|_| Some(36.7))
+/// .with_semi_dynamic_correction(|_| Some((0., 0., 0.02)));
+///
+/// let (y, x, height) = converter
+/// .convert(((140.085555556_f64.to_radians(), 36.1041666667_f64.to_radians()), 60.))
+/// .unwrap();
+///
+/// assert!((height - 23.28).abs() < 1e-9);
+/// ```
+pub struct GeodeticConverter {
+    projection: GeodeticProjection,
+    datums: (GeodeticDatum, GeodeticDatum),
+    vertical_datums: (VerticalDatum, VerticalDatum),
+    geoid: Option<GeoidFn>,
+    correction: Option<CorrectionFn>,
+    velocity: Option<VelocityFn>,
+    target_epoch: Option<Epoch>,
+}
+
+impl GeodeticConverter {
+    /// Create a converter to the projection, without changing the horizontal datum (JGD2011 at the reference epoch) and the vertical datum (ellipsoidal height)
+    ///
+    /// 投影への変換器を作成する。水平基準(元期のJGD2011)と鉛直基準(楕円体高)は変更しない
+    pub fn new(projection: GeodeticProjection) -> Self {
+        Self {
+            projection,
+            datums: (GeodeticDatum::default(), GeodeticDatum::default()),
+            vertical_datums: (VerticalDatum::Ellipsoidal, VerticalDatum::Ellipsoidal),
+            geoid: None,
+            correction: None,
+            velocity: None,
+            target_epoch: None,
+        }
+    }
+
+    /// Set the horizontal datums of the input and the output
+    ///
+    /// 入力と出力の水平基準を設定する
+    pub fn with_datums(self, from: GeodeticDatum, to: GeodeticDatum) -> Self {
+        Self {
+            datums: (from, to),
+            ..self
+        }
+    }
+
+    /// Set the vertical datums of the heights of the input and the output.
+    /// The output height of `GeodeticProjection::Cartesian` is always ellipsoidal.
+    ///
+    /// 入力と出力の高さの鉛直基準を設定する。
+    /// `GeodeticProjection::Cartesian`の出力の高さは常に楕円体高である。
+    pub fn with_vertical_datums(self, from: VerticalDatum, to: VerticalDatum) -> Self {
+        Self {
+            vertical_datums: (from, to),
+            ..self
+        }
+    }
+
+    /// Set the function returning the geoid height (m) at (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)におけるジオイド高(m)を返す関数を設定する
+    pub fn with_geoid<F>(self, geoid: F) -> Self
+    where
+        F: Fn((f64, f64)) -> Option<f64> + Send + Sync + 'static,
+    {
+        Self {
+            geoid: Some(Box::new(geoid)),
+            ..self
+        }
+    }
+
+    /// Set the function returning the semi-dynamic correction (longitude, latitude, height) at (longitude, latitude) of the reference epoch, with the angles expressed in the arc degree method and the height in m.
+    /// The correction is added to move a point from the reference epoch to the epoch of observation.
+    ///
+    /// 元期の(経度, 緯度)におけるセミ・ダイナミック補正量(経度, 緯度, 高さ)を返す関数を設定する。角度は弧度法、高さはmで表す。
+    /// 補正量は、点を元期から観測時に移す際に加えられる。
+    pub fn with_semi_dynamic_correction<F>(self, correction: F) -> Self
+    where
+        F: Fn((f64, f64)) -> Option<(f64, f64, f64)> + Send + Sync + 'static,
+    {
+        Self {
+            correction: Some(Box::new(correction)),
+            ..self
+        }
+    }
+
+    /// Set the function returning the velocity (longitude, latitude, height) per year at (longitude, latitude) of the input, with the angles expressed in the arc degree method and the height in m.
+    /// It is used by `convert_epoched` to move points to the target epoch.
+    ///
+    /// 入力の(経度, 緯度)における1年あたりの速度(経度, 緯度, 高さ)を返す関数を設定する。角度は弧度法、高さはmで表す。
+    /// `convert_epoched`で点を変換先のエポックに移す際に用いる。
+    pub fn with_velocity<F>(self, velocity: F) -> Self
+    where
+        F: Fn((f64, f64)) -> Option<(f64, f64, f64)> + Send + Sync + 'static,
+    {
+        Self {
+            velocity: Some(Box::new(velocity)),
+            ..self
+        }
+    }
+
+    /// Set the epoch to which `convert_epoched` moves points
+    ///
+    /// `convert_epoched`が点を移すエポックを設定する
+    pub fn with_target_epoch(self, epoch: Epoch) -> Self {
+        Self {
+            target_epoch: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Returns the target projection
+    ///
+    /// 変換先の投影を返す
+    pub fn projection(&self) -> GeodeticProjection {
+        self.projection
+    }
+
+    /// Convert ((longitude, latitude), height) expressed in the arc degree method in the source datums.
+    /// Returns an error if the geoid height or the semi-dynamic correction is needed but not available.
+    ///
+    /// 変換元の基準で弧度法で表された((経度, 緯度), 高さ)を変換する。
+    /// ジオイド高やセミ・ダイナミック補正が必要だが得られない場合はエラーを返す。
+    pub fn convert(&self, point: ((f64, f64), f64)) -> Result<(f64, f64, f64), GeodeticConvertError> {
+        let (ll, height) = self.convert_datum(point)?;
+
+        let (from, to) = self.vertical_datums;
+        let to = match self.projection {
+            GeodeticProjection::Cartesian => VerticalDatum::Ellipsoidal,
+            _ => to,
+        };
+        let height = if (from == VerticalDatum::Ellipsoidal) == (to == VerticalDatum::Ellipsoidal) {
+            convert_height(height, from, to, 0.)
+        } else {
+            let geoid_height = self
+                .geoid
+                .as_ref()
+                .and_then(|geoid| geoid(ll))
+                .ok_or(GeodeticConvertError::GeoidUnavailable { ll })?;
+            convert_height(height, from, to, geoid_height)
+        };
+
+        Ok(match self.projection {
+            GeodeticProjection::LongLat => (ll.0, ll.1, height),
+            GeodeticProjection::Jpr(origin) => {
+                let (y, x) = ll2jpr(ll, origin);
+                (y, x, height)
+            }
+            GeodeticProjection::TransverseMercator(tm) => {
+                let (y, x) = tm.forward(ll);
+                (y, x, height)
+            }
+            GeodeticProjection::Utm(zone, hemisphere) => {
+                let (easting, northing) = ll2utm(ll, zone, hemisphere);
+                (easting, northing, height)
+            }
+            GeodeticProjection::Cartesian => llz2xyz(ll, height),
+        })
+    }
+
+    /// Convert the points like `convert`, stopping at the first error
+    ///
+    /// `convert`と同様に点を変換する。最初のエラーで停止する
+    pub fn convert_batch(&self, points: &[((f64, f64), f64)]) -> Result<Vec<(f64, f64, f64)>, GeodeticConvertError> {
+        points.iter().map(|&point| self.convert(point)).collect()
+    }
+
+    /// Convert ((longitude, latitude), height) tagged with its epoch like `convert`.
+    /// If the target epoch is set, the point is first moved from its epoch to the target epoch with the velocity, and the output is tagged with the target epoch. Otherwise the output keeps the epoch of the input.
+    /// Returns an error if the velocity is needed but not available, in addition to the errors of `convert`.
+    ///
+    /// エポックを付けた((経度, 緯度), 高さ)を`convert`と同様に変換する。
+    /// 変換先のエポックが設定されている場合、点はまず速度によってそのエポックから変換先のエポックに移され、出力には変換先のエポックが付けられる。設定されていない場合、出力は入力のエポックを保つ。
+    /// `convert`のエラーに加えて、速度が必要だが得られない場合はエラーを返す。
+    ///
+    /// # Examples
+    ///
+    /// Move a position observed in 2015 to 2024 with a velocity of 3 cm per year to the north, keeping the ellipsoidal height.
+    ///
+    /// 2015年に観測された位置を、北に年3cmの速度で2024年に移す。楕円体高は保つ。
+    ///
+    /// ```
+    /// use coordinate_transformer::epoch::{Epoch, Epoched};
+    /// use coordinate_transformer::geodetic_converter::{GeodeticConverter, GeodeticProjection};
+    /// use coordinate_transformer::geodesic::{offset_by_meters, offset_in_meters};
+    ///
+    /// let ll = (140_f64.to_radians(), 36_f64.to_radians());
+    /// let north_per_year = offset_by_meters(ll, 0., 0.03).1 - ll.1;
+    ///
+    /// let converter = GeodeticConverter::new(GeodeticProjection::LongLat)
+    /// .with_velocity(move |_| Some((0., north_per_year, 0.)))
+    /// .with_target_epoch(Epoch::new(2024.));
+    ///
+    /// let moved = converter.convert_epoched(Epoched::new((ll, 50.), Epoch::new(2015.))).unwrap();
+    /// let (long, lat, height) = moved.coordinate;
+    ///
+    /// assert_eq!(moved.epoch, Epoch::new(2024.));
+    /// assert!((offset_in_meters(ll, (long, lat)).1 - 0.27).abs() < 1e-6);
+    /// assert_eq!(height, 50.);
+    /// ```
+    pub fn convert_epoched(
+        &self,
+        point: Epoched<((f64, f64), f64)>,
+    ) -> Result<Epoched<(f64, f64, f64)>, GeodeticConvertError> {
+        let Some(target_epoch) = self.target_epoch else {
+            return Ok(Epoched::new(self.convert(point.coordinate)?, point.epoch));
+        };
+
+        let ((long, lat), height) = point.coordinate;
+        let years = point.epoch.years_until(target_epoch);
+
+        let coordinate = if years == 0. {
+            point.coordinate
+        } else {
+            let (v_long, v_lat, v_height) = self
+                .velocity
+                .as_ref()
+                .and_then(|velocity| velocity((long, lat)))
+                .ok_or(GeodeticConvertError::VelocityUnavailable { ll: (long, lat) })?;
+            ((long + v_long * years, lat + v_lat * years), height + v_height * years)
+        };
+
+        Ok(Epoched::new(self.convert(coordinate)?, target_epoch))
+    }
+
+    /// Move the point between the horizontal datums
+    ///
+    /// 点を水平基準の間で移す
+    fn convert_datum(&self, point: ((f64, f64), f64)) -> Result<((f64, f64), f64), GeodeticConvertError> {
+        let ((long, lat), height) = point;
+
+        let correction = |ll: (f64, f64)| {
+            self.correction
+                .as_ref()
+                .and_then(|correction| correction(ll))
+                .ok_or(GeodeticConvertError::CorrectionUnavailable { ll })
+        };
+
+        match self.datums {
+            (GeodeticDatum::Jgd2011, GeodeticDatum::Jgd2011Observed) => {
+                let (d_long, d_lat, d_height) = correction((long, lat))?;
+                Ok(((long + d_long, lat + d_lat), height + d_height))
+            }
+            (GeodeticDatum::Jgd2011Observed, GeodeticDatum::Jgd2011) => {
+                // 補正量は元期の座標で与えられるため、元期の座標を反復して求める
+                let mut d = correction((long, lat))?;
+                for _ in 0..INVERSE_ITERATIONS {
+                    d = correction((long - d.0, lat - d.1))?;
+                }
+                Ok(((long - d.0, lat - d.1), height - d.2))
+            }
+            _ => Ok(point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::utm::utm_zone;
+
+    use super::*;
+
+    #[test]
+    fn default_converter_projects_only() {
+        let ll = (140.085555556_f64.to_radians(), 36.1041666667_f64.to_radians());
+
+        let converter = GeodeticConverter::new(GeodeticProjection::Jpr(JprOrigin::Nine));
+        let (y, x) = ll2jpr(ll, JprOrigin::Nine);
+        assert_eq!(converter.convert((ll, 12.)), Ok((y, x, 12.)));
+
+        let zone = utm_zone(ll.0);
+        let converter = GeodeticConverter::new(GeodeticProjection::Utm(zone, Hemisphere::North));
+        let (easting, northing) = ll2utm(ll, zone, Hemisphere::North);
+        assert_eq!(converter.convert_batch(&[(ll, 12.)]), Ok(vec![(easting, northing, 12.)]));
+    }
+
+    #[test]
+    fn semi_dynamic_correction_round_trips() {
+        // 位置によって変化する補正量
+        let correction = |(long, lat): (f64, f64)| Some((1e-7 * lat.sin(), -2e-7 * long.cos(), 0.05));
+        let ll = (135.5_f64.to_radians(), 34.7_f64.to_radians());
+
+        let forward = GeodeticConverter::new(GeodeticProjection::LongLat)
+            .with_datums(GeodeticDatum::Jgd2011, GeodeticDatum::Jgd2011Observed)
+            .with_semi_dynamic_correction(correction);
+        let inverse = GeodeticConverter::new(GeodeticProjection::LongLat)
+            .with_datums(GeodeticDatum::Jgd2011Observed, GeodeticDatum::Jgd2011)
+            .with_semi_dynamic_correction(correction);
+
+        let (long, lat, height) = forward.convert((ll, 10.)).unwrap();
+        assert_ne!((long, lat), ll);
+
+        let (long, lat, height) = inverse.convert(((long, lat), height)).unwrap();
+        assert_close_to(long, ll.0, 15);
+        assert_close_to(lat, ll.1, 15);
+        assert_close_to(height, 10., 12);
+    }
+
+    #[test]
+    fn missing_parameters_are_errors() {
+        let ll = (135.5_f64.to_radians(), 34.7_f64.to_radians());
+
+        let converter = GeodeticConverter::new(GeodeticProjection::Cartesian)
+            .with_vertical_datums(VerticalDatum::Tp, VerticalDatum::Tp);
+        assert_eq!(converter.convert((ll, 5.)), Err(GeodeticConvertError::GeoidUnavailable { ll }));
+
+        let converter = converter.with_geoid(|_| Some(37.));
+        assert_eq!(converter.convert((ll, 5.)), Ok(llz2xyz(ll, 42.)));
+
+        let converter = GeodeticConverter::new(GeodeticProjection::LongLat)
+            .with_datums(GeodeticDatum::Jgd2011, GeodeticDatum::Jgd2011Observed);
+        assert_eq!(converter.convert((ll, 5.)), Err(GeodeticConvertError::CorrectionUnavailable { ll }));
+    }
+
+    #[test]
+    fn convert_epoched_moves_between_epochs() {
+        let ll = (135.5_f64.to_radians(), 34.7_f64.to_radians());
+        let point = Epoched::new((ll, 5.), Epoch::new(2015.));
+
+        // 変換先のエポックがない場合はエポックを保つ
+        let converter = GeodeticConverter::new(GeodeticProjection::LongLat).with_velocity(|_| Some((1e-9, 2e-9, 0.01)));
+        assert_eq!(converter.convert_epoched(point), Ok(Epoched::new((ll.0, ll.1, 5.), Epoch::new(2015.))));
+
+        // 過去のエポックへは速度を逆向きに適用する
+        let converter = converter.with_target_epoch(Epoch::new(2011.));
+        let moved = converter.convert_epoched(point).unwrap();
+        assert_eq!(moved.epoch, Epoch::new(2011.));
+        assert_close_to(moved.coordinate.0, ll.0 - 4e-9, 15);
+        assert_close_to(moved.coordinate.1, ll.1 - 8e-9, 15);
+        assert_close_to(moved.coordinate.2, 4.96, 12);
+
+        let converter = GeodeticConverter::new(GeodeticProjection::LongLat).with_target_epoch(Epoch::new(2024.));
+        assert_eq!(converter.convert_epoched(point), Err(GeodeticConvertError::VelocityUnavailable { ll }));
+    }
+}
@@ -0,0 +1,511 @@
+//! Georeferencing of images from ground control points.
+//! A transform between image pixel coordinates and map coordinates, such as (longitude, latitude) or JPR (y, x), is fitted by least squares, e.g. for scanned maps or drone orthoimages.
+//!
+//! 地上基準点による画像の地理参照。
+//! 画像のピクセル座標と、(経度, 緯度)や平面直角座標(y, x)などの地図座標との間の変換を最小二乗法で求める。スキャンした地図やドローンのオルソ画像などに用いる。
+
+use std::fmt::{Display, Formatter};
+
+/// Structure representing a ground control point, a pair of image pixel coordinates and the corresponding map coordinates
+///
+/// 画像のピクセル座標と対応する地図座標の組である地上基準点を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ControlPoint {
+    /// Pixel coordinates (column, row) in the image
+    ///
+    /// 画像におけるピクセル座標(列, 行)
+    pub pixel: (f64, f64),
+    /// Map coordinates
+    ///
+    /// 地図座標
+    pub map: (f64, f64),
+}
+
+impl ControlPoint {
+    /// Create a new ground control point
+    ///
+    /// 地上基準点を新しく作成する
+    pub fn new(pixel: (f64, f64), map: (f64, f64)) -> Self {
+        Self { pixel, map }
+    }
+}
+
+/// Enumerated type representing errors when fitting a transform from ground control points
+///
+/// 地上基準点から変換を求める際のエラーを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeorefError {
+    /// There are fewer control points than the transform requires
+    ///
+    /// 変換に必要な数より地上基準点が少ない
+    NotEnoughPoints {
+        /// Number of control points the transform requires
+        ///
+        /// 変換に必要な地上基準点の数
+        required: usize,
+        /// Number of control points given
+        ///
+        /// 与えられた地上基準点の数
+        actual: usize,
+    },
+    /// The control points do not determine the transform, e.g. because they are collinear
+    ///
+    /// 地上基準点が一直線上にあるなどの理由で変換が定まらない
+    Degenerate,
+}
+
+impl Display for GeorefError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeorefError::NotEnoughPoints { required, actual } => {
+                write!(f, "at least {} control points are required, but got {}", required, actual)
+            }
+            GeorefError::Degenerate => write!(f, "control points do not determine the transform"),
+        }
+    }
+}
+
+impl std::error::Error for GeorefError {}
+
+/// Solves the linear least squares problem of `rows` times the unknowns equal to `targets` through the normal equations.
+/// Returns `None` if the normal equations are singular.
+///
+/// `rows`と未知数の積が`targets`に等しいという線形最小二乗問題を正規方程式によって解く。
+/// 正規方程式が特異な場合は`None`を返す。
+fn least_squares<const N: usize>(rows: &[[f64; N]], targets: &[f64]) -> Option<[f64; N]> {
+    // 正規方程式 A^T A p = A^T t の拡大係数行列
+    let mut m = [[0_f64; N]; N];
+    let mut v = [0_f64; N];
+    for (row, &t) in rows.iter().zip(targets) {
+        for i in 0..N {
+            for j in 0..N {
+                m[i][j] += row[i] * row[j];
+            }
+            v[i] += row[i] * t;
+        }
+    }
+
+    let scale = (0..N).map(|i| m[i][i]).fold(0_f64, f64::max);
+
+    // 部分ピボット選択付きのガウスの消去法
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() <= scale * 1e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        v.swap(col, pivot);
+
+        let pivot_row = m[col];
+        for row in col + 1..N {
+            let factor = m[row][col] / pivot_row[col];
+            for (value, pivot) in m[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot;
+            }
+            v[row] -= factor * v[col];
+        }
+    }
+
+    let mut p = [0_f64; N];
+    for i in (0..N).rev() {
+        let sum = (i + 1..N).map(|k| m[i][k] * p[k]).sum::<f64>();
+        p[i] = (v[i] - sum) / m[i][i];
+    }
+
+    Some(p)
+}
+
+/// Returns the differences (map coordinates minus transformed pixel coordinates) at the control points
+///
+/// 地上基準点における差(地図座標から変換したピクセル座標を引いたもの)を返す
+fn residuals_with(points: &[ControlPoint], forward: impl Fn((f64, f64)) -> (f64, f64)) -> Vec<(f64, f64)> {
+    points
+        .iter()
+        .map(|p| {
+            let (x, y) = forward(p.pixel);
+            (p.map.0 - x, p.map.1 - y)
+        })
+        .collect()
+}
+
+/// Returns the root mean square of the lengths of the residuals
+///
+/// 残差の長さの二乗平均平方根を返す
+fn rmse_of(residuals: &[(f64, f64)]) -> f64 {
+    let sum = residuals.iter().map(|(dx, dy)| dx * dx + dy * dy).sum::<f64>();
+    (sum / residuals.len() as f64).sqrt()
+}
+
+/// Structure representing an affine transform from pixel coordinates (u, v) to map coordinates (x, y), where `x = x0 + x1 * u + x2 * v` and `y = y0 + y1 * u + y2 * v`
+///
+/// ピクセル座標(u, v)から地図座標(x, y)へのアフィン変換を表す構造体。`x = x0 + x1 * u + x2 * v`、`y = y0 + y1 * u + y2 * v`となる
+///
+/// # Examples
+///
+/// Georeference a scanned map with four control points in JPR coordinates.
+///
+/// 平面直角座標の4つの地上基準点でスキャンした地図を地理参照する。
+///
+/// ```
+/// use coordinate_transformer::georef::{AffineTransform, ControlPoint};
+///
+/// let points = [
+///     ControlPoint::new((0., 0.), (-5000., 10000.)),
+///     ControlPoint::new((4000., 0.), (-4000., 10000.)),
+///     ControlPoint::new((0., 3000.), (-5000., 9250.)),
+///     ControlPoint::new((4000., 3000.), (-4000., 9250.)),
+/// ];
+///
+/// let transform = AffineTransform::fit(&points).unwrap();
+///
+/// assert_eq!(transform.forward((2000., 1500.)), (-4500., 9625.));
+/// assert!(transform.rmse(&points) < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct AffineTransform {
+    x: [f64; 3],
+    y: [f64; 3],
+}
+
+impl AffineTransform {
+    /// Minimum number of control points
+    ///
+    /// 地上基準点の最小数
+    pub const MIN_POINTS: usize = 3;
+
+    /// Create a new affine transform from the coefficients [x0, x1, x2] and [y0, y1, y2]
+    ///
+    /// 係数[x0, x1, x2]と[y0, y1, y2]からアフィン変換を新しく作成する
+    pub fn new(x: [f64; 3], y: [f64; 3]) -> Self {
+        Self { x, y }
+    }
+
+    /// Fit an affine transform to three or more control points by least squares
+    ///
+    /// 3つ以上の地上基準点に最小二乗法でアフィン変換を当てはめる
+    pub fn fit(points: &[ControlPoint]) -> Result<Self, GeorefError> {
+        if points.len() < Self::MIN_POINTS {
+            return Err(GeorefError::NotEnoughPoints {
+                required: Self::MIN_POINTS,
+                actual: points.len(),
+            });
+        }
+
+        // 桁落ちを避けるため、ピクセル座標と地図座標は最初の点からの差で扱う
+        let (pu, pv) = points[0].pixel;
+        let (px, py) = points[0].map;
+        let rows = points
+            .iter()
+            .map(|p| [1., p.pixel.0 - pu, p.pixel.1 - pv])
+            .collect::<Vec<_>>();
+        let xs = points.iter().map(|p| p.map.0 - px).collect::<Vec<_>>();
+        let ys = points.iter().map(|p| p.map.1 - py).collect::<Vec<_>>();
+
+        let [x0, x1, x2] = least_squares(&rows, &xs).ok_or(GeorefError::Degenerate)?;
+        let [y0, y1, y2] = least_squares(&rows, &ys).ok_or(GeorefError::Degenerate)?;
+
+        Ok(Self::new(
+            [px + x0 - x1 * pu - x2 * pv, x1, x2],
+            [py + y0 - y1 * pu - y2 * pv, y1, y2],
+        ))
+    }
+
+    /// Returns the coefficients [x0, x1, x2] and [y0, y1, y2]
+    ///
+    /// 係数[x0, x1, x2]と[y0, y1, y2]を返す
+    pub fn coefficients(&self) -> ([f64; 3], [f64; 3]) {
+        (self.x, self.y)
+    }
+
+    /// Transform pixel coordinates to map coordinates
+    ///
+    /// ピクセル座標を地図座標に変換する
+    pub fn forward(&self, pixel: (f64, f64)) -> (f64, f64) {
+        let (u, v) = pixel;
+        let [x0, x1, x2] = self.x;
+        let [y0, y1, y2] = self.y;
+
+        (x0 + x1 * u + x2 * v, y0 + y1 * u + y2 * v)
+    }
+
+    /// Transform map coordinates to pixel coordinates.
+    /// Returns `None` if the transform is not invertible.
+    ///
+    /// 地図座標をピクセル座標に変換する。
+    /// 変換が逆変換を持たない場合は`None`を返す。
+    pub fn inverse(&self, map: (f64, f64)) -> Option<(f64, f64)> {
+        let [x0, x1, x2] = self.x;
+        let [y0, y1, y2] = self.y;
+
+        let det = x1 * y2 - x2 * y1;
+        if det == 0. {
+            return None;
+        }
+
+        let (dx, dy) = (map.0 - x0, map.1 - y0);
+        Some(((y2 * dx - x2 * dy) / det, (x1 * dy - y1 * dx) / det))
+    }
+
+    /// Returns the residuals (map coordinates minus transformed pixel coordinates) at the control points
+    ///
+    /// 地上基準点における残差(地図座標から変換したピクセル座標を引いたもの)を返す
+    pub fn residuals(&self, points: &[ControlPoint]) -> Vec<(f64, f64)> {
+        residuals_with(points, |pixel| self.forward(pixel))
+    }
+
+    /// Returns the root mean square error at the control points in map units
+    ///
+    /// 地上基準点における地図座標の単位での二乗平均平方根誤差を返す
+    pub fn rmse(&self, points: &[ControlPoint]) -> f64 {
+        rmse_of(&self.residuals(points))
+    }
+}
+
+/// Structure representing a second-order polynomial transform from pixel coordinates to map coordinates, for imagery with mild distortion.
+/// Each map coordinate is a polynomial in `s` and `t` with the terms [1, s, t, s², st, t²], where (s, t) are the pixel coordinates centered on the control points and scaled to about ±1 for numerical stability.
+///
+/// 軽い歪みを持つ画像のための、ピクセル座標から地図座標への2次の多項式変換を表す構造体。
+/// 各地図座標は項[1, s, t, s², st, t²]による`s`と`t`の多項式である。ここで(s, t)は、数値的な安定のために地上基準点を中心とし約±1に縮尺したピクセル座標である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::georef::{ControlPoint, PolynomialTransform};
+///
+/// let points = (0..9)
+///     .map(|i| {
+///         let (u, v) = ((i % 3) as f64 * 1000., (i / 3) as f64 * 1000.);
+///         ControlPoint::new((u, v), (u + 1e-5 * u * v, -v))
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let transform = PolynomialTransform::fit(&points).unwrap();
+/// let (x, y) = transform.forward((500., 500.));
+///
+/// assert!((x - 502.5).abs() < 1e-6 && (y + 500.).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PolynomialTransform {
+    center: (f64, f64),
+    scale: f64,
+    origin: (f64, f64),
+    x: [f64; 6],
+    y: [f64; 6],
+}
+
+impl PolynomialTransform {
+    /// Minimum number of control points
+    ///
+    /// 地上基準点の最小数
+    pub const MIN_POINTS: usize = 6;
+
+    /// Returns the terms of the polynomial at pixel coordinates
+    ///
+    /// ピクセル座標における多項式の項を返す
+    fn terms(&self, pixel: (f64, f64)) -> [f64; 6] {
+        let s = (pixel.0 - self.center.0) / self.scale;
+        let t = (pixel.1 - self.center.1) / self.scale;
+        [1., s, t, s * s, s * t, t * t]
+    }
+
+    /// Fit a second-order polynomial transform to six or more control points by least squares
+    ///
+    /// 6つ以上の地上基準点に最小二乗法で2次の多項式変換を当てはめる
+    pub fn fit(points: &[ControlPoint]) -> Result<Self, GeorefError> {
+        if points.len() < Self::MIN_POINTS {
+            return Err(GeorefError::NotEnoughPoints {
+                required: Self::MIN_POINTS,
+                actual: points.len(),
+            });
+        }
+
+        let n = points.len() as f64;
+        let center = (
+            points.iter().map(|p| p.pixel.0).sum::<f64>() / n,
+            points.iter().map(|p| p.pixel.1).sum::<f64>() / n,
+        );
+        let scale = points
+            .iter()
+            .map(|p| (p.pixel.0 - center.0).abs().max((p.pixel.1 - center.1).abs()))
+            .fold(0_f64, f64::max);
+        if scale == 0. {
+            return Err(GeorefError::Degenerate);
+        }
+
+        // 桁落ちを避けるため、地図座標は最初の点からの差で扱う
+        let origin = points[0].map;
+        let mut transform = Self {
+            center,
+            scale,
+            origin,
+            x: [0.; 6],
+            y: [0.; 6],
+        };
+
+        let rows = points.iter().map(|p| transform.terms(p.pixel)).collect::<Vec<_>>();
+        let xs = points.iter().map(|p| p.map.0 - origin.0).collect::<Vec<_>>();
+        let ys = points.iter().map(|p| p.map.1 - origin.1).collect::<Vec<_>>();
+
+        transform.x = least_squares(&rows, &xs).ok_or(GeorefError::Degenerate)?;
+        transform.y = least_squares(&rows, &ys).ok_or(GeorefError::Degenerate)?;
+
+        Ok(transform)
+    }
+
+    /// Transform pixel coordinates to map coordinates
+    ///
+    /// ピクセル座標を地図座標に変換する
+    pub fn forward(&self, pixel: (f64, f64)) -> (f64, f64) {
+        let terms = self.terms(pixel);
+        let dot = |c: &[f64; 6]| c.iter().zip(&terms).map(|(c, t)| c * t).sum::<f64>();
+
+        (self.origin.0 + dot(&self.x), self.origin.1 + dot(&self.y))
+    }
+
+    /// Transform map coordinates to pixel coordinates by Newton's method.
+    /// Returns `None` if the iteration does not converge, e.g. far outside the control points where the polynomial folds over.
+    ///
+    /// ニュートン法で地図座標をピクセル座標に変換する。
+    /// 多項式が折り返す地上基準点から遠く離れた場所など、反復が収束しない場合は`None`を返す。
+    pub fn inverse(&self, map: (f64, f64)) -> Option<(f64, f64)> {
+        let (mut s, mut t) = (0_f64, 0_f64);
+
+        for _ in 0..50 {
+            let pixel = (self.center.0 + s * self.scale, self.center.1 + t * self.scale);
+            let (x, y) = self.forward(pixel);
+            let (rx, ry) = (map.0 - x, map.1 - y);
+
+            // (s, t)に関するヤコビ行列
+            let [_, x1, x2, x3, x4, x5] = self.x;
+            let [_, y1, y2, y3, y4, y5] = self.y;
+            let (xs, xt) = (x1 + 2. * x3 * s + x4 * t, x2 + x4 * s + 2. * x5 * t);
+            let (ys, yt) = (y1 + 2. * y3 * s + y4 * t, y2 + y4 * s + 2. * y5 * t);
+
+            let det = xs * yt - xt * ys;
+            if det == 0. {
+                return None;
+            }
+            let (ds, dt) = ((yt * rx - xt * ry) / det, (xs * ry - ys * rx) / det);
+            s += ds;
+            t += dt;
+
+            if (ds.abs() + dt.abs()) * self.scale < 1e-9 {
+                return Some((self.center.0 + s * self.scale, self.center.1 + t * self.scale));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the residuals (map coordinates minus transformed pixel coordinates) at the control points
+    ///
+    /// 地上基準点における残差(地図座標から変換したピクセル座標を引いたもの)を返す
+    pub fn residuals(&self, points: &[ControlPoint]) -> Vec<(f64, f64)> {
+        residuals_with(points, |pixel| self.forward(pixel))
+    }
+
+    /// Returns the root mean square error at the control points in map units
+    ///
+    /// 地上基準点における地図座標の単位での二乗平均平方根誤差を返す
+    pub fn rmse(&self, points: &[ControlPoint]) -> f64 {
+        rmse_of(&self.residuals(points))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    /// Control points of a map rotated by 30 degrees with 0.5 m pixels, in JPR coordinates
+    ///
+    /// 0.5mのピクセルで30度回転した地図の、平面直角座標の地上基準点
+    fn rotated_points() -> Vec<ControlPoint> {
+        let (sin, cos) = 30_f64.to_radians().sin_cos();
+        [(0., 0.), (5000., 0.), (0., 4000.), (5000., 4000.), (2500., 1000.)]
+            .into_iter()
+            .map(|(u, v)| {
+                let map = (-12000. + 0.5 * (cos * u - sin * v), 35000. - 0.5 * (sin * u + cos * v));
+                ControlPoint::new((u, v), map)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn affine_fit_recovers_transform() {
+        let points = rotated_points();
+        let transform = AffineTransform::fit(&points).unwrap();
+
+        let ([x0, x1, _], [y0, _, y2]) = transform.coefficients();
+        assert_close_to(x0, -12000., 6);
+        assert_close_to(y0, 35000., 6);
+        assert_close_to(x1, 0.5 * 30_f64.to_radians().cos(), 9);
+        assert_close_to(y2, -0.5 * 30_f64.to_radians().cos(), 9);
+        assert!(transform.rmse(&points) < 1e-6);
+
+        let (u, v) = transform.inverse(transform.forward((1234.5, 678.9))).unwrap();
+        assert_close_to(u, 1234.5, 6);
+        assert_close_to(v, 678.9, 6);
+
+        // 誤差のある点は残差に現れる
+        let mut noisy = points.clone();
+        noisy[4].map.0 += 1.;
+        let transform = AffineTransform::fit(&noisy).unwrap();
+        let residuals = transform.residuals(&noisy);
+        assert!(residuals[4].0 > 0.5);
+        assert!(transform.rmse(&noisy) > 0.1);
+    }
+
+    #[test]
+    fn affine_fit_rejects_bad_points() {
+        let points = rotated_points();
+
+        assert_eq!(
+            AffineTransform::fit(&points[..2]),
+            Err(GeorefError::NotEnoughPoints {
+                required: 3,
+                actual: 2,
+            })
+        );
+
+        let collinear = [0., 1., 2.].map(|t| ControlPoint::new((t, 2. * t), (t, t)));
+        assert_eq!(AffineTransform::fit(&collinear), Err(GeorefError::Degenerate));
+    }
+
+    #[test]
+    fn polynomial_fit_handles_distortion() {
+        // 2次の歪みを持つ格子
+        let distort = |(u, v): (f64, f64)| {
+            let (du, dv) = (u - 2000., v - 1500.);
+            (30000. + 0.5 * u + 2e-6 * du * du, -8000. - 0.5 * v - 3e-6 * du * dv)
+        };
+        let points = (0..25)
+            .map(|i| {
+                let pixel = ((i % 5) as f64 * 1000., (i / 5) as f64 * 750.);
+                ControlPoint::new(pixel, distort(pixel))
+            })
+            .collect::<Vec<_>>();
+
+        let transform = PolynomialTransform::fit(&points).unwrap();
+        assert!(transform.rmse(&points) < 1e-6);
+        assert!(AffineTransform::fit(&points).unwrap().rmse(&points) > 1.);
+
+        let (x, y) = transform.forward((1234., 567.));
+        let expected = distort((1234., 567.));
+        assert_close_to(x, expected.0, 6);
+        assert_close_to(y, expected.1, 6);
+
+        let (u, v) = transform.inverse(expected).unwrap();
+        assert_close_to(u, 1234., 6);
+        assert_close_to(v, 567., 6);
+
+        assert_eq!(
+            PolynomialTransform::fit(&points[..5]),
+            Err(GeorefError::NotEnoughPoints {
+                required: 6,
+                actual: 5,
+            })
+        );
+    }
+}
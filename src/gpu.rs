@@ -0,0 +1,363 @@
+//! Batch conversion on the GPU with `wgpu`, for pipelines converting billions of points where the conversion on the CPU is the bottleneck.
+//! The coordinates are uploaded as offsets from a reference point and the kernels generated by the `wgsl` module run as compute shaders, so the results carry the precision of `f32` relative to the offsets (see the `wgsl` module).
+//! The buffers are split into chunks within the limits of the device.
+//!
+//! ボトルネックがCPU上の変換となる数十億点を変換するパイプラインのための、`wgpu`によるGPU上の一括変換。
+//! 座標は基準点からの差としてアップロードされ、`wgsl`モジュールで生成したカーネルをコンピュートシェーダとして実行するため、結果は差に対して`f32`の精度を持つ(`wgsl`モジュールを参照)。
+//! バッファはデバイスの制限内のまとまりに分割される。
+
+use std::fmt::{Display, Formatter};
+use std::sync::mpsc;
+
+use wgpu::util::DeviceExt;
+
+use crate::jpr_ll::{LAT0, LONG0};
+use crate::pixel_ll::ll2pixel_f64;
+use crate::wgsl::{ll2jpr_wgsl, ll2pixel_wgsl, llz2xyz_wgsl};
+use crate::{llz2xyz, JprOrigin, ZoomLv};
+
+/// Workgroup size of the generated shaders
+///
+/// 生成されるシェーダのワークグループサイズ
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Error that occurs while setting up the GPU
+///
+/// GPUの準備中に発生するエラー
+#[derive(Debug)]
+pub enum GpuError {
+    /// No adapter is available
+    ///
+    /// アダプタが利用できない
+    NoAdapter(wgpu::RequestAdapterError),
+    /// Requesting the device failed
+    ///
+    /// デバイスの要求に失敗した
+    RequestDevice(wgpu::RequestDeviceError),
+}
+
+impl Display for GpuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GpuError::NoAdapter(e) => write!(f, "no GPU adapter is available: {}", e),
+            GpuError::RequestDevice(e) => write!(f, "failed to request a GPU device: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GpuError {}
+
+/// Structure representing a GPU device that runs batch conversions
+///
+/// 一括変換を実行するGPUデバイスを表す構造体
+///
+/// # Examples
+///
+/// ```no_run
+/// use coordinate_transformer::gpu::GpuBatchConverter;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let gpu = GpuBatchConverter::new().unwrap();
+/// let points = vec![(140.08_f64.to_radians(), 36.10_f64.to_radians()); 1_000_000];
+///
+/// let jpr = gpu.ll2jpr_batch(&points, JprOrigin::Nine);
+/// ```
+#[derive(Debug)]
+pub struct GpuBatchConverter {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl GpuBatchConverter {
+    /// Requests an adapter and a device with the highest limits the adapter supports, blocking until they are ready
+    ///
+    /// アダプタと、アダプタが対応する最大の制限を持つデバイスを要求し、準備ができるまで待つ
+    pub fn new() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle_from_env());
+        let adapter = futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .map_err(GpuError::NoAdapter)?;
+
+        let (device, queue) = futures::executor::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            required_limits: adapter.limits(),
+            ..Default::default()
+        }))
+        .map_err(GpuError::RequestDevice)?;
+
+        Ok(Self { device, queue })
+    }
+
+    /// Same as `ll2jpr_batch` but converts on the GPU.
+    /// The error is below 1e-6 of the distance from the origin, e.g. about 5 cm at 100 km.
+    ///
+    /// `ll2jpr_batch`と同じだが、GPU上で変換する。
+    /// 誤差は原点からの距離の1e-6未満であり、例えば100kmで約5cmである。
+    pub fn ll2jpr_batch(&self, points: &[(f64, f64)], origin: JprOrigin) -> Vec<(f64, f64)> {
+        let (long0, lat0) = (LONG0[origin as usize], LAT0[origin as usize]);
+        let input = points
+            .iter()
+            .flat_map(|&(long, lat)| [(long - long0) as f32, (lat - lat0) as f32])
+            .collect::<Vec<_>>();
+
+        let output = self.run(&ll2jpr_wgsl(origin), None, &input, 2, 2);
+
+        output.chunks_exact(2).map(|yx| (yx[0] as f64, yx[1] as f64)).collect()
+    }
+
+    /// Same as `ll2pixel_batch` but converts on the GPU.
+    /// The error is below 1e-6 of the distance in pixels from the center of the bounding box of the points, e.g. up to 3 pixels for points spanning 1° at Zoom level 24, so points near the edge of a pixel may fall in a neighboring pixel.
+    ///
+    /// `ll2pixel_batch`と同じだが、GPU上で変換する。
+    /// 誤差は点を囲む矩形の中心からのピクセル単位の距離の1e-6未満であり、例えばZoomレベル24で1度にわたる点では最大3ピクセルとなるため、ピクセルの境界付近の点は隣のピクセルとなる場合がある。
+    pub fn ll2pixel_batch(&self, points: &[(f64, f64)], zoom: ZoomLv) -> Vec<(u32, u32)> {
+        let center = center_of(points.iter().copied());
+        let (center_x, center_y) = ll2pixel_f64(center, zoom);
+        let input = points
+            .iter()
+            .flat_map(|&(long, lat)| [(long - center.0) as f32, (lat - center.1) as f32])
+            .collect::<Vec<_>>();
+
+        let output = self.run(&ll2pixel_wgsl(zoom), Some(center), &input, 2, 2);
+
+        output
+            .chunks_exact(2)
+            .map(|xy| (
+                (center_x + xy[0] as f64) as u32,
+                (center_y + xy[1] as f64) as u32,
+            ))
+            .collect()
+    }
+
+    /// Converts ((longitude, latitude), altitude) with the longitude and latitude expressed in the arc degree method into (x, y, z) in the same way as `llz2xyz` on the GPU.
+    /// The error is below 1e-6 of the distance from the center of the bounding box of the points, e.g. about 3 cm at 100 km.
+    ///
+    /// 弧度法で表された経度と緯度による((経度, 緯度), 標高)を、GPU上で`llz2xyz`と同様に(x, y, z)に変換する。
+    /// 誤差は点を囲む矩形の中心からの距離の1e-6未満であり、例えば100kmで約3cmである。
+    pub fn llz2xyz_batch(&self, points: &[((f64, f64), f64)]) -> Vec<(f64, f64, f64)> {
+        let center = center_of(points.iter().map(|&(ll, _)| ll));
+        let (center_x, center_y, center_z) = llz2xyz(center, 0.);
+        let input = points
+            .iter()
+            .flat_map(|&((long, lat), altitude)| {
+                [(long - center.0) as f32, (lat - center.1) as f32, altitude as f32]
+            })
+            .collect::<Vec<_>>();
+
+        let output = self.run(&llz2xyz_wgsl(), Some(center), &input, 3, 3);
+
+        output
+            .chunks_exact(3)
+            .map(|xyz| (
+                center_x + xyz[0] as f64,
+                center_y + xyz[1] as f64,
+                center_z + xyz[2] as f64,
+            ))
+            .collect()
+    }
+
+    /// Runs the shader `source` on `input` of records of `input_stride` values and returns the records of `output_stride` values.
+    /// The center is bound as the uniform buffer at binding 2 if given.
+    ///
+    /// `input_stride`個の値のレコードからなる`input`にシェーダ`source`を実行し、`output_stride`個の値のレコードを返す。
+    /// 中心が与えられた場合は、バインディング2のユニフォームバッファとする。
+    fn run(
+        &self,
+        source: &str,
+        center: Option<(f64, f64)>,
+        input: &[f32],
+        input_stride: usize,
+        output_stride: usize,
+    ) -> Vec<f32> {
+        let count = input.len() / input_stride;
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let center = center.map(|(long, lat)| {
+            let bytes = [long as f32, lat as f32]
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect::<Vec<_>>();
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &bytes,
+                usage: wgpu::BufferUsages::UNIFORM,
+            })
+        });
+
+        // ディスパッチ数とバッファの大きさの制限に収まる点の数ずつ変換する
+        let limits = self.device.limits();
+        let record_size = (4 * input_stride.max(output_stride)) as u64;
+        let chunk_len = (limits.max_compute_workgroups_per_dimension as u64 * WORKGROUP_SIZE as u64)
+            .min(limits.max_storage_buffer_binding_size / record_size)
+            .min(limits.max_buffer_size / record_size) as usize;
+
+        let mut output = Vec::with_capacity(count * output_stride);
+        for chunk in input.chunks(chunk_len * input_stride) {
+            let len = chunk.len() / input_stride;
+            let bytes = chunk.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>();
+            let output_size = (len * output_stride * 4) as u64;
+
+            let input_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &bytes,
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+            let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: output_size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            });
+            let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: output_size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let mut entries = vec![
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ];
+            if let Some(center) = &center {
+                entries.push(wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: center.as_entire_binding(),
+                });
+            }
+            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &entries,
+            });
+
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups((len as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+            }
+            encoder.copy_buffer_to_buffer(&output_buffer, 0, &readback, 0, output_size);
+            self.queue.submit([encoder.finish()]);
+
+            let (sender, receiver) = mpsc::channel();
+            readback.map_async(wgpu::MapMode::Read, .., move |result| {
+                let _ = sender.send(result);
+            });
+            self.device
+                .poll(wgpu::PollType::wait_indefinitely())
+                .expect("the GPU device was lost");
+            receiver
+                .recv()
+                .expect("the mapping callback was dropped")
+                .expect("failed to map the output buffer");
+
+            let view = readback.get_mapped_range(..).expect("failed to read the output buffer");
+            output.extend(
+                view.chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+            );
+        }
+
+        output
+    }
+}
+
+/// Returns the center of the bounding box of the finite coordinates, or (0, 0) if there is none
+///
+/// 有限な座標を囲む矩形の中心を返す。ない場合は(0, 0)を返す
+fn center_of(points: impl Iterator<Item = (f64, f64)>) -> (f64, f64) {
+    let bounds = points
+        .filter(|(long, lat)| long.is_finite() && lat.is_finite())
+        .fold(None, |bounds: Option<((f64, f64), (f64, f64))>, (long, lat)| {
+            Some(match bounds {
+                None => ((long, lat), (long, lat)),
+                Some(((min_long, min_lat), (max_long, max_lat))) => (
+                    (min_long.min(long), min_lat.min(lat)),
+                    (max_long.max(long), max_lat.max(lat)),
+                ),
+            })
+        });
+
+    match bounds {
+        Some(((min_long, min_lat), (max_long, max_lat))) => ((min_long + max_long) / 2., (min_lat + max_lat) / 2.),
+        None => (0., 0.),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ll2jpr, ll2pixel};
+
+    /// Points around Tsukuba in a grid of about 100 km
+    ///
+    /// つくば周辺の約100km四方の格子上の点
+    fn points() -> Vec<(f64, f64)> {
+        (0..40)
+            .flat_map(|i| (0..40).map(move |j| (139.5 + i as f64 * 0.03, 35.6 + j as f64 * 0.025)))
+            .map(|(long, lat): (f64, f64)| (long.to_radians(), lat.to_radians()))
+            .collect()
+    }
+
+    // アダプタの無い環境でも通ったように見えないよう、`cargo test --features wgpu -- --ignored`で明示的に実行する
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn gpu_matches_cpu() {
+        let gpu = GpuBatchConverter::new().unwrap();
+        let points = points();
+
+        // 誤差は基準点からの距離の1e-6未満となる
+        for (&ll, (y, x)) in points.iter().zip(gpu.ll2jpr_batch(&points, JprOrigin::Nine)) {
+            let (expected_y, expected_x) = ll2jpr(ll, JprOrigin::Nine);
+            let error = (y - expected_y).hypot(x - expected_x);
+            assert!(error < 1e-6 * expected_y.hypot(expected_x) + 1e-3, "{:?} {}", ll, error);
+        }
+
+        let center = center_of(points.iter().copied());
+        let (center_x, center_y) = ll2pixel_f64(center, ZoomLv::Lv24);
+        for (&ll, (x, y)) in points.iter().zip(gpu.ll2pixel_batch(&points, ZoomLv::Lv24)) {
+            let (expected_x, expected_y) = ll2pixel(ll, ZoomLv::Lv24);
+            let distance = (expected_x as f64 - center_x).hypot(expected_y as f64 - center_y);
+            let error = x.abs_diff(expected_x).max(y.abs_diff(expected_y));
+            assert!((error as f64) <= 1. + 1e-6 * distance, "{:?} {}", ll, error);
+        }
+
+        let llz = points.iter().map(|&ll| (ll, 100.)).collect::<Vec<_>>();
+        let (center_x, center_y, center_z) = llz2xyz(center, 0.);
+        for (&(ll, altitude), (x, y, z)) in llz.iter().zip(gpu.llz2xyz_batch(&llz)) {
+            let (expected_x, expected_y, expected_z) = llz2xyz(ll, altitude);
+            let distance = (expected_x - center_x).hypot(expected_y - center_y).hypot(expected_z - center_z);
+            let error = (x - expected_x).hypot(y - expected_y).hypot(z - expected_z);
+            assert!(error < 1e-6 * distance + 1e-3, "{:?} {}", ll, error);
+        }
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn empty_batch_is_empty() {
+        let gpu = GpuBatchConverter::new().unwrap();
+
+        assert!(gpu.ll2jpr_batch(&[], JprOrigin::Nine).is_empty());
+        assert!(gpu.llz2xyz_batch(&[]).is_empty());
+    }
+}
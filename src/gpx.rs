@@ -0,0 +1,512 @@
+//! Reading and writing the points of GPX files, enabled by the `gpx` feature.
+//! Only the `lat`/`lon` attributes and the `ele` element of waypoints, track points and route points are kept; other elements such as `name`, `time` and `extensions` are skipped when reading and are not written.
+//! Documents are read with an XML parser, so comments, CDATA sections and elements nested in `extensions` are handled.
+//!
+//! `gpx`フィーチャで有効になる、GPXファイルの点の読み書き。
+//! ウェイポイント、トラックポイント、ルートポイントの`lat`/`lon`属性と`ele`要素のみを扱い、`name`、`time`、`extensions`などの他の要素は読み込み時に読み飛ばし、書き出さない。
+//! 文書はXMLパーサで読み込むため、コメント、CDATAセクション、`extensions`内に入れ子になった要素も正しく扱う。
+
+use std::fmt::{Display, Formatter};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+
+use crate::{JprOrigin, Pixel, ZoomLv, JPR, LL, XYZ};
+
+/// Error that occurs while reading a GPX file
+///
+/// GPXファイルの読み込み中に発生するエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpxError {
+    /// The point on the given line (1-based) could not be read
+    ///
+    /// 指定された行(1始まり)の点を読み込めなかった
+    InvalidPoint(usize),
+    /// The document is not well-formed XML at the given line (1-based)
+    ///
+    /// 指定された行(1始まり)で文書が整形式のXMLではない
+    InvalidXml(usize),
+}
+
+impl Display for GpxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GpxError::InvalidPoint(line) => write!(f, "invalid GPX point at line {}", line),
+            GpxError::InvalidXml(line) => write!(f, "invalid XML at line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for GpxError {}
+
+/// Kind of GPX point
+///
+/// GPXの点の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GpxPointKind {
+    /// `wpt`
+    Waypoint,
+    /// `trkpt`
+    TrackPoint,
+    /// `rtept`
+    RoutePoint,
+}
+
+impl GpxPointKind {
+    /// Returns the name of the element of the point
+    ///
+    /// 点の要素の名前を返す
+    fn element_name(&self) -> &'static str {
+        match self {
+            GpxPointKind::Waypoint => "wpt",
+            GpxPointKind::TrackPoint => "trkpt",
+            GpxPointKind::RoutePoint => "rtept",
+        }
+    }
+}
+
+/// Structure representing a point of a GPX file
+///
+/// GPXファイルの点を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct GpxPoint {
+    /// Kind of point
+    ///
+    /// 点の種類
+    pub kind: GpxPointKind,
+    /// Latitude and longitude
+    ///
+    /// 緯度経度
+    pub ll: LL,
+    /// Elevation (m)
+    ///
+    /// 標高(m)
+    pub ele: Option<f64>,
+}
+
+impl GpxPoint {
+    /// Convert to a structure representing JPR coordinates
+    ///
+    /// 平面直角座標を表す構造体に変換する
+    pub fn to_jpr(&self, origin: JprOrigin) -> JPR {
+        self.ll.to_jpr(origin)
+    }
+
+    /// Convert to a structure representing pixel coordinates
+    ///
+    /// ピクセル座標を表す構造体に変換する
+    pub fn to_pixel(&self, zoom_lv: ZoomLv) -> Pixel {
+        self.ll.to_pixel(zoom_lv)
+    }
+
+    /// Convert to a structure representing Cartesian (EPSG:4979) coordinates.
+    /// Points without elevation are placed at an elevation of 0 m.
+    ///
+    /// 直交座標系(EPSG:4979)座標を表す構造体に変換する。
+    /// 標高を持たない点は標高0mとする。
+    pub fn to_xyz(&self) -> XYZ {
+        self.ll.to_xyz(self.ele.unwrap_or(0.))
+    }
+}
+
+/// Structure representing the waypoints, routes and tracks of a GPX file
+///
+/// GPXファイルのウェイポイント、ルート、トラックを表す構造体
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::gpx::{read_gpx_document, write_gpx};
+///
+/// let gpx = r#"<gpx><trk><trkseg><trkpt lat="35.6812" lon="139.7671"><ele>3.5</ele></trkpt></trkseg></trk></gpx>"#;
+///
+/// let document = read_gpx_document(gpx).unwrap();
+/// assert_eq!(document.tracks[0][0][0].ele, Some(3.5));
+///
+/// let text = write_gpx(&document);
+/// assert_eq!(read_gpx_document(&text).unwrap().tracks.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GpxDocument {
+    /// Waypoints (`wpt`)
+    ///
+    /// ウェイポイント(`wpt`)
+    pub waypoints: Vec<GpxPoint>,
+    /// Points of each route (`rte`)
+    ///
+    /// 各ルート(`rte`)の点
+    pub routes: Vec<Vec<GpxPoint>>,
+    /// Points of each segment (`trkseg`) of each track (`trk`)
+    ///
+    /// 各トラック(`trk`)の各セグメント(`trkseg`)の点
+    pub tracks: Vec<Vec<Vec<GpxPoint>>>,
+}
+
+impl GpxDocument {
+    /// Returns an iterator over all points: waypoints, then route points, then track points
+    ///
+    /// ウェイポイント、ルートポイント、トラックポイントの順に全ての点を返すイテレータを返す
+    pub fn points(&self) -> impl Iterator<Item=&GpxPoint> {
+        self.waypoints
+            .iter()
+            .chain(self.routes.iter().flatten())
+            .chain(self.tracks.iter().flatten().flatten())
+    }
+
+    /// Converts the latitude and longitude of every point with `f`, keeping the structure and the elevations
+    ///
+    /// 構造と標高を保ったまま、全ての点の緯度経度を`f`で変換する
+    ///
+    /// # Examples
+    ///
+    /// Round the points to about 10 m before sharing a track.
+    ///
+    /// トラックを共有する前に点を約10mに丸める。
+    ///
+    /// ```
+    /// use coordinate_transformer::gpx::read_gpx_document;
+    /// use coordinate_transformer::precision::round_ll_to_m;
+    /// use coordinate_transformer::structure::LL;
+    ///
+    /// let gpx = r#"<gpx><wpt lat="35.6812" lon="139.7671"/></gpx>"#;
+    ///
+    /// let document = read_gpx_document(gpx).unwrap();
+    /// let fuzzed = document.map_ll(|ll| {
+    ///     let (long, lat) = round_ll_to_m(ll.to_tuple(), 10.);
+    ///     LL::new(long, lat)
+    /// });
+    /// ```
+    pub fn map_ll<F: FnMut(LL) -> LL>(&self, mut f: F) -> GpxDocument {
+        let mut map = |point: &GpxPoint| GpxPoint { ll: f(point.ll), ..*point };
+
+        GpxDocument {
+            waypoints: self.waypoints.iter().map(&mut map).collect(),
+            routes: self.routes.iter().map(|route| route.iter().map(&mut map).collect()).collect(),
+            tracks: self
+                .tracks
+                .iter()
+                .map(|track| track.iter().map(|segment| segment.iter().map(&mut map).collect()).collect())
+                .collect(),
+        }
+    }
+}
+
+/// Returns the line (1-based) of the byte position `pos` of `text`
+///
+/// `text`のバイト位置`pos`の行(1始まり)を返す
+fn line_at(text: &str, pos: usize) -> usize {
+    text.as_bytes()[..pos.min(text.len())].iter().filter(|&&b| b == b'\n').count() + 1
+}
+
+/// Reads the coordinate attributes of a point element
+///
+/// 点の要素の座標の属性を読み込む
+fn read_ll(tag: &BytesStart) -> Option<LL> {
+    let value = |name: &str| {
+        let attribute = tag.try_get_attribute(name).ok()??;
+        attribute.normalized_value(XmlVersion::Implicit1_0).ok()?.trim().parse::<f64>().ok()
+    };
+
+    Some(LL::new(value("lon")?.to_radians(), value("lat")?.to_radians()))
+}
+
+/// Reads the points of a GPX document in document order, together with the index of the route or the indices of the track and segment they belong to
+///
+/// GPX文書の点を、属するルートの番号またはトラックとセグメントの番号とともに文書の順に読み込む
+fn read_indexed(text: &str) -> Result<Vec<(GpxPoint, usize, usize)>, GpxError> {
+    let mut reader = Reader::from_str(text);
+    let mut points = Vec::new();
+
+    // 開いている要素の名前、読み込み中の点とその番号、ルート・トラック・セグメントの数
+    let mut stack: Vec<String> = Vec::new();
+    let mut current: Option<(GpxPoint, usize, usize)> = None;
+    let (mut routes, mut tracks, mut segments) = (0, 0, 0);
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event().map_err(|_| GpxError::InvalidXml(line_at(text, reader.error_position() as usize)))?;
+
+        match event {
+            Event::Start(ref tag) | Event::Empty(ref tag) => {
+                let name = String::from_utf8_lossy(tag.local_name().as_ref().as_bytes()).into_owned();
+
+                let kind = match name.as_str() {
+                    "wpt" => Some(GpxPointKind::Waypoint),
+                    "trkpt" => Some(GpxPointKind::TrackPoint),
+                    "rtept" => Some(GpxPointKind::RoutePoint),
+                    "rte" => {
+                        routes += 1;
+                        None
+                    }
+                    "trk" => {
+                        tracks += 1;
+                        segments = 0;
+                        None
+                    }
+                    "trkseg" => {
+                        segments += 1;
+                        None
+                    }
+                    _ => None,
+                };
+
+                // extensions内などに現れた同名の要素は点として扱わない
+                if let (Some(kind), None) = (kind, &current) {
+                    let ll = read_ll(tag).ok_or(GpxError::InvalidPoint(line_at(text, start)))?;
+                    let (i, j) = match kind {
+                        GpxPointKind::Waypoint => (0, 0),
+                        GpxPointKind::RoutePoint => (routes.max(1) - 1, 0),
+                        GpxPointKind::TrackPoint => (tracks.max(1) - 1, segments.max(1) - 1),
+                    };
+                    current = Some((GpxPoint { kind, ll, ele: None }, i, j));
+
+                    if matches!(event, Event::Empty(_)) {
+                        points.extend(current.take());
+                        continue;
+                    }
+                }
+
+                if matches!(event, Event::Start(_)) {
+                    stack.push(name);
+                }
+            }
+            Event::Text(ref content) if stack.last().map(String::as_str) == Some("ele") => {
+                set_ele(&mut current, &stack, content, text, start)?;
+            }
+            Event::CData(ref content) if stack.last().map(String::as_str) == Some("ele") => {
+                set_ele(&mut current, &stack, content, text, start)?;
+            }
+            Event::End(_) => {
+                stack.pop();
+
+                // 点の要素は入れ子にならないため、スタックから点の要素が無くなれば点を確定する
+                if let Some((point, _, _)) = &current {
+                    if !stack.iter().any(|name| name == point.kind.element_name()) {
+                        points.extend(current.take());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(points)
+}
+
+/// Sets the elevation of the point being read if `ele` is its direct child
+///
+/// `ele`が読み込み中の点の直接の子であれば、その点の標高を設定する
+fn set_ele(current: &mut Option<(GpxPoint, usize, usize)>, stack: &[String], content: &str, text: &str, start: usize) -> Result<(), GpxError> {
+    let parent = stack.len().checked_sub(2).map(|i| stack[i].as_str());
+
+    if let Some((point, _, _)) = current.as_mut().filter(|(point, _, _)| parent == Some(point.kind.element_name())) {
+        let ele = content.trim();
+        if !ele.is_empty() {
+            point.ele = Some(ele.parse::<f64>().map_err(|_| GpxError::InvalidPoint(line_at(text, start)))?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads all waypoints, track points and route points of a GPX document in document order.
+///
+/// GPX文書の全てのウェイポイント、トラックポイント、ルートポイントを文書の順に読み込む。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::gpx::read_gpx;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+///
+/// let gpx = r#"<gpx><trk><trkseg><trkpt lat="35.6812" lon="139.7671"><ele>3.5</ele></trkpt></trkseg></trk></gpx>"#;
+///
+/// let points = read_gpx(gpx).unwrap();
+/// let jpr = points[0].to_jpr(JprOrigin::Nine);
+/// ```
+pub fn read_gpx(text: &str) -> Result<Vec<GpxPoint>, GpxError> {
+    Ok(read_indexed(text)?.into_iter().map(|(point, _, _)| point).collect())
+}
+
+/// Reads a GPX document keeping its waypoints, routes and track segments.
+///
+/// GPX文書をウェイポイント、ルート、トラックのセグメントの構造を保って読み込む。
+pub fn read_gpx_document(text: &str) -> Result<GpxDocument, GpxError> {
+    let mut document = GpxDocument::default();
+
+    for (point, i, j) in read_indexed(text)? {
+        match point.kind {
+            GpxPointKind::Waypoint => document.waypoints.push(point),
+            GpxPointKind::RoutePoint => {
+                document.routes.resize_with(document.routes.len().max(i + 1), Vec::new);
+                document.routes[i].push(point);
+            }
+            GpxPointKind::TrackPoint => {
+                document.tracks.resize_with(document.tracks.len().max(i + 1), Vec::new);
+                let track = &mut document.tracks[i];
+                track.resize_with(track.len().max(j + 1), Vec::new);
+                track[j].push(point);
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Writes a point element with the given name
+///
+/// 指定した名前の点の要素を書き出す
+fn write_point(out: &mut String, indent: &str, name: &str, point: &GpxPoint) {
+    let (long, lat) = point.ll.to_tuple();
+    let attributes = format!("lat=\"{:.8}\" lon=\"{:.8}\"", lat.to_degrees(), long.to_degrees());
+
+    match point.ele {
+        Some(ele) => out.push_str(&format!("{}<{} {}><ele>{:.3}</ele></{}>\n", indent, name, attributes, ele, name)),
+        None => out.push_str(&format!("{}<{} {}/>\n", indent, name, attributes)),
+    }
+}
+
+/// Writes a GPX 1.1 document with the waypoints, routes and tracks.
+/// The coordinates are written with 8 decimal places of degrees (about 1 mm) and the elevations with 3 decimal places (1 mm).
+///
+/// ウェイポイント、ルート、トラックをGPX 1.1の文書として書き出す。
+/// 座標は度の小数点以下8桁(約1mm)、標高は小数点以下3桁(1mm)で書き出す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::gpx::{write_gpx, GpxDocument, GpxPoint, GpxPointKind};
+/// use coordinate_transformer::structure::LL;
+///
+/// let document = GpxDocument {
+///     waypoints: vec![GpxPoint {
+///         kind: GpxPointKind::Waypoint,
+///         ll: LL::new(139.7671_f64.to_radians(), 35.6812_f64.to_radians()),
+///         ele: Some(3.5),
+///     }],
+///     ..Default::default()
+/// };
+///
+/// assert!(write_gpx(&document).contains(r#"<wpt lat="35.68120000" lon="139.76710000"><ele>3.500</ele></wpt>"#));
+/// ```
+pub fn write_gpx(document: &GpxDocument) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<gpx version=\"1.1\" creator=\"coordinate-transformer\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n");
+
+    for point in &document.waypoints {
+        write_point(&mut out, "  ", "wpt", point);
+    }
+
+    for route in &document.routes {
+        out.push_str("  <rte>\n");
+        for point in route {
+            write_point(&mut out, "    ", "rtept", point);
+        }
+        out.push_str("  </rte>\n");
+    }
+
+    for track in &document.tracks {
+        out.push_str("  <trk>\n");
+        for segment in track {
+            out.push_str("    <trkseg>\n");
+            for point in segment {
+                write_point(&mut out, "      ", "trkpt", point);
+            }
+            out.push_str("    </trkseg>\n");
+        }
+        out.push_str("  </trk>\n");
+    }
+
+    out.push_str("</gpx>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn read_gpx_works() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test">
+  <wpt lat="35.0" lon='139.0'/>
+  <rte><rtept lon="140.5" lat="36.5"><name>R</name></rtept></rte>
+  <trk>
+    <trkseg>
+      <trkpt lat="35.6812" lon="139.7671"><ele>3.5</ele><time>2024-01-01T00:00:00Z</time></trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+        let points = read_gpx(gpx).unwrap();
+
+        assert_eq!(
+            points.iter().map(|p| p.kind).collect::<Vec<_>>(),
+            vec![GpxPointKind::Waypoint, GpxPointKind::RoutePoint, GpxPointKind::TrackPoint]
+        );
+        assert_close_to(points[1].ll.to_tuple().0.to_degrees(), 140.5, 9);
+        assert_close_to(points[1].ll.to_tuple().1.to_degrees(), 36.5, 9);
+        assert_eq!(points[0].ele, None);
+        assert_eq!(points[2].ele, Some(3.5));
+    }
+
+    #[test]
+    fn read_gpx_skips_comments_and_extensions() {
+        let gpx = r#"<gpx>
+  <!-- <wpt lat="1.0" lon="1.0"/> -->
+  <wpt lat="35.0" lon="139.0">
+    <ele><![CDATA[ 12.5 ]]></ele>
+  </wpt>
+  <trk><trkseg>
+    <trkpt lat="36.0" lon="140.0">
+      <extensions><gpxtpx:TrackPointExtension><ele>999</ele></gpxtpx:TrackPointExtension></extensions>
+    </trkpt>
+  </trkseg></trk>
+</gpx>"#;
+
+        let points = read_gpx(gpx).unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].ele, Some(12.5));
+        assert_eq!(points[1].ele, None);
+        assert_eq!(read_gpx("<gpx><wpt lat=\"35.0\" lon=\"139.0\"></gpx>"), Err(GpxError::InvalidXml(1)));
+    }
+
+    #[test]
+    fn write_gpx_round_trips() {
+        let point = |kind, long: f64, lat: f64, ele| GpxPoint { kind, ll: LL::new(long.to_radians(), lat.to_radians()), ele };
+        let document = GpxDocument {
+            waypoints: vec![point(GpxPointKind::Waypoint, 139.7671, 35.6812, Some(3.5))],
+            routes: vec![vec![
+                point(GpxPointKind::RoutePoint, 140.1, 36.1, None),
+                point(GpxPointKind::RoutePoint, 140.2, 36.2, Some(-1.25)),
+            ]],
+            tracks: vec![vec![
+                vec![point(GpxPointKind::TrackPoint, 141.0, 43.0, Some(10.))],
+                vec![point(GpxPointKind::TrackPoint, 141.1, 43.1, None), point(GpxPointKind::TrackPoint, 141.2, 43.2, Some(20.))],
+            ]],
+        };
+
+        let read = read_gpx_document(&write_gpx(&document)).unwrap();
+
+        assert_eq!(read.waypoints.len(), 1);
+        assert_eq!(read.routes.iter().map(Vec::len).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(read.tracks[0].iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 2]);
+        for (a, b) in document.points().zip(read.points()) {
+            assert_eq!(a.kind, b.kind);
+            assert_eq!(a.ele, b.ele);
+            assert_close_to(a.ll.to_tuple().0, b.ll.to_tuple().0, 10);
+            assert_close_to(a.ll.to_tuple().1, b.ll.to_tuple().1, 10);
+        }
+    }
+
+    #[test]
+    fn read_gpx_rejects_invalid_point() {
+        let gpx = "<gpx>\n<wpt lat=\"abc\" lon=\"139.0\"/>\n</gpx>";
+
+        assert_eq!(read_gpx(gpx), Err(GpxError::InvalidPoint(2)));
+    }
+}
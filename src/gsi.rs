@@ -0,0 +1,290 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::pixel_ll::{ll2pixel, pixel2tile, ZoomLv};
+
+/// Enumerated type representing commonly used layers of the GSI tiles (地理院タイル).
+/// The zoom ranges follow the GSI tile list at the time of writing.
+///
+/// よく用いられる地理院タイルのレイヤを表す列挙型。
+/// ズームレベルの範囲は執筆時点の地理院タイル一覧に従う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GsiLayer {
+    /// 標準地図
+    Std,
+    /// 淡色地図
+    Pale,
+    /// 白地図
+    Blank,
+    /// English
+    English,
+    /// 全国最新写真(シームレス)
+    SeamlessPhoto,
+    /// 色別標高図
+    Relief,
+    /// 標高タイル(基盤地図情報数値標高モデル DEM10B, PNG形式)
+    DemPng,
+    /// 標高タイル(基盤地図情報数値標高モデル DEM5A, PNG形式)
+    Dem5aPng,
+    /// 標高タイル(基盤地図情報数値標高モデル DEM10B, テキスト形式)
+    DemTxt,
+    /// 標高タイル(基盤地図情報数値標高モデル DEM5A, テキスト形式)
+    Dem5aTxt,
+}
+
+impl GsiLayer {
+    /// Returns the identifier of the layer used in the URL
+    ///
+    /// URLで用いられるレイヤの識別子を返す
+    pub fn id(&self) -> &'static str {
+        match self {
+            GsiLayer::Std => "std",
+            GsiLayer::Pale => "pale",
+            GsiLayer::Blank => "blank",
+            GsiLayer::English => "english",
+            GsiLayer::SeamlessPhoto => "seamlessphoto",
+            GsiLayer::Relief => "relief",
+            GsiLayer::DemPng => "dem_png",
+            GsiLayer::Dem5aPng => "dem5a_png",
+            GsiLayer::DemTxt => "dem",
+            GsiLayer::Dem5aTxt => "dem5a",
+        }
+    }
+
+    /// Returns the file extension of the tiles
+    ///
+    /// タイルのファイル拡張子を返す
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GsiLayer::SeamlessPhoto => "jpg",
+            GsiLayer::DemTxt | GsiLayer::Dem5aTxt => "txt",
+            _ => "png",
+        }
+    }
+
+    /// Returns the range of the available Zoom levels
+    ///
+    /// 提供されているZoomレベルの範囲を返す
+    pub fn zoom_range(&self) -> RangeInclusive<ZoomLv> {
+        match self {
+            GsiLayer::Std | GsiLayer::Pale | GsiLayer::SeamlessPhoto => ZoomLv::Lv2..=ZoomLv::Lv18,
+            GsiLayer::Blank => ZoomLv::Lv5..=ZoomLv::Lv14,
+            GsiLayer::English => ZoomLv::Lv5..=ZoomLv::Lv11,
+            GsiLayer::Relief => ZoomLv::Lv5..=ZoomLv::Lv15,
+            GsiLayer::DemPng | GsiLayer::DemTxt => ZoomLv::Lv1..=ZoomLv::Lv14,
+            GsiLayer::Dem5aPng | GsiLayer::Dem5aTxt => ZoomLv::Lv15..=ZoomLv::Lv15,
+        }
+    }
+
+    /// Returns the standard URL template of the layer with `{z}`, `{x}` and `{y}` placeholders
+    ///
+    /// `{z}`、`{x}`、`{y}`のプレースホルダを含むレイヤの標準のURLテンプレートを返す
+    pub fn url_template(&self) -> String {
+        format!(
+            "https://cyberjapandata.gsi.go.jp/xyz/{}/{{z}}/{{x}}/{{y}}.{}",
+            self.id(),
+            self.extension()
+        )
+    }
+}
+
+/// Enumerated type representing errors of the GSI tile helpers
+///
+/// 地理院タイルのヘルパーのエラーを表す列挙型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GsiError {
+    /// The layer is not provided at the Zoom level
+    ///
+    /// レイヤがそのZoomレベルで提供されていない
+    ZoomOutOfRange {
+        /// Layer
+        ///
+        /// レイヤ
+        layer: GsiLayer,
+        /// Zoom level
+        ///
+        /// Zoomレベル
+        zoom: ZoomLv,
+    },
+    /// The tile ID is not of the form `z/x/y` or is outside the world
+    ///
+    /// タイルIDが`z/x/y`の形式でないか、世界の範囲外である
+    InvalidTileId(String),
+}
+
+impl Display for GsiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GsiError::ZoomOutOfRange { layer, zoom } => {
+                write!(f, "layer {} is not provided at zoom level {}", layer.id(), *zoom as u8)
+            }
+            GsiError::InvalidTileId(id) => write!(f, "invalid tile id: {}", id),
+        }
+    }
+}
+
+impl std::error::Error for GsiError {}
+
+/// Structure representing a GSI tile ID `z/x/y`.
+/// GSI tiles use the same XYZ convention as this crate, with the origin at the upper left (north west) and y increasing southward.
+///
+/// 地理院タイルのタイルID`z/x/y`を表す構造体。
+/// 地理院タイルはこのクレートと同じXYZ方式であり、原点は左上(北西)、yは南に向かって増加する。
+///
+/// # Examples
+///
+/// Find the URL of the standard map tile containing Tokyo Station.
+///
+/// 東京駅を含む標準地図のタイルのURLを求める。
+///
+/// ```
+/// use coordinate_transformer::gsi::{GsiLayer, GsiTile};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let tile = GsiTile::from_ll((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), ZoomLv::Lv16);
+///
+/// assert_eq!(
+/// tile.url(GsiLayer::Std),
+/// Ok("https://cyberjapandata.gsi.go.jp/xyz/std/16/58211/25806.png".to_string())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GsiTile {
+    /// Zoom level
+    ///
+    /// Zoomレベル
+    pub zoom: ZoomLv,
+    /// Tile coordinate x
+    ///
+    /// タイル座標x
+    pub x: u32,
+    /// Tile coordinate y
+    ///
+    /// タイル座標y
+    pub y: u32,
+}
+
+impl GsiTile {
+    /// Create a new tile ID from tile coordinates and the Zoom level
+    ///
+    /// タイル座標とZoomレベルからタイルIDを新しく作成する
+    pub fn new(tile: (u32, u32), zoom: ZoomLv) -> Self {
+        let (x, y) = tile;
+        Self { zoom, x, y }
+    }
+
+    /// Returns the tile ID containing the pixel coordinates
+    ///
+    /// ピクセル座標を含むタイルIDを返す
+    pub fn from_pixel(pixel: (u32, u32), zoom: ZoomLv) -> Self {
+        Self::new(pixel2tile(pixel), zoom)
+    }
+
+    /// Returns the tile ID containing (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)を含むタイルIDを返す
+    pub fn from_ll(ll: (f64, f64), zoom: ZoomLv) -> Self {
+        Self::from_pixel(ll2pixel(ll, zoom), zoom)
+    }
+
+    /// Returns the tile coordinates
+    ///
+    /// タイル座標を返す
+    pub fn tile(&self) -> (u32, u32) {
+        (self.x, self.y)
+    }
+
+    /// Returns the pixel coordinates of the upper left and lower right pixels of the tile (both inclusive)
+    ///
+    /// タイルの左上と右下のピクセル座標(両端を含む)を返す
+    pub fn pixel_bounds(&self) -> ((u32, u32), (u32, u32)) {
+        let min = (self.x * 256, self.y * 256);
+        (min, (min.0 + 255, min.1 + 255))
+    }
+
+    /// Returns the URL of the tile in the layer, or an error if the layer is not provided at the Zoom level
+    ///
+    /// レイヤにおけるタイルのURLを返す。レイヤがそのZoomレベルで提供されていない場合はエラーを返す
+    pub fn url(&self, layer: GsiLayer) -> Result<String, GsiError> {
+        if !layer.zoom_range().contains(&self.zoom) {
+            return Err(GsiError::ZoomOutOfRange {
+                layer,
+                zoom: self.zoom,
+            });
+        }
+
+        Ok(layer
+            .url_template()
+            .replace("{z}", &(self.zoom as u8).to_string())
+            .replace("{x}", &self.x.to_string())
+            .replace("{y}", &self.y.to_string()))
+    }
+}
+
+impl Display for GsiTile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}/{}", self.zoom as u8, self.x, self.y)
+    }
+}
+
+impl FromStr for GsiTile {
+    type Err = GsiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || GsiError::InvalidTileId(s.to_string());
+
+        let mut parts = s.trim_matches('/').split('/');
+        let (Some(z), Some(x), Some(y), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(err());
+        };
+
+        let z = z.parse::<u8>().map_err(|_| err())?;
+        let zoom = ZoomLv::parse(z).map_err(|_| err())?;
+        let x = x.parse::<u32>().map_err(|_| err())?;
+        let y = y.parse::<u32>().map_err(|_| err())?;
+
+        let tiles = 1_u64 << z;
+        if x as u64 >= tiles || y as u64 >= tiles {
+            return Err(err());
+        }
+
+        Ok(Self { zoom, x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gsi_tile_id_round_trips() {
+        let tile: GsiTile = "16/58211/25806".parse().unwrap();
+
+        assert_eq!(tile, GsiTile::new((58211, 25806), ZoomLv::Lv16));
+        assert_eq!(tile.to_string(), "16/58211/25806");
+        assert_eq!(tile.pixel_bounds(), ((14902016, 6606336), (14902271, 6606591)));
+        assert_eq!(GsiTile::from_pixel(tile.pixel_bounds().1, ZoomLv::Lv16), tile);
+
+        assert!("16/58211".parse::<GsiTile>().is_err());
+        assert!("1/2/0".parse::<GsiTile>().is_err());
+        assert!("25/0/0".parse::<GsiTile>().is_err());
+    }
+
+    #[test]
+    fn gsi_url_checks_zoom_range() {
+        let tile = GsiTile::new((0, 0), ZoomLv::Lv16);
+
+        assert_eq!(
+            tile.url(GsiLayer::DemPng),
+            Err(GsiError::ZoomOutOfRange {
+                layer: GsiLayer::DemPng,
+                zoom: ZoomLv::Lv16,
+            })
+        );
+        assert_eq!(
+            GsiTile::new((29105, 12903), ZoomLv::Lv15).url(GsiLayer::Dem5aTxt),
+            Ok("https://cyberjapandata.gsi.go.jp/xyz/dem5a/15/29105/12903.txt".to_string())
+        );
+    }
+}
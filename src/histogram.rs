@@ -0,0 +1,177 @@
+//! Binning of points into counts per pixel or per tile, e.g. for heatmap tiles and density analysis.
+//!
+//! ヒートマップのタイルや密度の分析などのための、点のピクセルごとまたはタイルごとの数への集計。
+
+use std::collections::HashMap;
+
+use crate::pixel_ll::{ll2pixel, pixel2tile, ZoomLv};
+use crate::structure::LL;
+
+/// Enumerated type representing the size of the bins of a histogram
+///
+/// ヒストグラムの区間の大きさを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HistogramBin {
+    /// One bin per pixel
+    ///
+    /// ピクセルごとの区間
+    Pixel,
+    /// One bin per tile of 256 x 256 pixels
+    ///
+    /// 256 x 256ピクセルのタイルごとの区間
+    Tile,
+}
+
+/// Structure representing a sparse histogram of points per pixel or per tile at a Zoom level.
+/// The bin of a point follows the rounding of `ll2pixel`.
+///
+/// Zoomレベルにおけるピクセルごとまたはタイルごとの点の疎なヒストグラムを表す構造体。
+/// 点の区間は`ll2pixel`の丸めに従う。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::histogram::{Histogram, HistogramBin};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::LL;
+///
+/// let tokyo = LL::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let osaka = LL::new(135.4959_f64.to_radians(), 34.7024_f64.to_radians());
+///
+/// let mut histogram = Histogram::new(ZoomLv::Lv10, HistogramBin::Tile);
+/// histogram.extend([tokyo, tokyo, osaka]);
+///
+/// assert_eq!(histogram.count((909, 403)), 2);
+/// assert_eq!(histogram.total(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    zoom: ZoomLv,
+    bin: HistogramBin,
+    counts: HashMap<(u32, u32), u64>,
+}
+
+impl Histogram {
+    /// Create a new empty histogram
+    ///
+    /// 空のヒストグラムを新しく作成する
+    pub fn new(zoom: ZoomLv, bin: HistogramBin) -> Self {
+        Self {
+            zoom,
+            bin,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom
+    }
+
+    /// Returns the size of the bins
+    ///
+    /// 区間の大きさを返す
+    pub fn bin(&self) -> HistogramBin {
+        self.bin
+    }
+
+    /// Returns the bin (pixel or tile coordinates) containing (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)を含む区間(ピクセル座標またはタイル座標)を返す
+    pub fn bin_of(&self, ll: (f64, f64)) -> (u32, u32) {
+        let pixel = ll2pixel(ll, self.zoom);
+
+        match self.bin {
+            HistogramBin::Pixel => pixel,
+            HistogramBin::Tile => pixel2tile(pixel),
+        }
+    }
+
+    /// Add a point
+    ///
+    /// 点を追加する
+    pub fn add(&mut self, ll: LL) {
+        *self.counts.entry(self.bin_of(ll.to_tuple())).or_default() += 1;
+    }
+
+    /// Returns the number of points in the bin
+    ///
+    /// 区間内の点の数を返す
+    pub fn count(&self, bin: (u32, u32)) -> u64 {
+        self.counts.get(&bin).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of points
+    ///
+    /// 点の総数を返す
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Returns the bins with at least one point and their counts, in no particular order
+    ///
+    /// 1つ以上の点を持つ区間とその数を順不同で返す
+    pub fn iter(&self) -> impl Iterator<Item=((u32, u32), u64)> + '_ {
+        self.counts.iter().map(|(&bin, &count)| (bin, count))
+    }
+
+    /// Returns the counts of the bins from `min` to `max` (both inclusive) as a dense grid in row-major order from the upper left.
+    /// Points outside the range are ignored.
+    ///
+    /// `min`から`max`まで(両端を含む)の区間の数を、左上から行優先の順の密な格子として返す。
+    /// 範囲外の点は無視される。
+    pub fn to_dense(&self, min: (u32, u32), max: (u32, u32)) -> Vec<u64> {
+        let width = (max.0 - min.0 + 1) as usize;
+        let height = (max.1 - min.1 + 1) as usize;
+        let mut grid = vec![0; width * height];
+
+        for (&(x, y), &count) in &self.counts {
+            if (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y) {
+                grid[(y - min.1) as usize * width + (x - min.0) as usize] = count;
+            }
+        }
+
+        grid
+    }
+}
+
+impl Extend<LL> for Histogram {
+    fn extend<T: IntoIterator<Item=LL>>(&mut self, iter: T) {
+        for ll in iter {
+            self.add(ll);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pixel_ll::pixel2ll_f64;
+
+    use super::*;
+
+    #[test]
+    fn histogram_bins_points() {
+        let zoom = ZoomLv::Lv2;
+        let at = |x: f64, y: f64| {
+            let (long, lat) = pixel2ll_f64((x, y), zoom);
+            LL::new(long, lat)
+        };
+
+        let mut pixels = Histogram::new(zoom, HistogramBin::Pixel);
+        let mut tiles = Histogram::new(zoom, HistogramBin::Tile);
+        let points = [at(10.5, 20.5), at(10.7, 20.2), at(300.5, 20.5), at(1000.5, 700.5)];
+        pixels.extend(points);
+        tiles.extend(points);
+
+        assert_eq!(pixels.count((10, 20)), 2);
+        assert_eq!(pixels.iter().count(), 3);
+        assert_eq!(tiles.count((0, 0)), 2);
+        assert_eq!(tiles.count((1, 0)), 1);
+
+        // 左上の2 x 2タイルの範囲には右下の点は含まれない
+        assert_eq!(tiles.to_dense((0, 0), (1, 1)), vec![2, 1, 0, 0]);
+        assert_eq!(tiles.to_dense((0, 0), (3, 3)).iter().sum::<u64>(), tiles.total());
+    }
+}
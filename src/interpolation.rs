@@ -0,0 +1,122 @@
+//! Interpolation between two points of longitude and latitude.
+//! The longitude always takes the shorter way around the globe, so interpolating across the antimeridian stays on the correct side of the planet.
+//!
+//! 2つの経度緯度の点の間の補間。
+//! 経度は常に地球を回る短い方を通るため、180度経線をまたぐ補間でも正しい側に留まる。
+
+use std::f64::consts::{PI, TAU};
+
+use crate::math;
+
+/// Returns the longitude expressed in the arc degree method wrapped into -π to π
+///
+/// 弧度法で表された経度を-πからπに折り返して返す
+fn wrap_long(long: f64) -> f64 {
+    (long + PI).rem_euclid(TAU) - PI
+}
+
+/// Function to interpolate linearly in longitude and latitude between (longitude, latitude) expressed in the arc degree method, where `t` is 0 at `a` and 1 at `b`.
+/// The longitude difference is taken the shorter way, and the result is wrapped into -π to π.
+///
+/// 弧度法で表された(経度, 緯度)の間を経度緯度で線形に補間する関数。`t`は`a`で0、`b`で1となる。
+/// 経度の差は短い方をとり、結果は-πからπに折り返される。
+///
+/// # Examples
+///
+/// The midpoint between 170°E and 170°W is on the antimeridian, not on the prime meridian.
+///
+/// 東経170度と西経170度の中点は本初子午線ではなく180度経線上にある。
+///
+/// ```
+/// use coordinate_transformer::interpolation::lerp_ll;
+///
+/// let (long, _) = lerp_ll((170_f64.to_radians(), 0.), (-170_f64.to_radians(), 0.), 0.5);
+///
+/// assert!((long.abs() - std::f64::consts::PI).abs() < 1e-12);
+/// ```
+pub fn lerp_ll(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    let d_long = wrap_long(b.0 - a.0);
+    (wrap_long(a.0 + d_long * t), a.1 + (b.1 - a.1) * t)
+}
+
+/// Function to interpolate along the great circle between (longitude, latitude) expressed in the arc degree method, where `t` is 0 at `a` and 1 at `b`.
+/// The great circle is taken on a sphere, which approximates the geodesic of the ellipsoid. The points must not be antipodal.
+///
+/// 弧度法で表された(経度, 緯度)の間を大円に沿って補間する関数。`t`は`a`で0、`b`で1となる。
+/// 大円は球面上でとり、楕円体の測地線を近似する。2点は対蹠点であってはならない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::interpolation::slerp_ll;
+///
+/// let (_, lat) = slerp_ll((-120_f64.to_radians(), 40_f64.to_radians()), (-20_f64.to_radians(), 40_f64.to_radians()), 0.5);
+///
+/// assert!(lat > 40_f64.to_radians());
+/// ```
+pub fn slerp_ll(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    let vector = |(long, lat): (f64, f64)| {
+        let (sin_long, cos_long) = math::sin_cos(long);
+        let (sin_lat, cos_lat) = math::sin_cos(lat);
+        [cos_lat * cos_long, cos_lat * sin_long, sin_lat]
+    };
+    let (va, vb) = (vector(a), vector(b));
+
+    let dot = va[0] * vb[0] + va[1] * vb[1] + va[2] * vb[2];
+    let cross = [
+        va[1] * vb[2] - va[2] * vb[1],
+        va[2] * vb[0] - va[0] * vb[2],
+        va[0] * vb[1] - va[1] * vb[0],
+    ];
+    let omega = math::atan2((cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt(), dot);
+
+    // 非常に近い2点では線形補間に等しい
+    if omega < 1e-12 {
+        return lerp_ll(a, b, t);
+    }
+
+    let sin_omega = math::sin(omega);
+    let (wa, wb) = (math::sin((1. - t) * omega) / sin_omega, math::sin(t * omega) / sin_omega);
+    let [x, y, z] = [0, 1, 2].map(|i| wa * va[i] + wb * vb[i]);
+
+    (math::atan2(y, x), math::atan2(z, (x * x + y * y).sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn lerp_ll_wraps_longitude() {
+        let a = (170_f64.to_radians(), 10_f64.to_radians());
+        let b = (-160_f64.to_radians(), 20_f64.to_radians());
+
+        let (long, lat) = lerp_ll(a, b, 1. / 3.);
+        assert_close_to(long.abs(), PI, 9);
+        assert_close_to(lat, 40_f64.to_radians() / 3., 9);
+
+        let (long, _) = lerp_ll(a, b, 2. / 3.);
+        assert_close_to(long, -170_f64.to_radians(), 9);
+        assert_eq!(lerp_ll(a, b, 0.), a);
+    }
+
+    #[test]
+    fn slerp_ll_follows_great_circle() {
+        let a = (170_f64.to_radians(), 0.);
+        let b = (-170_f64.to_radians(), 0.);
+
+        // 赤道上の大円は赤道そのもの
+        let (long, lat) = slerp_ll(a, b, 0.25);
+        assert_close_to(long, 175_f64.to_radians(), 9);
+        assert_close_to(lat, 0., 9);
+
+        // 経線上の大円は経線そのもの
+        let (long, lat) = slerp_ll((0.5, 0.), (0.5, 1.), 0.3);
+        assert_close_to(long, 0.5, 9);
+        assert_close_to(lat, 0.3, 9);
+
+        assert_eq!(slerp_ll(a, a, 0.5), a);
+    }
+}
@@ -1,9 +1,14 @@
 use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use num::cast::AsPrimitive;
+use num::complex::Complex64;
 use num::Integer;
 
+use crate::consts::{GRS80_INVERSE_FLATTENING, GRS80_SEMI_MAJOR_AXIS, JPR_SCALE_FACTOR};
+use crate::math;
+
 /// Origin of plane rectangular coordinate system
 ///
 /// 平面直角座標系の原点
@@ -51,6 +56,7 @@ impl JprOrigin {
     /// let origin = JprOrigin::parse(20);
     /// assert!(origin.is_ok());
     /// ```
+    #[allow(clippy::result_unit_err)]
     pub fn parse<T: Integer + AsPrimitive<u8>>(num: T) -> Result<Self, ()> {
         match num.as_() {
             1 => Ok(Self::One),
@@ -168,9 +174,87 @@ impl FromStr for JprOrigin {
     }
 }
 
+/// Enumerated type representing the notation of the name of a zone of the plane rectangular coordinate system
+///
+/// 平面直角座標系の系の名称の表記を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JprZoneNotation {
+    /// Japanese notation with a Roman numeral, e.g. "第IX系"
+    ///
+    /// ローマ数字による日本語の表記。例えば"第IX系"
+    Japanese,
+    /// English notation with a Roman numeral, e.g. "Zone IX"
+    ///
+    /// ローマ数字による英語の表記。例えば"Zone IX"
+    English,
+    /// Arabic numeral, e.g. "9"
+    ///
+    /// アラビア数字。例えば"9"
+    Number,
+}
+
+impl JprOrigin {
+    /// Returns the Roman numeral of the zone, e.g. "IX" for `JprOrigin::Nine`
+    ///
+    /// 系のローマ数字を返す。例えば`JprOrigin::Nine`では"IX"
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// assert_eq!(JprOrigin::Nineteen.roman(), "XIX");
+    /// ```
+    pub fn roman(&self) -> &'static str {
+        const ROMAN: [&str; 20] = [
+            "", "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII", "XIII", "XIV", "XV", "XVI",
+            "XVII", "XVIII", "XIX",
+        ];
+
+        ROMAN[*self as usize]
+    }
+
+    /// Returns the name of the zone in the notation.
+    /// `Display` uses `JprZoneNotation::Japanese`, or `JprZoneNotation::English` with the alternate flag (`{:#}`).
+    ///
+    /// 系の名称を指定した表記で返す。
+    /// `Display`は`JprZoneNotation::Japanese`を用い、代替フラグ(`{:#}`)を指定した場合は`JprZoneNotation::English`を用いる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::{JprOrigin, JprZoneNotation};
+    ///
+    /// assert_eq!(JprOrigin::Nine.name(JprZoneNotation::Japanese), "第IX系");
+    /// assert_eq!(JprOrigin::Nine.name(JprZoneNotation::English), "Zone IX");
+    /// assert_eq!(JprOrigin::Nine.name(JprZoneNotation::Number), "9");
+    ///
+    /// assert_eq!(format!("{}", JprOrigin::Nine), "第IX系");
+    /// assert_eq!(format!("{:#}", JprOrigin::Nine), "Zone IX");
+    /// ```
+    pub fn name(&self, notation: JprZoneNotation) -> String {
+        match notation {
+            JprZoneNotation::Japanese => format!("第{}系", self.roman()),
+            JprZoneNotation::English => format!("Zone {}", self.roman()),
+            JprZoneNotation::Number => (*self as u8).to_string(),
+        }
+    }
+}
+
+impl Display for JprOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let notation = if f.alternate() {
+            JprZoneNotation::English
+        } else {
+            JprZoneNotation::Japanese
+        };
+        write!(f, "{}", self.name(notation))
+    }
+}
+
 const DEG2RAD: f64 = PI / 180.;
 // 秒単位
-const LAT0: [f64; 20] = [
+pub(crate) const LAT0: [f64; 20] = [
     0.,
     33. * DEG2RAD,
     33. * DEG2RAD,
@@ -193,7 +277,7 @@ const LAT0: [f64; 20] = [
     26. * DEG2RAD,
 ];
 // 分単位
-const LONG0: [f64; 20] = [
+pub(crate) const LONG0: [f64; 20] = [
     0.,
     7770. / 60. * DEG2RAD,
     7860. / 60. * DEG2RAD,
@@ -216,6 +300,531 @@ const LONG0: [f64; 20] = [
     9240. / 60. * DEG2RAD,
 ];
 
+// 各系の適用区域を表す多角形の集合 [(経度, 緯度)]、度単位
+// 都道府県界・振興局界などを数km程度の精度で簡略化した折れ線で囲み、海上では島嶼を分けるように境界を引いている
+// 隣接する系の多角形は境界の頂点を共有するため、区域は重なり合わず、陸上に隙間もない
+const AREAS: [&[&[(f64, f64)]]; 20] = [
+    &[],
+    // 長崎県、鹿児島県のうち北緯32度以南かつ東経130度以西(奄美群島は東経130度13分まで)
+    &[&[
+        (127.50, 27.25), (127.80, 32.00), (128.40, 33.40), (129.00, 33.80), (129.05, 34.80), (129.80, 34.80),
+        (129.90, 34.10), (129.95, 33.90), (129.82, 33.60), (129.75, 33.38), (129.78, 33.33), (129.84, 33.22),
+        (129.90, 33.16), (129.98, 33.07), (130.10, 32.98), (130.20, 32.96), (130.40, 32.90), (130.45, 32.75),
+        (130.42, 32.62), (130.25, 32.57), (130.10, 32.575), (129.95, 32.55), (129.90, 32.40), (129.90, 32.00),
+        (130.00, 32.00), (130.00, 29.80), (130.00, 28.80), (130.2167, 28.80), (130.2167, 26.95), (128.33, 26.95),
+        (128.20, 27.25),
+    ]],
+    // 福岡県、佐賀県、熊本県、大分県、宮崎県、鹿児島県(I系に属する区域を除く)
+    &[&[
+        (129.80, 34.80), (129.90, 34.10), (129.95, 33.90), (129.82, 33.60), (129.75, 33.38), (129.78, 33.33),
+        (129.84, 33.22), (129.90, 33.16), (129.98, 33.07), (130.10, 32.98), (130.20, 32.96), (130.40, 32.90),
+        (130.45, 32.75), (130.42, 32.62), (130.25, 32.57), (130.10, 32.575), (129.95, 32.55), (129.90, 32.40),
+        (129.90, 32.00), (130.00, 32.00), (130.00, 29.80), (132.30, 29.80), (132.30, 32.30), (132.30, 32.75),
+        (132.20, 33.00), (131.97, 33.30), (131.85, 33.65), (131.40, 33.85), (131.05, 33.93), (130.96, 33.955),
+        (130.93, 33.945), (130.88, 33.92), (130.80, 34.10), (130.60, 34.50), (130.60, 34.80),
+    ]],
+    // 山口県、島根県、広島県
+    &[&[
+        (130.60, 36.50), (133.55, 36.50), (133.55, 35.70), (133.30, 35.55), (133.22, 35.55), (133.18, 35.50),
+        (133.29, 35.46), (133.29, 35.40), (133.20, 35.28), (133.10, 35.10), (133.25, 35.05), (133.40, 35.00),
+        (133.35, 34.85), (133.43, 34.60), (133.43, 34.45), (133.45, 34.33), (133.20, 34.30), (133.05, 34.32),
+        (132.95, 34.15), (132.70, 34.05), (132.40, 33.85), (132.00, 33.70), (131.85, 33.65), (131.40, 33.85),
+        (131.05, 33.93), (130.96, 33.955), (130.93, 33.945), (130.88, 33.92), (130.80, 34.10), (130.60, 34.50),
+        (130.60, 34.80),
+    ]],
+    // 香川県、愛媛県、徳島県、高知県
+    &[&[
+        (131.85, 33.65), (131.97, 33.30), (132.20, 33.00), (132.30, 32.75), (132.30, 32.30), (135.00, 32.30),
+        (135.00, 33.20), (134.97, 34.10), (134.75, 34.12), (134.66, 34.24), (134.50, 34.50), (134.25, 34.58),
+        (134.05, 34.52), (133.99, 34.48), (133.90, 34.42), (133.80, 34.40), (133.60, 34.33), (133.45, 34.33),
+        (133.20, 34.30), (133.05, 34.32), (132.95, 34.15), (132.70, 34.05), (132.40, 33.85), (132.00, 33.70),
+    ]],
+    // 兵庫県、鳥取県、岡山県
+    &[&[
+        (134.80, 36.50), (134.80, 35.95), (134.86, 35.63), (134.95, 35.45), (135.05, 35.28), (135.30, 35.24),
+        (135.40, 35.05), (135.43, 34.95), (135.423, 34.82), (135.42, 34.72), (135.42, 34.67), (135.25, 34.55),
+        (135.05, 34.40), (134.98, 34.28), (134.97, 34.10), (134.75, 34.12), (134.66, 34.24), (134.50, 34.50),
+        (134.25, 34.58), (134.05, 34.52), (133.99, 34.48), (133.90, 34.42), (133.80, 34.40), (133.60, 34.33),
+        (133.45, 34.33), (133.43, 34.45), (133.43, 34.60), (133.35, 34.85), (133.40, 35.00), (133.25, 35.05),
+        (133.10, 35.10), (133.20, 35.28), (133.29, 35.40), (133.29, 35.46), (133.18, 35.50), (133.22, 35.55),
+        (133.30, 35.55), (133.55, 35.70), (133.55, 36.50),
+    ]],
+    // 京都府、大阪府、福井県、滋賀県、三重県、奈良県、和歌山県
+    &[&[
+        (136.10, 36.50), (136.27, 36.25), (136.55, 36.18), (136.75, 36.08), (136.80, 35.93), (136.60, 35.75),
+        (136.40, 35.72), (136.28, 35.62), (136.40, 35.45), (136.40, 35.28), (136.50, 35.17), (136.62, 35.13),
+        (136.70, 35.10), (136.74, 35.02), (136.80, 34.80), (136.95, 34.62), (137.05, 34.50), (136.40, 33.30),
+        (135.00, 33.20), (134.97, 34.10), (134.98, 34.28), (135.05, 34.40), (135.25, 34.55), (135.42, 34.67),
+        (135.42, 34.72), (135.423, 34.82), (135.43, 34.95), (135.40, 35.05), (135.30, 35.24), (135.05, 35.28),
+        (134.95, 35.45), (134.86, 35.63), (134.80, 35.95), (134.80, 36.50),
+    ]],
+    // 石川県、富山県、岐阜県、愛知県
+    &[&[
+        (136.10, 36.50), (136.27, 36.25), (136.55, 36.18), (136.75, 36.08), (136.80, 35.93), (136.60, 35.75),
+        (136.40, 35.72), (136.28, 35.62), (136.40, 35.45), (136.40, 35.28), (136.50, 35.17), (136.62, 35.13),
+        (136.70, 35.10), (136.74, 35.02), (136.80, 34.80), (136.95, 34.62), (137.05, 34.50), (137.40, 34.40),
+        (137.47, 34.67), (137.63, 34.85), (137.76, 35.02), (137.78, 35.17), (137.60, 35.19), (137.48, 35.28),
+        (137.59, 35.52), (137.60, 35.62), (137.47, 35.85), (137.55, 36.15), (137.65, 36.45), (137.72, 36.75),
+        (137.62, 36.97), (137.62, 37.30), (137.80, 38.75), (136.10, 38.75),
+    ]],
+    // 新潟県、長野県、山梨県、静岡県
+    &[&[
+        (137.80, 38.75), (139.20, 38.75), (139.55, 38.55), (139.75, 38.40), (139.65, 38.10), (139.70, 37.85),
+        (139.55, 37.70), (139.45, 37.50), (139.15, 37.30), (139.25, 36.95), (138.95, 36.85), (138.62, 36.80),
+        (138.52, 36.55), (138.52, 36.40), (138.72, 36.36), (138.62, 36.20), (138.70, 36.00), (138.73, 35.90),
+        (138.94, 35.86), (139.00, 35.75), (139.13, 35.68), (139.16, 35.62), (139.05, 35.52), (138.97, 35.42),
+        (139.04, 35.31), (138.98, 35.20), (139.12, 35.13), (139.20, 34.90), (139.15, 34.55), (138.80, 34.40),
+        (137.40, 34.40), (137.47, 34.67), (137.63, 34.85), (137.76, 35.02), (137.78, 35.17), (137.60, 35.19),
+        (137.48, 35.28), (137.59, 35.52), (137.60, 35.62), (137.47, 35.85), (137.55, 36.15), (137.65, 36.45),
+        (137.72, 36.75), (137.62, 36.97), (137.62, 37.30),
+    ]],
+    // 東京都(XIV系、XVIII系及びXIX系に属する区域を除く)、福島県、栃木県、茨城県、埼玉県、千葉県、群馬県、神奈川県
+    &[&[
+        (139.70, 37.85), (139.55, 37.70), (139.45, 37.50), (139.15, 37.30), (139.25, 36.95), (138.95, 36.85),
+        (138.62, 36.80), (138.52, 36.55), (138.52, 36.40), (138.72, 36.36), (138.62, 36.20), (138.70, 36.00),
+        (138.73, 35.90), (138.94, 35.86), (139.00, 35.75), (139.13, 35.68), (139.16, 35.62), (139.05, 35.52),
+        (138.97, 35.42), (139.04, 35.31), (138.98, 35.20), (139.12, 35.13), (139.20, 34.90), (139.15, 34.55),
+        (138.80, 34.40), (138.60, 33.80), (139.00, 28.00), (141.00, 28.00), (140.90, 34.80), (141.20, 35.70),
+        (141.50, 37.90), (140.95, 37.90), (140.85, 37.92), (140.72, 37.86), (140.60, 37.95), (140.50, 37.92),
+        (140.30, 37.80), (140.00, 37.82),
+    ]],
+    // 青森県、秋田県、山形県、岩手県、宮城県
+    &[&[
+        (139.20, 38.75), (139.20, 41.30), (140.30, 41.33), (140.70, 41.60), (141.30, 41.65), (142.20, 41.65),
+        (142.20, 37.90), (141.50, 37.90), (140.95, 37.90), (140.85, 37.92), (140.72, 37.86), (140.60, 37.95),
+        (140.50, 37.92), (140.30, 37.80), (140.00, 37.82), (139.70, 37.85), (139.65, 38.10), (139.75, 38.40),
+        (139.55, 38.55),
+    ]],
+    // 北海道のうち小樽市、函館市、伊達市、北斗市、後志総合振興局、檜山振興局、渡島総合振興局の管轄区域等
+    &[&[
+        (139.20, 41.30), (140.30, 41.33), (140.70, 41.60), (141.30, 41.65), (141.30, 41.85), (140.85, 42.25),
+        (140.91, 42.39), (141.00, 42.47), (141.20, 42.60), (141.20, 42.75), (141.05, 42.85), (141.15, 43.10),
+        (141.17, 43.25), (141.00, 43.55), (139.20, 43.55),
+    ]],
+    // 北海道のうちXI系及びXIII系に属する区域を除いた区域
+    &[&[
+        (141.30, 41.85), (140.85, 42.25), (140.91, 42.39), (141.00, 42.47), (141.20, 42.60), (141.20, 42.75),
+        (141.05, 42.85), (141.15, 43.10), (141.17, 43.25), (141.00, 43.55), (140.80, 45.60), (142.30, 45.65),
+        (143.85, 44.60), (143.80, 44.18), (143.75, 44.00), (143.55, 43.85), (143.35, 43.75), (143.15, 43.60),
+        (142.85, 43.53), (142.80, 43.25), (142.75, 42.90), (142.95, 42.55), (143.30, 42.15), (143.40, 41.80),
+    ]],
+    // 北海道のうち北見市、帯広市、釧路市、網走市、根室市、オホーツク総合振興局、十勝総合振興局、釧路総合振興局、根室振興局の管轄区域等
+    &[&[
+        (143.40, 41.80), (143.30, 42.15), (142.95, 42.55), (142.75, 42.90), (142.80, 43.25), (142.85, 43.53),
+        (143.15, 43.60), (143.35, 43.75), (143.55, 43.85), (143.75, 44.00), (143.80, 44.18), (143.85, 44.60),
+        (146.50, 45.00), (148.00, 45.70), (149.10, 45.70), (149.10, 44.90), (147.50, 43.30), (146.50, 42.90),
+        (145.00, 42.50),
+    ]],
+    // 東京都のうち北緯28度以南かつ東経140度30分以東、東経143度以西
+    &[&[
+        (140.50, 24.00), (140.50, 28.00), (143.00, 28.00), (143.00, 24.00),
+    ]],
+    // 沖縄県のうち東経126度以東かつ東経130度以西
+    &[&[
+        (126.00, 25.50), (126.00, 27.25), (128.20, 27.25), (128.33, 26.95), (130.2167, 26.95), (130.00, 26.95),
+        (130.00, 25.50),
+    ]],
+    // 沖縄県のうち東経126度以西
+    &[&[
+        (122.50, 23.80), (122.50, 26.20), (126.00, 26.20), (126.00, 23.80),
+    ]],
+    // 沖縄県のうち東経130度以東
+    &[&[
+        (130.00, 24.00), (130.00, 26.50), (132.00, 26.50), (132.00, 24.00),
+    ]],
+    // 東京都のうち北緯28度以南かつ東経140度30分以西
+    &[&[
+        (135.50, 20.00), (135.50, 28.00), (140.50, 28.00), (140.50, 20.00),
+    ]],
+    // 東京都のうち北緯28度以南かつ東経143度以東
+    &[&[
+        (143.00, 24.00), (143.00, 28.00), (154.50, 28.00), (154.50, 24.00),
+    ]],
+];
+
+impl JprOrigin {
+    /// Returns the areas where this origin applies as a list of polygons [(longitude, latitude)] expressed in the arc degree method.
+    /// The polygons simplify the prefecture and subprefecture borders to within a few kilometres, and share their boundary vertices with those of neighbouring origins, so areas neither overlap nor leave gaps on land.
+    ///
+    /// この原点が適用される区域を多角形[(経度, 緯度)]のリストとして弧度法で返す。
+    /// 多角形は都道府県界・振興局界などを数km程度の精度で簡略化したもので、隣接する系と境界の頂点を共有するため、区域は重なり合わず陸上に隙間もない。
+    pub fn areas(&self) -> Vec<Vec<(f64, f64)>> {
+        AREAS[*self as usize]
+            .iter()
+            .map(|ring| ring.iter().map(|&(long, lat)| (long * DEG2RAD, lat * DEG2RAD)).collect())
+            .collect()
+    }
+
+    /// Returns the latitude of the origin expressed in the arc degree method
+    ///
+    /// 原点の緯度を弧度法で返す
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// assert_eq!(JprOrigin::Nine.origin_lat(), 36_f64.to_radians());
+    /// ```
+    pub fn origin_lat(&self) -> f64 {
+        LAT0[*self as usize]
+    }
+
+    /// Returns the longitude of the origin, the central meridian of the zone, expressed in the arc degree method
+    ///
+    /// 原点の経度(系の中央子午線)を弧度法で返す
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// assert!((JprOrigin::Nine.origin_long() - (139. + 50. / 60_f64).to_radians()).abs() < 1e-15);
+    /// ```
+    pub fn origin_long(&self) -> f64 {
+        LONG0[*self as usize]
+    }
+
+    /// Returns the latitude of the origin expressed in the degree method
+    ///
+    /// 原点の緯度を度数法で返す
+    pub fn origin_lat_deg(&self) -> f64 {
+        self.origin_lat().to_degrees()
+    }
+
+    /// Returns the longitude of the origin, the central meridian of the zone, expressed in the degree method
+    ///
+    /// 原点の経度(系の中央子午線)を度数法で返す
+    pub fn origin_long_deg(&self) -> f64 {
+        self.origin_long().to_degrees()
+    }
+
+    /// Returns the rectangle ((minimum longitude, minimum latitude), (maximum longitude, maximum latitude)) enclosing the area where this origin applies, expressed in the arc degree method.
+    /// The rectangle also contains sea and parts of neighbouring areas, so rectangles of neighbouring origins overlap.
+    ///
+    /// この原点が適用される区域を囲む矩形((最小経度, 最小緯度), (最大経度, 最大緯度))を弧度法で返す。
+    /// 矩形は海域や隣接する区域の一部も含むため、隣接する系の矩形とは重なり合う。
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        self.areas().into_iter().flatten().fold(
+            ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |((min_long, min_lat), (max_long, max_lat)), (long, lat)| {
+                ((min_long.min(long), min_lat.min(lat)), (max_long.max(long), max_lat.max(lat)))
+            },
+        )
+    }
+
+    /// Returns the range ((minimum y, minimum x), (maximum y, maximum x)) (m) of plane rectangular coordinates of this origin, which encloses the projected areas where the origin applies with a margin of about 10 km
+    ///
+    /// この原点の平面直角座標の範囲((最小y, 最小x), (最大y, 最大x))(m)を返す。原点の適用区域を投影したものを約10kmの余裕をもって囲む
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// let ((min_y, min_x), (max_y, max_x)) = JprOrigin::Nine.jpr_range();
+    ///
+    /// assert!(min_x < -612000. && min_y < 0. && max_y > 0. && max_x > 0.);
+    /// ```
+    pub fn jpr_range(&self) -> ((f64, f64), (f64, f64)) {
+        JPR_RANGES[*self as usize]
+    }
+
+    /// Returns whether (longitude, latitude) expressed in the arc degree method is within the area where this origin applies.
+    ///
+    /// 弧度法で表された(経度, 緯度)がこの原点の適用区域内にあるかどうかを返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// let tokyo = (139.7671_f64.to_radians(), 35.6812_f64.to_radians());
+    /// assert!(JprOrigin::Nine.contains(tokyo));
+    /// ```
+    pub fn contains(&self, ll: (f64, f64)) -> bool {
+        let (long, lat) = (ll.0 / DEG2RAD, ll.1 / DEG2RAD);
+
+        AREAS[*self as usize].iter().any(|ring| {
+            // 点から東へ伸ばした半直線と辺の交差数の偶奇で判定する
+            // 下端を含み上端を含まない半開区間で数えるため、共有する境界上の点はちょうど一方の系に属する
+            let mut inside = false;
+            let mut prev = ring[ring.len() - 1];
+            for &(x, y) in ring.iter() {
+                if (y > lat) != (prev.1 > lat) && long < (prev.0 - x) * (lat - y) / (prev.1 - y) + x {
+                    inside = !inside;
+                }
+                prev = (x, y);
+            }
+            inside
+        })
+    }
+
+    /// Returns all origins whose area contains (longitude, latitude) expressed in the arc degree method.
+    /// Since the areas do not overlap, at most one origin is returned, and none for points outside Japan.
+    ///
+    /// 弧度法で表された(経度, 緯度)を適用区域に含む全ての原点を返す。
+    /// 区域は重なり合わないため、返される原点は高々1つであり、日本の外の点では空となる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// let tokyo = (139.7671_f64.to_radians(), 35.6812_f64.to_radians());
+    /// assert_eq!(JprOrigin::applicable_zones(tokyo), vec![JprOrigin::Nine]);
+    /// ```
+    pub fn applicable_zones(ll: (f64, f64)) -> Vec<JprOrigin> {
+        (1..=19)
+            .filter_map(|num| JprOrigin::parse(num).ok())
+            .filter(|origin| origin.contains(ll))
+            .collect()
+    }
+
+    /// Returns the origin whose area contains (longitude, latitude) expressed in the arc degree method, or `None` if no origin applies.
+    ///
+    /// 弧度法で表された(経度, 緯度)を適用区域に含む原点を返す。適用される原点がない場合は`None`を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// let tokyo = (139.7671_f64.to_radians(), 35.6812_f64.to_radians());
+    /// assert_eq!(JprOrigin::best_zone(tokyo), Some(JprOrigin::Nine));
+    /// ```
+    pub fn best_zone(ll: (f64, f64)) -> Option<JprOrigin> {
+        Self::applicable_zones(ll).into_iter().next()
+    }
+
+    /// Returns the origin among `origins` whose central meridian is nearest to the longitude
+    ///
+    /// `origins`のうち中央子午線が経度に最も近い原点を返す
+    pub(crate) fn nearest(origins: impl IntoIterator<Item=JprOrigin>, ll: (f64, f64)) -> Option<JprOrigin> {
+        origins
+            .into_iter()
+            .min_by(|a, b| {
+                let da = (ll.0 - LONG0[*a as usize]).abs();
+                let db = (ll.0 - LONG0[*b as usize]).abs();
+                da.total_cmp(&db)
+            })
+    }
+}
+
+// 矩形 ((最小経度, 最小緯度), (最大経度, 最大緯度))、度単位
+type Rect = ((f64, f64), (f64, f64));
+
+/// Coarse rectangle ((minimum longitude, minimum latitude), (maximum longitude, maximum latitude)) enclosing Japan including its remote islands, in degrees
+///
+/// 離島を含む日本を囲む大まかな矩形((最小経度, 最小緯度), (最大経度, 最大緯度))、度単位
+const JAPAN_BBOX: Rect = ((122., 20.), (154., 46.));
+
+/// Function to return whether (longitude, latitude) expressed in the arc degree method is within the area of any origin of the plane rectangular coordinate system.
+/// Points outside the coverage should be handled with e.g. UTM or Web Mercator, since `ll2jpr` would silently produce distorted values for them.
+///
+/// 弧度法で表された(経度, 緯度)が平面直角座標系のいずれかの原点の適用区域内にあるかどうかを返す関数。
+/// 適用区域外の点は`ll2jpr`でひずんだ値が黙って得られるため、UTMやWebメルカトルなどで扱うべきである。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::is_within_jpr_coverage;
+///
+/// assert!(is_within_jpr_coverage((139.7671_f64.to_radians(), 35.6812_f64.to_radians())));
+/// assert!(!is_within_jpr_coverage((126.978_f64.to_radians(), 37.5665_f64.to_radians())));
+/// ```
+pub fn is_within_jpr_coverage(ll: (f64, f64)) -> bool {
+    (1..=19)
+        .filter_map(|num| JprOrigin::parse(num).ok())
+        .any(|origin| origin.contains(ll))
+}
+
+/// Function to return whether (longitude, latitude) expressed in the arc degree method is within a coarse rectangle enclosing Japan (122°E to 154°E, 20°N to 46°N).
+/// It is cheaper than `is_within_jpr_coverage` but also contains parts of neighbouring countries and the sea.
+///
+/// 弧度法で表された(経度, 緯度)が日本を囲む大まかな矩形(東経122°から154°、北緯20°から46°)内にあるかどうかを返す関数。
+/// `is_within_jpr_coverage`より軽量だが、近隣国の一部や海域も含む。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::is_within_japan_bbox;
+///
+/// assert!(is_within_japan_bbox((139.7671_f64.to_radians(), 35.6812_f64.to_radians())));
+/// assert!(!is_within_japan_bbox((-0.1276_f64.to_radians(), 51.5072_f64.to_radians())));
+/// ```
+pub fn is_within_japan_bbox(ll: (f64, f64)) -> bool {
+    let (long, lat) = (ll.0 / DEG2RAD, ll.1 / DEG2RAD);
+    let ((min_long, min_lat), (max_long, max_lat)) = JAPAN_BBOX;
+
+    (min_long..=max_long).contains(&long) && (min_lat..=max_lat).contains(&lat)
+}
+
+/*
+Since floating-point arithmetic cannot be performed at compile-time at this time, the result of executing the following code is used as a constant.
+浮動小数点演算は現時点でコンパイル時実行できないため、以下のコードを実行した結果を定数として用いる
+
+const F: f64 = 298.257222101;
+const N: f64 = 1. / (2. * F - 1.);
+
+let a0 = 1. + (N.powf(2.)) / 4. + (N.powf(4.)) / 64.;
+
+let a_arr = [
+    -(3. / 2.) * (N - (N.powf(3.)) / 8. - (N.powf(5.)) / 64.),
+    (15. / 16.) * (N.powf(2.) - (N.powf(4.)) / 4.),
+    -(35. / 48.) * (N.powf(3.) - (5. / 16.) * (N.powf(5.))),
+    (315. / 512.) * (N.powf(4.)),
+    -(693. / 1280.) * (N.powf(5.)),
+];
+
+let alpha_arr = [
+    (1. / 2.) * N - (2. / 3.) * (N.powf(2.))
+        + (5. / 16.) * (N.powf(3.))
+        + (41. / 180.) * (N.powf(4.))
+        - (127. / 288.) * (N.powf(5.)),
+    (13. / 48.) * (N.powf(2.)) - (3. / 5.) * (N.powf(3.))
+        + (557. / 1440.) * (N.powf(4.))
+        + (281. / 630.) * (N.powf(5.)),
+    (61. / 240.) * (N.powf(3.)) - (103. / 140.) * (N.powf(4.))
+        + (15061. / 26880.) * (N.powf(5.)),
+    (49561. / 161280.) * (N.powf(4.)) - (179. / 168.) * (N.powf(5.)),
+    (34729. / 80640.) * (N.powf(5.)),
+];
+
+let beta_arr = [
+    (1. / 2.) * N - (2. / 3.) * (N.powf(2.)) + (37. / 96.) * (N.powf(3.))
+        - (1. / 360.) * (N.powf(4.))
+        - (81. / 512.) * (N.powf(5.)),
+    (1. / 48.) * (N.powf(2.)) + (1. / 15.) * (N.powf(3.)) - (437. / 1440.) * (N.powf(4.))
+        + (46. / 105.) * (N.powf(5.)),
+    (17. / 480.) * (N.powf(3.)) - (37. / 840.) * (N.powf(4.)) - (209. / 4480.) * (N.powf(5.)),
+    (4397. / 161280.) * (N.powf(4.)) - (11. / 504.) * (N.powf(5.)),
+    (4583. / 161280.) * (N.powf(5.)),
+];
+
+let delta_arr = [
+    2. * N - (2. / 3.) * (N.powf(2.)) - 2. * (N.powf(3.))
+        + (116. / 45.) * (N.powf(4.))
+        + (26. / 45.) * (N.powf(5.))
+        - (2854. / 675.) * (N.powf(6.)),
+    (7. / 3.) * (N.powf(2.)) - (8. / 5.) * (N.powf(3.)) - (227. / 45.) * (N.powf(4.))
+        + (2704. / 315.) * (N.powf(5.))
+        + (2323. / 945.) * (N.powf(6.)),
+    (56. / 15.) * (N.powf(3.)) - (136. / 35.) * (N.powf(4.)) - (1262. / 105.) * (N.powf(5.))
+        + (73814. / 2835.) * (N.powf(6.)),
+    (4279. / 630.) * (N.powf(4.))
+        - (332. / 35.) * (N.powf(5.))
+        - (399572. / 14175.) * (N.powf(6.)),
+    (4174. / 315.) * (N.powf(5.)) - (144838. / 6237.) * (N.powf(6.)),
+    (601676. / 22275.) * (N.powf(6.)),
+];
+
+println!("const A0: f64 = {:?};", a0);
+println!("const A_ARR: [f64; 5] = {:?};", a_arr);
+println!("const ALPHA_ARR: [f64; 5] = {:?};", alpha_arr);
+println!("const BETA_ARR: [f64; 5] = {:?};", beta_arr);
+println!("const DELTA_ARR: [f64; 6] = {:?};", delta_arr);
+
+// 各原点における赤道からの子午線弧長
+let s0_arr = LAT0.map(|lat0| {
+    ((M0 * A) / (1. + N))
+        * (A0 * lat0
+        + a_arr.iter().enumerate().fold(0., |acc, (i, &a)| {
+        acc + a * (2. * (i as f64 + 1.) * lat0).sin()
+    }))
+});
+
+println!("const S0_ARR: [f64; 20] = {:?};", s0_arr);
+ */
+
+pub(crate) const A0: f64 = 1.0000007049454078;
+pub(crate) const ALPHA_ARR: [f64; 5] = [
+    0.0008377318247285465,
+    7.608527848379248e-7,
+    1.1976455002315586e-9,
+    2.4291502606542468e-12,
+    5.750164384091974e-15,
+];
+const BETA_ARR: [f64; 5] = [
+    0.0008377321681620316,
+    5.905870211016955e-8,
+    1.6734826761541112e-10,
+    2.1648237311010893e-13,
+    3.79409187887551e-16,
+];
+const DELTA_ARR: [f64; 6] = [
+    0.003356551485604312,
+    6.571873263127177e-6,
+    1.7646404372866207e-8,
+    5.3877538900094696e-11,
+    1.7640075159133883e-13,
+    6.056074055207582e-16,
+];
+
+pub(crate) const S0_ARR: [f64; 20] = [
+    0.0,
+    3652382.768270788,
+    3652382.768270788,
+    3985144.116029223,
+    3652382.768270788,
+    3985144.116029223,
+    3985144.116029223,
+    3985144.116029223,
+    3985144.116029223,
+    3985144.116029223,
+    4429086.077333566,
+    4873334.987359202,
+    4873334.987359202,
+    4873334.987359202,
+    2876546.889061122,
+    2876546.889061122,
+    2876546.889061122,
+    2876546.889061122,
+    2212145.0174775715,
+    2876546.889061122,
+];
+
+// 定数
+pub(crate) const M0: f64 = JPR_SCALE_FACTOR;
+pub(crate) const A: f64 = GRS80_SEMI_MAJOR_AXIS;
+const F: f64 = GRS80_INVERSE_FLATTENING;
+pub(crate) const N: f64 = 1. / (2. * F - 1.);
+
+/// Evaluates Σ c_k sin(2kχ) (k = 1, 2, ...) with Clenshaw's recurrence.
+///
+/// Σ c_k sin(2kχ) (k = 1, 2, ...)をClenshawの漸化式で評価する。
+fn clenshaw_sin(coeffs: &[f64], chi: f64) -> f64 {
+    let (sin2, cos2) = math::sin_cos(2. * chi);
+
+    let (b1, _) = coeffs.iter().rev().fold((0., 0.), |(b1, b2), &c| {
+        (c + 2. * cos2 * b1 - b2, b1)
+    });
+
+    b1 * sin2
+}
+
+/// Evaluates Σ c_k sin(2kζ) (k = 1, 2, ...) for the complex number ζ = ξ + iη with Clenshaw's recurrence.
+/// Returns (Σ c_k sin(2kξ)cosh(2kη), Σ c_k cos(2kξ)sinh(2kη)), the real and imaginary parts, with only four transcendental calls.
+///
+/// 複素数ζ = ξ + iηについてΣ c_k sin(2kζ) (k = 1, 2, ...)をClenshawの漸化式で評価する。
+/// 実部と虚部である(Σ c_k sin(2kξ)cosh(2kη), Σ c_k cos(2kξ)sinh(2kη))を、4回の超越関数の呼び出しのみで返す。
+pub(crate) fn clenshaw_complex_sin(coeffs: &[f64], (xi, eta): (f64, f64)) -> (f64, f64) {
+    let (s, c) = math::sin_cos(2. * xi);
+    let (sh, ch) = (math::sinh(2. * eta), math::cosh(2. * eta));
+
+    // sin(2ζ)とcos(2ζ)
+    let sin2 = Complex64::new(s * ch, c * sh);
+    let cos2 = Complex64::new(c * ch, -s * sh);
+
+    let (b1, _) = coeffs.iter().rev().fold((Complex64::new(0., 0.), Complex64::new(0., 0.)), |(b1, b2), &c| {
+        (c + 2. * cos2 * b1 - b2, b1)
+    });
+
+    let sum = b1 * sin2;
+
+    (sum.re, sum.im)
+}
+
 /// Convert plane rectangular coordinates (y, x) to (longitude, latitude) expressed in arc degree method.
 /// Origin is based on Japan Geodetic System 2011.
 ///
@@ -234,123 +843,30 @@ const LONG0: [f64; 20] = [
 /// let (long, lat) = jpr2ll((22694.980, 11573.375), JprOrigin::Nine);
 /// ```
 pub fn jpr2ll(yx: (f64, f64), origin: JprOrigin) -> (f64, f64) {
-    let (y, x) = yx;
-
-    /*
-    Since floating-point arithmetic cannot be performed at compile-time at this time, the result of executing the following code is used as a constant.
-    浮動小数点演算は現時点でコンパイル時実行できないため、以下のコードを実行した結果を定数として用いる
-
-    const F: f64 = 298.257222101;
-    const N: f64 = 1. / (2. * F - 1.);
-
-    let a0 =
-        1. + (N.powf(2.)) / 4. + (N.powf(4.)) / 64.;
-
-    let a_array: [f64; 5] = [
-        -(3. / 2.) * (N - (N.powf(3.)) / 8. - (N.powf(5.)) / 64.),
-        (15. / 16.) * (N.powf(2.) - (N.powf(4.)) / 4.),
-        -(35. / 48.) * (N.powf(3.) - (5. / 16.) * (N.powf(5.))),
-        (315. / 512.) * (N.powf(4.)),
-        -(693. / 1280.) * (N.powf(5.)),
-        ];
+    tm2ll(yx, LONG0[origin as usize], M0, S0_ARR[origin as usize])
+}
 
-    let beta_array: [f64; 5] = [
-        (1. / 2.) * N - (2. / 3.) * (N.powf(2.)) + (37. / 96.) * (N.powf(3.))
-            - (1. / 360.) * (N.powf(4.))
-            - (81. / 512.) * (N.powf(5.)),
-        (1. / 48.) * (N.powf(2.)) + (1. / 15.) * (N.powf(3.)) - (437. / 1440.) * (N.powf(4.))
-            + (46. / 105.) * (N.powf(5.)),
-        (17. / 480.) * (N.powf(3.)) - (37. / 840.) * (N.powf(4.)) - (209. / 4480.) * (N.powf(5.)),
-        (4397. / 161280.) * (N.powf(4.)) - (11. / 504.) * (N.powf(5.)),
-        (4583. / 161280.) * (N.powf(5.)),
-    ];
-
-    let delta_array: [f64; 6] = [
-        2. * N - (2. / 3.) * (N.powf(2.)) - 2. * (N.powf(3.))
-            + (116. / 45.) * (N.powf(4.))
-            + (26. / 45.) * (N.powf(5.))
-            - (2854. / 675.) * (N.powf(6.)),
-        (7. / 3.) * (N.powf(2.)) - (8. / 5.) * (N.powf(3.)) - (227. / 45.) * (N.powf(4.))
-            + (2704. / 315.) * (N.powf(5.))
-            + (2323. / 945.) * (N.powf(6.)),
-        (56. / 15.) * (N.powf(3.)) - (136. / 35.) * (N.powf(4.)) - (1262. / 105.) * (N.powf(5.))
-            + (73814. / 2835.) * (N.powf(6.)),
-        (4279. / 630.) * (N.powf(4.))
-            - (332. / 35.) * (N.powf(5.))
-            - (399572. / 14175.) * (N.powf(6.)),
-        (4174. / 315.) * (N.powf(5.)) - (144838. / 6237.) * (N.powf(6.)),
-        (601676. / 22275.) * (N.powf(6.)),
-    ];
-
-
-    println!("const A0:f64 = {:?};", a0);
-    println!("const A_ARR:[f64;5] = {:?};", a_array);
-    println!("const BETA_ARR:[f64;5] = {:?};", beta_array);
-    println!("const DELTA_ARR:[f64;6] = {:?};", delta_array);
-     */
-
-    const A0: f64 = 1.0000007049454078;
-    const A_ARR: [f64; 5] = [
-        -0.0025188297041239312,
-        2.6435429493240994e-6,
-        -3.4526259073074147e-9,
-        4.891830424387949e-12,
-        -7.228726045813916e-15,
-    ];
-    const BETA_ARR: [f64; 5] = [
-        0.0008377321681620316,
-        5.905870211016955e-8,
-        1.6734826761541112e-10,
-        2.1648237311010893e-13,
-        3.79409187887551e-16,
-    ];
-    const DELTA_ARR: [f64; 6] = [
-        0.003356551485604312,
-        6.571873263127177e-6,
-        1.7646404372866207e-8,
-        5.3877538900094696e-11,
-        1.7640075159133883e-13,
-        6.056074055207582e-16,
-    ];
-
-    // 定数
-    const M0: f64 = 0.9999;
-    const A: f64 = 6378137.;
-    const F: f64 = 298.257222101;
-    const N: f64 = 1. / (2. * F - 1.);
-
-    const A_: f64 = M0 * A * A0 / (1. + N);
-
-    let lat0 = LAT0[origin as usize];
-    let long0 = LONG0[origin as usize];
+/// Convert transverse Mercator coordinates (y, x) with the central meridian `long0`, the scale factor `m0` and the meridian arc length `s_` (m) of the origin from the equator, before the false easting and northing, to (longitude, latitude) expressed in arc degree method.
+///
+/// 中央経線`long0`、縮尺係数`m0`、原点の赤道からの子午線弧長`s_`(m)の横メルカトル座標(y, x)を、偽東距・偽北距を加える前の値として、弧度法で表現された(経度, 緯度)に変換する。
+pub(crate) fn tm2ll(yx: (f64, f64), long0: f64, m0: f64, s_: f64) -> (f64, f64) {
+    let (y, x) = yx;
 
-    let s_ = ((M0 * A) / (1. + N))
-        * (A0 * lat0
-        + A_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
-        acc + a * (2. * (i as f64 + 1.) * lat0).sin()
-    }));
+    let a_ = m0 * A * A0 / (1. + N);
 
-    let xi = (x + s_) / A_;
-    let eta = y / A_;
+    let xi = (x + s_) / a_;
+    let eta = y / a_;
 
-    let xi2 = xi
-        - BETA_ARR.iter().enumerate().fold(0., |acc, (i, &b)| {
-        acc + b * (2. * (i as f64 + 1.) * xi).sin() * (2. * (i as f64 + 1.) * eta).cosh()
-    });
+    let (d_xi, d_eta) = clenshaw_complex_sin(&BETA_ARR, (xi, eta));
 
-    let eta2 = eta
-        - BETA_ARR.iter().enumerate().fold(0., |acc, (i, &b)| {
-        acc + b * (2. * (i as f64 + 1.) * xi).cos() * (2. * (i as f64 + 1.) * eta).sinh()
-    });
+    let xi2 = xi - d_xi;
+    let eta2 = eta - d_eta;
 
-    let chi = (xi2.sin() / eta2.cosh()).asin();
+    let chi = math::asin(math::sin(xi2) / math::cosh(eta2));
 
-    let lat = chi
-        + DELTA_ARR.iter().enumerate().fold(0., |acc, (i, &d)| {
-        acc + d * (2. * (i as f64 + 1.) * chi).sin()
-    });
+    let lat = chi + clenshaw_sin(&DELTA_ARR, chi);
 
-    let long = long0 + (eta2.sinh() / xi2.cos()).atan();
+    let long = long0 + math::atan(math::sinh(eta2) / math::cos(xi2));
 
     (long, lat)
 }
@@ -379,101 +895,586 @@ pub fn jpr2ll(yx: (f64, f64), origin: JprOrigin) -> (f64, f64) {
 /// );
 /// ```
 pub fn ll2jpr(ll: (f64, f64), origin: JprOrigin) -> (f64, f64) {
+    ll2tm(ll, LONG0[origin as usize], M0, S0_ARR[origin as usize])
+}
+
+/// Convert (longitude, latitude) expressed in arc degree method to transverse Mercator coordinates (y, x) with the central meridian `long0`, the scale factor `m0` and the meridian arc length `s_` (m) of the origin from the equator, before the false easting and northing.
+///
+/// 弧度法で表現された(経度, 緯度)を、中央経線`long0`、縮尺係数`m0`、原点の赤道からの子午線弧長`s_`(m)の横メルカトル座標(y, x)に、偽東距・偽北距を加える前の値として変換する。
+pub(crate) fn ll2tm(ll: (f64, f64), long0: f64, m0: f64, s_: f64) -> (f64, f64) {
     let (long, lat) = ll;
 
-    /*
-    Since floating-point arithmetic cannot be performed at compile-time at this time, the result of executing the following code is used as a constant.
-    浮動小数点演算は現時点でコンパイル時実行できないため、以下のコードを実行した結果を定数として用いる
-
-    const F: f64 = 298.257222101;
-    const N: f64 = 1. / (2. * F - 1.);
-
-    let a0 = 1. + (N.powf(2.)) / 4. + (N.powf(4.)) / 64.;
-
-    let a_arr = [
-        -(3. / 2.) * (N - (N.powf(3.)) / 8. - (N.powf(5.)) / 64.),
-        (15. / 16.) * (N.powf(2.) - (N.powf(4.)) / 4.),
-        -(35. / 48.) * (N.powf(3.) - (5. / 16.) * (N.powf(5.))),
-        (315. / 512.) * (N.powf(4.)),
-        -(693. / 1280.) * (N.powf(5.)),
-    ];
-    let alpha_arr = [
-        (1. / 2.) * N - (2. / 3.) * (N.powf(2.))
-            + (5. / 16.) * (N.powf(3.))
-            + (41. / 180.) * (N.powf(4.))
-            - (127. / 288.) * (N.powf(5.)),
-        (13. / 48.) * (N.powf(2.)) - (3. / 5.) * (N.powf(3.))
-            + (557. / 1440.) * (N.powf(4.))
-            + (281. / 630.) * (N.powf(5.)),
-        (61. / 240.) * (N.powf(3.)) - (103. / 140.) * (N.powf(4.))
-            + (15061. / 26880.) * (N.powf(5.)),
-        (49561. / 161280.) * (N.powf(4.)) - (179. / 168.) * (N.powf(5.)),
-        (34729. / 80640.) * (N.powf(5.)),
-    ];
-
-    println!("const A0: f64 = {};", a0);
-    println!("const A_ARR: [f64; 5] = {:?};", a_arr);
-    println!("const ALPHA_ARR: [f64; 5] = {:?};", alpha_arr);
-     */
-
-    let lat0 = LAT0[origin as usize];
-    let long0 = LONG0[origin as usize];
+    let a_ = ((m0 * A) / (1. + N)) * A0;
+
+    let (lambda_s, lambda_c) = math::sin_cos(long - long0);
+    let sin_lat = math::sin(lat);
+
+    let t = math::sinh(
+        math::atanh(sin_lat)
+            - ((2. * N.sqrt()) / (1. + N)) * math::atanh(((2. * N.sqrt()) / (1. + N)) * sin_lat),
+    );
+    let t_ = (1. + t * t).sqrt();
+
+    let xi2 = math::atan(t / lambda_c);
+    let eta2 = math::atanh(lambda_s / t_);
+
+    let (d_xi, d_eta) = clenshaw_complex_sin(&ALPHA_ARR, (xi2, eta2));
+
+    let x = a_ * (xi2 + d_xi) - s_;
+    let y = a_ * (eta2 + d_eta);
+
+    (y, x)
+}
+
+/// Convert plane rectangular coordinates (y, x) of one origin to plane rectangular coordinates (y, x) of another origin.
+/// If both origins are the same, the coordinates are returned unchanged.
+///
+/// ある原点の平面直角座標(y, x)を別の原点の平面直角座標(y, x)に変換する。
+/// 両方の原点が同じ場合は、座標をそのまま返す。
+///
+/// # Examples
+///
+/// Conversion from zone IX to zone VIII
+///
+/// IX系からVIII系への変換
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr2jpr, JprOrigin};
+///
+/// let (y, x) = jpr2jpr((-50000., -10000.), JprOrigin::Nine, JprOrigin::Eight);
+/// ```
+pub fn jpr2jpr(yx: (f64, f64), from_origin: JprOrigin, to_origin: JprOrigin) -> (f64, f64) {
+    if from_origin == to_origin {
+        return yx;
+    }
 
-    const A0: f64 = 1.0000007049454078;
-    const A_ARR: [f64; 5] = [
-        -0.0025188297041239312,
-        2.6435429493240994e-6,
-        -3.4526259073074147e-9,
-        4.891830424387949e-12,
-        -7.228726045813916e-15,
-    ];
-    const ALPHA_ARR: [f64; 5] = [
-        0.0008377318247285465,
-        7.608527848379248e-7,
-        1.1976455002315586e-9,
-        2.4291502606542468e-12,
-        5.750164384091974e-15,
-    ];
-
-    // 定数
-    const M0: f64 = 0.9999;
-    const A: f64 = 6378137.;
-    const F: f64 = 298.257222101;
-    const N: f64 = 1. / (2. * F - 1.);
+    ll2jpr(jpr2ll(yx, from_origin), to_origin)
+}
+
+/// Ranges ((minimum y, minimum x), (maximum y, maximum x)) (m) of plane rectangular coordinates of each origin, the extents of the projected areas of the origin widened by a margin of 10 km and rounded outward to 10 km.
+/// Remote islands put some of them far from the origin, e.g. Torishima at x ≈ -612 km in zone IX and Yoronjima at x ≈ -660 km in zone I.
+///
+/// 各原点の平面直角座標の範囲((最小y, 最小x), (最大y, 最大x))(m)。原点の適用区域を投影した範囲を10km広げ、10km単位で外側に丸めたもの。
+/// 離島のため原点から遠く離れるものもある。IX系の鳥島はx ≈ -612km、I系の与論島はx ≈ -660kmとなる。
+const JPR_RANGES: [((f64, f64), (f64, f64)); 20] = [
+    ((0., 0.), (0., 0.)),
+    // I系
+    ((-210000., -690000.), (100000., 210000.)),
+    // II系
+    ((-130000., -370000.), (140000., 220000.)),
+    // III系
+    ((-160000., -280000.), (140000., 70000.)),
+    // IV系
+    ((-170000., -90000.), (160000., 190000.)),
+    // V系
+    ((-130000., -230000.), (120000., 70000.)),
+    // VI系
+    ((-120000., -330000.), (110000., 70000.)),
+    // VII系
+    ((-110000., -190000.), (70000., 320000.)),
+    // VIII系
+    ((-120000., -190000.), (120000., 320000.)),
+    // IX系
+    ((-130000., -900000.), (160000., 230000.)),
+    // X系
+    ((-160000., -260000.), (140000., 200000.)),
+    // XI系
+    ((-100000., -310000.), (100000., -30000.)),
+    // XII系
+    ((-130000., -260000.), (140000., 200000.)),
+    // XIII系
+    ((-140000., -260000.), (400000., 220000.)),
+    // XIV系
+    ((-170000., -240000.), (120000., 240000.)),
+    // XV系
+    ((-170000., -70000.), (280000., 150000.)),
+    // XVI系
+    ((-170000., -260000.), (220000., 40000.)),
+    // XVII系
+    ((-120000., -240000.), (120000., 70000.)),
+    // XVIII系
+    ((-70000., -10000.), (490000., 910000.)),
+    // XIX系
+    ((-1140000., -240000.), (70000., 290000.)),
+];
+
+/// Range (m) of the distance from the equator of the latitudes of Japan, which a value measured from the equator instead of the origin, e.g. a UTM northing, falls into
+///
+/// 日本の緯度の赤道からの距離の範囲(m)。UTMの北距など、原点ではなく赤道から測った値はこの範囲に入る
+const NORTHING_FROM_EQUATOR: std::ops::RangeInclusive<f64> = 2000000.0..=5200000.;
+
+/// Enumerated type representing implausible magnitudes of plane rectangular coordinates (y, x)
+///
+/// 平面直角座標(y, x)のあり得ない大きさを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum JprMagnitudeError {
+    /// y or x is NaN or infinite
+    ///
+    /// yまたはxがNaNまたは無限大である
+    NotFinite {
+        /// Coordinate y (m)
+        ///
+        /// y座標(m)
+        y: f64,
+        /// Coordinate x (m)
+        ///
+        /// x座標(m)
+        x: f64,
+    },
+    /// x looks like a distance from the equator, e.g. a UTM northing or coordinates with a false northing
+    ///
+    /// xが赤道からの距離に見える。UTMの北距や偽北距を加えた座標など
+    FalseNorthing {
+        /// Coordinate y (m)
+        ///
+        /// y座標(m)
+        y: f64,
+        /// Coordinate x (m)
+        ///
+        /// x座標(m)
+        x: f64,
+    },
+    /// y looks like a distance from the equator while x is plausible, so the axes are likely to be swapped into (x, y)
+    ///
+    /// xはあり得る値だがyが赤道からの距離に見えるため、軸が(x, y)に入れ替わっている可能性が高い
+    SwappedAxes {
+        /// Coordinate y (m)
+        ///
+        /// y座標(m)
+        y: f64,
+        /// Coordinate x (m)
+        ///
+        /// x座標(m)
+        x: f64,
+    },
+    /// y or x is outside `JprOrigin::jpr_range` for another reason, e.g. a false easting, a wrong unit or a wrong origin
+    ///
+    /// その他の理由でyまたはxが`JprOrigin::jpr_range`の範囲外にある。偽東距や単位の誤り、原点の誤りなど
+    OutOfRange {
+        /// Coordinate y (m)
+        ///
+        /// y座標(m)
+        y: f64,
+        /// Coordinate x (m)
+        ///
+        /// x座標(m)
+        x: f64,
+    },
+}
+
+impl Display for JprMagnitudeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JprMagnitudeError::NotFinite { y, x } => write!(f, "JPR coordinates ({}, {}) are not finite", y, x),
+            JprMagnitudeError::FalseNorthing { y, x } => write!(
+                f,
+                "x of JPR coordinates ({}, {}) looks like a distance from the equator, e.g. with a false northing",
+                y, x
+            ),
+            JprMagnitudeError::SwappedAxes { y, x } => write!(
+                f,
+                "y of JPR coordinates ({}, {}) looks like a northing, the axes may be swapped",
+                y, x
+            ),
+            JprMagnitudeError::OutOfRange { y, x } => {
+                write!(f, "JPR coordinates ({}, {}) are outside the range of the origin", y, x)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JprMagnitudeError {}
+
+/// Function to check that plane rectangular coordinates (y, x) are within `JprOrigin::jpr_range` of the origin.
+/// Values outside it almost always mean that the coordinates have a false northing or swapped axes, which the error tells apart where possible.
+///
+/// 平面直角座標(y, x)が原点の`JprOrigin::jpr_range`の範囲内にあることを検査する関数。
+/// 範囲外の値は、ほとんどの場合偽北距を持つ座標か軸が入れ替わった座標であり、エラーは可能な範囲でそれらを区別する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{validate_jpr_magnitude, JprMagnitudeError, JprOrigin};
+///
+/// assert!(validate_jpr_magnitude((22694.980, 11573.375), JprOrigin::Nine).is_ok());
+/// assert!(matches!(
+/// validate_jpr_magnitude((22694.980, 3911573.375), JprOrigin::Nine),
+/// Err(JprMagnitudeError::FalseNorthing { .. })
+/// ));
+/// assert!(matches!(
+/// validate_jpr_magnitude((3911573.375, 22694.980), JprOrigin::Nine),
+/// Err(JprMagnitudeError::SwappedAxes { .. })
+/// ));
+/// ```
+pub fn validate_jpr_magnitude(yx: (f64, f64), origin: JprOrigin) -> Result<(), JprMagnitudeError> {
+    let (y, x) = yx;
+
+    if !y.is_finite() || !x.is_finite() {
+        return Err(JprMagnitudeError::NotFinite { y, x });
+    }
+
+    let ((min_y, min_x), (max_y, max_x)) = origin.jpr_range();
+
+    match ((min_y..=max_y).contains(&y), (min_x..=max_x).contains(&x)) {
+        (true, true) => Ok(()),
+        (true, false) if NORTHING_FROM_EQUATOR.contains(&x) => Err(JprMagnitudeError::FalseNorthing { y, x }),
+        (false, true) if NORTHING_FROM_EQUATOR.contains(&y) => Err(JprMagnitudeError::SwappedAxes { y, x }),
+        _ => Err(JprMagnitudeError::OutOfRange { y, x }),
+    }
+}
+
+/// Checked version of `jpr2ll`.
+/// Returns `Err` if the coordinates do not pass `validate_jpr_magnitude`.
+///
+/// `jpr2ll`の検査付き版。
+/// 座標が`validate_jpr_magnitude`の検査を通らない場合は`Err`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr2ll, try_jpr2ll, JprOrigin};
+///
+/// let yx = (22694.980, 11573.375);
+///
+/// assert_eq!(try_jpr2ll(yx, JprOrigin::Nine), Ok(jpr2ll(yx, JprOrigin::Nine)));
+/// assert!(try_jpr2ll((yx.0, yx.1 + 4000000.), JprOrigin::Nine).is_err());
+/// ```
+pub fn try_jpr2ll(yx: (f64, f64), origin: JprOrigin) -> Result<(f64, f64), JprMagnitudeError> {
+    validate_jpr_magnitude(yx, origin)?;
+
+    Ok(jpr2ll(yx, origin))
+}
+
+/// Axis order of stored plane rectangular coordinates
+///
+/// 保存された平面直角座標の軸の順序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AxisOrder {
+    /// (y, x) order used by this crate
+    ///
+    /// このクレートで用いる(y, x)の順序
+    YX,
+    /// (x, y) order
+    ///
+    /// (x, y)の順序
+    XY,
+}
+
+/// Convention of stored plane rectangular coordinates that differ from the standard (y, x) in meters.
+/// The false offsets are expressed in the stored unit and are subtracted before the unit scale is applied.
+///
+/// 標準の(y, x)(m)とは異なる形式で保存された平面直角座標の規約。
+/// 偽原点のオフセットは保存された単位で表し、単位の換算の前に差し引く。
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct JprConvention {
+    /// Offset added to y (easting)
+    ///
+    /// y(東西方向)に加えられたオフセット
+    pub false_easting: f64,
+    /// Offset added to x (northing)
+    ///
+    /// x(南北方向)に加えられたオフセット
+    pub false_northing: f64,
+    /// Axis order
+    ///
+    /// 軸の順序
+    pub axis_order: AxisOrder,
+    /// Length of the stored unit in meters (e.g. `0.001` for millimeters)
+    ///
+    /// 保存された単位の長さ(m) (例: ミリメートルなら`0.001`)
+    pub unit_scale: f64,
+}
+
+impl Default for JprConvention {
+    fn default() -> Self {
+        Self {
+            false_easting: 0.,
+            false_northing: 0.,
+            axis_order: AxisOrder::YX,
+            unit_scale: 1.,
+        }
+    }
+}
+
+impl JprConvention {
+    /// Convert stored coordinates to the standard plane rectangular coordinates (y, x) in meters.
+    ///
+    /// 保存された座標を標準の平面直角座標(y, x)(m)に変換する。
+    pub fn to_standard(&self, coord: (f64, f64)) -> (f64, f64) {
+        let (y, x) = match self.axis_order {
+            AxisOrder::YX => coord,
+            AxisOrder::XY => (coord.1, coord.0),
+        };
+
+        (
+            (y - self.false_easting) * self.unit_scale,
+            (x - self.false_northing) * self.unit_scale,
+        )
+    }
+
+    /// Convert the standard plane rectangular coordinates (y, x) in meters to stored coordinates.
+    ///
+    /// 標準の平面直角座標(y, x)(m)を保存形式の座標に変換する。
+    pub fn from_standard(&self, yx: (f64, f64)) -> (f64, f64) {
+        let y = yx.0 / self.unit_scale + self.false_easting;
+        let x = yx.1 / self.unit_scale + self.false_northing;
+
+        match self.axis_order {
+            AxisOrder::YX => (y, x),
+            AxisOrder::XY => (x, y),
+        }
+    }
+}
+
+/// Convert plane rectangular coordinates stored with the given convention to (longitude, latitude) expressed in arc degree method.
+///
+/// 与えられた規約で保存された平面直角座標を弧度法で表現された(経度, 緯度)に変換する。
+///
+/// # Examples
+///
+/// Conversion from (x, y) in millimeters to longitude and latitude
+///
+/// ミリメートル単位の(x, y)から緯経度への変換
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr2ll_with, AxisOrder, JprConvention, JprOrigin};
+///
+/// let convention = JprConvention {
+///     axis_order: AxisOrder::XY,
+///     unit_scale: 0.001,
+///     ..Default::default()
+/// };
+///
+/// let (long, lat) = jpr2ll_with((11573375., 22694980.), JprOrigin::Nine, &convention);
+/// ```
+pub fn jpr2ll_with(coord: (f64, f64), origin: JprOrigin, convention: &JprConvention) -> (f64, f64) {
+    jpr2ll(convention.to_standard(coord), origin)
+}
+
+/// Convert (longitude, latitude) expressed in arc degree method to plane rectangular coordinates stored with the given convention.
+///
+/// 弧度法で表現された(経度, 緯度)を与えられた規約で保存される平面直角座標に変換する。
+///
+/// # Examples
+///
+/// Conversion from longitude and latitude to coordinates with a false northing
+///
+/// 緯経度から偽原点のオフセットを持つ座標への変換
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{ll2jpr_with, JprConvention, JprOrigin};
+///
+/// let convention = JprConvention {
+///     false_northing: 100000.,
+///     ..Default::default()
+/// };
+///
+/// let (y, x) = ll2jpr_with(
+/// (
+/// 140.08785504166664_f64.to_radians(),
+/// 36.103774791666666_f64.to_radians(),
+/// ),
+/// JprOrigin::Nine,
+/// &convention,
+/// );
+/// ```
+pub fn ll2jpr_with(ll: (f64, f64), origin: JprOrigin, convention: &JprConvention) -> (f64, f64) {
+    convention.from_standard(ll2jpr(ll, origin))
+}
+
+/// Returns (point scale factor, meridian convergence) at (longitude, latitude) expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)における(縮尺係数, 子午線収差角)を返す。
+fn scale_factor_and_convergence(ll: (f64, f64), origin: JprOrigin) -> (f64, f64) {
+    let (long, lat) = ll;
 
     const A_: f64 = ((M0 * A) / (1. + N)) * A0;
 
-    let s_ = ((M0 * A) / (1. + N))
-        * (A0 * lat0
-        + A_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
-        acc + a * (2. * (i as f64 + 1.) * lat0).sin()
-    }));
+    let long0 = LONG0[origin as usize];
 
-    let lambda_c = (long - long0).cos();
-    let lambda_s = (long - long0).sin();
+    let (lambda_s, lambda_c) = math::sin_cos(long - long0);
 
-    let t = (lat.sin().atanh()
-        - ((2. * N.sqrt()) / (1. + N)) * (((2. * N.sqrt()) / (1. + N)) * lat.sin()).atanh())
-        .sinh();
-    let t_ = (1. + t.powf(2.)).sqrt();
+    let sin_lat = math::sin(lat);
+    let t = math::sinh(
+        math::atanh(sin_lat) - ((2. * N.sqrt()) / (1. + N)) * math::atanh(((2. * N.sqrt()) / (1. + N)) * sin_lat),
+    );
+    let t_ = (1. + t.powi(2)).sqrt();
 
-    let xi2 = (t / lambda_c).atan();
-    let eta2 = (lambda_s / t_).atanh();
+    let xi2 = math::atan(t / lambda_c);
+    let eta2 = math::atanh(lambda_s / t_);
 
-    let x = A_
-        * (xi2
+    let sigma = 1.
         + ALPHA_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
-        acc + a * (2. * (i as f64 + 1.) * xi2).sin() * (2. * (i as f64 + 1.) * eta2).cosh()
-    }))
-        - s_;
+        let j = i as f64 + 1.;
+        acc + 2. * j * a * math::cos(2. * j * xi2) * math::cosh(2. * j * eta2)
+    });
+    let tau = ALPHA_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
+        let j = i as f64 + 1.;
+        acc + 2. * j * a * math::sin(2. * j * xi2) * math::sinh(2. * j * eta2)
+    });
 
-    let y = A_
-        * (eta2
-        + ALPHA_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
-        acc + a * (2. * (i as f64 + 1.) * xi2).cos() * (2. * (i as f64 + 1.) * eta2).sinh()
-    }));
+    let convergence = math::atan(
+        (tau * t_ + sigma * t * lambda_s / lambda_c) / (sigma * t_ - tau * t * lambda_s / lambda_c),
+    );
 
-    (y, x)
+    let scale_factor = (A_ / A)
+        * ((1. + ((1. - N) / (1. + N) * math::tan(lat)).powi(2)) * (sigma.powi(2) + tau.powi(2))
+        / (t.powi(2) + lambda_c.powi(2)))
+        .sqrt();
+
+    (scale_factor, convergence)
+}
+
+/// Returns the point scale factor of the plane rectangular coordinate system at (longitude, latitude) expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)における平面直角座標系の縮尺係数を返す。
+///
+/// # Examples
+///
+/// Calculate the scale factor at a point.
+///
+/// ある地点の縮尺係数を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr_scale_factor, JprOrigin};
+///
+/// let m = jpr_scale_factor((140_f64.to_radians(), 36_f64.to_radians()), JprOrigin::Nine);
+/// ```
+pub fn jpr_scale_factor(ll: (f64, f64), origin: JprOrigin) -> f64 {
+    scale_factor_and_convergence(ll, origin).0
+}
+
+/// Returns the meridian convergence of the plane rectangular coordinate system at (longitude, latitude) expressed in the arc degree method.
+/// The result is expressed in the arc degree method and is positive east of the central meridian.
+///
+/// 弧度法で表された(経度, 緯度)における平面直角座標系の子午線収差角を返す。
+/// 結果は弧度法で表され、中央子午線より東側で正となる。
+///
+/// # Examples
+///
+/// Calculate the meridian convergence at a point.
+///
+/// ある地点の子午線収差角を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr_convergence, JprOrigin};
+///
+/// let gamma = jpr_convergence((140_f64.to_radians(), 36_f64.to_radians()), JprOrigin::Nine);
+/// ```
+pub fn jpr_convergence(ll: (f64, f64), origin: JprOrigin) -> f64 {
+    scale_factor_and_convergence(ll, origin).1
+}
+
+/// Structure representing the distortion of the plane rectangular coordinate system at a point
+///
+/// ある地点における平面直角座標系のひずみを表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct JprDistortion {
+    /// Point scale factor
+    ///
+    /// 縮尺係数
+    pub scale_factor: f64,
+    /// Meridian convergence expressed in the arc degree method
+    ///
+    /// 弧度法で表された子午線収差角
+    pub convergence: f64,
+    /// Distance distortion (%)
+    ///
+    /// 距離のひずみ(%)
+    pub distance_distortion: f64,
+    /// Area distortion (%)
+    ///
+    /// 面積のひずみ(%)
+    pub area_distortion: f64,
+}
+
+impl JprDistortion {
+    /// Returns whether the distance distortion is within the given tolerance (e.g. `1. / 10000.`).
+    ///
+    /// 距離のひずみが与えられた許容値(例: `1. / 10000.`)以内かどうかを返す。
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        (self.scale_factor - 1.).abs() <= tolerance
+    }
+}
+
+/// Returns the distortion of the plane rectangular coordinate system at (longitude, latitude) expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)における平面直角座標系のひずみを返す。
+///
+/// # Examples
+///
+/// Check whether the distortion at a point is within 1/10,000.
+///
+/// ある地点のひずみが1/10,000以内かどうかを確認する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr_distortion, JprOrigin};
+///
+/// let distortion = jpr_distortion((140_f64.to_radians(), 36_f64.to_radians()), JprOrigin::Nine);
+/// assert!(distortion.is_within(1. / 10000.));
+/// ```
+pub fn jpr_distortion(ll: (f64, f64), origin: JprOrigin) -> JprDistortion {
+    let (scale_factor, convergence) = scale_factor_and_convergence(ll, origin);
+
+    JprDistortion {
+        scale_factor,
+        convergence,
+        distance_distortion: (scale_factor - 1.) * 100.,
+        area_distortion: (scale_factor.powi(2) - 1.) * 100.,
+    }
+}
+
+/// Returns the scale factor averaged over the line between two plane rectangular coordinates (y, x) by Simpson's rule.
+///
+/// 2つの平面直角座標(y, x)を結ぶ線分上で平均した縮尺係数をシンプソン則で返す。
+fn mean_scale_factor(yx1: (f64, f64), yx2: (f64, f64), origin: JprOrigin) -> f64 {
+    let mid = ((yx1.0 + yx2.0) / 2., (yx1.1 + yx2.1) / 2.);
+
+    let m1 = jpr_scale_factor(jpr2ll(yx1, origin), origin);
+    let m2 = jpr_scale_factor(jpr2ll(yx2, origin), origin);
+    let mm = jpr_scale_factor(jpr2ll(mid, origin), origin);
+
+    (m1 + 4. * mm + m2) / 6.
+}
+
+/// Converts the distance between two plane rectangular coordinates (y, x) into the distance on the ellipsoid (m) using the point scale factors.
+///
+/// 2つの平面直角座標(y, x)間の距離を、縮尺係数を用いて楕円体面上の距離(m)に変換する。
+///
+/// # Examples
+///
+/// Calculate the ellipsoidal distance between two plane rectangular coordinates.
+///
+/// 2つの平面直角座標間の楕円体面上の距離を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr_ground_distance, JprOrigin};
+///
+/// let s = jpr_ground_distance((50000., 10000.), (51000., 10000.), JprOrigin::Nine);
+/// ```
+pub fn jpr_ground_distance(yx1: (f64, f64), yx2: (f64, f64), origin: JprOrigin) -> f64 {
+    let grid_distance = ((yx2.0 - yx1.0).powi(2) + (yx2.1 - yx1.1).powi(2)).sqrt();
+
+    grid_distance / mean_scale_factor(yx1, yx2, origin)
+}
+
+/// Converts a distance on the ellipsoid (m) between two plane rectangular coordinates (y, x) into the distance on the plane using the point scale factors.
+///
+/// 2つの平面直角座標(y, x)間の楕円体面上の距離(m)を、縮尺係数を用いて平面上の距離に変換する。
+///
+/// # Examples
+///
+/// Calculate the grid distance from a measured ellipsoidal distance.
+///
+/// 観測した楕円体面上の距離から平面上の距離を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{jpr_grid_distance, JprOrigin};
+///
+/// let s = jpr_grid_distance(1000., (50000., 10000.), (51000., 10000.), JprOrigin::Nine);
+/// ```
+pub fn jpr_grid_distance(ground_distance: f64, yx1: (f64, f64), yx2: (f64, f64), origin: JprOrigin) -> f64 {
+    ground_distance * mean_scale_factor(yx1, yx2, origin)
 }
 
 #[cfg(test)]
@@ -482,6 +1483,155 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn clenshaw_matches_direct_summation() {
+        for &(xi, eta) in &[(0., 0.), (0.63, 0.), (0.7, 0.05), (-0.2, -0.03), (1.2, 0.4)] {
+            let (d_xi, d_eta) = clenshaw_complex_sin(&ALPHA_ARR, (xi, eta));
+
+            let direct_xi = ALPHA_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
+                acc + a * (2. * (i as f64 + 1.) * xi).sin() * (2. * (i as f64 + 1.) * eta).cosh()
+            });
+            let direct_eta = ALPHA_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
+                acc + a * (2. * (i as f64 + 1.) * xi).cos() * (2. * (i as f64 + 1.) * eta).sinh()
+            });
+            let direct_chi = DELTA_ARR.iter().enumerate().fold(0., |acc, (i, &d)| {
+                acc + d * (2. * (i as f64 + 1.) * xi).sin()
+            });
+
+            assert!((d_xi - direct_xi).abs() < 1e-17);
+            assert!((d_eta - direct_eta).abs() < 1e-17);
+            assert!((clenshaw_sin(&DELTA_ARR, xi) - direct_chi).abs() < 1e-17);
+        }
+    }
+
+    #[test]
+    fn validate_jpr_magnitude_works() {
+        // 原点から遠い離島の点も妥当な大きさになる
+        for &(long, lat, origin) in &[
+            (141.35_f64, 43.06_f64, JprOrigin::Twelve),
+            (127.68, 26.21, JprOrigin::Fifteen),
+            (153.98, 24.28, JprOrigin::Nineteen),
+            (136.07, 20.42, JprOrigin::Eighteen),
+            // 八丈島、青ヶ島、鳥島、奄美大島、与論島
+            (139.79, 33.11, JprOrigin::Nine),
+            (139.76, 32.46, JprOrigin::Nine),
+            (140.303, 30.483, JprOrigin::Nine),
+            (129.49, 28.38, JprOrigin::One),
+            (128.42, 27.04, JprOrigin::One),
+        ] {
+            let yx = ll2jpr((long.to_radians(), lat.to_radians()), origin);
+            assert_eq!(validate_jpr_magnitude(yx, origin), Ok(()));
+            assert!(try_jpr2ll(yx, origin).is_ok());
+        }
+
+        let (y, x) = (-12000., 3950000.);
+        assert_eq!(
+            validate_jpr_magnitude((y, x), JprOrigin::Nine),
+            Err(JprMagnitudeError::FalseNorthing { y, x })
+        );
+        assert_eq!(
+            validate_jpr_magnitude((x, y), JprOrigin::Nine),
+            Err(JprMagnitudeError::SwappedAxes { y: x, x: y })
+        );
+        assert_eq!(
+            validate_jpr_magnitude((512000., 3950000.), JprOrigin::Nine),
+            Err(JprMagnitudeError::OutOfRange { y: 512000., x: 3950000. })
+        );
+        // 鳥島はIX系の範囲内だが、XI系の範囲外となる
+        assert!(matches!(
+            validate_jpr_magnitude((-60000., -612000.), JprOrigin::Eleven),
+            Err(JprMagnitudeError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            validate_jpr_magnitude((f64::NAN, 0.), JprOrigin::Nine),
+            Err(JprMagnitudeError::NotFinite { .. })
+        ));
+    }
+
+    #[test]
+    fn jpr_ranges_cover_areas() {
+        // 適用区域の境界を細かく分割して投影した点は、10km狭めた範囲にも収まる
+        for n in 1..=19_u8 {
+            let origin = JprOrigin::try_from(n).unwrap();
+            let ((min_y, min_x), (max_y, max_x)) = origin.jpr_range();
+
+            for ring in origin.areas() {
+                for (i, &(long0, lat0)) in ring.iter().enumerate() {
+                    let (long1, lat1) = ring[(i + 1) % ring.len()];
+                    for k in 0..=64 {
+                        let t = k as f64 / 64.;
+                        let (y, x) = ll2jpr((long0 + (long1 - long0) * t, lat0 + (lat1 - lat0) * t), origin);
+                        assert!(min_y + 10000. <= y && y <= max_y - 10000., "{:?} y = {}", origin, y);
+                        assert!(min_x + 10000. <= x && x <= max_x - 10000., "{:?} x = {}", origin, x);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn s0_arr_matches_generator() {
+        // 子午線弧長の級数の係数
+        const A_ARR: [f64; 5] = [
+            -0.0025188297041239312,
+            2.6435429493240994e-6,
+            -3.4526259073074147e-9,
+            4.891830424387949e-12,
+            -7.228726045813916e-15,
+        ];
+
+        for (&lat0, &s0) in LAT0.iter().zip(S0_ARR.iter()) {
+            let s_ = ((M0 * A) / (1. + N))
+                * (A0 * lat0
+                + A_ARR.iter().enumerate().fold(0., |acc, (i, &a)| {
+                acc + a * (2. * (i as f64 + 1.) * lat0).sin()
+            }));
+
+            assert_eq!(s_, s0);
+        }
+    }
+
+    #[test]
+    fn origin_accessors_match_jpr_origin() {
+        for origin in (1..=19).map(|num| JprOrigin::parse(num).unwrap()) {
+            let (long, lat) = jpr2ll((0., 0.), origin);
+            assert_close_to(long, origin.origin_long(), 12);
+            assert_close_to(lat, origin.origin_lat(), 12);
+        }
+
+        assert_close_to(JprOrigin::Nineteen.origin_long_deg(), 154., 12);
+        assert_close_to(JprOrigin::Nineteen.origin_lat_deg(), 26., 12);
+        assert_close_to(JprOrigin::One.origin_long_deg(), 129.5, 12);
+    }
+
+    #[test]
+    fn zone_names_use_roman_numerals() {
+        assert_eq!(JprOrigin::One.name(JprZoneNotation::Japanese), "第I系");
+        assert_eq!(JprOrigin::Fourteen.roman(), "XIV");
+        assert_eq!(format!("{:#}", JprOrigin::Eighteen), "Zone XVIII");
+        assert_eq!(JprOrigin::Twelve.name(JprZoneNotation::Number).parse::<JprOrigin>(), Ok(JprOrigin::Twelve));
+    }
+
+    #[test]
+    fn coverage_checks_work() {
+        let points = [
+            // 南鳥島、沖ノ鳥島、与那国島、宗谷岬
+            ((153.9806, 24.2867), true),
+            ((136.0819, 20.4253), true),
+            ((122.9336, 24.4494), true),
+            ((141.9369, 45.5227), true),
+            // ソウル
+            ((126.978, 37.5665), false),
+        ];
+
+        for ((long, lat), inside) in points {
+            let ll = (long * DEG2RAD, lat * DEG2RAD);
+            assert_eq!(is_within_jpr_coverage(ll), inside, "{:?}", (long, lat));
+            assert!(is_within_japan_bbox(ll));
+        }
+        assert!(!is_within_japan_bbox((0., 0.)));
+    }
+
     #[test]
     fn jpr2ll_works() {
         let (long, lat) = jpr2ll((22694.980, 11573.375), JprOrigin::Nine);
@@ -514,4 +1664,151 @@ mod tests {
         assert_close_to(y, 22916.2436, 4);
         assert_close_to(x, 11543.6883, 4);
     }
+
+    #[test]
+    fn jpr2jpr_works() {
+        let yx = (-50000., -10000.);
+        let (y, x) = jpr2jpr(yx, JprOrigin::Nine, JprOrigin::Eight);
+        let (y2, x2) = ll2jpr(jpr2ll(yx, JprOrigin::Nine), JprOrigin::Eight);
+
+        assert_eq!((y, x), (y2, x2));
+
+        let (y3, x3) = jpr2jpr((y, x), JprOrigin::Eight, JprOrigin::Nine);
+        assert_close_to(y3, yx.0, 4);
+        assert_close_to(x3, yx.1, 4);
+    }
+
+    #[test]
+    fn jpr_convention_works() {
+        let convention = JprConvention {
+            false_easting: 500.,
+            false_northing: 1000.,
+            axis_order: AxisOrder::XY,
+            unit_scale: 0.001,
+        };
+
+        let yx = (22694.980, 11573.375);
+        let stored = convention.from_standard(yx);
+        assert_close_to(stored.0, 11573375. + 1000., 3);
+        assert_close_to(stored.1, 22694980. + 500., 3);
+
+        let (long, lat) = jpr2ll_with(stored, JprOrigin::Nine, &convention);
+        let (long2, lat2) = jpr2ll(yx, JprOrigin::Nine);
+        assert_close_to(long, long2, 12);
+        assert_close_to(lat, lat2, 12);
+    }
+
+    #[test]
+    fn applicable_zones_works() {
+        let ll = |long: f64, lat: f64| (long.to_radians(), lat.to_radians());
+
+        assert_eq!(JprOrigin::applicable_zones(ll(135.526, 34.687)), vec![JprOrigin::Six]);
+        assert_eq!(JprOrigin::applicable_zones(ll(127.681, 26.212)), vec![JprOrigin::Fifteen]);
+        assert_eq!(JprOrigin::applicable_zones(ll(142.191, 27.094)), vec![JprOrigin::Fourteen]);
+        assert_eq!(JprOrigin::applicable_zones(ll(141.354, 43.062)), vec![JprOrigin::Twelve]);
+        assert!(JprOrigin::applicable_zones(ll(0., 0.)).is_empty());
+    }
+
+    #[test]
+    fn applicable_zones_works_on_islands_and_boundaries() {
+        let ll = |long: f64, lat: f64| (long.to_radians(), lat.to_radians());
+
+        let points = [
+            // 鳥島、沖ノ鳥島、南鳥島、父島、与論島(鹿児島県)、伊平屋島(沖縄県)、甑島、天草下島
+            ((140.303, 30.483), JprOrigin::Nine),
+            ((136.0819, 20.4253), JprOrigin::Eighteen),
+            ((153.9806, 24.2867), JprOrigin::Nineteen),
+            ((142.19, 27.09), JprOrigin::Fourteen),
+            ((128.42, 27.04), JprOrigin::One),
+            ((127.97, 27.08), JprOrigin::Fifteen),
+            ((129.80, 31.80), JprOrigin::One),
+            ((130.03, 32.19), JprOrigin::Two),
+            // 北海道のXI系・XII系・XIII系の境界: 小樽市と札幌市、伊達市と室蘭市、帯広市と南富良野町、広尾町とえりも町、北見市と遠軽町
+            ((141.00, 43.19), JprOrigin::Eleven),
+            ((141.35, 43.06), JprOrigin::Twelve),
+            ((140.865, 42.47), JprOrigin::Eleven),
+            ((140.97, 42.315), JprOrigin::Twelve),
+            ((143.20, 42.92), JprOrigin::Thirteen),
+            ((142.58, 43.16), JprOrigin::Twelve),
+            ((143.32, 42.29), JprOrigin::Thirteen),
+            ((143.15, 42.02), JprOrigin::Twelve),
+            ((143.90, 43.80), JprOrigin::Thirteen),
+            ((143.52, 44.05), JprOrigin::Twelve),
+            // 県境: 軽井沢町(長野県)と安中市(群馬県)、上野原市(山梨県)と相模原市(神奈川県)、境港市(鳥取県)と美保関(島根県)
+            ((138.597, 36.343), JprOrigin::Eight),
+            ((138.80, 36.33), JprOrigin::Nine),
+            ((139.1126, 35.6303), JprOrigin::Eight),
+            ((139.178, 35.603), JprOrigin::Nine),
+            ((133.2314, 35.5395), JprOrigin::Five),
+            ((133.3145, 35.5634), JprOrigin::Three),
+        ];
+
+        for ((long, lat), origin) in points {
+            assert_eq!(JprOrigin::applicable_zones(ll(long, lat)), vec![origin], "{:?}", (long, lat));
+            assert_eq!(JprOrigin::best_zone(ll(long, lat)), Some(origin));
+        }
+    }
+
+    #[test]
+    fn areas_do_not_overlap() {
+        let (mut long, mut count) = (122., 0);
+        while long < 154.5 {
+            let mut lat = 20.;
+            while lat < 46. {
+                let zones = JprOrigin::applicable_zones((long * DEG2RAD, lat * DEG2RAD));
+                assert!(zones.len() <= 1, "{:?}: {:?}", (long, lat), zones);
+                count += zones.len();
+                lat += 0.05;
+            }
+            long += 0.05;
+        }
+
+        // 格子点の一部は区域内にある
+        assert!(count > 10000);
+    }
+
+    #[test]
+    fn jpr_scale_factor_works() {
+        let origin = JprOrigin::Nine;
+
+        assert_close_to(jpr_scale_factor(jpr2ll((0., 0.), origin), origin), 0.9999, 9);
+
+        // 近似式 m = m0 (1 + y^2 / (2 R0^2)) と比較する
+        let y = 90000_f64;
+        let r0 = 6370000_f64;
+        assert_close_to(
+            jpr_scale_factor(jpr2ll((y, 0.), origin), origin),
+            0.9999 * (1. + y.powi(2) / (2. * r0.powi(2))),
+            6,
+        );
+    }
+
+    #[test]
+    fn jpr_distortion_works() {
+        let origin = JprOrigin::Nine;
+        let (long0, lat) = jpr2ll((0., 0.), origin);
+
+        let distortion = jpr_distortion((long0, lat), origin);
+        assert_close_to(distortion.convergence, 0., 12);
+        assert_close_to(distortion.distance_distortion, -0.01, 9);
+        assert!(distortion.is_within(1. / 10000. + 1e-12));
+
+        // 近似式 γ = Δλ sin φ と比較する
+        let d_long = 0.5_f64.to_radians();
+        let distortion = jpr_distortion((long0 + d_long, lat), origin);
+        assert_close_to(distortion.convergence, (d_long.tan() * lat.sin()).atan(), 5);
+
+        let distortion = jpr_distortion((long0 + 2_f64.to_radians(), lat), origin);
+        assert!(!distortion.is_within(1. / 10000.));
+    }
+
+    #[test]
+    fn jpr_ground_distance_works() {
+        let origin = JprOrigin::Nine;
+        let (yx1, yx2) = ((0., 0.), (0., 1000.));
+
+        let s = jpr_ground_distance(yx1, yx2, origin);
+        assert_close_to(s, 1000. / 0.9999, 6);
+        assert_close_to(jpr_grid_distance(s, yx1, yx2, origin), 1000., 6);
+    }
 }
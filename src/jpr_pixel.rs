@@ -0,0 +1,272 @@
+use crate::polygon::{jpr_line2pixel, pixel_ring2jpr};
+use crate::{jpr2ll, ll2jpr, ll2pixel, pixel2ll, pixel2tile, JprOrigin, ZoomLv};
+
+/// Function to convert plane rectangular coordinates (y, x) to pixel coordinates (x, y) according to Zoom level.
+/// The conversion goes through longitude and latitude internally.
+///
+/// 平面直角座標(y, x)をZoomレベルに応じたピクセル座標(x, y)に変換する関数。
+/// 内部では緯経度を経由して変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::jpr_pixel::jpr2pixel;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let (x, y) = jpr2pixel((22694.980, 11573.375), JprOrigin::Nine, ZoomLv::Lv21);
+/// ```
+pub fn jpr2pixel(yx: (f64, f64), origin: JprOrigin, zoom: ZoomLv) -> (u32, u32) {
+    ll2pixel(jpr2ll(yx, origin), zoom)
+}
+
+/// Function to convert pixel coordinates (x, y) according to Zoom level to plane rectangular coordinates (y, x).
+/// The conversion goes through longitude and latitude internally.
+///
+/// Zoomレベルに応じたピクセル座標(x, y)を平面直角座標(y, x)に変換する関数。
+/// 内部では緯経度を経由して変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::jpr_pixel::pixel2jpr;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let (y, x) = pixel2jpr((476868027, 211407949), ZoomLv::Lv21, JprOrigin::Nine);
+/// ```
+pub fn pixel2jpr(pixel: (u32, u32), zoom: ZoomLv, origin: JprOrigin) -> (f64, f64) {
+    ll2jpr(pixel2ll(pixel, zoom), origin)
+}
+
+/// Function to return the pixel coordinate range ((minimum x, minimum y), (maximum x, maximum y)) covering the area where the origin of the plane rectangular coordinate system applies.
+/// Both ends of the range are inclusive.
+///
+/// 平面直角座標系の原点が適用される区域を覆うピクセル座標の範囲((最小x, 最小y), (最大x, 最大y))を返す関数。
+/// 範囲の両端を含む。
+///
+/// # Examples
+///
+/// Calculate the pixel coordinate range of zone IX.
+///
+/// IX系のピクセル座標の範囲を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_pixel::jpr_zone_pixel_bounds;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let ((min_x, min_y), (max_x, max_y)) = jpr_zone_pixel_bounds(JprOrigin::Nine, ZoomLv::Lv10);
+/// ```
+pub fn jpr_zone_pixel_bounds(origin: JprOrigin, zoom: ZoomLv) -> ((u32, u32), (u32, u32)) {
+    let ((min_long, min_lat), (max_long, max_lat)) = origin.bounds();
+
+    let min = ll2pixel((min_long, max_lat), zoom);
+    let max = ll2pixel((max_long, min_lat), zoom);
+
+    (min, max)
+}
+
+/// Function to return the tile coordinate range ((minimum x, minimum y), (maximum x, maximum y)) covering the area where the origin of the plane rectangular coordinate system applies.
+/// Both ends of the range are inclusive.
+///
+/// 平面直角座標系の原点が適用される区域を覆うタイル座標の範囲((最小x, 最小y), (最大x, 最大y))を返す関数。
+/// 範囲の両端を含む。
+///
+/// # Examples
+///
+/// Calculate the tile coordinate range of zone IX.
+///
+/// IX系のタイル座標の範囲を計算する。
+///
+/// ```
+/// use coordinate_transformer::jpr_pixel::jpr_zone_tile_bounds;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let ((min_x, min_y), (max_x, max_y)) = jpr_zone_tile_bounds(JprOrigin::Nine, ZoomLv::Lv10);
+/// ```
+pub fn jpr_zone_tile_bounds(origin: JprOrigin, zoom: ZoomLv) -> ((u32, u32), (u32, u32)) {
+    let (min, max) = jpr_zone_pixel_bounds(origin, zoom);
+
+    (pixel2tile(min), pixel2tile(max))
+}
+
+/// Enumerated type representing the coordinate held constant along a grid line of plane rectangular coordinates
+///
+/// 平面直角座標の格子線に沿って一定である座標を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JprGridAxis {
+    /// Line of constant y, running north and south
+    ///
+    /// yが一定の線。南北に延びる
+    Y,
+    /// Line of constant x, running east and west
+    ///
+    /// xが一定の線。東西に延びる
+    X,
+}
+
+/// Structure representing a grid line of plane rectangular coordinates as a polyline of fractional pixel coordinates (x, y)
+///
+/// 平面直角座標の格子線を小数のピクセル座標(x, y)の折れ線で表す構造体
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct JprGridLine {
+    /// Coordinate held constant along the line
+    ///
+    /// 線に沿って一定である座標
+    pub axis: JprGridAxis,
+    /// Value (m) of the constant coordinate
+    ///
+    /// 一定である座標の値(m)
+    pub value: f64,
+    /// Polyline of fractional pixel coordinates (x, y)
+    ///
+    /// 小数のピクセル座標(x, y)の折れ線
+    pub pixels: Vec<(f64, f64)>,
+}
+
+/// Function to generate the grid lines of plane rectangular coordinates at every `spacing_m` (m), e.g. 1 km or 100 m, covering the viewport of fractional pixel coordinates from `min` (x, y) to `max` (x, y), for CAD-style map overlays.
+/// The lines are straight in plane rectangular coordinates and curved in pixel coordinates, so they are densified following `jpr_line2pixel` with `max_deviation_m` (m).
+/// Each line spans the extent of plane rectangular coordinates enclosing the viewport, so its ends may lie outside the viewport and should be clipped by the renderer.
+///
+/// 小数のピクセル座標の`min`(x, y)から`max`(x, y)までの表示範囲を覆う、`spacing_m`(m)ごと(1kmや100mなど)の平面直角座標の格子線を生成する関数。CAD風の地図のオーバーレイに用いる。
+/// 線は平面直角座標では直線、ピクセル座標では曲線であるため、`jpr_line2pixel`に従って`max_deviation_m`(m)で高密度化する。
+/// 各線は表示範囲を囲む平面直角座標の範囲にわたるため、端は表示範囲の外に出ることがあり、描画側で切り取る必要がある。
+///
+/// # Examples
+///
+/// Generate the 1 km grid of zone IX around Tokyo Station at Zoom level 14.
+///
+/// Zoomレベル14で東京駅周辺のIX系の1kmの格子を生成する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::jpr_pixel::{jpr_grid_lines, JprGridAxis};
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+///
+/// let lines = jpr_grid_lines((3725000., 1652000.), (3726024., 1652768.), ZoomLv::Lv14, JprOrigin::Nine, 1000., 0.5);
+///
+/// assert!(lines.iter().any(|line| line.axis == JprGridAxis::Y));
+/// assert!(lines.iter().all(|line| line.value % 1000. == 0.));
+/// ```
+pub fn jpr_grid_lines(
+    min: (f64, f64),
+    max: (f64, f64),
+    zoom: ZoomLv,
+    origin: JprOrigin,
+    spacing_m: f64,
+    max_deviation_m: f64,
+) -> Vec<JprGridLine> {
+    assert!(spacing_m > 0., "spacing must be positive");
+
+    let viewport = [(min.0, min.1), (max.0, min.1), (max.0, max.1), (min.0, max.1)];
+    let ((min_y, min_x), (max_y, max_x)) = pixel_ring2jpr(&viewport, zoom, origin, max_deviation_m)
+        .into_iter()
+        .fold(
+            ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |((min_y, min_x), (max_y, max_x)), (y, x)| ((min_y.min(y), min_x.min(x)), (max_y.max(y), max_x.max(x))),
+        );
+
+    let values = |min: f64, max: f64| {
+        ((min / spacing_m).ceil() as i64..=(max / spacing_m).floor() as i64).map(|i| i as f64 * spacing_m)
+    };
+
+    let y_lines = values(min_y, max_y).map(|y| JprGridLine {
+        axis: JprGridAxis::Y,
+        value: y,
+        pixels: jpr_line2pixel(&[(y, max_x), (y, min_x)], origin, zoom, max_deviation_m),
+    });
+    let x_lines = values(min_x, max_x).map(|x| JprGridLine {
+        axis: JprGridAxis::X,
+        value: x,
+        pixels: jpr_line2pixel(&[(min_y, x), (max_y, x)], origin, zoom, max_deviation_m),
+    });
+
+    y_lines.chain(x_lines).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::pixel_ll::{ll2pixel_f64, pixel2ll_f64};
+    use crate::{Pixel, JPR};
+
+    use super::*;
+
+    #[test]
+    fn jpr2pixel_works() {
+        let yx = (22694.980, 11573.375);
+        let pixel = jpr2pixel(yx, JprOrigin::Nine, ZoomLv::Lv21);
+
+        assert_eq!(pixel, ll2pixel(jpr2ll(yx, JprOrigin::Nine), ZoomLv::Lv21));
+        assert_eq!(JPR::new(yx.0, yx.1, JprOrigin::Nine).to_pixel(ZoomLv::Lv21).to_tuple(), pixel);
+
+        // ピクセルの大きさ(約0.06m)の範囲で元に戻る
+        let (y, x) = pixel2jpr(pixel, ZoomLv::Lv21, JprOrigin::Nine);
+        assert_close_to(y, yx.0, 0);
+        assert_close_to(x, yx.1, 0);
+        assert_eq!(Pixel::new(pixel.0, pixel.1, ZoomLv::Lv21).to_jpr(JprOrigin::Nine).to_tuple(), (y, x));
+    }
+
+    #[test]
+    fn jpr_zone_pixel_bounds_contains_origin() {
+        let zoom_lv = ZoomLv::Lv12;
+
+        for num in 1..=19 {
+            let origin = JprOrigin::parse(num).unwrap();
+            let ((min_x, min_y), (max_x, max_y)) = jpr_zone_pixel_bounds(origin, zoom_lv);
+            let (x, _) = ll2pixel(jpr2ll((0., 0.), origin), zoom_lv);
+
+            assert!(min_x < max_x && min_y < max_y);
+            // 原点の緯度は適用区域の外にある系もあるため、経度方向のみ確認する
+            assert!((min_x..=max_x).contains(&x), "{:?}", origin);
+        }
+    }
+
+    #[test]
+    fn jpr_zone_tile_bounds_works() {
+        let (origin, zoom_lv) = (JprOrigin::Nine, ZoomLv::Lv10);
+        let bounds = jpr_zone_tile_bounds(origin, zoom_lv);
+
+        // 適用区域の北西端と南東端の点を含むタイルと一致する
+        let ((min_long, min_lat), (max_long, max_lat)) = origin.bounds();
+        let (nw_x, nw_y) = ll2pixel((min_long, max_lat), zoom_lv);
+        let (se_x, se_y) = ll2pixel((max_long, min_lat), zoom_lv);
+        assert_eq!(bounds, ((nw_x / 256, nw_y / 256), (se_x / 256, se_y / 256)));
+
+        // 東京駅を含むタイル(909, 403)は範囲内にあり、福岡(II系)を含むタイルは範囲外にある
+        let ((min_x, min_y), (max_x, max_y)) = bounds;
+        assert!(min_x < max_x && min_y < max_y);
+        let contains = |(x, y): (u32, u32)| (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y);
+        assert!(contains((909, 403)));
+        let (x, y) = ll2pixel((130.4017_f64.to_radians(), 33.5904_f64.to_radians()), zoom_lv);
+        assert!(!contains((x / 256, y / 256)));
+    }
+
+    #[test]
+    fn jpr_grid_lines_cover_viewport() {
+        let (zoom, origin) = (ZoomLv::Lv12, JprOrigin::Nine);
+        let (cx, cy) = ll2pixel_f64(jpr2ll((25000., -15000.), origin), zoom);
+        let lines = jpr_grid_lines((cx - 512., cy - 512.), (cx + 512., cy + 512.), zoom, origin, 10000., 0.5);
+
+        // 表示範囲は約30km四方
+        let has = |axis, value| lines.iter().any(|line| line.axis == axis && line.value == value);
+        assert!(has(JprGridAxis::Y, 20000.) && has(JprGridAxis::Y, 30000.));
+        assert!(has(JprGridAxis::X, -10000.) && has(JprGridAxis::X, -20000.));
+
+        // 線上の点は平面直角座標で値が一定である
+        for line in &lines {
+            assert!(line.pixels.len() > 2);
+            for &p in &line.pixels {
+                let (y, x) = ll2jpr(pixel2ll_f64(p, zoom), origin);
+                let value = match line.axis {
+                    JprGridAxis::Y => y,
+                    JprGridAxis::X => x,
+                };
+                assert_close_to(value, line.value, 3);
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{jpr2ll, ll2jpr, llz2xyz, xyz2llz, JprOrigin};
+
+/// Function to convert plane rectangular coordinates with altitude (y, x, altitude) to (x, y, z) in the Cartesian coordinate system (EPSG:4979).
+/// The altitude is treated as the height above the ellipsoid, in the same way as `llz2xyz`.
+///
+/// 標高付きの平面直角座標(y, x, 標高)を直交座標系(EPSG:4979)の(x, y, z)に変換する関数。
+/// 標高は`llz2xyz`と同様に楕円体からの高さとして扱う。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::jpr_xyz::jprz2xyz;
+///
+/// let (x, y, z) = jprz2xyz((22694.980, 11573.375, 100.), JprOrigin::Nine);
+/// ```
+pub fn jprz2xyz(yxz: (f64, f64, f64), origin: JprOrigin) -> (f64, f64, f64) {
+    let (y, x, altitude) = yxz;
+
+    llz2xyz(jpr2ll((y, x), origin), altitude)
+}
+
+/// Function to convert (x, y, z) in the Cartesian coordinate system (EPSG:4979) to plane rectangular coordinates with altitude (y, x, altitude).
+/// The altitude is the height above the ellipsoid, in the same way as `xyz2llz`.
+///
+/// 直交座標系(EPSG:4979)の(x, y, z)を標高付きの平面直角座標(y, x, 標高)に変換する関数。
+/// 標高は`xyz2llz`と同様に楕円体からの高さである。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::jpr_xyz::xyz2jprz;
+///
+/// let (y, x, altitude) = xyz2jprz((-3957446.631, 3320692.008, 3728250.454), JprOrigin::Nine);
+/// ```
+pub fn xyz2jprz(xyz: (f64, f64, f64), origin: JprOrigin) -> (f64, f64, f64) {
+    let (ll, altitude) = xyz2llz(xyz);
+    let (y, x) = ll2jpr(ll, origin);
+
+    (y, x, altitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn jprz2xyz_round_trip_works() {
+        let yxz = (22694.980, 11573.375, 100.);
+
+        let (y, x, altitude) = xyz2jprz(jprz2xyz(yxz, JprOrigin::Nine), JprOrigin::Nine);
+
+        assert_close_to(y, yxz.0, 6);
+        assert_close_to(x, yxz.1, 6);
+        assert_close_to(altitude, yxz.2, 6);
+    }
+}
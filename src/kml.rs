@@ -0,0 +1,352 @@
+//! Reading and writing of the coordinates of KML documents with their altitude modes.
+//! Only the `<coordinates>` blocks and the `<altitudeMode>` elements of the geometries are read; other elements such as styles and names are skipped.
+//!
+//! KML文書の座標の高度モード付きでの読み書き。
+//! ジオメトリの`<coordinates>`ブロックと`<altitudeMode>`要素のみを読み込み、スタイルや名前などの他の要素は読み飛ばす。
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::LLZ;
+
+/// Error that occurs while reading KML coordinates
+///
+/// KMLの座標の読み込み中に発生するエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KmlError {
+    /// The coordinate tuple could not be parsed
+    ///
+    /// 座標の組を解析できなかった
+    InvalidTuple(String),
+    /// The altitude mode is unknown
+    ///
+    /// 高度モードが不明
+    InvalidAltitudeMode(String),
+}
+
+impl Display for KmlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KmlError::InvalidTuple(tuple) => write!(f, "invalid KML coordinate tuple: {}", tuple),
+            KmlError::InvalidAltitudeMode(mode) => write!(f, "invalid KML altitude mode: {}", mode),
+        }
+    }
+}
+
+impl std::error::Error for KmlError {}
+
+/// Altitude mode of KML geometry
+///
+/// KMLのジオメトリの高度モード
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum AltitudeMode {
+    /// Altitude is ignored and the geometry is placed on the ground
+    ///
+    /// 高度は無視され、ジオメトリは地表に配置される
+    #[default]
+    ClampToGround,
+    /// Altitude is the height (m) above the ground
+    ///
+    /// 高度は地表からの高さ(m)
+    RelativeToGround,
+    /// Altitude is the height (m) above sea level
+    ///
+    /// 高度は海面からの高さ(m)
+    Absolute,
+}
+
+impl FromStr for AltitudeMode {
+    type Err = KmlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "clampToGround" => Ok(AltitudeMode::ClampToGround),
+            "relativeToGround" => Ok(AltitudeMode::RelativeToGround),
+            "absolute" => Ok(AltitudeMode::Absolute),
+            other => Err(KmlError::InvalidAltitudeMode(other.to_string())),
+        }
+    }
+}
+
+impl Display for AltitudeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AltitudeMode::ClampToGround => write!(f, "clampToGround"),
+            AltitudeMode::RelativeToGround => write!(f, "relativeToGround"),
+            AltitudeMode::Absolute => write!(f, "absolute"),
+        }
+    }
+}
+
+/// Structure representing a KML `<coordinates>` block together with its altitude mode
+///
+/// KMLの`<coordinates>`ブロックとその高度モードを表す構造体
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct KmlCoordinates {
+    /// Altitude mode
+    ///
+    /// 高度モード
+    pub altitude_mode: AltitudeMode,
+    /// Points. With `ClampToGround` the altitude is always 0 m.
+    ///
+    /// 点。`ClampToGround`では高度は常に0mとなる。
+    pub points: Vec<LLZ>,
+}
+
+/// Parses the content of a KML `<coordinates>` element (longitude,latitude[,altitude] tuples in degrees).
+/// With `AltitudeMode::ClampToGround` the altitude is ignored and set to 0 m, as KML renderers do.
+///
+/// KMLの`<coordinates>`要素の内容(度単位の経度,緯度[,高度]の組)を解析する。
+/// `AltitudeMode::ClampToGround`では、KMLの描画系と同様に高度を無視して0mとする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::kml::{parse_kml_coordinates, AltitudeMode};
+///
+/// let points = parse_kml_coordinates("139.7671,35.6812,10 140.0,36.0,20", AltitudeMode::Absolute).unwrap();
+/// ```
+pub fn parse_kml_coordinates(text: &str, altitude_mode: AltitudeMode) -> Result<Vec<LLZ>, KmlError> {
+    text.split_whitespace()
+        .map(|tuple| {
+            let err = || KmlError::InvalidTuple(tuple.to_string());
+            let values = tuple
+                .split(',')
+                .map(|v| v.parse::<f64>().map_err(|_| err()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let (long, lat, altitude) = match values.as_slice() {
+                [long, lat] => (*long, *lat, 0.),
+                [long, lat, altitude] => (*long, *lat, *altitude),
+                _ => return Err(err()),
+            };
+
+            let altitude = match altitude_mode {
+                AltitudeMode::ClampToGround => 0.,
+                _ => altitude,
+            };
+
+            Ok(LLZ::new(long.to_radians(), lat.to_radians(), altitude))
+        })
+        .collect()
+}
+
+/// Writes points as the content of a KML `<coordinates>` element.
+/// With `AltitudeMode::ClampToGround` the altitude is omitted.
+///
+/// 点をKMLの`<coordinates>`要素の内容として書き出す。
+/// `AltitudeMode::ClampToGround`では高度を省略する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::kml::{write_kml_coordinates, AltitudeMode};
+/// use coordinate_transformer::structure::LLZ;
+///
+/// let points = [LLZ::new(139.7671_f64.to_radians(), 35.6812_f64.to_radians(), 10.)];
+/// let text = write_kml_coordinates(&points, AltitudeMode::Absolute);
+/// ```
+pub fn write_kml_coordinates(points: &[LLZ], altitude_mode: AltitudeMode) -> String {
+    points
+        .iter()
+        .map(|point| {
+            let ((long, lat), altitude) = point.to_tuple();
+            match altitude_mode {
+                AltitudeMode::ClampToGround => format!("{:.8},{:.8}", long.to_degrees(), lat.to_degrees()),
+                _ => format!("{:.8},{:.8},{:.3}", long.to_degrees(), lat.to_degrees(), altitude),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Names of the KML geometry elements, which can carry an `<altitudeMode>` element
+///
+/// `<altitudeMode>`要素を持つことができるKMLのジオメトリ要素の名前
+const GEOMETRIES: [&str; 7] = ["Point", "LineString", "LinearRing", "Polygon", "MultiGeometry", "gx:Track", "gx:MultiTrack"];
+
+/// Reads all `<coordinates>` blocks of a KML document.
+/// The altitude mode of each block is taken from the `<altitudeMode>` (or `<gx:altitudeMode>`) element of the innermost enclosing geometry that has one, defaulting to `ClampToGround`.
+/// So the inner rings of a Polygon and the geometries in a MultiGeometry inherit the altitude mode of the enclosing element.
+///
+/// KML文書の全ての`<coordinates>`ブロックを読み込む。
+/// 各ブロックの高度モードは、`<altitudeMode>`(または`<gx:altitudeMode>`)要素を持つ最も内側の囲むジオメトリから取得し、無い場合は`ClampToGround`とする。
+/// そのため、Polygonの内側の環やMultiGeometry内のジオメトリは、囲む要素の高度モードを引き継ぐ。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::kml::{read_kml_coordinates, AltitudeMode};
+///
+/// let kml = "<Placemark><Point><altitudeMode>absolute</altitudeMode><coordinates>139.7671,35.6812,10</coordinates></Point></Placemark>";
+///
+/// let blocks = read_kml_coordinates(kml).unwrap();
+/// assert_eq!(blocks[0].altitude_mode, AltitudeMode::Absolute);
+/// ```
+pub fn read_kml_coordinates(text: &str) -> Result<Vec<KmlCoordinates>, KmlError> {
+    // ジオメトリの範囲ごとの(親の範囲, 高度モード)
+    let mut scopes: Vec<(Option<usize>, Option<AltitudeMode>)> = Vec::new();
+    // 開いているジオメトリの(要素名, 範囲)
+    let mut open: Vec<(&str, usize)> = Vec::new();
+    // <coordinates>ブロックの(範囲, 内容)
+    let mut contents: Vec<(Option<usize>, &str)> = Vec::new();
+
+    let mut pos = 0;
+    while let Some(i) = text[pos..].find('<') {
+        let tag_start = pos + i;
+        let rest = &text[tag_start..];
+
+        // コメントとCDATAセクションは読み飛ばす
+        if let Some((open_delim, close_delim)) = [("<!--", "-->"), ("<![CDATA[", "]]>")]
+            .into_iter()
+            .find(|(open_delim, _)| rest.starts_with(open_delim))
+        {
+            pos = match rest[open_delim.len()..].find(close_delim) {
+                Some(j) => tag_start + open_delim.len() + j + close_delim.len(),
+                None => break,
+            };
+            continue;
+        }
+
+        let tag_end = match rest.find('>') {
+            Some(j) => tag_start + j + 1,
+            None => break,
+        };
+        let tag = &text[tag_start + 1..tag_end - 1];
+        pos = tag_end;
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let self_closing = tag.ends_with('/');
+        let name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+
+        if GEOMETRIES.contains(&name) {
+            if closing {
+                // 対応する要素まで閉じる
+                if let Some(k) = open.iter().rposition(|(open_name, _)| *open_name == name) {
+                    open.truncate(k);
+                }
+            } else if !self_closing {
+                scopes.push((open.last().map(|(_, scope)| *scope), None));
+                open.push((name, scopes.len() - 1));
+            }
+            continue;
+        }
+
+        if closing || self_closing {
+            continue;
+        }
+
+        match name {
+            "altitudeMode" | "gx:altitudeMode" => {
+                let end = text[pos..].find('<').map_or(text.len(), |j| pos + j);
+                // ジオメトリの外(LookAtなど)の高度モードは座標に関係しない
+                if let Some(&(_, scope)) = open.last() {
+                    scopes[scope].1 = Some(text[pos..end].parse::<AltitudeMode>()?);
+                }
+                pos = end;
+            }
+            "coordinates" => {
+                let end = match text[pos..].find("</coordinates>") {
+                    Some(j) => pos + j,
+                    None => break,
+                };
+                contents.push((open.last().map(|(_, scope)| *scope), &text[pos..end]));
+                pos = end + "</coordinates>".len();
+            }
+            _ => {}
+        }
+    }
+
+    // 高度モードは座標の後に書かれることもあるため、全体を読んでから内側の範囲から順に探す
+    contents
+        .into_iter()
+        .map(|(mut scope, content)| {
+            let altitude_mode = loop {
+                match scope {
+                    Some(i) => match scopes[i] {
+                        (_, Some(mode)) => break mode,
+                        (parent, None) => scope = parent,
+                    },
+                    None => break AltitudeMode::default(),
+                }
+            };
+
+            Ok(KmlCoordinates {
+                altitude_mode,
+                points: parse_kml_coordinates(content, altitude_mode)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn parse_kml_coordinates_works() {
+        let points = parse_kml_coordinates("\n  139.5,35.5,10\n  140,36 ", AltitudeMode::RelativeToGround).unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert_close_to(points[0].to_tuple().0 .0.to_degrees(), 139.5, 9);
+        assert_eq!(points[0].altitude(), 10.);
+        assert_eq!(points[1].altitude(), 0.);
+
+        let points = parse_kml_coordinates("139.5,35.5,10", AltitudeMode::ClampToGround).unwrap();
+        assert_eq!(points[0].altitude(), 0.);
+
+        assert!(parse_kml_coordinates("139.5", AltitudeMode::Absolute).is_err());
+    }
+
+    #[test]
+    fn write_kml_coordinates_works() {
+        let points = [LLZ::new(139.5_f64.to_radians(), 35.5_f64.to_radians(), 10.)];
+
+        assert_eq!(write_kml_coordinates(&points, AltitudeMode::Absolute), "139.50000000,35.50000000,10.000");
+        assert_eq!(write_kml_coordinates(&points, AltitudeMode::ClampToGround), "139.50000000,35.50000000");
+    }
+
+    #[test]
+    fn read_kml_coordinates_works() {
+        let kml = r#"<kml><Document>
+<Placemark><LineString><altitudeMode>relativeToGround</altitudeMode><coordinates>139,35,5 140,36,6</coordinates></LineString></Placemark>
+<Placemark><Point><coordinates>139,35,5</coordinates></Point></Placemark>
+</Document></kml>"#;
+
+        let blocks = read_kml_coordinates(kml).unwrap();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].altitude_mode, AltitudeMode::RelativeToGround);
+        assert_eq!(blocks[0].points[1].altitude(), 6.);
+        assert_eq!(blocks[1].altitude_mode, AltitudeMode::ClampToGround);
+        assert_eq!(blocks[1].points[0].altitude(), 0.);
+    }
+
+    #[test]
+    fn inner_rings_inherit_altitude_mode() {
+        let kml = r#"<Placemark><LookAt><altitudeMode>relativeToGround</altitudeMode></LookAt>
+<MultiGeometry>
+<Polygon><altitudeMode>absolute</altitudeMode>
+<outerBoundaryIs><LinearRing><coordinates>139,35,50 140,35,50 140,36,50 139,35,50</coordinates></LinearRing></outerBoundaryIs>
+<!-- <altitudeMode>clampToGround</altitudeMode> -->
+<innerBoundaryIs><LinearRing><coordinates>139.4,35.4,50 139.6,35.4,50 139.6,35.6,50 139.4,35.4,50</coordinates></LinearRing></innerBoundaryIs>
+</Polygon>
+<Point><coordinates>139,35,5</coordinates></Point>
+</MultiGeometry></Placemark>"#;
+
+        let blocks = read_kml_coordinates(kml).unwrap();
+
+        assert_eq!(blocks.len(), 3);
+        for block in &blocks[..2] {
+            assert_eq!(block.altitude_mode, AltitudeMode::Absolute);
+            assert!(block.points.iter().all(|point| point.altitude() == 50.));
+        }
+        // LookAtの高度モードはジオメトリに適用されない
+        assert_eq!(blocks[2].altitude_mode, AltitudeMode::ClampToGround);
+    }
+}
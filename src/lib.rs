@@ -1,10 +1,134 @@
+pub use accuracy::*;
+#[cfg(feature = "arrow")]
+pub use arrow_interop::*;
+pub use batch::*;
+pub use cache::*;
+pub use consts::*;
+pub use convert::*;
+pub use dms::*;
+pub use elevation::*;
+pub use enu::*;
+pub use epoch::*;
+pub use fast::*;
+pub use geodesic::*;
+pub use geodetic_converter::*;
+pub use georef::*;
+#[cfg(feature = "geo")]
+pub use geo_interop::*;
+#[cfg(feature = "wgpu")]
+pub use gpu::*;
+#[cfg(feature = "gpx")]
+pub use gpx::*;
+pub use gsi::*;
+pub use histogram::*;
+pub use interpolation::*;
 pub use jpr_ll::*;
+pub use jpr_pixel::*;
+pub use jpr_xyz::*;
+pub use kml::*;
+pub use maidenhead::*;
+pub use map_sheet::*;
+#[cfg(feature = "mmap")]
+pub use mmap::*;
+pub use nmea::*;
 pub use pixel_ll::*;
+pub use pixel_xyz::*;
+pub use plateau::*;
+pub use polygon::*;
+pub use precision::*;
+pub use prefecture::*;
+pub use quantized_mesh::*;
+pub use raster::*;
+#[cfg(feature = "rand")]
+pub use sampling::*;
+#[cfg(feature = "sima")]
+pub use sima::*;
+#[cfg(feature = "futures")]
+pub use stream::*;
 pub use structure::*;
+pub use tile::*;
+pub use tile_matrix_set::*;
+pub use transform::*;
+pub use transverse_mercator::*;
+pub use typed::*;
+#[cfg(feature = "uom")]
+pub use uom_interop::*;
+pub use utm::*;
+pub use vertical_datum::*;
+pub use voxel_codec::*;
+pub use voxel_lod::*;
+pub use voxel_region::*;
+pub use voxel_set::*;
+pub use wgsl::*;
+pub use world_file::*;
 pub use xyz_ll::*;
+pub use zoomed_pixel::*;
 
+pub mod accuracy;
+#[cfg(feature = "arrow")]
+pub mod arrow_interop;
+pub mod batch;
+pub mod cache;
+pub mod consts;
+pub mod convert;
+pub mod dms;
+pub mod elevation;
+pub mod enu;
+pub mod epoch;
+pub mod fast;
+pub mod geodesic;
+pub mod geodetic_converter;
+pub mod georef;
+#[cfg(feature = "geo")]
+pub mod geo_interop;
+#[cfg(feature = "wgpu")]
+pub mod gpu;
+#[cfg(feature = "gpx")]
+pub mod gpx;
+pub mod gsi;
+pub mod histogram;
+pub mod interpolation;
 pub mod jpr_ll;
+pub mod jpr_pixel;
+pub mod jpr_xyz;
+pub mod kml;
+pub mod maidenhead;
+pub mod map_sheet;
+mod math;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod nmea;
 pub mod pixel_ll;
+pub mod pixel_xyz;
+pub mod plateau;
+pub mod polygon;
+pub mod precision;
+pub mod prefecture;
+pub mod quantized_mesh;
+pub mod raster;
+#[cfg(feature = "rand")]
+pub mod sampling;
 pub mod xyz_ll;
+#[cfg(feature = "sima")]
+pub mod sima;
+#[cfg(feature = "futures")]
+pub mod stream;
 pub mod structure;
-
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod tile;
+pub mod tile_matrix_set;
+pub mod transform;
+pub mod transverse_mercator;
+pub mod typed;
+#[cfg(feature = "uom")]
+pub mod uom_interop;
+pub mod utm;
+pub mod vertical_datum;
+pub mod voxel_codec;
+pub mod voxel_lod;
+pub mod voxel_region;
+pub mod voxel_set;
+pub mod wgsl;
+pub mod world_file;
+pub mod zoomed_pixel;
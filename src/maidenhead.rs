@@ -0,0 +1,213 @@
+//! Encoding and decoding of Maidenhead locators (e.g. `PM95vq`), the grid system used in amateur radio.
+//! A locator consists of up to four pairs of a field (20° x 10°, `A` to `R`), a square (2° x 1°, `0` to `9`), a subsquare (5' x 2.5', `a` to `x`) and an extended square (30" x 15", `0` to `9`), each pair being (longitude, latitude).
+//!
+//! アマチュア無線で用いられる格子系であるメイデンヘッドロケーター(例: `PM95vq`)の符号化と復号。
+//! ロケーターは、フィールド(20° x 10°、`A`から`R`)、スクエア(2° x 1°、`0`から`9`)、サブスクエア(5' x 2.5'、`a`から`x`)、拡張スクエア(30" x 15"、`0`から`9`)の最大4つの組からなり、各組は(経度, 緯度)である。
+
+use std::fmt::{Display, Formatter};
+
+/// Numbers of divisions of each pair of characters
+///
+/// 各組の文字による分割数
+const DIVISIONS: [u32; 4] = [18, 10, 24, 10];
+
+/// Number of cells of the finest grid along each axis, the product of `DIVISIONS`
+///
+/// 各軸における最も細かい格子の数。`DIVISIONS`の積
+const CELLS: u32 = 18 * 10 * 24 * 10;
+
+/// South west and north east corners ((longitude, latitude), (longitude, latitude)) of a square expressed in the arc degree method
+///
+/// 弧度法で表されたスクエアの南西の角と北東の角((経度, 緯度), (経度, 緯度))
+pub type MaidenheadBounds = ((f64, f64), (f64, f64));
+
+/// Enumerated type representing errors when decoding Maidenhead locators
+///
+/// メイデンヘッドロケーターの復号時のエラーを表す列挙型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaidenheadError {
+    /// The length of the locator is not 2, 4, 6 or 8
+    ///
+    /// ロケーターの長さが2, 4, 6, 8のいずれでもない
+    InvalidLength(usize),
+    /// The locator contains a character outside the range of its position
+    ///
+    /// ロケーターがその位置の範囲外の文字を含む
+    InvalidCharacter(String),
+}
+
+impl Display for MaidenheadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaidenheadError::InvalidLength(len) => write!(f, "invalid Maidenhead locator length: {}", len),
+            MaidenheadError::InvalidCharacter(s) => write!(f, "invalid Maidenhead locator: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for MaidenheadError {}
+
+/// Returns the first character used at the pair of the index
+///
+/// その番号の組で用いられる最初の文字を返す
+fn base_char(pair: usize) -> u8 {
+    match pair {
+        0 => b'A',
+        2 => b'a',
+        _ => b'0',
+    }
+}
+
+/// Function to encode (longitude, latitude) expressed in the arc degree method into a Maidenhead locator of `length` characters (2, 4, 6 or 8).
+/// Fields are written in upper case and subsquares in lower case. The north pole and the antimeridian of 180°E belong to the northernmost and easternmost squares.
+/// Panics if the length is not 2, 4, 6 or 8.
+///
+/// 弧度法で表された(経度, 緯度)を`length`文字(2, 4, 6, 8)のメイデンヘッドロケーターに符号化する関数。
+/// フィールドは大文字、サブスクエアは小文字で表記する。北極と東経180度の経線は最も北及び東のスクエアに含まれる。
+/// 長さが2, 4, 6, 8のいずれでもない場合はパニックする。
+///
+/// # Examples
+///
+/// Find the locator of Tokyo Station.
+///
+/// 東京駅のロケーターを求める。
+///
+/// ```
+/// use coordinate_transformer::maidenhead::ll2maidenhead;
+///
+/// let ll = (139.7671248_f64.to_radians(), 35.6812362_f64.to_radians());
+///
+/// assert_eq!(ll2maidenhead(ll, 6), "PM95vq");
+/// ```
+pub fn ll2maidenhead(ll: (f64, f64), length: usize) -> String {
+    assert!(
+        matches!(length, 2 | 4 | 6 | 8),
+        "length of Maidenhead locator must be 2, 4, 6 or 8, got {length}"
+    );
+
+    let (long, lat) = ll;
+    let cell = |degrees: f64, range: f64| {
+        ((degrees / range * CELLS as f64).floor() as i64).clamp(0, CELLS as i64 - 1) as u32
+    };
+    let mut long_cell = cell(long.to_degrees() + 180., 360.);
+    let mut lat_cell = cell(lat.to_degrees() + 90., 180.);
+
+    // 細かい組から順に剰余をとり、最後に逆順にする
+    let mut pairs = [(0, 0); 4];
+    for (pair, &division) in pairs.iter_mut().zip(DIVISIONS.iter()).rev() {
+        *pair = (long_cell % division, lat_cell % division);
+        long_cell /= division;
+        lat_cell /= division;
+    }
+
+    pairs
+        .iter()
+        .enumerate()
+        .take(length / 2)
+        .flat_map(|(i, &(x, y))| [x, y].map(|d| (base_char(i) + d as u8) as char))
+        .collect()
+}
+
+/// Function to decode a Maidenhead locator of 2, 4, 6 or 8 characters into the south west and north east corners ((longitude, latitude), (longitude, latitude)) of its square, expressed in the arc degree method.
+/// Letters are accepted in either case.
+///
+/// 2, 4, 6, 8文字のメイデンヘッドロケーターを、そのスクエアの南西の角と北東の角((経度, 緯度), (経度, 緯度))に復号する関数。弧度法で表される。
+/// 英字は大文字と小文字のどちらでもよい。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::maidenhead::maidenhead_bounds;
+///
+/// let ((min_long, min_lat), (max_long, max_lat)) = maidenhead_bounds("PM95").unwrap();
+///
+/// assert_eq!((min_long.to_degrees().round(), min_lat.to_degrees().round()), (138., 35.));
+/// assert_eq!((max_long.to_degrees().round(), max_lat.to_degrees().round()), (140., 36.));
+/// ```
+pub fn maidenhead_bounds(locator: &str) -> Result<MaidenheadBounds, MaidenheadError> {
+    let bytes = locator.as_bytes();
+    if !matches!(bytes.len(), 2 | 4 | 6 | 8) {
+        return Err(MaidenheadError::InvalidLength(locator.chars().count()));
+    }
+
+    let (mut long, mut lat) = (-180., -90.);
+    let (mut width, mut height) = (360., 180.);
+
+    for (i, pair) in bytes.chunks(2).enumerate() {
+        let division = DIVISIONS[i];
+        let digit = |c: u8| {
+            let c = if i % 2 == 0 { c.to_ascii_uppercase() } else { c };
+            let base = base_char(i).to_ascii_uppercase();
+            match c.checked_sub(base) {
+                Some(d) if (d as u32) < division => Ok(d as f64),
+                _ => Err(MaidenheadError::InvalidCharacter(locator.to_string())),
+            }
+        };
+        let (x, y) = (digit(pair[0])?, digit(pair[1])?);
+
+        width /= division as f64;
+        height /= division as f64;
+        long += x * width;
+        lat += y * height;
+    }
+
+    Ok((
+        (long.to_radians(), lat.to_radians()),
+        ((long + width).to_radians(), (lat + height).to_radians()),
+    ))
+}
+
+/// Function to decode a Maidenhead locator of 2, 4, 6 or 8 characters into the center (longitude, latitude) of its square, expressed in the arc degree method
+///
+/// 2, 4, 6, 8文字のメイデンヘッドロケーターを、そのスクエアの中心(経度, 緯度)に復号する関数。弧度法で表される
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::maidenhead::maidenhead2ll;
+///
+/// let (long, lat) = maidenhead2ll("FN31pr").unwrap();
+///
+/// assert!((long.to_degrees() - -72.7083).abs() < 1e-3);
+/// assert!((lat.to_degrees() - 41.7292).abs() < 1e-3);
+/// ```
+pub fn maidenhead2ll(locator: &str) -> Result<(f64, f64), MaidenheadError> {
+    let ((min_long, min_lat), (max_long, max_lat)) = maidenhead_bounds(locator)?;
+
+    Ok(((min_long + max_long) / 2., (min_lat + max_lat) / 2.))
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn ll2maidenhead_works() {
+        let ll = (-72.727260_f64.to_radians(), 41.714775_f64.to_radians());
+        assert_eq!(ll2maidenhead(ll, 2), "FN");
+        assert_eq!(ll2maidenhead(ll, 4), "FN31");
+        assert_eq!(ll2maidenhead(ll, 6), "FN31pr");
+        assert_eq!(ll2maidenhead(ll, 8), "FN31pr21");
+
+        // 範囲の端は最も南西及び北東のスクエアに含まれる
+        assert_eq!(ll2maidenhead((-180_f64.to_radians(), -90_f64.to_radians()), 8), "AA00aa00");
+        assert_eq!(ll2maidenhead((180_f64.to_radians(), 90_f64.to_radians()), 8), "RR99xx99");
+    }
+
+    #[test]
+    fn maidenhead2ll_works() {
+        let ((min_long, min_lat), (max_long, max_lat)) = maidenhead_bounds("fn31PR21").unwrap();
+        assert_close_to(max_long - min_long, (0.5 / 60_f64).to_radians(), 12);
+        assert_close_to(max_lat - min_lat, (0.25 / 60_f64).to_radians(), 12);
+
+        // 中心を符号化すると元のロケーターに戻る
+        let ll = maidenhead2ll("FN31pr21").unwrap();
+        assert_eq!(ll2maidenhead(ll, 8), "FN31pr21");
+
+        assert_eq!(maidenhead2ll("FN3"), Err(MaidenheadError::InvalidLength(3)));
+        assert_eq!(maidenhead2ll("SN31"), Err(MaidenheadError::InvalidCharacter("SN31".to_string())));
+        assert_eq!(maidenhead2ll("FN31py"), Err(MaidenheadError::InvalidCharacter("FN31py".to_string())));
+    }
+}
@@ -0,0 +1,376 @@
+//! Codes of the sheets of the national base map (国土基本図図郭) on the plane rectangular coordinate system, e.g. `09LD3824`.
+//! The extent of each zone from x = +300 km to -300 km and from y = -160 km to +160 km is divided into 1/50000 sheets of 20 rows `A` to `T` and 8 columns `A` to `H`.
+//! Each 1/50000 sheet is divided into 10 x 10 sheets of 1/5000, and each 1/5000 sheet into 2 x 2 sheets of 1/2500 (`1` to `4`), 5 x 5 sheets of 1/1000 (row `0` to `4` and column `A` to `E`) or 10 x 10 sheets of 1/500.
+//! Rows are counted from the north and columns from the west.
+//!
+//! 平面直角座標系における国土基本図の図郭のコード(例: `09LD3824`)。
+//! 各系のx = +300kmから-300km、y = -160kmから+160kmの範囲を、`A`から`T`の20行、`A`から`H`の8列の1/50000の図郭に分割する。
+//! 1/50000の図郭を10 x 10の1/5000の図郭に分割し、1/5000の図郭を2 x 2の1/2500(`1`から`4`)、5 x 5の1/1000(行`0`から`4`と列`A`から`E`)、または10 x 10の1/500の図郭に分割する。
+//! 行は北から、列は西から数える。
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::jpr_ll::{jpr2ll, JprOrigin};
+
+/// x coordinate of the northern edge of the sheets (m)
+///
+/// 図郭の北端のx座標(m)
+const NORTH_X: f64 = 300000.;
+
+/// y coordinate of the western edge of the sheets (m)
+///
+/// 図郭の西端のy座標(m)
+const WEST_Y: f64 = -160000.;
+
+/// Numbers of rows and columns of the 1/50000 sheets in a zone
+///
+/// 1つの系における1/50000の図郭の行数と列数
+const ROWS_50000: u32 = 20;
+const COLUMNS_50000: u32 = 8;
+
+/// Enumerated type representing the scale of a sheet of the national base map
+///
+/// 国土基本図の図郭の縮尺を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SheetScale {
+    /// 1/50000, 30 km x 40 km
+    ///
+    /// 1/50000。30km x 40km
+    Scale50000,
+    /// 1/5000, 3 km x 4 km
+    ///
+    /// 1/5000。3km x 4km
+    Scale5000,
+    /// 1/2500, 1.5 km x 2 km
+    ///
+    /// 1/2500。1.5km x 2km
+    Scale2500,
+    /// 1/1000, 600 m x 800 m
+    ///
+    /// 1/1000。600m x 800m
+    Scale1000,
+    /// 1/500, 300 m x 400 m
+    ///
+    /// 1/500。300m x 400m
+    Scale500,
+}
+
+impl SheetScale {
+    /// Returns the size of a sheet (north-south (m), east-west (m))
+    ///
+    /// 図郭の大きさ(南北(m), 東西(m))を返す
+    pub fn size(&self) -> (f64, f64) {
+        match self {
+            SheetScale::Scale50000 => (30000., 40000.),
+            SheetScale::Scale5000 => (3000., 4000.),
+            SheetScale::Scale2500 => (1500., 2000.),
+            SheetScale::Scale1000 => (600., 800.),
+            SheetScale::Scale500 => (300., 400.),
+        }
+    }
+
+    /// Returns the number of divisions of a 1/5000 sheet along each axis. 1/50000 sheets are not divisions of 1/5000 sheets and return `None`.
+    ///
+    /// 1/5000の図郭の各軸の分割数を返す。1/50000の図郭は1/5000の図郭の分割ではないため`None`を返す。
+    fn divisions_of_5000(&self) -> Option<u32> {
+        match self {
+            SheetScale::Scale50000 => None,
+            SheetScale::Scale5000 => Some(1),
+            SheetScale::Scale2500 => Some(2),
+            SheetScale::Scale1000 => Some(5),
+            SheetScale::Scale500 => Some(10),
+        }
+    }
+
+    /// Returns the number of rows and columns of the sheets in a zone
+    ///
+    /// 1つの系における図郭の行数と列数を返す
+    fn grid(&self) -> (u32, u32) {
+        let n = self.divisions_of_5000().map_or(1, |d| d * 10);
+        (ROWS_50000 * n, COLUMNS_50000 * n)
+    }
+}
+
+/// Enumerated type representing errors when parsing map sheet codes
+///
+/// 図郭のコードの解析時のエラーを表す列挙型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapSheetError {
+    /// The code is not of any of the forms of the scales
+    ///
+    /// コードがいずれの縮尺の形式でもない
+    InvalidCode(String),
+}
+
+impl Display for MapSheetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapSheetError::InvalidCode(s) => write!(f, "invalid map sheet code: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for MapSheetError {}
+
+/// Structure representing a sheet of the national base map
+///
+/// 国土基本図の図郭を表す構造体
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::map_sheet::{MapSheet, SheetScale};
+///
+/// let sheet = MapSheet::from_jpr((-6200., -39750.), JprOrigin::Nine, SheetScale::Scale500).unwrap();
+///
+/// assert_eq!(sheet.code(), "09LD3824");
+/// assert_eq!(sheet.bounds_jpr(), ((-6400., -39900.), (-6000., -39600.)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MapSheet {
+    origin: JprOrigin,
+    scale: SheetScale,
+    // 系の北西の角から数えた図郭の行と列
+    row: u32,
+    column: u32,
+}
+
+impl MapSheet {
+    /// Returns the sheet of the scale containing plane rectangular coordinates (y, x) of the origin, or `None` if they are outside the extent of the sheets.
+    /// Points on the northern and western edges of a sheet belong to it.
+    ///
+    /// 原点の平面直角座標(y, x)を含む縮尺の図郭を返す。図郭の範囲外の場合は`None`を返す。
+    /// 図郭の北端と西端の上の点はその図郭に含まれる。
+    pub fn from_jpr(yx: (f64, f64), origin: JprOrigin, scale: SheetScale) -> Option<Self> {
+        let (y, x) = yx;
+        let (height, width) = scale.size();
+        let (rows, columns) = scale.grid();
+
+        let row = ((NORTH_X - x) / height).floor();
+        let column = ((y - WEST_Y) / width).floor();
+
+        if row < 0. || row >= rows as f64 || column < 0. || column >= columns as f64 {
+            return None;
+        }
+
+        Some(Self {
+            origin,
+            scale,
+            row: row as u32,
+            column: column as u32,
+        })
+    }
+
+    /// Parse a map sheet code such as `09LD3824`.
+    /// The scale is determined by the length: 4 characters for 1/50000, 6 for 1/5000, 7 for 1/2500, and 8 for 1/1000 (ending with a letter) or 1/500 (ending with a digit).
+    ///
+    /// `09LD3824`のような図郭のコードを解析する。
+    /// 縮尺は長さで決まり、1/50000は4文字、1/5000は6文字、1/2500は7文字、1/1000(英字で終わる)と1/500(数字で終わる)は8文字である。
+    pub fn from_code(code: &str) -> Result<Self, MapSheetError> {
+        let invalid = || MapSheetError::InvalidCode(code.to_string());
+        let bytes = code.as_bytes();
+        let digit = |c: u8, max: u8| match c {
+            b'0'..=b'9' if c - b'0' <= max => Ok((c - b'0') as u32),
+            _ => Err(invalid()),
+        };
+        let letter = |c: u8, max: u8| match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' if c - b'A' <= max => Ok((c - b'A') as u32),
+            _ => Err(invalid()),
+        };
+
+        if !code.is_ascii() || bytes.len() < 4 {
+            return Err(invalid());
+        }
+
+        let origin = JprOrigin::parse(digit(bytes[0], 9)? * 10 + digit(bytes[1], 9)?).map_err(|_| invalid())?;
+        let row = letter(bytes[2], ROWS_50000 as u8 - 1)?;
+        let column = letter(bytes[3], COLUMNS_50000 as u8 - 1)?;
+
+        let (scale, sub_row, sub_column) = match bytes.len() {
+            4 => {
+                return Ok(Self {
+                    origin,
+                    scale: SheetScale::Scale50000,
+                    row,
+                    column,
+                })
+            }
+            6 => (SheetScale::Scale5000, 0, 0),
+            7 => {
+                let quarter = digit(bytes[6], 4)?.checked_sub(1).ok_or_else(invalid)?;
+                (SheetScale::Scale2500, quarter / 2, quarter % 2)
+            }
+            8 if bytes[7].is_ascii_alphabetic() => (SheetScale::Scale1000, digit(bytes[6], 4)?, letter(bytes[7], 4)?),
+            8 => (SheetScale::Scale500, digit(bytes[6], 9)?, digit(bytes[7], 9)?),
+            _ => return Err(invalid()),
+        };
+
+        let n = scale.divisions_of_5000().unwrap_or(1);
+        let row_5000 = row * 10 + digit(bytes[4], 9)?;
+        let column_5000 = column * 10 + digit(bytes[5], 9)?;
+
+        Ok(Self {
+            origin,
+            scale,
+            row: row_5000 * n + sub_row,
+            column: column_5000 * n + sub_column,
+        })
+    }
+
+    /// Returns the origin of the plane rectangular coordinate system
+    ///
+    /// 平面直角座標系の原点を返す
+    pub fn origin(&self) -> JprOrigin {
+        self.origin
+    }
+
+    /// Returns the scale
+    ///
+    /// 縮尺を返す
+    pub fn scale(&self) -> SheetScale {
+        self.scale
+    }
+
+    /// Returns the code of the sheet
+    ///
+    /// 図郭のコードを返す
+    pub fn code(&self) -> String {
+        let letter = |i: u32| (b'A' + i as u8) as char;
+        let Some(n) = self.scale.divisions_of_5000() else {
+            return format!("{:02}{}{}", self.origin as u8, letter(self.row), letter(self.column));
+        };
+
+        let (row_5000, column_5000) = (self.row / n, self.column / n);
+        let (sub_row, sub_column) = (self.row % n, self.column % n);
+
+        let code_5000 = format!(
+            "{:02}{}{}{}{}",
+            self.origin as u8,
+            letter(row_5000 / 10),
+            letter(column_5000 / 10),
+            row_5000 % 10,
+            column_5000 % 10
+        );
+
+        match self.scale {
+            SheetScale::Scale2500 => format!("{}{}", code_5000, sub_row * 2 + sub_column + 1),
+            SheetScale::Scale1000 => format!("{}{}{}", code_5000, sub_row, letter(sub_column)),
+            SheetScale::Scale500 => format!("{}{}{}", code_5000, sub_row, sub_column),
+            _ => code_5000,
+        }
+    }
+
+    /// Returns the south west and north east corners ((y, x), (y, x)) of the sheet in plane rectangular coordinates
+    ///
+    /// 図郭の南西の角と北東の角((y, x), (y, x))を平面直角座標で返す
+    pub fn bounds_jpr(&self) -> ((f64, f64), (f64, f64)) {
+        let (height, width) = self.scale.size();
+        let north = NORTH_X - self.row as f64 * height;
+        let west = WEST_Y + self.column as f64 * width;
+
+        ((west, north - height), (west + width, north))
+    }
+
+    /// Returns the minimum and maximum ((longitude, latitude), (longitude, latitude)) expressed in the arc degree method of the sheet.
+    /// The edges of the sheet are curved in longitude and latitude, so they are sampled at several points in addition to the corners.
+    ///
+    /// 図郭の最小値と最大値((経度, 緯度), (経度, 緯度))を弧度法で返す。
+    /// 図郭の辺は経度緯度では曲がっているため、角に加えて辺上の複数の点で標本化する。
+    pub fn bounds_ll(&self) -> ((f64, f64), (f64, f64)) {
+        const SAMPLES: u32 = 8;
+
+        let ((y0, x0), (y1, x1)) = self.bounds_jpr();
+        let lerp = |a: f64, b: f64, i: u32| a + (b - a) * i as f64 / SAMPLES as f64;
+
+        (0..=SAMPLES)
+            .flat_map(|i| {
+                [
+                    (lerp(y0, y1, i), x0),
+                    (lerp(y0, y1, i), x1),
+                    (y0, lerp(x0, x1, i)),
+                    (y1, lerp(x0, x1, i)),
+                ]
+            })
+            .map(|yx| jpr2ll(yx, self.origin))
+            .fold(
+                ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+                |((min_long, min_lat), (max_long, max_lat)), (long, lat)| {
+                    ((min_long.min(long), min_lat.min(lat)), (max_long.max(long), max_lat.max(lat)))
+                },
+            )
+    }
+}
+
+impl Display for MapSheet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for MapSheet {
+    type Err = MapSheetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MapSheet::from_code(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jpr_ll::ll2jpr;
+
+    use super::*;
+
+    #[test]
+    fn map_sheet_codes_round_trip() {
+        let yx = (-6200., -39750.);
+        let codes = [
+            (SheetScale::Scale50000, "09LD"),
+            (SheetScale::Scale5000, "09LD38"),
+            (SheetScale::Scale2500, "09LD381"),
+            (SheetScale::Scale1000, "09LD381C"),
+            (SheetScale::Scale500, "09LD3824"),
+        ];
+
+        for (scale, code) in codes {
+            let sheet = MapSheet::from_jpr(yx, JprOrigin::Nine, scale).unwrap();
+            assert_eq!(sheet.code(), code);
+            assert_eq!(code.parse::<MapSheet>(), Ok(sheet));
+
+            let ((y0, x0), (y1, x1)) = sheet.bounds_jpr();
+            assert!(y0 <= yx.0 && yx.0 < y1 && x0 < yx.1 && yx.1 <= x1);
+        }
+
+        // 範囲の北西の角と南東の角
+        let first = MapSheet::from_jpr((-160000., 300000.), JprOrigin::One, SheetScale::Scale1000).unwrap();
+        assert_eq!(first.code(), "01AA000A");
+        let last = MapSheet::from_jpr((159999., -299999.), JprOrigin::Nineteen, SheetScale::Scale2500).unwrap();
+        assert_eq!(last.code(), "19TH994");
+        assert_eq!(MapSheet::from_jpr((160000., 0.), JprOrigin::Nine, SheetScale::Scale50000), None);
+    }
+
+    #[test]
+    fn invalid_codes_are_rejected() {
+        for code in ["09L", "20LD", "09UD", "09LI", "09LD385", "09LD385F", "09LD3X", "09LD38240"] {
+            assert_eq!(MapSheet::from_code(code), Err(MapSheetError::InvalidCode(code.to_string())));
+        }
+    }
+
+    #[test]
+    fn bounds_ll_contains_the_sheet() {
+        let sheet = MapSheet::from_code("09LD").unwrap();
+        let ((min_long, min_lat), (max_long, max_lat)) = sheet.bounds_ll();
+
+        let ((y0, x0), (y1, x1)) = sheet.bounds_jpr();
+        for yx in [(y0, x0), (y0, x1), (y1, x0), (y1, x1), ((y0 + y1) / 2., x1)] {
+            let (long, lat) = jpr2ll(yx, JprOrigin::Nine);
+            assert!(min_long <= long && long <= max_long && min_lat <= lat && lat <= max_lat);
+        }
+
+        // 外接する矩形の角を平面直角座標に戻すと図郭の辺上か外側にある
+        let (y, x) = ll2jpr((min_long, min_lat), JprOrigin::Nine);
+        assert!(y <= y0 + 1e-3 || x <= x0 + 1e-3);
+    }
+}
@@ -0,0 +1,147 @@
+//! Transcendental functions used by the conversions
+//! With the `deterministic` feature, the bundled `libm` implementation is used so that the results are bit-for-bit identical on every platform.
+//! Otherwise the functions of the standard library (which depend on the platform) are used.
+//! `sqrt` is not included because IEEE 754 requires it to be correctly rounded.
+//!
+//! 変換で用いる超越関数
+//! `deterministic`フィーチャを有効にすると、同梱の`libm`の実装を用いるため、どのプラットフォームでも結果がビット単位で一致する。
+//! そうでない場合は、(プラットフォームに依存する)標準ライブラリの関数を用いる。
+//! `sqrt`はIEEE 754で正しい丸めが要求されているため含めない。
+
+#[cfg(feature = "deterministic")]
+mod imp {
+    pub(crate) use libm::{asin, atan, atan2, atanh, cos, cosh, hypot, log10, pow, sin, sinh, tan, tanh};
+
+    #[inline]
+    pub(crate) fn sin_cos(x: f64) -> (f64, f64) {
+        libm::sincos(x)
+    }
+}
+
+#[cfg(not(feature = "deterministic"))]
+mod imp {
+    #[inline]
+    pub(crate) fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+
+    #[inline]
+    pub(crate) fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+
+    #[inline]
+    pub(crate) fn sin_cos(x: f64) -> (f64, f64) {
+        x.sin_cos()
+    }
+
+    #[inline]
+    pub(crate) fn tan(x: f64) -> f64 {
+        x.tan()
+    }
+
+    #[inline]
+    pub(crate) fn asin(x: f64) -> f64 {
+        x.asin()
+    }
+
+    #[inline]
+    pub(crate) fn atan(x: f64) -> f64 {
+        x.atan()
+    }
+
+    #[inline]
+    pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+
+    #[inline]
+    pub(crate) fn sinh(x: f64) -> f64 {
+        x.sinh()
+    }
+
+    #[inline]
+    pub(crate) fn cosh(x: f64) -> f64 {
+        x.cosh()
+    }
+
+    #[inline]
+    pub(crate) fn tanh(x: f64) -> f64 {
+        x.tanh()
+    }
+
+    #[inline]
+    pub(crate) fn atanh(x: f64) -> f64 {
+        x.atanh()
+    }
+
+    #[inline]
+    pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+        x.hypot(y)
+    }
+
+    #[inline]
+    pub(crate) fn log10(x: f64) -> f64 {
+        x.log10()
+    }
+
+    #[inline]
+    pub(crate) fn pow(x: f64, y: f64) -> f64 {
+        x.powf(y)
+    }
+}
+
+pub(crate) use imp::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions_match_std() {
+        for &x in &[-0.9, -0.3, 0., 0.1, 0.6283, 0.99] {
+            assert!((sin(x) - x.sin()).abs() < 1e-15);
+            assert!((sin_cos(x).1 - x.cos()).abs() < 1e-15);
+            assert!((atanh(x) - x.atanh()).abs() < 1e-14);
+            assert!((asin(x) - x.asin()).abs() < 1e-15);
+            assert!((tan(x) - x.tan()).abs() < 1e-15);
+        }
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn conversions_are_bit_reproducible() {
+        use crate::{
+            destination, jpr_convergence, jpr_scale_factor, ll2jpr, llz2xyz, pixel2ll, pixel_distance_m, FastMercator,
+            JprOrigin, ZoomLv,
+        };
+
+        let ll = (140.08785504166664_f64.to_radians(), 36.103774791666666_f64.to_radians());
+
+        let (y, x) = ll2jpr(ll, JprOrigin::Nine);
+        assert_eq!((y.to_bits(), x.to_bits()), (0x40d6610f9664da85, 0x40c68bd81aeb1d00));
+
+        let (long, lat) = pixel2ll((476868027, 211407949), ZoomLv::Lv21);
+        assert_eq!((long.to_bits(), lat.to_bits()), (0x400383ce3ce9457c, 0x3fe3ed9c2d6f39ec));
+
+        let (x, y, z) = llz2xyz(ll, 100.);
+        assert_eq!(
+            (x.to_bits(), y.to_bits(), z.to_bits()),
+            (0xc14e312be54e3165, 0x4149415febcaf234, 0x414c83ec162cf4ae)
+        );
+
+        let (long, lat) = destination(ll, 0.7, 12345.6);
+        assert_eq!((long.to_bits(), lat.to_bits()), (0x4003928245d80ad2, 0x3fe4362f5eb6269a));
+
+        assert_eq!(jpr_scale_factor(ll, JprOrigin::Nine).to_bits(), 0x3fefff3bd9b35bf6);
+        assert_eq!(jpr_convergence(ll, JprOrigin::Nine).to_bits(), 0x3f6571819946af25);
+
+        let distance = pixel_distance_m((476868027, 211407949), (476868127, 211408049), ZoomLv::Lv21);
+        assert_eq!(distance.to_bits(), 0x4021264699a906f0);
+
+        // ルックアップテーブルの作成も同梱の実装で行う
+        let mercator = FastMercator::new();
+        assert_eq!(mercator.isometric_latitude(ll.1).to_bits(), 0x3fe5a6045323001e);
+        assert_eq!(mercator.ll2pixel(ll, ZoomLv::Lv21), (477349607, 210630122));
+    }
+}
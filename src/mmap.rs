@@ -0,0 +1,228 @@
+//! Out-of-core conversion of flat binary files of coordinates through memory mapping, for datasets larger than RAM such as LiDAR point clouds.
+//! A file is a sequence of records of `N` little-endian `f64` values, e.g. (longitude, latitude) or (x, y, z), without any header.
+//!
+//! LiDARの点群のようなメモリに収まらないデータセットのための、メモリマップによる座標のフラットなバイナリファイルの変換。
+//! ファイルはヘッダを持たず、`N`個のリトルエンディアンの`f64`の値からなるレコード((経度, 緯度)や(x, y, z)など)の並びである。
+
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+use std::thread;
+
+use memmap2::{Mmap, MmapMut};
+
+/// Size of an `f64` value in bytes
+///
+/// `f64`の値のバイト数
+const F64_SIZE: usize = std::mem::size_of::<f64>();
+
+/// Error that occurs while converting a memory-mapped file
+///
+/// メモリマップしたファイルの変換中に発生するエラー
+#[derive(Debug)]
+pub enum MmapConvertError {
+    /// Reading or writing a file failed
+    ///
+    /// ファイルの読み書きに失敗した
+    Io(std::io::Error),
+    /// The length (bytes) of the input file is not a multiple of the size of a record
+    ///
+    /// 入力ファイルの長さ(バイト)がレコードの大きさの倍数ではない
+    InvalidLength {
+        /// Length (bytes) of the input file
+        ///
+        /// 入力ファイルの長さ(バイト)
+        len: u64,
+        /// Size (bytes) of a record
+        ///
+        /// レコードの大きさ(バイト)
+        record_size: usize,
+    },
+    /// The input and output are the same file, which would be truncated before it is read
+    ///
+    /// 入力と出力が同じファイルであり、読み込む前に切り詰められてしまう
+    SameFile,
+}
+
+impl Display for MmapConvertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MmapConvertError::Io(e) => write!(f, "I/O error: {}", e),
+            MmapConvertError::InvalidLength { len, record_size } => {
+                write!(f, "file length {} is not a multiple of the record size {}", len, record_size)
+            }
+            MmapConvertError::SameFile => write!(f, "input and output are the same file"),
+        }
+    }
+}
+
+impl std::error::Error for MmapConvertError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MmapConvertError::Io(e) => Some(e),
+            MmapConvertError::InvalidLength { .. } | MmapConvertError::SameFile => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MmapConvertError {
+    fn from(e: std::io::Error) -> Self {
+        MmapConvertError::Io(e)
+    }
+}
+
+/// Converts the records of `N` values of the file `input` with `f` and writes the records of `M` values to the file `output`, returning the number of records.
+/// The records are divided into contiguous chunks converted in parallel on `threads` threads, or on as many threads as the available parallelism if `threads` is 0.
+/// The output file is created or truncated. The input file must not be modified while it is converted, since it is memory-mapped.
+/// Returns `MmapConvertError::SameFile` if `output` is the same file as `input`, since an in-place conversion would destroy the input before reading it.
+///
+/// ファイル`input`の`N`個の値のレコードを`f`で変換し、`M`個の値のレコードをファイル`output`に書き込み、レコードの数を返す。
+/// レコードは連続したまとまりに分けられ、`threads`個のスレッド、`threads`が0の場合は利用可能な並列度と同じ数のスレッドで並列に変換される。
+/// 出力ファイルは作成されるか、長さ0に切り詰められる。入力ファイルはメモリマップされるため、変換中に変更してはならない。
+/// その場での変換は入力を読み込む前に破壊してしまうため、`output`が`input`と同じファイルである場合は`MmapConvertError::SameFile`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{ll2jpr, JprOrigin};
+/// use coordinate_transformer::mmap::convert_mapped_file;
+///
+/// let dir = std::env::temp_dir();
+/// let (input, output) = (dir.join("mmap_doc_ll.bin"), dir.join("mmap_doc_jpr.bin"));
+///
+/// let ll = [140_f64.to_radians(), 36_f64.to_radians()];
+/// std::fs::write(&input, ll.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>()).unwrap();
+///
+/// let count = convert_mapped_file(&input, &output, 0, |[long, lat]: [f64; 2]| {
+/// let (y, x) = ll2jpr((long, lat), JprOrigin::Nine);
+/// [y, x]
+/// })
+/// .unwrap();
+///
+/// assert_eq!(count, 1);
+/// assert_eq!(std::fs::metadata(&output).unwrap().len(), 16);
+/// ```
+pub fn convert_mapped_file<const N: usize, const M: usize, F>(
+    input: &Path,
+    output: &Path,
+    threads: usize,
+    f: F,
+) -> Result<usize, MmapConvertError>
+where
+    F: Fn([f64; N]) -> [f64; M] + Sync,
+{
+    let (in_record, out_record) = (N * F64_SIZE, M * F64_SIZE);
+
+    // 切り詰めると入力も失われるため、同じファイルへの出力は拒否する
+    if output.exists() && fs::canonicalize(output)? == fs::canonicalize(input)? {
+        return Err(MmapConvertError::SameFile);
+    }
+
+    let input = File::open(input)?;
+    let len = input.metadata()?.len();
+    if in_record == 0 || len % in_record as u64 != 0 {
+        return Err(MmapConvertError::InvalidLength { len, record_size: in_record });
+    }
+    let count = (len / in_record as u64) as usize;
+
+    let output = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(output)?;
+    output.set_len((count * out_record) as u64)?;
+    if count == 0 || out_record == 0 {
+        return Ok(count);
+    }
+
+    // 長さ0のファイルはマップできないため、レコードがある場合のみマップする
+    // SAFETY: 入力ファイルは変換中に変更されないことを呼び出し側が保証する
+    let source = unsafe { Mmap::map(&input)? };
+    // SAFETY: 出力ファイルはこの関数で作成し、他から参照されない
+    let mut target = unsafe { MmapMut::map_mut(&output)? };
+
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let chunk = count.div_ceil(threads);
+
+    thread::scope(|scope| {
+        for (source, target) in source.chunks(chunk * in_record).zip(target.chunks_mut(chunk * out_record)) {
+            let f = &f;
+            scope.spawn(move || {
+                for (record, out) in source.chunks_exact(in_record).zip(target.chunks_exact_mut(out_record)) {
+                    let values = std::array::from_fn(|i| {
+                        f64::from_le_bytes(record[i * F64_SIZE..(i + 1) * F64_SIZE].try_into().unwrap())
+                    });
+                    for (bytes, value) in out.chunks_exact_mut(F64_SIZE).zip(f(values)) {
+                        bytes.copy_from_slice(&value.to_le_bytes());
+                    }
+                }
+            });
+        }
+    });
+
+    target.flush()?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::xyz_ll::llz2xyz;
+
+    use super::*;
+
+    fn to_bytes(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn convert_mapped_file_works() {
+        let dir = std::env::temp_dir();
+        let (input, output) = (dir.join("mmap_test_llz.bin"), dir.join("mmap_test_xyz.bin"));
+
+        let points = (0..1000)
+            .map(|i| [(130. + i as f64 * 0.01).to_radians(), 35_f64.to_radians(), i as f64])
+            .collect::<Vec<_>>();
+        fs::write(&input, to_bytes(&points.concat())).unwrap();
+
+        let convert = |[long, lat, height]: [f64; 3]| {
+            let (x, y, z) = llz2xyz((long, lat), height);
+            [x, y, z]
+        };
+        assert_eq!(convert_mapped_file(&input, &output, 7, convert).unwrap(), 1000);
+
+        let expected = points.iter().flat_map(|&p| convert(p)).collect::<Vec<_>>();
+        assert_eq!(fs::read(&output).unwrap(), to_bytes(&expected));
+
+        fs::remove_file(input).unwrap();
+        fs::remove_file(output).unwrap();
+    }
+
+    #[test]
+    fn invalid_length_is_rejected() {
+        let dir = std::env::temp_dir();
+        let (input, output) = (dir.join("mmap_test_invalid.bin"), dir.join("mmap_test_invalid_out.bin"));
+        fs::write(&input, [0; 12]).unwrap();
+
+        let result = convert_mapped_file(&input, &output, 1, |ll: [f64; 2]| ll);
+        assert!(matches!(result, Err(MmapConvertError::InvalidLength { len: 12, record_size: 16 })));
+
+        fs::remove_file(input).unwrap();
+    }
+
+    #[test]
+    fn same_file_is_rejected() {
+        let dir = std::env::temp_dir();
+        let input = dir.join("mmap_test_same.bin");
+        fs::write(&input, to_bytes(&[1., 2.])).unwrap();
+
+        // 別の表記の同じパスも同じファイルとみなす
+        let output = dir.join(".").join("mmap_test_same.bin");
+        let result = convert_mapped_file(&input, &output, 1, |ll: [f64; 2]| ll);
+        assert!(matches!(result, Err(MmapConvertError::SameFile)));
+        assert_eq!(fs::read(&input).unwrap(), to_bytes(&[1., 2.]));
+
+        fs::remove_file(input).unwrap();
+    }
+}
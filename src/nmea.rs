@@ -0,0 +1,206 @@
+//! Parsing of the positions in GGA and RMC sentences of NMEA 0183, the output of GNSS receivers.
+//! Sentences come from receiver streams, so a malformed sentence always results in `Err`, never in a panic.
+//!
+//! GNSS受信機の出力であるNMEA 0183のGGA及びRMCセンテンスの位置の解析。
+//! センテンスは受信機のストリームから得られるため、不正なセンテンスはパニックではなく必ず`Err`となる。
+
+use std::fmt::{Display, Formatter};
+
+use crate::{LL, LLZ};
+
+/// Error that occurs while parsing an NMEA sentence
+///
+/// NMEAセンテンスの解析中に発生するエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NmeaError {
+    /// The sentence is not of the expected type
+    ///
+    /// センテンスが期待した種類ではない
+    UnsupportedSentence,
+    /// The checksum does not match
+    ///
+    /// チェックサムが一致しない
+    InvalidChecksum,
+    /// The field at the given index (0 is the sentence type) could not be parsed
+    ///
+    /// 指定された位置(0はセンテンスの種類)のフィールドを解析できなかった
+    InvalidField(usize),
+    /// The receiver reports no valid fix
+    ///
+    /// 受信機が有効な測位解を報告していない
+    NoFix,
+}
+
+impl Display for NmeaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NmeaError::UnsupportedSentence => write!(f, "unsupported NMEA sentence"),
+            NmeaError::InvalidChecksum => write!(f, "NMEA checksum mismatch"),
+            NmeaError::InvalidField(i) => write!(f, "invalid NMEA field {}", i),
+            NmeaError::NoFix => write!(f, "no valid fix"),
+        }
+    }
+}
+
+impl std::error::Error for NmeaError {}
+
+/// Splits a sentence into fields after verifying the checksum (when present) and the sentence type.
+///
+/// チェックサム(存在する場合)とセンテンスの種類を確認した上で、センテンスをフィールドに分割する。
+fn fields<'a>(sentence: &'a str, kind: &str) -> Result<Vec<&'a str>, NmeaError> {
+    let sentence = sentence.trim();
+    let body = sentence
+        .strip_prefix('$')
+        .or_else(|| sentence.strip_prefix('!'))
+        .ok_or(NmeaError::UnsupportedSentence)?;
+
+    let body = match body.split_once('*') {
+        Some((body, checksum)) => {
+            let expected = u8::from_str_radix(checksum, 16).map_err(|_| NmeaError::InvalidChecksum)?;
+            if body.bytes().fold(0, |acc, b| acc ^ b) != expected {
+                return Err(NmeaError::InvalidChecksum);
+            }
+            body
+        }
+        None => body,
+    };
+
+    let fields = body.split(',').collect::<Vec<_>>();
+
+    // 先頭2文字はトーカーID(GP, GN, QZなど)
+    // 文字の境界でない位置では切り出せないため、getを用いる
+    if fields[0].len() != 5 || fields[0].get(2..) != Some(kind) {
+        return Err(NmeaError::UnsupportedSentence);
+    }
+
+    Ok(fields)
+}
+
+/// Converts a (d)ddmm.mmmm field and hemisphere letter to an angle expressed in the arc degree method.
+///
+/// (d)ddmm.mmmm形式のフィールドと半球を表す文字を弧度法の角度に変換する。
+fn angle(fields: &[&str], i: usize, negative: char) -> Result<f64, NmeaError> {
+    let value = fields.get(i).ok_or(NmeaError::InvalidField(i))?;
+    let hemisphere = fields.get(i + 1).ok_or(NmeaError::InvalidField(i + 1))?;
+
+    let dot = value.find('.').unwrap_or(value.len());
+    if dot < 2 {
+        return Err(NmeaError::InvalidField(i));
+    }
+
+    // ASCII以外の文字を含むフィールドは文字の境界でない位置で切り出せないため、getを用いる
+    let parse = |part: Option<&str>| part.and_then(|part| part.parse::<f64>().ok()).ok_or(NmeaError::InvalidField(i));
+    let degrees = parse(value.get(..dot - 2))?;
+    let minutes = parse(value.get(dot - 2..))?;
+    let angle = (degrees + minutes / 60.).to_radians();
+
+    match hemisphere.chars().next() {
+        Some(c) if c == negative => Ok(-angle),
+        Some('N') | Some('E') => Ok(angle),
+        _ => Err(NmeaError::InvalidField(i + 1)),
+    }
+}
+
+/// Parses a GGA sentence into latitude and longitude with ellipsoidal height (m).
+/// The ellipsoidal height is the sum of the altitude above mean sea level and the geoid separation.
+///
+/// GGAセンテンスを楕円体高(m)付きの緯度経度に変換する。
+/// 楕円体高は平均海面からの高さとジオイド高の和とする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::nmea::parse_gga;
+///
+/// let llz = parse_gga("$GPGGA,085120.307,3541.1493,N,13945.3994,E,1,08,1.0,6.9,M,35.9,M,,0000*5E").unwrap();
+/// ```
+pub fn parse_gga(sentence: &str) -> Result<LLZ, NmeaError> {
+    let fields = fields(sentence, "GGA")?;
+
+    if fields.get(6).is_none_or(|q| q.is_empty() || *q == "0") {
+        return Err(NmeaError::NoFix);
+    }
+
+    let lat = angle(&fields, 2, 'S')?;
+    let long = angle(&fields, 4, 'W')?;
+
+    let altitude = fields.get(9).and_then(|v| v.parse::<f64>().ok()).ok_or(NmeaError::InvalidField(9))?;
+    let separation = match fields.get(11) {
+        Some(v) if !v.is_empty() => v.parse::<f64>().map_err(|_| NmeaError::InvalidField(11))?,
+        _ => 0.,
+    };
+
+    Ok(LLZ::new(long, lat, altitude + separation))
+}
+
+/// Parses an RMC sentence into latitude and longitude.
+///
+/// RMCセンテンスを緯度経度に変換する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::nmea::parse_rmc;
+///
+/// let ll = parse_rmc("$GPRMC,085120.307,A,3541.1493,N,13945.3994,E,000.0,240.3,181211,,,A*6A").unwrap();
+/// ```
+pub fn parse_rmc(sentence: &str) -> Result<LL, NmeaError> {
+    let fields = fields(sentence, "RMC")?;
+
+    if fields.get(2) != Some(&"A") {
+        return Err(NmeaError::NoFix);
+    }
+
+    let lat = angle(&fields, 3, 'S')?;
+    let long = angle(&fields, 5, 'W')?;
+
+    Ok(LL::new(long, lat))
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn parse_gga_works() {
+        let llz = parse_gga("$GPGGA,085120.307,3541.1493,N,13945.3994,E,1,08,1.0,6.9,M,35.9,M,,0000*5E").unwrap();
+        let ((long, lat), altitude) = llz.to_tuple();
+
+        assert_close_to(long.to_degrees(), 139. + 45.3994 / 60., 9);
+        assert_close_to(lat.to_degrees(), 35. + 41.1493 / 60., 9);
+        assert_close_to(altitude, 42.8, 9);
+    }
+
+    #[test]
+    fn parse_gga_rejects_invalid_sentences() {
+        assert_eq!(
+            parse_gga("$GPGGA,085120.307,3541.1493,N,13945.3994,E,1,08,1.0,6.9,M,35.9,M,,0000*00"),
+            Err(NmeaError::InvalidChecksum)
+        );
+        assert_eq!(
+            parse_gga("$GPGGA,085120.307,,,,,0,00,,,M,,M,,"),
+            Err(NmeaError::NoFix)
+        );
+        assert_eq!(
+            parse_gga("$GPRMC,085120.307,A,3541.1493,N,13945.3994,E,000.0,240.3,181211,,,A"),
+            Err(NmeaError::UnsupportedSentence)
+        );
+    }
+
+    #[test]
+    fn non_ascii_fields_are_rejected() {
+        assert_eq!(parse_gga("$GéGA,1"), Err(NmeaError::UnsupportedSentence));
+        assert_eq!(parse_rmc("$GPRMC,0,A,é1.1,N,1,E"), Err(NmeaError::InvalidField(3)));
+        assert_eq!(parse_rmc("$GPRMC,0,A,3541.1493,N,1é.1,E"), Err(NmeaError::InvalidField(5)));
+    }
+
+    #[test]
+    fn parse_rmc_works() {
+        let (long, lat) = parse_rmc("$GNRMC,085120.307,A,3541.1493,S,13945.3994,W,000.0,240.3,181211,,,A").unwrap().to_tuple();
+
+        assert_close_to(long.to_degrees(), -(139. + 45.3994 / 60.), 9);
+        assert_close_to(lat.to_degrees(), -(35. + 41.1493 / 60.), 9);
+    }
+}
@@ -4,6 +4,8 @@ use std::str::FromStr;
 use num::cast::AsPrimitive;
 use num::Integer;
 
+use crate::math;
+
 /// Enumerated type representing the Zoom level.
 ///
 /// Zoomレベルを表す列挙型。
@@ -61,6 +63,7 @@ impl ZoomLv {
     /// let zoom_lv = ZoomLv::parse(25);
     /// assert!(zoom_lv.is_ok());
     /// ```
+    #[allow(clippy::result_unit_err)]
     pub fn parse<T: Integer + AsPrimitive<u8>>(num: T) -> Result<Self, ()> {
         match num.as_() {
             0 => Ok(Self::Lv0),
@@ -91,6 +94,52 @@ impl ZoomLv {
             _ => Err(()),
         }
     }
+
+    /// Returns the width (= height) of the world in pixels at the Zoom level, the same as `pixel_extent`
+    ///
+    /// Zoomレベルにおける世界の幅(=高さ)をピクセル数で返す。`pixel_extent`と同じ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    ///
+    /// assert_eq!(ZoomLv::Lv1.world_size_px(), 512);
+    /// ```
+    pub fn world_size_px(&self) -> u64 {
+        self.pixel_extent()
+    }
+
+    /// Returns the number of tiles along one axis of the world at the Zoom level, 2^z
+    ///
+    /// Zoomレベルにおける世界の一辺のタイル数2^zを返す
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    ///
+    /// assert_eq!(ZoomLv::Lv0.tile_count(), 1);
+    /// assert_eq!(ZoomLv::Lv24.tile_count(), 1 << 24);
+    /// ```
+    pub fn tile_count(&self) -> u32 {
+        1 << *self as u32
+    }
+
+    /// Returns the number of pixels along one axis of the world at the Zoom level, 2^(z+8), the same as `world_size_px`
+    ///
+    /// Zoomレベルにおける世界の一辺のピクセル数2^(z+8)を返す。`world_size_px`と同じ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    ///
+    /// assert_eq!(ZoomLv::Lv24.pixel_extent(), 1 << 32);
+    /// ```
+    pub fn pixel_extent(&self) -> u64 {
+        (self.tile_count() as u64) << 8
+    }
 }
 
 impl TryFrom<u8> for ZoomLv
@@ -194,6 +243,44 @@ impl FromStr for ZoomLv {
     }
 }
 
+pub use crate::consts::{MAX_MERCATOR_LAT, MAX_MERCATOR_LAT_DEG};
+
+/// Function to return the width (= height) of the world in pixels at the Zoom level.
+///
+/// Zoomレベルにおける世界の幅(=高さ)をピクセル数で返す関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{world_size_px, ZoomLv};
+///
+/// assert_eq!(world_size_px(ZoomLv::Lv0), 256);
+/// assert_eq!(world_size_px(ZoomLv::Lv24), 1 << 32);
+/// ```
+pub fn world_size_px(zoom: ZoomLv) -> u64 {
+    zoom.pixel_extent()
+}
+
+/// Function to clamp (longitude, latitude) expressed in the arc degree method to the range covered by Web Mercator.
+/// The longitude is clamped to -π to π and the latitude to ±`MAX_MERCATOR_LAT`.
+///
+/// 弧度法で表された(経度, 緯度)をWebメルカトルが覆う範囲に収める関数。
+/// 経度は-πからπ、緯度は±`MAX_MERCATOR_LAT`に収める。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{clamp_to_mercator, MAX_MERCATOR_LAT};
+///
+/// let (long, lat) = clamp_to_mercator((0., 90_f64.to_radians()));
+/// assert_eq!(lat, MAX_MERCATOR_LAT);
+/// ```
+pub fn clamp_to_mercator(ll: (f64, f64)) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    (long.clamp(-PI, PI), lat.clamp(-MAX_MERCATOR_LAT, MAX_MERCATOR_LAT))
+}
+
 /// Function to convert longitude and latitude to pixel coordinates.
 /// Converts (longitude, latitude) given by the arc degree method to pixel coordinates (x, y) according to Zoom level.
 ///
@@ -215,14 +302,160 @@ impl FromStr for ZoomLv {
 /// );
 /// ```
 pub fn ll2pixel(ll: (f64, f64), zoom: ZoomLv) -> (u32, u32) {
+    ll2pixel_with_rounding(ll, zoom, Rounding::Truncate)
+}
+
+/// Same as `ll2pixel` but returns fractional pixel coordinates without rounding.
+///
+/// `ll2pixel`と同じだが、丸めを行わずに小数のピクセル座標を返す。
+pub(crate) fn ll2pixel_f64(ll: (f64, f64), zoom: ZoomLv) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    let x = (2_f64.powi(zoom as i32 + 7)) * (long / PI + 1.);
+    let y = (2_f64.powi(zoom as i32 + 7) / PI)
+        * (-math::atanh(math::sin(lat)) + math::atanh(math::sin(MAX_MERCATOR_LAT)));
+
+    (x, y)
+}
+
+/// Enumerated type representing how fractional pixel coordinates are rounded to integers.
+///
+/// 小数のピクセル座標を整数に丸める方法を表す列挙型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Rounding {
+    /// Rounds toward negative infinity. Matches most tiling libraries.
+    ///
+    /// 負の無限大方向に丸める。多くのタイルライブラリと一致する。
+    Floor,
+    /// Rounds to the nearest integer, with halves away from zero.
+    ///
+    /// 最も近い整数に丸める。0.5は0から遠い方に丸める。
+    Round,
+    /// Rounds toward positive infinity.
+    ///
+    /// 正の無限大方向に丸める。
+    Ceil,
+    /// Rounds toward zero. This is the behavior of `ll2pixel`.
+    ///
+    /// 0方向に丸める。`ll2pixel`の挙動である。
+    #[default]
+    Truncate,
+}
+
+impl Rounding {
+    /// Rounds a value according to the rounding mode.
+    ///
+    /// 丸め方法に従って値を丸める。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::Rounding;
+    ///
+    /// assert_eq!(Rounding::Round.apply(1.5), 2.);
+    /// assert_eq!(Rounding::Truncate.apply(-1.5), -1.);
+    /// ```
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            Rounding::Floor => value.floor(),
+            Rounding::Round => value.round(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Truncate => value.trunc(),
+        }
+    }
+}
+
+/// Same as `ll2pixel` but rounds the pixel coordinates with the given rounding mode.
+/// Values outside the range of `u32` saturate at 0 or `u32::MAX`.
+///
+/// `ll2pixel`と同じだが、指定した丸め方法でピクセル座標を丸める。
+/// `u32`の範囲外の値は0または`u32::MAX`に飽和する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{ll2pixel_with_rounding, Rounding, ZoomLv};
+///
+/// let (x, y) = ll2pixel_with_rounding(
+/// (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()),
+/// ZoomLv::Lv21,
+/// Rounding::Floor,
+/// );
+/// ```
+pub fn ll2pixel_with_rounding(ll: (f64, f64), zoom: ZoomLv, rounding: Rounding) -> (u32, u32) {
+    let (x, y) = ll2pixel_f64(ll, zoom);
+
+    (rounding.apply(x) as u32, rounding.apply(y) as u32)
+}
+
+/// Enumerated type representing errors when converting longitude and latitude to pixel coordinates.
+///
+/// 緯経度をピクセル座標に変換する際のエラーを表す列挙型。
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum PixelError {
+    /// The latitude (arc degree method) exceeds the latitude limit of Web Mercator (±85.05112878°).
+    ///
+    /// 緯度(弧度法)がWebメルカトルの緯度の限界(±85.05112878°)を超えている。
+    LatitudeOutOfRange(f64),
+    /// The longitude (arc degree method) is outside the range -π to π.
+    ///
+    /// 経度(弧度法)が-πからπの範囲外である。
+    LongitudeOutOfRange(f64),
+}
+
+impl std::fmt::Display for PixelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelError::LatitudeOutOfRange(lat) => write!(f, "latitude {} rad is beyond the Web Mercator limit", lat),
+            PixelError::LongitudeOutOfRange(long) => write!(f, "longitude {} rad is outside -π to π", long),
+        }
+    }
+}
+
+impl std::error::Error for PixelError {}
+
+/// Checks that (longitude, latitude) expressed in the arc degree method is in the domain of `try_ll2pixel`
+///
+/// 弧度法で表された(経度, 緯度)が`try_ll2pixel`の定義域内にあることを検査する
+pub(crate) fn check_pixel_domain(ll: (f64, f64)) -> Result<(), PixelError> {
     let (long, lat) = ll;
-    const L: f64 = 85.05112878;
 
-    let x = (2_f64.powf(zoom as i32 as f64 + 7.)) * (long / PI + 1.);
-    let y = (2_f64.powf(zoom as i32 as f64 + 7.) / PI)
-        * (-(lat.sin().atanh()) + (L * PI / 180.).sin().atanh());
+    if !(-PI..=PI).contains(&long) {
+        return Err(PixelError::LongitudeOutOfRange(long));
+    }
+    if !(-MAX_MERCATOR_LAT..=MAX_MERCATOR_LAT).contains(&lat) {
+        return Err(PixelError::LatitudeOutOfRange(lat));
+    }
+
+    Ok(())
+}
+
+/// Checked version of `ll2pixel`.
+/// Returns `Err` if the latitude exceeds the latitude limit of Web Mercator or the longitude is outside -π to π (including NaN).
+/// Coordinates exactly on the limit are valid; those on the southern or eastern edge of the world are assigned to the last pixel.
+///
+/// `ll2pixel`の検査付き版。
+/// 緯度がWebメルカトルの緯度の限界を超える場合や、経度が-πからπの範囲外の場合(NaNを含む)は`Err`を返す。
+/// ちょうど限界上の座標は有効であり、世界の南端または東端にある座標は最後のピクセルとする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{try_ll2pixel, PixelError, ZoomLv};
+///
+/// assert!(try_ll2pixel((0., 85_f64.to_radians()), ZoomLv::Lv10).is_ok());
+/// assert!(matches!(
+/// try_ll2pixel((0., 86_f64.to_radians()), ZoomLv::Lv10),
+/// Err(PixelError::LatitudeOutOfRange(_))
+/// ));
+/// ```
+pub fn try_ll2pixel(ll: (f64, f64), zoom: ZoomLv) -> Result<(u32, u32), PixelError> {
+    check_pixel_domain(ll)?;
+
+    let max = (world_size_px(zoom) - 1) as f64;
+    let (x, y) = ll2pixel_f64(ll, zoom);
 
-    (x as u32, y as u32)
+    Ok((x.clamp(0., max) as u32, y.clamp(0., max) as u32))
 }
 
 /// Function to convert pixel coordinates to longitude and latitude.
@@ -244,17 +477,30 @@ pub fn ll2pixel(ll: (f64, f64), zoom: ZoomLv) -> (u32, u32) {
 /// ```
 pub fn pixel2ll(pixel: (u32, u32), zoom: ZoomLv) -> (f64, f64) {
     let (x, y) = pixel;
-    const L: f64 = 85.05112878;
+    pixel2ll_f64((x as f64, y as f64), zoom)
+}
+
+/// Same as `pixel2ll` but accepts fractional pixel coordinates, e.g. pixel centers or the edge of the world.
+///
+/// `pixel2ll`と同じだが、ピクセルの中心や世界の端などの小数のピクセル座標を受け付ける。
+pub(crate) fn pixel2ll_f64(pixel: (f64, f64), zoom: ZoomLv) -> (f64, f64) {
+    let (x, y) = pixel;
 
-    let long = PI * (x as f64 / 2_f64.powf(zoom as i32 as f64 + 7.) - 1.);
-    let lat = ((-PI * y as f64 / (2_f64.powf(zoom as i32 as f64 + 7.))
-        + (PI * L / 180.).sin().atanh())
-        .tanh())
-        .asin();
+    let long = PI * (x / 2_f64.powi(zoom as i32 + 7) - 1.);
+    let lat = math::asin(math::tanh(
+        -PI * y / (2_f64.powi(zoom as i32 + 7)) + math::atanh(math::sin(MAX_MERCATOR_LAT)),
+    ));
 
     (long, lat)
 }
 
+/// Length per pixel (m) at the equator at Zoom level 0 used by `pixel_resolution` and `pixel_distance_m`.
+/// It is the approximation of `ZOOM0_RESOLUTION` (156543.0339...) that these functions have always used, kept so that their results do not change.
+///
+/// `pixel_resolution`と`pixel_distance_m`で用いる、Zoomレベル0の赤道上における1ピクセルあたりの長さ(m)。
+/// これらの関数が従来用いてきた`ZOOM0_RESOLUTION`(156543.0339...)の近似値であり、結果が変わらないよう維持している。
+pub(crate) const PIXEL_RESOLUTION_ZOOM0: f64 = 156543.04;
+
 /// Function to return the length per pixel (m) in pixel coordinates according to the latitude and Zoom level of the arc degree method.
 ///
 /// 弧度法の緯度とZoomレベルに応じたピクセル座標における1ピクセルあたりの長さ(m)を返す関数。
@@ -270,7 +516,76 @@ pub fn pixel2ll(pixel: (u32, u32), zoom: ZoomLv) -> (f64, f64) {
 /// let resolution = pixel_resolution(0_f64.to_radians(), ZoomLv::Lv17);
 ///```
 pub fn pixel_resolution(lat: f64, zoom: ZoomLv) -> f64 {
-    156543.04 * lat.cos() / 2_f64.powf(zoom as i32 as f64)
+    PIXEL_RESOLUTION_ZOOM0 * math::cos(lat) / zoom.tile_count() as f64
+}
+
+/// Function to return a scale bar for the latitude expressed in the arc degree method and the Zoom level, as a pair of a round distance (m) and its length in pixels.
+/// The distance is the largest of 1, 2 and 5 times a power of 10 whose length does not exceed `max_px` pixels.
+///
+/// 弧度法で表された緯度とZoomレベルに対する縮尺バーを、切りの良い距離(m)とそのピクセル数の組で返す関数。
+/// 距離は、長さが`max_px`ピクセルを超えない、10の累乗の1倍、2倍、5倍のうち最大のものとなる。
+///
+/// # Examples
+///
+/// Calculate a scale bar of at most 150 pixels in Tokyo.
+///
+/// 東京における最大150ピクセルの縮尺バーを計算する。
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{scale_bar, ZoomLv};
+///
+/// let (distance_m, length_px) = scale_bar(35.6812405_f64.to_radians(), ZoomLv::Lv16, 150);
+///
+/// assert_eq!((distance_m, length_px), (200., 103));
+/// ```
+pub fn scale_bar(lat: f64, zoom: ZoomLv, max_px: u32) -> (f64, u32) {
+    let resolution = pixel_resolution(lat, zoom);
+    let max_m = max_px as f64 * resolution;
+
+    let magnitude = math::pow(10., math::log10(max_m).floor());
+    let distance_m = [5., 2., 1.]
+        .into_iter()
+        .map(|k| k * magnitude)
+        .find(|&d| d <= max_m)
+        .unwrap_or(magnitude);
+
+    (distance_m, (distance_m / resolution).round() as u32)
+}
+
+/// Function to convert the Euclidean distance between two pixel coordinates into a length (m).
+/// The latitude-dependent resolution is integrated along the line, so the result is correct also for long north-south spans.
+///
+/// 2つのピクセル座標間のユークリッド距離を長さ(m)に変換する関数。
+/// 緯度に依存する分解能を線分に沿って積分するため、南北に長い区間でも正しい結果となる。
+///
+/// # Examples
+///
+/// Calculate the length (m) between two pixel coordinates.
+///
+/// 2つのピクセル座標間の長さ(m)を計算する。
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{pixel_distance_m, ZoomLv};
+///
+/// let distance = pixel_distance_m((476868027, 211407949), (476868127, 211408049), ZoomLv::Lv21);
+/// ```
+pub fn pixel_distance_m(p1: (u32, u32), p2: (u32, u32), zoom: ZoomLv) -> f64 {
+    let world = 2_f64.powi(zoom as i32 + 7);
+    let psi = |y: u32| -PI * y as f64 / world + math::atanh(math::sin(MAX_MERCATOR_LAT));
+
+    let (psi1, psi2) = (psi(p1.1), psi(p2.1));
+
+    // 等長緯度に対してcos(緯度)を積分した平均値
+    let mean_cos = if psi1 == psi2 {
+        1. / math::cosh(psi1)
+    } else {
+        (math::asin(math::tanh(psi2)) - math::asin(math::tanh(psi1))) / (psi2 - psi1)
+    };
+
+    let dx = p2.0 as f64 - p1.0 as f64;
+    let dy = p2.1 as f64 - p1.1 as f64;
+
+    (dx.powi(2) + dy.powi(2)).sqrt() * PIXEL_RESOLUTION_ZOOM0 / zoom.tile_count() as f64 * mean_cos
 }
 
 /// Function to convert pixel coordinates to tile coordinates.
@@ -293,12 +608,140 @@ pub fn pixel2tile(pixel: (u32, u32)) -> (u32, u32) {
     (x / 256, y / 256)
 }
 
+/// Function to rescale pixel coordinates from one Zoom level to another.
+/// When zooming out, the coordinates are floored, i.e. the pixel containing the original pixel is returned.
+/// When zooming in, the upper left pixel of the block of pixels covering the original pixel is returned.
+///
+/// ピクセル座標をあるZoomレベルから別のZoomレベルに変換する関数。
+/// Zoomアウトでは切り捨てとなり、元のピクセルを含むピクセルを返す。
+/// Zoomインでは、元のピクセルを覆うピクセルのブロックのうち左上のピクセルを返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{pixel_at_zoom, ZoomLv};
+///
+/// assert_eq!(pixel_at_zoom((5, 7), ZoomLv::Lv10, ZoomLv::Lv9), (2, 3));
+/// assert_eq!(pixel_at_zoom((2, 3), ZoomLv::Lv9, ZoomLv::Lv10), (4, 6));
+/// ```
+pub fn pixel_at_zoom(pixel: (u32, u32), from_zoom: ZoomLv, to_zoom: ZoomLv) -> (u32, u32) {
+    let (x, y) = pixel;
+
+    if to_zoom >= from_zoom {
+        let shift = to_zoom as u32 - from_zoom as u32;
+        (x << shift, y << shift)
+    } else {
+        let shift = from_zoom as u32 - to_zoom as u32;
+        (x >> shift, y >> shift)
+    }
+}
+
+/// Function to rescale tile coordinates from one Zoom level to another.
+/// The rounding follows `pixel_at_zoom`.
+///
+/// タイル座標をあるZoomレベルから別のZoomレベルに変換する関数。
+/// 丸めは`pixel_at_zoom`に従う。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{tile_at_zoom, ZoomLv};
+///
+/// assert_eq!(tile_at_zoom((14552, 6451), ZoomLv::Lv14, ZoomLv::Lv12), (3638, 1612));
+/// ```
+pub fn tile_at_zoom(tile: (u32, u32), from_zoom: ZoomLv, to_zoom: ZoomLv) -> (u32, u32) {
+    pixel_at_zoom(tile, from_zoom, to_zoom)
+}
+
+/// Returns the pixel containing (longitude, latitude) expressed in the arc degree method, clamped to the world
+///
+/// 弧度法で表された(経度, 緯度)を含むピクセルを、世界の範囲内に収めて返す
+fn containing_pixel(ll: (f64, f64), zoom: ZoomLv) -> (f64, f64) {
+    let max = (world_size_px(zoom) - 1) as f64;
+    let (x, y) = ll2pixel_f64(ll, zoom);
+
+    (x.floor().clamp(0., max), y.floor().clamp(0., max))
+}
+
+/// Function to snap (longitude, latitude) expressed in the arc degree method to the center of the pixel containing it at the Zoom level.
+/// The result is expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)を、Zoomレベルにおいてそれを含むピクセルの中心に揃える関数。
+/// 結果は弧度法で表される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{ll2pixel, snap_to_pixel, ZoomLv};
+///
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let snapped = snap_to_pixel(ll, ZoomLv::Lv21);
+///
+/// assert_eq!(ll2pixel(snapped, ZoomLv::Lv21), ll2pixel(ll, ZoomLv::Lv21));
+/// ```
+pub fn snap_to_pixel(ll: (f64, f64), zoom: ZoomLv) -> (f64, f64) {
+    let (x, y) = containing_pixel(ll, zoom);
+    pixel2ll_f64((x + 0.5, y + 0.5), zoom)
+}
+
+/// Function to snap (longitude, latitude) expressed in the arc degree method to the upper left corner of the tile containing it at the Zoom level.
+/// The result is expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)を、Zoomレベルにおいてそれを含むタイルの左上の角に揃える関数。
+/// 結果は弧度法で表される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{snap_to_tile_origin, ZoomLv};
+///
+/// let (long, lat) = snap_to_tile_origin((139_f64.to_radians(), 35_f64.to_radians()), ZoomLv::Lv0);
+///
+/// assert_eq!(long, -std::f64::consts::PI);
+/// ```
+pub fn snap_to_tile_origin(ll: (f64, f64), zoom: ZoomLv) -> (f64, f64) {
+    let (x, y) = containing_pixel(ll, zoom);
+    pixel2ll_f64(((x / 256.).floor() * 256., (y / 256.).floor() * 256.), zoom)
+}
+
 #[cfg(test)]
 mod tests {
     use close_to::assert_close_to;
 
     use super::*;
 
+    #[test]
+    fn scale_bar_works() {
+        for zoom in [ZoomLv::Lv0, ZoomLv::Lv10, ZoomLv::Lv24] {
+            let (distance_m, length_px) = scale_bar(45_f64.to_radians(), zoom, 120);
+            let mantissa = distance_m / 10_f64.powf(distance_m.log10().floor());
+
+            assert!([1., 2., 5.].iter().any(|m| (m - mantissa).abs() < 1e-9));
+            assert!(length_px <= 120);
+            // 次の切りの良い距離は最大2.5倍なので、長さは最大値の4割以上となる
+            assert!(length_px >= 48);
+        }
+    }
+
+    #[test]
+    fn snap_works() {
+        let zoom = ZoomLv::Lv10;
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+
+        let (x, y) = ll2pixel_f64(snap_to_pixel(ll, zoom), zoom);
+        assert_close_to(x.fract(), 0.5, 6);
+        assert_close_to(y.fract(), 0.5, 6);
+
+        let (x, y) = ll2pixel_f64(snap_to_tile_origin(ll, zoom), zoom);
+        assert_eq!(((x / 256.).round(), (y / 256.).round()), (909., 403.));
+        assert_close_to(x, 909. * 256., 6);
+        assert_close_to(y, 403. * 256., 6);
+
+        // 世界の東端の点は最後のピクセルに揃える
+        let (long, _) = snap_to_pixel((PI, 0.), zoom);
+        assert!(long < PI);
+    }
+
     #[test]
     fn ll2pixel_works() {
         let (x, y) = ll2pixel(
@@ -309,6 +752,57 @@ mod tests {
         assert_eq!((x, y), (476868027, 211407949));
     }
 
+    #[test]
+    fn ll2pixel_with_rounding_works() {
+        let zoom_lv = ZoomLv::Lv0;
+        // ピクセルの境界をまたぐ小数のピクセル座標に対応する緯経度
+        let ll = pixel2ll_f64((127.6, 128.4), zoom_lv);
+
+        assert_eq!(ll2pixel_with_rounding(ll, zoom_lv, Rounding::Floor), (127, 128));
+        assert_eq!(ll2pixel_with_rounding(ll, zoom_lv, Rounding::Round), (128, 128));
+        assert_eq!(ll2pixel_with_rounding(ll, zoom_lv, Rounding::Ceil), (128, 129));
+        assert_eq!(ll2pixel_with_rounding(ll, zoom_lv, Rounding::Truncate), ll2pixel(ll, zoom_lv));
+    }
+
+    #[test]
+    fn zoom_lv_extents_work() {
+        for zoom in (0..=24).map(|z| ZoomLv::parse(z).unwrap()) {
+            assert_eq!(zoom.tile_count() as f64, 2_f64.powi(zoom as i32));
+            assert_eq!(zoom.pixel_extent(), zoom.tile_count() as u64 * 256);
+            assert_eq!(zoom.world_size_px(), zoom.pixel_extent());
+        }
+    }
+
+    #[test]
+    fn try_ll2pixel_works() {
+        assert_eq!(try_ll2pixel((-PI, MAX_MERCATOR_LAT), ZoomLv::Lv0), Ok((0, 0)));
+        assert_eq!(try_ll2pixel((PI, -MAX_MERCATOR_LAT), ZoomLv::Lv0), Ok((255, 255)));
+        assert_eq!(
+            try_ll2pixel((PI, -MAX_MERCATOR_LAT), ZoomLv::Lv24),
+            Ok((u32::MAX, u32::MAX))
+        );
+        assert!(matches!(
+            try_ll2pixel((0., -86_f64.to_radians()), ZoomLv::Lv0),
+            Err(PixelError::LatitudeOutOfRange(_))
+        ));
+        assert!(matches!(
+            try_ll2pixel((f64::NAN, 0.), ZoomLv::Lv0),
+            Err(PixelError::LongitudeOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn mercator_limits_work() {
+        let (_, y) = ll2pixel_f64((0., MAX_MERCATOR_LAT), ZoomLv::Lv3);
+        assert_close_to(y, 0., 6);
+
+        let (_, y) = ll2pixel_f64((0., -MAX_MERCATOR_LAT), ZoomLv::Lv3);
+        assert_close_to(y, world_size_px(ZoomLv::Lv3) as f64, 6);
+
+        assert_eq!(clamp_to_mercator((4., -2.)), (PI, -MAX_MERCATOR_LAT));
+        assert_eq!(clamp_to_mercator((1., 0.5)), (1., 0.5));
+    }
+
     #[test]
     fn pixel2ll_works() {
         let (long, lat) = pixel2ll((476868027, 211407949), ZoomLv::Lv21);
@@ -331,4 +825,39 @@ mod tests {
             5,
         );
     }
+
+    #[test]
+    fn pixel_distance_m_works() {
+        let zoom_lv = ZoomLv::Lv10;
+        let (x, y) = ll2pixel((0., 35_f64.to_radians()), zoom_lv);
+
+        // 東西方向は緯度一定なので分解能をそのまま掛けたものと一致する
+        let (_, lat) = pixel2ll((x, y), zoom_lv);
+        assert_close_to(
+            pixel_distance_m((x, y), (x + 100, y), zoom_lv),
+            100. * pixel_resolution(lat, zoom_lv),
+            6,
+        );
+
+        // 南北方向は子午線弧長(球)と一致する
+        let (x1, y1) = ll2pixel((0., 60_f64.to_radians()), zoom_lv);
+        let (_, lat1) = pixel2ll((x1, y1), zoom_lv);
+        let radius = PIXEL_RESOLUTION_ZOOM0 * 256. / (2. * PI);
+        assert_close_to(
+            pixel_distance_m((x, y), (x, y1), zoom_lv),
+            radius * (lat1 - lat),
+            3,
+        );
+    }
+
+    #[test]
+    fn pixel_at_zoom_works() {
+        assert_eq!(pixel_at_zoom((255, 255), ZoomLv::Lv0, ZoomLv::Lv24), (255 << 24, 255 << 24));
+        assert_eq!(pixel_at_zoom((u32::MAX, u32::MAX), ZoomLv::Lv24, ZoomLv::Lv0), (255, 255));
+        assert_eq!(pixel_at_zoom((123, 456), ZoomLv::Lv5, ZoomLv::Lv5), (123, 456));
+
+        let (x, y) = ll2pixel((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), ZoomLv::Lv21);
+        let (x2, y2) = ll2pixel((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), ZoomLv::Lv18);
+        assert_eq!(pixel_at_zoom((x, y), ZoomLv::Lv21, ZoomLv::Lv18), (x2, y2));
+    }
 }
@@ -0,0 +1,53 @@
+use crate::{ll2pixel, llz2xyz, pixel2ll, xyz2llz, ZoomLv};
+
+/// Function to convert pixel coordinates (x, y) according to Zoom level and altitude to (x, y, z) in the Cartesian coordinate system (EPSG:4979).
+///
+/// Zoomレベルに応じたピクセル座標(x, y)と標高を直交座標系(EPSG:4979)の(x, y, z)に変換する関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::pixel_xyz::pixel2xyz;
+///
+/// let (x, y, z) = pixel2xyz((476868027, 211407949), ZoomLv::Lv21, 100.);
+/// ```
+pub fn pixel2xyz(pixel: (u32, u32), zoom: ZoomLv, altitude: f64) -> (f64, f64, f64) {
+    llz2xyz(pixel2ll(pixel, zoom), altitude)
+}
+
+/// Function to convert (x, y, z) in the Cartesian coordinate system (EPSG:4979) to pixel coordinates (x, y) according to Zoom level and altitude.
+///
+/// 直交座標系(EPSG:4979)の(x, y, z)をZoomレベルに応じたピクセル座標(x, y)と標高に変換する関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::pixel_xyz::xyz2pixel;
+///
+/// let ((x, y), altitude) = xyz2pixel((-3957446.631, 3320692.008, 3728250.454), ZoomLv::Lv21);
+/// ```
+pub fn xyz2pixel(xyz: (f64, f64, f64), zoom: ZoomLv) -> ((u32, u32), f64) {
+    let (ll, altitude) = xyz2llz(xyz);
+
+    (ll2pixel(ll, zoom), altitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn pixel2xyz_round_trip_works() {
+        let pixel = (476868027, 211407949);
+
+        let (result, altitude) = xyz2pixel(pixel2xyz(pixel, ZoomLv::Lv21, 100.), ZoomLv::Lv21);
+
+        // 左上の角は丸め誤差で隣のピクセルになりうる
+        assert!(result.0.abs_diff(pixel.0) <= 1 && result.1.abs_diff(pixel.1) <= 1);
+        assert_close_to(altitude, 100., 6);
+    }
+}
@@ -0,0 +1,227 @@
+//! Handling of the coordinates of Project PLATEAU CityGML models, which use the compound CRS EPSG:6697 (JGD2011 latitude and longitude + JGD2011 (vertical) height).
+//! Positions in `gml:pos` and `gml:posList` are written as latitude, longitude in degrees and the orthometric height (m) above T.P.
+//! Conversion to the Cartesian coordinate system needs the geoid height at the point, which is given by the caller as in `vertical_datum`.
+//!
+//! 複合座標参照系EPSG:6697(JGD2011の緯度経度 + JGD2011(鉛直)の高さ)を用いるProject PLATEAUのCityGMLモデルの座標の取り扱い。
+//! `gml:pos`及び`gml:posList`の位置は、度単位の緯度、経度と、T.P.からの標高(m)の順に書かれる。
+//! 直交座標系への変換にはその地点のジオイド高が必要であり、`vertical_datum`と同様に呼び出し側が与える。
+
+use std::fmt::{Display, Formatter};
+
+use crate::jpr_ll::{jpr2ll, ll2jpr, JprOrigin};
+use crate::structure::{JPR, LLZ, XYZ};
+use crate::vertical_datum::{llz2xyz_with_datum, xyz2llz_with_datum, VerticalDatum};
+
+/// Error that occurs while reading PLATEAU positions
+///
+/// PLATEAUの位置の読み込み中に発生するエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlateauError {
+    /// A value could not be parsed as a number
+    ///
+    /// 値を数値として解析できなかった
+    InvalidValue(String),
+    /// The number of values is not a multiple of 3
+    ///
+    /// 値の数が3の倍数ではない
+    IncompletePosition(usize),
+}
+
+impl Display for PlateauError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlateauError::InvalidValue(value) => write!(f, "invalid PLATEAU coordinate value: {}", value),
+            PlateauError::IncompletePosition(len) => {
+                write!(f, "number of PLATEAU coordinate values is not a multiple of 3: {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlateauError {}
+
+/// Structure representing a position of EPSG:6697, (longitude, latitude) expressed in the arc degree method and the orthometric height (m) above T.P.
+///
+/// EPSG:6697の位置を表す構造体。弧度法で表された(経度, 緯度)とT.P.からの標高(m)からなる
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PlateauPoint {
+    long: f64,
+    lat: f64,
+    height: f64,
+}
+
+impl PlateauPoint {
+    /// Create a new position from (longitude, latitude) expressed in the arc degree method and the orthometric height (m)
+    ///
+    /// 弧度法で表された(経度, 緯度)と標高(m)から位置を新しく作成する
+    pub fn new(long: f64, lat: f64, height: f64) -> Self {
+        Self { long, lat, height }
+    }
+
+    /// Create a new position from (latitude, longitude, height) in the axis order and units of EPSG:6697, i.e. degrees and m
+    ///
+    /// EPSG:6697の軸順と単位、すなわち度とmによる(緯度, 経度, 高さ)から位置を新しく作成する
+    pub fn from_pos(pos: (f64, f64, f64)) -> Self {
+        let (lat, long, height) = pos;
+        Self::new(long.to_radians(), lat.to_radians(), height)
+    }
+
+    /// Returns (latitude, longitude, height) in the axis order and units of EPSG:6697
+    ///
+    /// EPSG:6697の軸順と単位による(緯度, 経度, 高さ)を返す
+    pub fn to_pos(&self) -> (f64, f64, f64) {
+        (self.lat.to_degrees(), self.long.to_degrees(), self.height)
+    }
+
+    /// Returns a tuple of ((longitude, latitude), orthometric height)
+    ///
+    /// ((経度, 緯度), 標高)をタプルで返す
+    pub fn to_tuple(&self) -> ((f64, f64), f64) {
+        ((self.long, self.lat), self.height)
+    }
+
+    /// Returns the orthometric height (m) above T.P.
+    ///
+    /// T.P.からの標高(m)を返す
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Create a position from plane rectangular coordinates and the orthometric height (m)
+    ///
+    /// 平面直角座標と標高(m)から位置を作成する
+    pub fn from_jpr(yx: (f64, f64), origin: JprOrigin, height: f64) -> Self {
+        let (long, lat) = jpr2ll(yx, origin);
+        Self::new(long, lat, height)
+    }
+
+    /// Convert to plane rectangular coordinates and the orthometric height (m), which is unchanged because both use T.P.
+    ///
+    /// 平面直角座標と標高(m)に変換する。いずれもT.P.を用いるため標高は変わらない
+    pub fn to_jpr(&self, origin: JprOrigin) -> (JPR, f64) {
+        let (y, x) = ll2jpr((self.long, self.lat), origin);
+        (JPR::new(y, x, origin), self.height)
+    }
+
+    /// Convert to latitude, longitude and ellipsoidal height with the geoid height (m) at the point
+    ///
+    /// その地点のジオイド高(m)を用いて緯度経度と楕円体高に変換する
+    pub fn to_llz(&self, geoid_height: f64) -> LLZ {
+        LLZ::new(self.long, self.lat, self.height + geoid_height)
+    }
+
+    /// Convert to a structure representing Cartesian (EPSG:4979) coordinates with the geoid height (m) at the point
+    ///
+    /// その地点のジオイド高(m)を用いて直交座標系(EPSG:4979)座標を表す構造体に変換する
+    pub fn to_xyz(&self, geoid_height: f64) -> XYZ {
+        let (x, y, z) = llz2xyz_with_datum((self.long, self.lat), self.height, VerticalDatum::Tp, geoid_height);
+        XYZ::new(x, y, z)
+    }
+
+    /// Create a position from Cartesian (EPSG:4979) coordinates with the geoid height (m) at the point
+    ///
+    /// その地点のジオイド高(m)を用いて直交座標系(EPSG:4979)座標から位置を作成する
+    pub fn from_xyz(xyz: &XYZ, geoid_height: f64) -> Self {
+        let ((long, lat), height) = xyz2llz_with_datum(xyz.to_tuple(), VerticalDatum::Tp, geoid_height);
+        Self::new(long, lat, height)
+    }
+}
+
+/// Parses the content of a `gml:pos` or `gml:posList` element of PLATEAU (latitude, longitude in degrees and height, separated by whitespace)
+///
+/// PLATEAUの`gml:pos`または`gml:posList`要素の内容(空白で区切られた度単位の緯度、経度と高さ)を解析する
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::plateau::parse_plateau_pos_list;
+///
+/// let points = parse_plateau_pos_list("35.6812 139.7671 3.5 35.6813 139.7672 3.6").unwrap();
+///
+/// assert_eq!(points.len(), 2);
+/// assert_eq!(points[0].to_pos(), (35.6812, 139.7671, 3.5));
+/// ```
+pub fn parse_plateau_pos_list(text: &str) -> Result<Vec<PlateauPoint>, PlateauError> {
+    let values = text
+        .split_whitespace()
+        .map(|v| v.parse::<f64>().map_err(|_| PlateauError::InvalidValue(v.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if values.len() % 3 != 0 {
+        return Err(PlateauError::IncompletePosition(values.len()));
+    }
+
+    Ok(values
+        .chunks(3)
+        .map(|pos| PlateauPoint::from_pos((pos[0], pos[1], pos[2])))
+        .collect())
+}
+
+/// Writes positions as the content of a `gml:posList` element of PLATEAU
+///
+/// 位置をPLATEAUの`gml:posList`要素の内容として書き出す
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::plateau::{write_plateau_pos_list, PlateauPoint};
+///
+/// let points = [PlateauPoint::from_pos((35.6812, 139.7671, 3.5))];
+///
+/// assert_eq!(write_plateau_pos_list(&points), "35.6812 139.7671 3.5");
+/// ```
+pub fn write_plateau_pos_list(points: &[PlateauPoint]) -> String {
+    points
+        .iter()
+        .map(|point| {
+            let (lat, long, height) = point.to_pos();
+            format!("{} {} {}", lat, long, height)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::xyz_ll::llz2xyz;
+
+    use super::*;
+
+    #[test]
+    fn parse_plateau_pos_list_works() {
+        let points = parse_plateau_pos_list("35.6812 139.7671 3.5\n35.6813  139.7672 3.6").unwrap();
+        assert_eq!(write_plateau_pos_list(&points), "35.6812 139.7671 3.5 35.6813 139.7672 3.6");
+
+        assert_eq!(
+            parse_plateau_pos_list("35.6812 139.7671"),
+            Err(PlateauError::IncompletePosition(2))
+        );
+        assert_eq!(
+            parse_plateau_pos_list("35.6812 139.7671 x"),
+            Err(PlateauError::InvalidValue("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn plateau_point_converts_heights() {
+        let point = PlateauPoint::from_pos((35.6812, 139.7671, 3.5));
+        let geoid_height = 36.7;
+
+        // 直交座標系には楕円体高で変換される
+        let (x, y, z) = point.to_xyz(geoid_height).to_tuple();
+        let expected = llz2xyz(point.to_tuple().0, 3.5 + geoid_height);
+        assert_eq!((x, y, z), expected);
+
+        let back = PlateauPoint::from_xyz(&point.to_xyz(geoid_height), geoid_height);
+        assert_close_to(back.height(), 3.5, 6);
+
+        // 平面直角座標では標高はそのまま
+        let (jpr, height) = point.to_jpr(JprOrigin::Nine);
+        assert_eq!(height, 3.5);
+        let back = PlateauPoint::from_jpr(jpr.to_tuple(), JprOrigin::Nine, height);
+        assert_close_to(back.to_pos().0, 35.6812, 9);
+        assert_close_to(back.to_pos().1, 139.7671, 9);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::pixel_ll::{ll2pixel_f64, pixel2ll_f64};
+use crate::{jpr2ll, ll2jpr, pixel_resolution, JprOrigin, ZoomLv};
+
+/// Maximum depth of the recursive subdivision of one edge (up to 2^16 segments)
+///
+/// 1つの辺を再帰的に分割する深さの上限(最大2^16区間)
+const MAX_DEPTH: u32 = 16;
+
+/// Appends the transformed points strictly between `a` and `b`, subdividing the edge until the transformed midpoint deviates from the transformed chord by at most `max_deviation_m` (m)
+///
+/// 変換後の中点と弦の中点のずれが`max_deviation_m`(m)以下になるまで辺を分割し、`a`と`b`の間にある変換後の点を追加する
+fn subdivide<T, D>(
+    (a, ta): ((f64, f64), (f64, f64)),
+    (b, tb): ((f64, f64), (f64, f64)),
+    depth: u32,
+    transform: &T,
+    deviation_m: &D,
+    max_deviation_m: f64,
+    result: &mut Vec<(f64, f64)>,
+) where
+    T: Fn((f64, f64)) -> (f64, f64),
+    D: Fn((f64, f64), (f64, f64)) -> f64,
+{
+    let m = ((a.0 + b.0) / 2., (a.1 + b.1) / 2.);
+    let tm = transform(m);
+    let chord = ((ta.0 + tb.0) / 2., (ta.1 + tb.1) / 2.);
+
+    if depth < MAX_DEPTH && deviation_m(tm, chord) > max_deviation_m {
+        subdivide((a, ta), (m, tm), depth + 1, transform, deviation_m, max_deviation_m, result);
+        result.push(tm);
+        subdivide((m, tm), (b, tb), depth + 1, transform, deviation_m, max_deviation_m, result);
+    }
+}
+
+/// Transforms a ring (a closing edge from the last vertex to the first one is assumed) while densifying the edges adaptively.
+/// Each edge is straight in the source coordinates and is subdivided until the transformed midpoint deviates from the transformed chord by at most `max_deviation_m` (m).
+///
+/// 環(最後の頂点から最初の頂点への辺を含む)を、辺を適応的に高密度化しながら変換する。
+/// 各辺は変換元の座標で直線であり、変換後の中点と弦の中点のずれが`max_deviation_m`(m)以下になるまで分割する。
+fn transform_ring<T, D>(ring: &[(f64, f64)], transform: T, deviation_m: D, max_deviation_m: f64) -> Vec<(f64, f64)>
+where
+    T: Fn((f64, f64)) -> (f64, f64),
+    D: Fn((f64, f64), (f64, f64)) -> f64,
+{
+    let closed = ring.len() > 1 && ring.first() == ring.last();
+    let vertices = if closed { &ring[..ring.len() - 1] } else { ring };
+
+    let mut result = Vec::with_capacity(vertices.len());
+
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        let (ta, tb) = (transform(a), transform(b));
+
+        result.push(ta);
+        if vertices.len() > 1 {
+            subdivide((a, ta), (b, tb), 0, &transform, &deviation_m, max_deviation_m, &mut result);
+        }
+    }
+
+    if closed {
+        if let Some(&first) = result.first() {
+            result.push(first);
+        }
+    }
+
+    result
+}
+
+/// Transforms a polyline while densifying the edges adaptively as in `transform_ring`, without a closing edge
+///
+/// `transform_ring`と同様に辺を適応的に高密度化しながら折れ線を変換する。閉じる辺は含まない
+fn transform_line<T, D>(line: &[(f64, f64)], transform: T, deviation_m: D, max_deviation_m: f64) -> Vec<(f64, f64)>
+where
+    T: Fn((f64, f64)) -> (f64, f64),
+    D: Fn((f64, f64), (f64, f64)) -> f64,
+{
+    let mut result = Vec::with_capacity(line.len());
+
+    for (i, &a) in line.iter().enumerate() {
+        let ta = transform(a);
+        result.push(ta);
+
+        if let Some(&b) = line.get(i + 1) {
+            subdivide((a, ta), (b, transform(b)), 0, &transform, &deviation_m, max_deviation_m, &mut result);
+        }
+    }
+
+    result
+}
+
+/// Function to convert a ring of (longitude, latitude) expressed in the arc degree method to plane rectangular coordinates (y, x).
+/// Edges, which are straight in longitude and latitude, are densified so that the result deviates from the true curve by at most `max_deviation_m` (m).
+/// If the ring is closed (the first vertex equals the last one), the result is closed as well.
+///
+/// 弧度法で表された(経度, 緯度)の環を平面直角座標(y, x)に変換する関数。
+/// 緯経度で直線である辺を、真の曲線からのずれが`max_deviation_m`(m)以下になるように高密度化する。
+/// 環が閉じている(最初と最後の頂点が等しい)場合は、結果も閉じる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::polygon::ll_ring2jpr;
+///
+/// let ring = [
+/// (138_f64.to_radians(), 36_f64.to_radians()),
+/// (142_f64.to_radians(), 36_f64.to_radians()),
+/// (142_f64.to_radians(), 37_f64.to_radians()),
+/// ];
+/// let yx = ll_ring2jpr(&ring, JprOrigin::Nine, 1.);
+/// assert!(yx.len() > ring.len());
+/// ```
+pub fn ll_ring2jpr(ring: &[(f64, f64)], origin: JprOrigin, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    transform_ring(
+        ring,
+        |ll| ll2jpr(ll, origin),
+        |p, q| ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt(),
+        max_deviation_m,
+    )
+}
+
+/// Function to convert a ring of plane rectangular coordinates (y, x) to (longitude, latitude) expressed in the arc degree method.
+/// Edges, which are straight in plane rectangular coordinates, are densified so that the result deviates from the true curve by at most `max_deviation_m` (m).
+///
+/// 平面直角座標(y, x)の環を弧度法で表された(経度, 緯度)に変換する関数。
+/// 平面直角座標で直線である辺を、真の曲線からのずれが`max_deviation_m`(m)以下になるように高密度化する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::polygon::jpr_ring2ll;
+///
+/// let ll = jpr_ring2ll(&[(0., 0.), (200000., 0.), (200000., 200000.), (0., 0.)], JprOrigin::Nine, 1.);
+/// assert_eq!(ll.first(), ll.last());
+/// ```
+pub fn jpr_ring2ll(ring: &[(f64, f64)], origin: JprOrigin, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    transform_ring(
+        ring,
+        |yx| jpr2ll(yx, origin),
+        |p, q| {
+            let (p, q) = (ll2jpr(p, origin), ll2jpr(q, origin));
+            ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt()
+        },
+        max_deviation_m,
+    )
+}
+
+/// Function to convert a ring of (longitude, latitude) expressed in the arc degree method to fractional pixel coordinates (x, y).
+/// Edges, which are straight in longitude and latitude, are densified so that the result deviates from the true curve by at most `max_deviation_m` (m).
+/// The coordinates are not rounded so that they can be rasterized directly.
+///
+/// 弧度法で表された(経度, 緯度)の環を小数のピクセル座標(x, y)に変換する関数。
+/// 緯経度で直線である辺を、真の曲線からのずれが`max_deviation_m`(m)以下になるように高密度化する。
+/// そのままラスタ化できるように、座標は丸めない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::polygon::ll_ring2pixel;
+///
+/// let ring = [(0., 0.), (0., 60_f64.to_radians()), (30_f64.to_radians(), 0.)];
+/// let pixels = ll_ring2pixel(&ring, ZoomLv::Lv10, 10.);
+/// ```
+pub fn ll_ring2pixel(ring: &[(f64, f64)], zoom: ZoomLv, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    transform_ring(
+        ring,
+        |ll| ll2pixel_f64(ll, zoom),
+        |p, q| {
+            let (_, lat) = pixel2ll_f64(p, zoom);
+            ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt() * pixel_resolution(lat, zoom)
+        },
+        max_deviation_m,
+    )
+}
+
+/// Function to convert a polyline of plane rectangular coordinates (y, x) to fractional pixel coordinates (x, y).
+/// Edges, which are straight in plane rectangular coordinates, are densified so that the result deviates from the true curve by at most `max_deviation_m` (m).
+///
+/// 平面直角座標(y, x)の折れ線を小数のピクセル座標(x, y)に変換する関数。
+/// 平面直角座標で直線である辺を、真の曲線からのずれが`max_deviation_m`(m)以下になるように高密度化する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::polygon::jpr_line2pixel;
+///
+/// let pixels = jpr_line2pixel(&[(100000., -100000.), (100000., 100000.)], JprOrigin::Nine, ZoomLv::Lv12, 1.);
+/// assert!(pixels.len() > 2);
+/// ```
+pub fn jpr_line2pixel(line: &[(f64, f64)], origin: JprOrigin, zoom: ZoomLv, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    transform_line(
+        line,
+        |yx| ll2pixel_f64(jpr2ll(yx, origin), zoom),
+        |p, q| {
+            let (_, lat) = pixel2ll_f64(p, zoom);
+            ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt() * pixel_resolution(lat, zoom)
+        },
+        max_deviation_m,
+    )
+}
+
+/// Function to convert a ring of fractional pixel coordinates (x, y) to plane rectangular coordinates (y, x).
+/// Edges, which are straight in pixel coordinates, are densified so that the result deviates from the true curve by at most `max_deviation_m` (m).
+///
+/// 小数のピクセル座標(x, y)の環を平面直角座標(y, x)に変換する関数。
+/// ピクセル座標で直線である辺を、真の曲線からのずれが`max_deviation_m`(m)以下になるように高密度化する。
+pub fn pixel_ring2jpr(ring: &[(f64, f64)], zoom: ZoomLv, origin: JprOrigin, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    transform_ring(
+        ring,
+        |pixel| ll2jpr(pixel2ll_f64(pixel, zoom), origin),
+        |p, q| ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt(),
+        max_deviation_m,
+    )
+}
+
+/// Function to return the footprint of the Web Mercator tile (x, y) of the Zoom level in plane rectangular coordinates (y, x), e.g. for clipping survey data to tile boundaries.
+/// The footprint is not a rectangle in plane rectangular coordinates, so the edges are densified as in `pixel_ring2jpr`.
+/// The ring starts at the north west corner, goes through the north east, south east and south west corners, and is closed.
+///
+/// ZoomレベルのWebメルカトルのタイル(x, y)の範囲を平面直角座標(y, x)で返す関数。測量データをタイルの境界で切り取る場合などに用いる。
+/// 範囲は平面直角座標では矩形にならないため、`pixel_ring2jpr`と同様に辺を高密度化する。
+/// 環は北西の角から始まり、北東、南東、南西の角を通って閉じる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::polygon::tile_footprint_jpr;
+///
+/// let footprint = tile_footprint_jpr((227, 100), ZoomLv::Lv8, JprOrigin::Nine, 0.01);
+///
+/// assert!(footprint.len() > 5);
+/// assert_eq!(footprint.first(), footprint.last());
+/// ```
+pub fn tile_footprint_jpr(tile: (u32, u32), zoom: ZoomLv, origin: JprOrigin, max_deviation_m: f64) -> Vec<(f64, f64)> {
+    let (west, north) = (tile.0 as f64 * 256., tile.1 as f64 * 256.);
+    let (east, south) = (west + 256., north + 256.);
+
+    pixel_ring2jpr(
+        &[(west, north), (east, north), (east, south), (west, south), (west, north)],
+        zoom,
+        origin,
+        max_deviation_m,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn ll_ring2jpr_works() {
+        let ring = [
+            (138_f64.to_radians(), 36_f64.to_radians()),
+            (142_f64.to_radians(), 36_f64.to_radians()),
+            (142_f64.to_radians(), 37_f64.to_radians()),
+            (138_f64.to_radians(), 36_f64.to_radians()),
+        ];
+
+        let coarse = ll_ring2jpr(&ring, JprOrigin::Nine, 1e6);
+        let fine = ll_ring2jpr(&ring, JprOrigin::Nine, 1.);
+        let finer = ll_ring2jpr(&ring, JprOrigin::Nine, 0.01);
+
+        assert_eq!(coarse.len(), ring.len());
+        assert!(fine.len() > coarse.len());
+        assert!(finer.len() > fine.len());
+        assert_eq!(fine.first(), fine.last());
+
+        let (y, x) = ll2jpr(ring[1], JprOrigin::Nine);
+        assert!(fine.contains(&(y, x)));
+    }
+
+    #[test]
+    fn densified_points_lie_on_the_curve() {
+        let ring = [(0., 0.), (200000., 0.), (200000., 200000.)];
+        let ll = jpr_ring2ll(&ring, JprOrigin::Nine, 0.1);
+
+        // 追加された点は平面直角座標で辺上にある
+        for &p in &ll {
+            let (y, x) = ll2jpr(p, JprOrigin::Nine);
+            let on_edge = x.abs() < 1e-3 || (y - 200000.).abs() < 1e-3 || (y - x).abs() < 1e-3;
+            assert!(on_edge, "({}, {})", y, x);
+        }
+
+        let (y, x) = ll2jpr(ll[0], JprOrigin::Nine);
+        assert_close_to(y, 0., 6);
+        assert_close_to(x, 0., 6);
+    }
+
+    #[test]
+    fn tile_footprint_jpr_follows_tile_edges() {
+        let (tile, zoom) = ((227, 100), ZoomLv::Lv8);
+        let footprint = tile_footprint_jpr(tile, zoom, JprOrigin::Nine, 0.01);
+
+        let (y, x) = ll2jpr(pixel2ll_f64((227. * 256., 100. * 256.), zoom), JprOrigin::Nine);
+        assert_eq!(footprint[0], (y, x));
+        assert_eq!(footprint.first(), footprint.last());
+
+        // 全ての点はタイルの辺上にある
+        for &yx in &footprint {
+            let (px, py) = ll2pixel_f64(jpr2ll(yx, JprOrigin::Nine), zoom);
+            let (px, py) = (px - 227. * 256., py - 100. * 256.);
+            let on_edge = px.abs() < 1e-6 || (px - 256.).abs() < 1e-6 || py.abs() < 1e-6 || (py - 256.).abs() < 1e-6;
+            assert!(on_edge, "({}, {})", px, py);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Rounding of coordinate values to a number of decimals or to a ground precision (m), for reducing the output size or fuzzing locations for privacy.
+//!
+//! 出力サイズの削減やプライバシーのための位置のぼかしを目的とした、座標値の小数点以下の桁数または地上での精度(m)への丸め。
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::consts::{GRS80_ECCENTRICITY_SQUARED, GRS80_SEMI_MAJOR_AXIS};
+use crate::math;
+
+const A: f64 = GRS80_SEMI_MAJOR_AXIS;
+const E2: f64 = GRS80_ECCENTRICITY_SQUARED;
+
+/// Mean radius of curvature of the meridian of GRS80, the meridian quadrant divided by π/2 (m)
+///
+/// GRS80の子午線の平均曲率半径。子午線象限の長さをπ/2で割ったもの(m)
+const MEAN_MERIDIAN_RADIUS: f64 = 10001965.729 / FRAC_PI_2;
+
+/// Function to round a value to `decimals` decimal places
+///
+/// 値を小数点以下`decimals`桁に丸める関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::precision::round_to_decimals;
+///
+/// assert_eq!(round_to_decimals(35.681236, 3), 35.681);
+/// ```
+pub fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    let scale = 10_f64.powi(decimals as i32);
+    (value * scale).round() / scale
+}
+
+/// Returns the ground lengths (m) of one radian of longitude and latitude at the latitude expressed in the arc degree method (GRS80)
+///
+/// 弧度法で表された緯度における経度と緯度の1ラジアンあたりの地上での長さ(m)を返す(GRS80)
+pub(crate) fn meters_per_radian(lat: f64) -> (f64, f64) {
+    let sin = math::sin(lat);
+    let w = (1. - E2 * sin * sin).sqrt();
+
+    // 卯酉線曲率半径と子午線曲率半径
+    let n = A / w;
+    let m = A * (1. - E2) / (w * w * w);
+
+    (n * math::cos(lat), m)
+}
+
+/// Function to return the numbers of decimal places of degrees (longitude, latitude) needed so that one unit of the last place is at most `precision_m` (m) on the ground at the latitude expressed in the arc degree method.
+/// The longitude needs fewer places at high latitudes because the meridians converge.
+///
+/// 弧度法で表された緯度において、最後の桁の1単位が地上で`precision_m`(m)以下となるのに必要な度の小数点以下の桁数(経度, 緯度)を返す関数。
+/// 高緯度では経線が収束するため、経度に必要な桁数は少なくなる。
+///
+/// # Examples
+///
+/// Find the number of decimal places needed for about 1 cm in Tokyo.
+///
+/// 東京で約1cmに必要な小数点以下の桁数を求める。
+///
+/// ```
+/// use coordinate_transformer::precision::degree_decimals_for_precision;
+///
+/// assert_eq!(degree_decimals_for_precision(35.68_f64.to_radians(), 0.01), (7, 8));
+/// ```
+pub fn degree_decimals_for_precision(lat: f64, precision_m: f64) -> (u32, u32) {
+    let (long_m, lat_m) = meters_per_radian(lat);
+
+    let decimals = |m_per_radian: f64| {
+        let m_per_degree = m_per_radian.to_radians();
+        math::log10(m_per_degree / precision_m).ceil().max(0.) as u32
+    };
+
+    (decimals(long_m), decimals(lat_m))
+}
+
+/// Function to round (longitude, latitude) expressed in the arc degree method to `decimals` decimal places of degrees.
+/// The result is expressed in the arc degree method.
+///
+/// 弧度法で表された(経度, 緯度)を度の小数点以下`decimals`桁に丸める関数。
+/// 結果は弧度法で表される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::precision::round_ll_decimals;
+///
+/// let (long, lat) = round_ll_decimals((139.7671248_f64.to_radians(), 35.6812362_f64.to_radians()), 4);
+///
+/// assert_eq!((long.to_degrees() * 1e4).round(), 1397671.);
+/// ```
+pub fn round_ll_decimals(ll: (f64, f64), decimals: u32) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    (
+        round_to_decimals(long.to_degrees(), decimals).to_radians(),
+        round_to_decimals(lat.to_degrees(), decimals).to_radians(),
+    )
+}
+
+/// Function to snap (longitude, latitude) expressed in the arc degree method to a grid whose spacing on the ground is `precision_m` (m) at that latitude.
+/// The longitude spacing is widened with the latitude, so the ground precision is the same in both directions.
+/// The latitude spacing uses the mean meridian radius so that the grid is fixed, which makes it differ from `precision_m` by up to 0.7%.
+/// The latitude is kept within ±π/2, and the longitude becomes 0 near the poles where the longitude spacing exceeds the full circle.
+///
+/// 弧度法で表された(経度, 緯度)を、その緯度における地上での間隔が`precision_m`(m)の格子に丸める関数。
+/// 経度の間隔は緯度に応じて広げられるため、地上での精度は両方向で等しくなる。
+/// 格子を固定するため緯度の間隔には子午線の平均曲率半径を用いており、`precision_m`との差は最大0.7%となる。
+/// 緯度は±π/2の範囲に留め、経度の間隔が全周を超える極付近では経度を0とする。
+///
+/// # Examples
+///
+/// Fuzz a location to about 100 m.
+///
+/// 位置を約100mにぼかす。
+///
+/// ```
+/// use coordinate_transformer::precision::round_ll_to_m;
+///
+/// let (long, lat) = round_ll_to_m((139.7671248_f64.to_radians(), 35.6812362_f64.to_radians()), 100.);
+/// ```
+pub fn round_ll_to_m(ll: (f64, f64), precision_m: f64) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    // 格子が緯度によらず一定となるよう、緯度の間隔には子午線の平均曲率半径を用いる
+    // 極を越えた緯度は極に留める
+    let lat_step = precision_m / MEAN_MERIDIAN_RADIUS;
+    let lat = ((lat / lat_step).round() * lat_step).clamp(-FRAC_PI_2, FRAC_PI_2);
+
+    // 経度の間隔は丸めた後の緯度で決めるため、同じ格子の点は同じ経度の間隔を持つ
+    // 極付近で間隔が全周を超える場合(極上ではNaNになる)は、経度を0に寄せる
+    let (long_m, _) = meters_per_radian(lat);
+    let long_step = precision_m / long_m;
+    let long = if long_step < 2. * PI {
+        (long / long_step).round() * long_step
+    } else {
+        0.
+    };
+
+    (long, lat)
+}
+
+/// Function to round plane rectangular coordinates (y, x) to a multiple of `precision_m` (m)
+///
+/// 平面直角座標(y, x)を`precision_m`(m)の倍数に丸める関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::precision::round_jpr_to_m;
+///
+/// assert_eq!(round_jpr_to_m((22694.9803, 11573.3752), 0.01), (22694.98, 11573.38));
+/// ```
+pub fn round_jpr_to_m(yx: (f64, f64), precision_m: f64) -> (f64, f64) {
+    let (y, x) = yx;
+    (round_step(y, precision_m), round_step(x, precision_m))
+}
+
+/// Function to round Cartesian coordinates (x, y, z) to a multiple of `precision_m` (m)
+///
+/// 直交座標(x, y, z)を`precision_m`(m)の倍数に丸める関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::precision::round_xyz_to_m;
+///
+/// assert_eq!(round_xyz_to_m((-3959786.0604, 3352557.0408, 3697508.6914), 0.1), (-3959786.1, 3352557., 3697508.7));
+/// ```
+pub fn round_xyz_to_m(xyz: (f64, f64, f64), precision_m: f64) -> (f64, f64, f64) {
+    let (x, y, z) = xyz;
+    (round_step(x, precision_m), round_step(y, precision_m), round_step(z, precision_m))
+}
+
+/// Rounds a value to a multiple of `step`.
+/// A `step` that is a negative power of 10 is applied by division, so that e.g. 0.01 gives exactly two decimal places.
+///
+/// 値を`step`の倍数に丸める。
+/// 10の負の累乗の`step`は除算で適用するため、例えば0.01では正確に小数点以下2桁となる。
+fn round_step(value: f64, step: f64) -> f64 {
+    let inverse = (1. / step).round();
+    if inverse >= 1. && (1. / inverse - step).abs() <= f64::EPSILON * step {
+        (value * inverse).round() / inverse
+    } else {
+        (value / step).round() * step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn degree_decimals_for_precision_works() {
+        // 緯度1度は約111km
+        assert_eq!(degree_decimals_for_precision(0., 100_000.), (1, 1));
+        assert_eq!(degree_decimals_for_precision(0., 1.).1, 6);
+        // 北緯80度では経度1度が約19kmとなる
+        assert_eq!(degree_decimals_for_precision(80_f64.to_radians(), 20.).0, 3);
+    }
+
+    #[test]
+    fn round_ll_to_m_works() {
+        let ll = (139.7671248_f64.to_radians(), 35.6812362_f64.to_radians());
+        let (long, lat) = round_ll_to_m(ll, 100.);
+
+        let (long_m, lat_m) = meters_per_radian(lat);
+        assert!(((long - ll.0) * long_m).abs() <= 50.);
+        assert!(((lat - ll.1) * lat_m).abs() <= 50.5);
+
+        // 丸めた点は再び丸めても変わらない
+        let (long2, lat2) = round_ll_to_m((long, lat), 100.);
+        assert_close_to(long2, long, 12);
+        assert_close_to(lat2, lat, 12);
+    }
+
+    #[test]
+    fn round_ll_to_m_stays_within_poles() {
+        // 極の手前の点は極を越えて丸められない
+        let (long, lat) = round_ll_to_m((0.3, 89.9995_f64.to_radians()), 1000.);
+        assert_eq!(lat, FRAC_PI_2);
+        assert_eq!(long, 0.);
+
+        let (long, lat) = round_ll_to_m((-2., -FRAC_PI_2), 10.);
+        assert_eq!((long, lat), (0., -FRAC_PI_2));
+
+        // 経度の間隔が全周に満たない高緯度ではそのまま丸める
+        let (long, lat) = round_ll_to_m((0.3, 89.9_f64.to_radians()), 1000.);
+        assert!(long.is_finite() && lat < FRAC_PI_2);
+    }
+}
@@ -0,0 +1,865 @@
+use crate::JprOrigin;
+
+/// Prefectures of Japan, numbered by the JIS X 0401 prefecture code
+///
+/// JIS X 0401の都道府県コードで番号付けされた都道府県
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Prefecture {
+    Hokkaido = 1,
+    Aomori,
+    Iwate,
+    Miyagi,
+    Akita,
+    Yamagata,
+    Fukushima,
+    Ibaraki,
+    Tochigi,
+    Gunma,
+    Saitama,
+    Chiba,
+    Tokyo,
+    Kanagawa,
+    Niigata,
+    Toyama,
+    Ishikawa,
+    Fukui,
+    Yamanashi,
+    Nagano,
+    Gifu,
+    Shizuoka,
+    Aichi,
+    Mie,
+    Shiga,
+    Kyoto,
+    Osaka,
+    Hyogo,
+    Nara,
+    Wakayama,
+    Tottori,
+    Shimane,
+    Okayama,
+    Hiroshima,
+    Yamaguchi,
+    Tokushima,
+    Kagawa,
+    Ehime,
+    Kochi,
+    Fukuoka,
+    Saga,
+    Nagasaki,
+    Kumamoto,
+    Oita,
+    Miyazaki,
+    Kagoshima,
+    Okinawa,
+}
+
+const PREFECTURES: [Prefecture; 47] = [
+    Prefecture::Hokkaido,
+    Prefecture::Aomori,
+    Prefecture::Iwate,
+    Prefecture::Miyagi,
+    Prefecture::Akita,
+    Prefecture::Yamagata,
+    Prefecture::Fukushima,
+    Prefecture::Ibaraki,
+    Prefecture::Tochigi,
+    Prefecture::Gunma,
+    Prefecture::Saitama,
+    Prefecture::Chiba,
+    Prefecture::Tokyo,
+    Prefecture::Kanagawa,
+    Prefecture::Niigata,
+    Prefecture::Toyama,
+    Prefecture::Ishikawa,
+    Prefecture::Fukui,
+    Prefecture::Yamanashi,
+    Prefecture::Nagano,
+    Prefecture::Gifu,
+    Prefecture::Shizuoka,
+    Prefecture::Aichi,
+    Prefecture::Mie,
+    Prefecture::Shiga,
+    Prefecture::Kyoto,
+    Prefecture::Osaka,
+    Prefecture::Hyogo,
+    Prefecture::Nara,
+    Prefecture::Wakayama,
+    Prefecture::Tottori,
+    Prefecture::Shimane,
+    Prefecture::Okayama,
+    Prefecture::Hiroshima,
+    Prefecture::Yamaguchi,
+    Prefecture::Tokushima,
+    Prefecture::Kagawa,
+    Prefecture::Ehime,
+    Prefecture::Kochi,
+    Prefecture::Fukuoka,
+    Prefecture::Saga,
+    Prefecture::Nagasaki,
+    Prefecture::Kumamoto,
+    Prefecture::Oita,
+    Prefecture::Miyazaki,
+    Prefecture::Kagoshima,
+    Prefecture::Okinawa,
+];
+
+impl Prefecture {
+    /// Returns all prefectures in the order of the prefecture code.
+    ///
+    /// 全ての都道府県を都道府県コード順に返す。
+    pub fn all() -> [Prefecture; 47] {
+        PREFECTURES
+    }
+
+    /// Returns the prefecture code (1-47).
+    ///
+    /// 都道府県コード(1〜47)を返す。
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the Japanese name of the prefecture.
+    ///
+    /// 都道府県の日本語名を返す。
+    pub fn name_ja(&self) -> &'static str {
+        match self {
+            Prefecture::Hokkaido => "北海道",
+            Prefecture::Aomori => "青森県",
+            Prefecture::Iwate => "岩手県",
+            Prefecture::Miyagi => "宮城県",
+            Prefecture::Akita => "秋田県",
+            Prefecture::Yamagata => "山形県",
+            Prefecture::Fukushima => "福島県",
+            Prefecture::Ibaraki => "茨城県",
+            Prefecture::Tochigi => "栃木県",
+            Prefecture::Gunma => "群馬県",
+            Prefecture::Saitama => "埼玉県",
+            Prefecture::Chiba => "千葉県",
+            Prefecture::Tokyo => "東京都",
+            Prefecture::Kanagawa => "神奈川県",
+            Prefecture::Niigata => "新潟県",
+            Prefecture::Toyama => "富山県",
+            Prefecture::Ishikawa => "石川県",
+            Prefecture::Fukui => "福井県",
+            Prefecture::Yamanashi => "山梨県",
+            Prefecture::Nagano => "長野県",
+            Prefecture::Gifu => "岐阜県",
+            Prefecture::Shizuoka => "静岡県",
+            Prefecture::Aichi => "愛知県",
+            Prefecture::Mie => "三重県",
+            Prefecture::Shiga => "滋賀県",
+            Prefecture::Kyoto => "京都府",
+            Prefecture::Osaka => "大阪府",
+            Prefecture::Hyogo => "兵庫県",
+            Prefecture::Nara => "奈良県",
+            Prefecture::Wakayama => "和歌山県",
+            Prefecture::Tottori => "鳥取県",
+            Prefecture::Shimane => "島根県",
+            Prefecture::Okayama => "岡山県",
+            Prefecture::Hiroshima => "広島県",
+            Prefecture::Yamaguchi => "山口県",
+            Prefecture::Tokushima => "徳島県",
+            Prefecture::Kagawa => "香川県",
+            Prefecture::Ehime => "愛媛県",
+            Prefecture::Kochi => "高知県",
+            Prefecture::Fukuoka => "福岡県",
+            Prefecture::Saga => "佐賀県",
+            Prefecture::Nagasaki => "長崎県",
+            Prefecture::Kumamoto => "熊本県",
+            Prefecture::Oita => "大分県",
+            Prefecture::Miyazaki => "宮崎県",
+            Prefecture::Kagoshima => "鹿児島県",
+            Prefecture::Okinawa => "沖縄県",
+        }
+    }
+
+    /// Returns the origins of the plane rectangular coordinate system that apply within the prefecture.
+    /// Prefectures divided between several origins return all of them; use [`JprOrigin::contains`] to decide for a specific point.
+    ///
+    /// 都道府県内で適用される平面直角座標系の原点を返す。
+    /// 複数の系に分かれる都道府県では全ての原点を返すため、特定の地点については[`JprOrigin::contains`]で判定する。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    /// use coordinate_transformer::prefecture::Prefecture;
+    ///
+    /// assert_eq!(Prefecture::Kagoshima.origins(), vec![JprOrigin::One, JprOrigin::Two]);
+    /// ```
+    pub fn origins(&self) -> Vec<JprOrigin> {
+        match self {
+            Prefecture::Hokkaido => vec![JprOrigin::Eleven, JprOrigin::Twelve, JprOrigin::Thirteen],
+            Prefecture::Aomori => vec![JprOrigin::Ten],
+            Prefecture::Iwate => vec![JprOrigin::Ten],
+            Prefecture::Miyagi => vec![JprOrigin::Ten],
+            Prefecture::Akita => vec![JprOrigin::Ten],
+            Prefecture::Yamagata => vec![JprOrigin::Ten],
+            Prefecture::Fukushima => vec![JprOrigin::Nine],
+            Prefecture::Ibaraki => vec![JprOrigin::Nine],
+            Prefecture::Tochigi => vec![JprOrigin::Nine],
+            Prefecture::Gunma => vec![JprOrigin::Nine],
+            Prefecture::Saitama => vec![JprOrigin::Nine],
+            Prefecture::Chiba => vec![JprOrigin::Nine],
+            Prefecture::Tokyo => vec![JprOrigin::Nine, JprOrigin::Fourteen, JprOrigin::Eighteen, JprOrigin::Nineteen],
+            Prefecture::Kanagawa => vec![JprOrigin::Nine],
+            Prefecture::Niigata => vec![JprOrigin::Eight],
+            Prefecture::Toyama => vec![JprOrigin::Seven],
+            Prefecture::Ishikawa => vec![JprOrigin::Seven],
+            Prefecture::Fukui => vec![JprOrigin::Six],
+            Prefecture::Yamanashi => vec![JprOrigin::Eight],
+            Prefecture::Nagano => vec![JprOrigin::Eight],
+            Prefecture::Gifu => vec![JprOrigin::Seven],
+            Prefecture::Shizuoka => vec![JprOrigin::Eight],
+            Prefecture::Aichi => vec![JprOrigin::Seven],
+            Prefecture::Mie => vec![JprOrigin::Six],
+            Prefecture::Shiga => vec![JprOrigin::Six],
+            Prefecture::Kyoto => vec![JprOrigin::Six],
+            Prefecture::Osaka => vec![JprOrigin::Six],
+            Prefecture::Hyogo => vec![JprOrigin::Five],
+            Prefecture::Nara => vec![JprOrigin::Six],
+            Prefecture::Wakayama => vec![JprOrigin::Six],
+            Prefecture::Tottori => vec![JprOrigin::Five],
+            Prefecture::Shimane => vec![JprOrigin::Three],
+            Prefecture::Okayama => vec![JprOrigin::Five],
+            Prefecture::Hiroshima => vec![JprOrigin::Three],
+            Prefecture::Yamaguchi => vec![JprOrigin::Three],
+            Prefecture::Tokushima => vec![JprOrigin::Four],
+            Prefecture::Kagawa => vec![JprOrigin::Four],
+            Prefecture::Ehime => vec![JprOrigin::Four],
+            Prefecture::Kochi => vec![JprOrigin::Four],
+            Prefecture::Fukuoka => vec![JprOrigin::Two],
+            Prefecture::Saga => vec![JprOrigin::Two],
+            Prefecture::Nagasaki => vec![JprOrigin::One],
+            Prefecture::Kumamoto => vec![JprOrigin::Two],
+            Prefecture::Oita => vec![JprOrigin::Two],
+            Prefecture::Miyazaki => vec![JprOrigin::Two],
+            Prefecture::Kagoshima => vec![JprOrigin::One, JprOrigin::Two],
+            Prefecture::Okinawa => vec![JprOrigin::Fifteen, JprOrigin::Sixteen, JprOrigin::Seventeen],
+        }
+    }
+}
+
+impl JprOrigin {
+    /// Returns the prefectures in which this origin applies, in the order of the prefecture code.
+    ///
+    /// この原点が適用される都道府県を都道府県コード順に返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    /// use coordinate_transformer::prefecture::Prefecture;
+    ///
+    /// assert_eq!(
+    ///     JprOrigin::Three.prefectures(),
+    ///     vec![Prefecture::Shimane, Prefecture::Hiroshima, Prefecture::Yamaguchi]
+    /// );
+    /// ```
+    pub fn prefectures(&self) -> Vec<Prefecture> {
+        PREFECTURES
+            .into_iter()
+            .filter(|prefecture| prefecture.origins().contains(self))
+            .collect()
+    }
+
+    /// Returns the regions that make up the area of this origin, in the order of the official table (国土交通省告示).
+    ///
+    /// この原点の適用区域を構成する区域を、告示の表の順に返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    /// use coordinate_transformer::prefecture::{Prefecture, ZoneRegion};
+    ///
+    /// assert_eq!(JprOrigin::Twelve.regions(), vec![ZoneRegion::Remainder(Prefecture::Hokkaido)]);
+    /// ```
+    pub fn regions(&self) -> Vec<ZoneRegion> {
+        use HokkaidoSubprefecture as Sub;
+        use Prefecture as P;
+        use ZoneRegion as R;
+
+        let whole = |prefectures: &[Prefecture]| prefectures.iter().map(|&p| R::Prefecture(p)).collect::<Vec<_>>();
+        let deg = |deg: f64| deg.to_radians();
+        let inf = f64::INFINITY;
+
+        match self {
+            JprOrigin::One => vec![
+                R::Prefecture(P::Nagasaki),
+                R::Bounded {
+                    prefecture: P::Kagoshima,
+                    min_ll: (deg(128. + 18. / 60.), deg(27.)),
+                    max_ll: (deg(130.), deg(32.)),
+                },
+                R::Municipalities(P::Kagoshima, AMAMI_ISLANDS),
+            ],
+            JprOrigin::Two => {
+                let mut regions = whole(&[P::Fukuoka, P::Saga, P::Kumamoto, P::Oita, P::Miyazaki]);
+                regions.push(R::Remainder(P::Kagoshima));
+                regions
+            }
+            JprOrigin::Three => whole(&[P::Yamaguchi, P::Shimane, P::Hiroshima]),
+            JprOrigin::Four => whole(&[P::Kagawa, P::Ehime, P::Tokushima, P::Kochi]),
+            JprOrigin::Five => whole(&[P::Hyogo, P::Tottori, P::Okayama]),
+            JprOrigin::Six => whole(&[P::Kyoto, P::Osaka, P::Fukui, P::Shiga, P::Mie, P::Nara, P::Wakayama]),
+            JprOrigin::Seven => whole(&[P::Ishikawa, P::Toyama, P::Gifu, P::Aichi]),
+            JprOrigin::Eight => whole(&[P::Niigata, P::Nagano, P::Yamanashi, P::Shizuoka]),
+            JprOrigin::Nine => {
+                let mut regions = vec![R::Remainder(P::Tokyo)];
+                regions.extend(whole(&[
+                    P::Fukushima,
+                    P::Tochigi,
+                    P::Ibaraki,
+                    P::Saitama,
+                    P::Chiba,
+                    P::Gunma,
+                    P::Kanagawa,
+                ]));
+                regions
+            }
+            JprOrigin::Ten => whole(&[P::Aomori, P::Akita, P::Yamagata, P::Iwate, P::Miyagi]),
+            JprOrigin::Eleven => vec![
+                R::Municipalities(P::Hokkaido, &["小樽市", "函館市", "伊達市", "北斗市"]),
+                R::Subprefecture(Sub::Shiribeshi),
+                R::SubprefectureTowns(Sub::Iburi, &["豊浦町", "壮瞥町", "洞爺湖町"]),
+                R::Subprefecture(Sub::Oshima),
+                R::Subprefecture(Sub::Hiyama),
+            ],
+            JprOrigin::Twelve => vec![R::Remainder(P::Hokkaido)],
+            JprOrigin::Thirteen => vec![
+                R::Municipalities(P::Hokkaido, &["北見市", "帯広市", "釧路市", "網走市", "根室市"]),
+                R::SubprefectureTowns(
+                    Sub::Okhotsk,
+                    &["美幌町", "津別町", "斜里町", "清里町", "小清水町", "訓子府町", "置戸町", "佐呂間町", "大空町"],
+                ),
+                R::Subprefecture(Sub::Tokachi),
+                R::Subprefecture(Sub::Kushiro),
+                R::Subprefecture(Sub::Nemuro),
+            ],
+            JprOrigin::Fourteen => vec![R::Bounded {
+                prefecture: P::Tokyo,
+                min_ll: (deg(140.5), -inf),
+                max_ll: (deg(143.), deg(28.)),
+            }],
+            JprOrigin::Fifteen => vec![R::Bounded {
+                prefecture: P::Okinawa,
+                min_ll: (deg(126.), -inf),
+                max_ll: (deg(130.), inf),
+            }],
+            JprOrigin::Sixteen => vec![R::Bounded {
+                prefecture: P::Okinawa,
+                min_ll: (-inf, -inf),
+                max_ll: (deg(126.), inf),
+            }],
+            JprOrigin::Seventeen => vec![R::Bounded {
+                prefecture: P::Okinawa,
+                min_ll: (deg(130.), -inf),
+                max_ll: (inf, inf),
+            }],
+            JprOrigin::Eighteen => vec![R::Bounded {
+                prefecture: P::Tokyo,
+                min_ll: (-inf, -inf),
+                max_ll: (deg(140.5), deg(28.)),
+            }],
+            JprOrigin::Nineteen => vec![R::Bounded {
+                prefecture: P::Tokyo,
+                min_ll: (deg(143.), -inf),
+                max_ll: (inf, deg(28.)),
+            }],
+        }
+    }
+
+    /// Returns the origins that apply within the municipality (city, town or village) named `name` in Japanese.
+    /// Municipalities divided between several origins, such as 小笠原村 of Tokyo, return all of them.
+    /// Names are compared with the municipalities used to define the areas; any other name falls in the area of the prefecture that is not assigned to a region defined by municipalities, so names are not validated except in Okinawa where every municipality is listed.
+    ///
+    /// 日本語名`name`の市町村内で適用される原点を返す。
+    /// 東京都の小笠原村のように複数の系に分かれる市町村では全ての原点を返す。
+    /// 名前は適用区域の定義に用いる市町村と比較し、それ以外の名前は市町村で定義された区域に含まれない都道府県の残りの区域となるため、全市町村を列挙している沖縄県以外では名前の検証は行わない。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    /// use coordinate_transformer::prefecture::Prefecture;
+    ///
+    /// assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "ニセコ町"), vec![JprOrigin::Eleven]);
+    /// assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "札幌市"), vec![JprOrigin::Twelve]);
+    /// assert_eq!(JprOrigin::for_municipality(Prefecture::Okinawa, "石垣市"), vec![JprOrigin::Sixteen]);
+    /// ```
+    pub fn for_municipality(prefecture: Prefecture, name: &str) -> Vec<JprOrigin> {
+        // 経緯度で区切られる区域の市町村
+        if let Some((_, _, origins)) = MUNICIPALITIES_BY_LOCATION
+            .iter()
+            .find(|(p, names, _)| *p == prefecture && names.contains(&name))
+        {
+            return origins.to_vec();
+        }
+        if prefecture == Prefecture::Okinawa {
+            return Vec::new();
+        }
+
+        let origins = prefecture.origins();
+
+        // 市町村で定義された区域
+        let named = origins
+            .iter()
+            .copied()
+            .filter(|origin| {
+                origin.regions().iter().any(|region| match region {
+                    ZoneRegion::Municipalities(_, names) | ZoneRegion::SubprefectureTowns(_, names) => {
+                        names.contains(&name)
+                    }
+                    ZoneRegion::Subprefecture(subprefecture) => subprefecture.municipalities().contains(&name),
+                    _ => false,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !named.is_empty() {
+            return named;
+        }
+
+        // 都道府県全体または残りの区域
+        origins
+            .into_iter()
+            .filter(|origin| {
+                origin.regions().iter().any(|region| {
+                    matches!(region, ZoneRegion::Prefecture(p) | ZoneRegion::Remainder(p) if *p == prefecture)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Amami Islands (奄美群島), which belong to origin I beyond 130°E up to 130°13′E
+///
+/// 東経130度13分まで第I系に含まれる奄美群島
+const AMAMI_ISLANDS: &[&str] = &[
+    "奄美市",
+    "大和村",
+    "宇検村",
+    "瀬戸内町",
+    "龍郷町",
+    "喜界町",
+    "徳之島町",
+    "天城町",
+    "伊仙町",
+    "和泊町",
+    "知名町",
+    "与論町",
+];
+
+/// Municipalities in the regions bounded by longitude and latitude, and the origins that apply within them
+///
+/// 経緯度で区切られる区域の市町村と、その中で適用される原点
+const MUNICIPALITIES_BY_LOCATION: &[(Prefecture, &[&str], &[JprOrigin])] = &[
+    (Prefecture::Kagoshima, &["十島村"], &[JprOrigin::One]),
+    // 甑島と黒島が第I系となる
+    (Prefecture::Kagoshima, &["薩摩川内市", "三島村"], &[JprOrigin::One, JprOrigin::Two]),
+    (
+        Prefecture::Tokyo,
+        &["小笠原村"],
+        &[JprOrigin::Fourteen, JprOrigin::Eighteen, JprOrigin::Nineteen],
+    ),
+    (
+        Prefecture::Okinawa,
+        &[
+            "那覇市",
+            "宜野湾市",
+            "浦添市",
+            "名護市",
+            "糸満市",
+            "沖縄市",
+            "豊見城市",
+            "うるま市",
+            "南城市",
+            "国頭村",
+            "大宜味村",
+            "東村",
+            "今帰仁村",
+            "本部町",
+            "恩納村",
+            "宜野座村",
+            "金武町",
+            "伊江村",
+            "読谷村",
+            "嘉手納町",
+            "北谷町",
+            "北中城村",
+            "中城村",
+            "西原町",
+            "与那原町",
+            "南風原町",
+            "渡嘉敷村",
+            "座間味村",
+            "粟国村",
+            "渡名喜村",
+            "伊平屋村",
+            "伊是名村",
+            "久米島町",
+            "八重瀬町",
+        ],
+        &[JprOrigin::Fifteen],
+    ),
+    (
+        Prefecture::Okinawa,
+        &["宮古島市", "石垣市", "多良間村", "竹富町", "与那国町"],
+        &[JprOrigin::Sixteen],
+    ),
+    (Prefecture::Okinawa, &["北大東村", "南大東村"], &[JprOrigin::Seventeen]),
+];
+
+/// Region making up part of the area of an origin in the official table of the plane rectangular coordinate system (国土交通省告示)
+///
+/// 平面直角座標系の告示の表において、原点の適用区域の一部を構成する区域
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoneRegion {
+    /// The whole prefecture
+    ///
+    /// 都道府県全体
+    Prefecture(Prefecture),
+    /// The prefecture except the regions assigned to the other origins
+    ///
+    /// 他の系に規定する区域を除いた都道府県
+    Remainder(Prefecture),
+    /// The listed municipalities of the prefecture
+    ///
+    /// 都道府県のうち列挙された市町村
+    Municipalities(Prefecture, &'static [&'static str]),
+    /// The area under the jurisdiction of the subprefecture of Hokkaido
+    ///
+    /// 北海道の振興局の所管区域
+    Subprefecture(HokkaidoSubprefecture),
+    /// The listed towns of the area under the jurisdiction of the subprefecture of Hokkaido
+    ///
+    /// 北海道の振興局の所管区域のうち列挙された町
+    SubprefectureTowns(HokkaidoSubprefecture, &'static [&'static str]),
+    /// The part of the prefecture within the longitudes and latitudes expressed in the arc degree method, infinite for open sides
+    ///
+    /// 都道府県のうち弧度法で表された経緯度の範囲内の区域。範囲のない側は無限大
+    Bounded {
+        /// Prefecture
+        ///
+        /// 都道府県
+        prefecture: Prefecture,
+        /// Minimum (longitude, latitude) expressed in the arc degree method
+        ///
+        /// 弧度法で表された最小の(経度, 緯度)
+        min_ll: (f64, f64),
+        /// Maximum (longitude, latitude) expressed in the arc degree method
+        ///
+        /// 弧度法で表された最大の(経度, 緯度)
+        max_ll: (f64, f64),
+    },
+}
+
+impl ZoneRegion {
+    /// Returns the prefecture the region belongs to.
+    ///
+    /// 区域が属する都道府県を返す。
+    pub fn prefecture(&self) -> Prefecture {
+        match self {
+            ZoneRegion::Prefecture(prefecture)
+            | ZoneRegion::Remainder(prefecture)
+            | ZoneRegion::Municipalities(prefecture, _)
+            | ZoneRegion::Bounded { prefecture, .. } => *prefecture,
+            ZoneRegion::Subprefecture(_) | ZoneRegion::SubprefectureTowns(_, _) => Prefecture::Hokkaido,
+        }
+    }
+
+    /// Returns the origin whose area the region is part of, or `None` if the region is not in the official table, e.g. `ZoneRegion::Prefecture(Prefecture::Kagoshima)`, which is divided between two origins.
+    ///
+    /// 区域を適用区域に含む原点を返す。2つの原点に分かれる`ZoneRegion::Prefecture(Prefecture::Kagoshima)`など、告示の表に無い区域では`None`を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    /// use coordinate_transformer::prefecture::{HokkaidoSubprefecture, Prefecture, ZoneRegion};
+    ///
+    /// assert_eq!(ZoneRegion::Subprefecture(HokkaidoSubprefecture::Tokachi).origin(), Some(JprOrigin::Thirteen));
+    /// assert_eq!(ZoneRegion::Prefecture(Prefecture::Kagoshima).origin(), None);
+    /// ```
+    pub fn origin(&self) -> Option<JprOrigin> {
+        self.prefecture()
+            .origins()
+            .into_iter()
+            .find(|origin| origin.regions().contains(self))
+    }
+
+    /// Returns the description of the region in Japanese, in the wording of the official table.
+    ///
+    /// 区域の日本語での説明を告示の表の表現で返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::jpr_ll::JprOrigin;
+    ///
+    /// assert_eq!(
+    ///     JprOrigin::Fourteen.regions()[0].name_ja(),
+    ///     "東京都のうち北緯28度以南、東経140度30分以東、東経143度以西の区域"
+    /// );
+    /// ```
+    pub fn name_ja(&self) -> String {
+        match self {
+            ZoneRegion::Prefecture(prefecture) => prefecture.name_ja().to_string(),
+            ZoneRegion::Remainder(prefecture) => format!("{}(他の系に規定する区域を除く。)", prefecture.name_ja()),
+            ZoneRegion::Municipalities(prefecture, names) => {
+                format!("{}のうち{}", prefecture.name_ja(), names.join("、"))
+            }
+            ZoneRegion::Subprefecture(subprefecture) => format!("北海道{}の所管区域", subprefecture.name_ja()),
+            ZoneRegion::SubprefectureTowns(subprefecture, names) => {
+                format!("北海道{}の所管区域のうち{}", subprefecture.name_ja(), names.join("、"))
+            }
+            ZoneRegion::Bounded {
+                prefecture,
+                min_ll,
+                max_ll,
+            } => {
+                let conditions = [
+                    (max_ll.1, "北緯", "以南"),
+                    (min_ll.1, "北緯", "以北"),
+                    (min_ll.0, "東経", "以東"),
+                    (max_ll.0, "東経", "以西"),
+                ]
+                .into_iter()
+                .filter(|(angle, _, _)| angle.is_finite())
+                .map(|(angle, prefix, suffix)| format!("{}{}{}", prefix, dm_ja(angle), suffix))
+                .collect::<Vec<_>>();
+
+                format!("{}のうち{}の区域", prefecture.name_ja(), conditions.join("、"))
+            }
+        }
+    }
+}
+
+/// Formats an angle expressed in the arc degree method as degrees and minutes in Japanese, omitting zero minutes
+///
+/// 弧度法で表された角度を日本語の度分で表す。0分は省略する
+fn dm_ja(angle: f64) -> String {
+    let minutes = (angle.to_degrees() * 60.).round() as i64;
+    match minutes % 60 {
+        0 => format!("{}度", minutes / 60),
+        m => format!("{}度{}分", minutes / 60, m),
+    }
+}
+
+/// Subprefectures of Hokkaido (総合振興局・振興局), used to define the areas of origins XI and XIII
+///
+/// 第XI系と第XIII系の適用区域の定義に用いられる北海道の総合振興局・振興局
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HokkaidoSubprefecture {
+    Sorachi,
+    Ishikari,
+    Shiribeshi,
+    Iburi,
+    Hidaka,
+    Oshima,
+    Hiyama,
+    Kamikawa,
+    Rumoi,
+    Soya,
+    Okhotsk,
+    Tokachi,
+    Kushiro,
+    Nemuro,
+}
+
+impl HokkaidoSubprefecture {
+    /// Returns all subprefectures.
+    ///
+    /// 全ての総合振興局・振興局を返す。
+    pub fn all() -> [HokkaidoSubprefecture; 14] {
+        use HokkaidoSubprefecture::*;
+        [
+            Sorachi, Ishikari, Shiribeshi, Iburi, Hidaka, Oshima, Hiyama, Kamikawa, Rumoi, Soya, Okhotsk, Tokachi,
+            Kushiro, Nemuro,
+        ]
+    }
+
+    /// Returns the Japanese name of the subprefecture.
+    ///
+    /// 総合振興局・振興局の日本語名を返す。
+    pub fn name_ja(&self) -> &'static str {
+        match self {
+            HokkaidoSubprefecture::Sorachi => "空知総合振興局",
+            HokkaidoSubprefecture::Ishikari => "石狩振興局",
+            HokkaidoSubprefecture::Shiribeshi => "後志総合振興局",
+            HokkaidoSubprefecture::Iburi => "胆振総合振興局",
+            HokkaidoSubprefecture::Hidaka => "日高振興局",
+            HokkaidoSubprefecture::Oshima => "渡島総合振興局",
+            HokkaidoSubprefecture::Hiyama => "檜山振興局",
+            HokkaidoSubprefecture::Kamikawa => "上川総合振興局",
+            HokkaidoSubprefecture::Rumoi => "留萌振興局",
+            HokkaidoSubprefecture::Soya => "宗谷総合振興局",
+            HokkaidoSubprefecture::Okhotsk => "オホーツク総合振興局",
+            HokkaidoSubprefecture::Tokachi => "十勝総合振興局",
+            HokkaidoSubprefecture::Kushiro => "釧路総合振興局",
+            HokkaidoSubprefecture::Nemuro => "根室振興局",
+        }
+    }
+
+    /// Returns the Japanese names of the municipalities under the jurisdiction of the subprefecture, excluding the villages of the Northern Territories.
+    ///
+    /// 総合振興局・振興局の所管区域の市町村の日本語名を返す。北方領土の村は除く。
+    pub fn municipalities(&self) -> &'static [&'static str] {
+        match self {
+            HokkaidoSubprefecture::Sorachi => &[
+                "夕張市", "岩見沢市", "美唄市", "芦別市", "赤平市", "三笠市", "滝川市", "砂川市", "歌志内市", "深川市",
+                "南幌町", "奈井江町", "上砂川町", "由仁町", "長沼町", "栗山町", "月形町", "浦臼町", "新十津川町",
+                "妹背牛町", "秩父別町", "雨竜町", "北竜町", "沼田町",
+            ],
+            HokkaidoSubprefecture::Ishikari => {
+                &["札幌市", "江別市", "千歳市", "恵庭市", "北広島市", "石狩市", "当別町", "新篠津村"]
+            }
+            HokkaidoSubprefecture::Shiribeshi => &[
+                "小樽市", "島牧村", "寿都町", "黒松内町", "蘭越町", "ニセコ町", "真狩村", "留寿都村", "喜茂別町",
+                "京極町", "倶知安町", "共和町", "岩内町", "泊村", "神恵内村", "積丹町", "古平町", "仁木町", "余市町",
+                "赤井川村",
+            ],
+            HokkaidoSubprefecture::Iburi => &[
+                "室蘭市", "苫小牧市", "登別市", "伊達市", "豊浦町", "壮瞥町", "白老町", "厚真町", "洞爺湖町", "安平町",
+                "むかわ町",
+            ],
+            HokkaidoSubprefecture::Hidaka => {
+                &["日高町", "平取町", "新冠町", "浦河町", "様似町", "えりも町", "新ひだか町"]
+            }
+            HokkaidoSubprefecture::Oshima => &[
+                "函館市", "北斗市", "松前町", "福島町", "知内町", "木古内町", "七飯町", "鹿部町", "森町", "八雲町",
+                "長万部町",
+            ],
+            HokkaidoSubprefecture::Hiyama => {
+                &["江差町", "上ノ国町", "厚沢部町", "乙部町", "奥尻町", "今金町", "せたな町"]
+            }
+            HokkaidoSubprefecture::Kamikawa => &[
+                "旭川市", "士別市", "名寄市", "富良野市", "鷹栖町", "東神楽町", "当麻町", "比布町", "愛別町", "上川町",
+                "東川町", "美瑛町", "上富良野町", "中富良野町", "南富良野町", "占冠村", "和寒町", "剣淵町", "下川町",
+                "美深町", "音威子府村", "中川町", "幌加内町",
+            ],
+            HokkaidoSubprefecture::Rumoi => {
+                &["留萌市", "増毛町", "小平町", "苫前町", "羽幌町", "初山別村", "遠別町", "天塩町"]
+            }
+            HokkaidoSubprefecture::Soya => &[
+                "稚内市", "猿払村", "浜頓別町", "中頓別町", "枝幸町", "豊富町", "礼文町", "利尻町", "利尻富士町",
+                "幌延町",
+            ],
+            HokkaidoSubprefecture::Okhotsk => &[
+                "北見市", "網走市", "紋別市", "美幌町", "津別町", "斜里町", "清里町", "小清水町", "訓子府町", "置戸町",
+                "佐呂間町", "遠軽町", "湧別町", "滝上町", "興部町", "西興部村", "雄武町", "大空町",
+            ],
+            HokkaidoSubprefecture::Tokachi => &[
+                "帯広市", "音更町", "士幌町", "上士幌町", "鹿追町", "新得町", "清水町", "芽室町", "中札内村", "更別村",
+                "大樹町", "広尾町", "幕別町", "池田町", "豊頃町", "本別町", "足寄町", "陸別町", "浦幌町",
+            ],
+            HokkaidoSubprefecture::Kushiro => {
+                &["釧路市", "釧路町", "厚岸町", "浜中町", "標茶町", "弟子屈町", "鶴居村", "白糠町"]
+            }
+            HokkaidoSubprefecture::Nemuro => &["根室市", "別海町", "中標津町", "標津町", "羅臼町"],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefecture_code_works() {
+        for (i, prefecture) in Prefecture::all().into_iter().enumerate() {
+            assert_eq!(prefecture.code() as usize, i + 1);
+        }
+    }
+
+    #[test]
+    fn prefectures_and_origins_are_consistent() {
+        for num in 1..=19 {
+            let origin = JprOrigin::parse(num).unwrap();
+            let prefectures = origin.prefectures();
+
+            assert!(!prefectures.is_empty(), "{:?}", origin);
+            for prefecture in prefectures {
+                assert!(prefecture.origins().contains(&origin));
+            }
+        }
+    }
+
+    #[test]
+    fn regions_and_origins_are_consistent() {
+        for num in 1..=19 {
+            let origin = JprOrigin::parse(num).unwrap();
+            let regions = origin.regions();
+
+            assert!(!regions.is_empty(), "{:?}", origin);
+            for region in regions {
+                assert_eq!(region.origin(), Some(origin));
+                assert!(origin.prefectures().contains(&region.prefecture()));
+            }
+        }
+
+        // 各都道府県の区域の原点は、都道府県の原点と一致する
+        for prefecture in Prefecture::all() {
+            let origins = prefecture
+                .origins()
+                .into_iter()
+                .filter(|origin| origin.regions().iter().any(|region| region.prefecture() == prefecture))
+                .collect::<Vec<_>>();
+            assert_eq!(origins, prefecture.origins(), "{:?}", prefecture);
+        }
+    }
+
+    #[test]
+    fn for_municipality_works() {
+        use JprOrigin::*;
+
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "伊達市"), vec![Eleven]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "室蘭市"), vec![Twelve]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "大空町"), vec![Thirteen]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Hokkaido, "紋別市"), vec![Twelve]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Tokyo, "八丈町"), vec![Nine]);
+        assert_eq!(
+            JprOrigin::for_municipality(Prefecture::Tokyo, "小笠原村"),
+            vec![Fourteen, Eighteen, Nineteen]
+        );
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Kagoshima, "与論町"), vec![One]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Kagoshima, "薩摩川内市"), vec![One, Two]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Kagoshima, "鹿屋市"), vec![Two]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Okinawa, "南大東村"), vec![Seventeen]);
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Okinawa, "那覇市"), vec![Fifteen]);
+        assert!(JprOrigin::for_municipality(Prefecture::Okinawa, "札幌市").is_empty());
+        assert_eq!(JprOrigin::for_municipality(Prefecture::Nagano, "松本市"), vec![Eight]);
+    }
+
+    #[test]
+    fn hokkaido_municipalities_have_one_origin() {
+        let mut municipalities = HokkaidoSubprefecture::all()
+            .iter()
+            .flat_map(|subprefecture| subprefecture.municipalities().iter().copied())
+            .collect::<Vec<_>>();
+        let count = municipalities.len();
+        municipalities.sort();
+        municipalities.dedup();
+        assert_eq!((count, municipalities.len()), (179, 179));
+
+        let count = |origin: JprOrigin| {
+            municipalities
+                .iter()
+                .filter(|name| JprOrigin::for_municipality(Prefecture::Hokkaido, name) == vec![origin])
+                .count()
+        };
+        assert_eq!(count(JprOrigin::Eleven) + count(JprOrigin::Twelve) + count(JprOrigin::Thirteen), 179);
+        assert_eq!(count(JprOrigin::Eleven), 42);
+        assert_eq!(count(JprOrigin::Thirteen), 43);
+
+        // 列挙された市町村は全て北海道の市町村である
+        for origin in [JprOrigin::Eleven, JprOrigin::Thirteen] {
+            for region in origin.regions() {
+                if let ZoneRegion::Municipalities(_, names) | ZoneRegion::SubprefectureTowns(_, names) = region {
+                    assert!(names.iter().all(|name| municipalities.contains(name)));
+                }
+            }
+        }
+    }
+}
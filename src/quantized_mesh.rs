@@ -0,0 +1,205 @@
+//! Quantization of vertex coordinates in the quantized-mesh terrain format of Cesium.
+//! Within a tile, the longitude and latitude are mapped linearly to u and v from 0 at the west and south edges to 32767 at the east and north edges, and the height to 0 to 32767 between the minimum and maximum height of the tile.
+//! The vertex data of a tile stores each of u, v and height as zig-zag encoded differences from the previous vertex.
+//!
+//! Cesiumの地形形式quantized-meshにおける頂点座標の量子化。
+//! タイル内で、経度と緯度は西端及び南端の0から東端及び北端の32767までのu及びvに線形に対応付けられ、高さはタイルの最低と最高の高さの間で0から32767に対応付けられる。
+//! タイルの頂点データは、u、v、高さのそれぞれを直前の頂点との差をジグザグ符号化して格納する。
+
+use crate::pixel_ll::{pixel2ll_f64, ZoomLv};
+
+/// Maximum value of quantized coordinates
+///
+/// 量子化された座標の最大値
+pub const QUANTIZED_MAX: u16 = 32767;
+
+/// Structure representing the extent of a quantized-mesh tile, the range of (longitude, latitude) expressed in the arc degree method and of the height (m)
+///
+/// quantized-meshのタイルの範囲を表す構造体。弧度法で表された(経度, 緯度)と高さ(m)の範囲からなる
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::quantized_mesh::QuantizedMeshBounds;
+///
+/// let bounds = QuantizedMeshBounds::from_tile((14552, 6451), ZoomLv::Lv14, 0., 50.);
+///
+/// let (u, v, h) = bounds.quantize((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), 3.5);
+/// let ((long, lat), height) = bounds.dequantize((u, v, h));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct QuantizedMeshBounds {
+    min_ll: (f64, f64),
+    max_ll: (f64, f64),
+    min_height: f64,
+    max_height: f64,
+}
+
+impl QuantizedMeshBounds {
+    /// Create the extent from the south west corner `min_ll`, the north east corner `max_ll` and the range of the height
+    ///
+    /// 南西の角`min_ll`、北東の角`max_ll`と高さの範囲から範囲を作成する
+    pub fn new(min_ll: (f64, f64), max_ll: (f64, f64), min_height: f64, max_height: f64) -> Self {
+        Self {
+            min_ll,
+            max_ll,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// Create the extent of the tile (x, y) of the Zoom level and the range of the height
+    ///
+    /// Zoomレベルのタイル(x, y)と高さの範囲から範囲を作成する
+    pub fn from_tile(tile: (u32, u32), zoom: ZoomLv, min_height: f64, max_height: f64) -> Self {
+        let (x, y) = (tile.0 as f64 * 256., tile.1 as f64 * 256.);
+        let (west, north) = pixel2ll_f64((x, y), zoom);
+        let (east, south) = pixel2ll_f64((x + 256., y + 256.), zoom);
+
+        Self::new((west, south), (east, north), min_height, max_height)
+    }
+
+    /// Returns the south west and north east corners ((longitude, latitude), (longitude, latitude))
+    ///
+    /// 南西の角と北東の角((経度, 緯度), (経度, 緯度))を返す
+    pub fn ll_bounds(&self) -> ((f64, f64), (f64, f64)) {
+        (self.min_ll, self.max_ll)
+    }
+
+    /// Returns the range of the height (minimum (m), maximum (m))
+    ///
+    /// 高さの範囲(最低(m), 最高(m))を返す
+    pub fn height_range(&self) -> (f64, f64) {
+        (self.min_height, self.max_height)
+    }
+
+    /// Quantize (longitude, latitude) expressed in the arc degree method and the height (m) to (u, v, height).
+    /// Values outside the extent are clamped to 0 to 32767. If the minimum and maximum height are equal, the height is quantized to 0.
+    ///
+    /// 弧度法で表された(経度, 緯度)と高さ(m)を(u, v, 高さ)に量子化する。
+    /// 範囲外の値は0から32767に切り詰められる。最低と最高の高さが等しい場合、高さは0に量子化される。
+    pub fn quantize(&self, ll: (f64, f64), height: f64) -> (u16, u16, u16) {
+        let (long, lat) = ll;
+
+        (
+            quantize(long, self.min_ll.0, self.max_ll.0),
+            quantize(lat, self.min_ll.1, self.max_ll.1),
+            quantize(height, self.min_height, self.max_height),
+        )
+    }
+
+    /// Convert quantized (u, v, height) to ((longitude, latitude), height) expressed in the arc degree method
+    ///
+    /// 量子化された(u, v, 高さ)を弧度法で表された((経度, 緯度), 高さ)に変換する
+    pub fn dequantize(&self, uvh: (u16, u16, u16)) -> ((f64, f64), f64) {
+        let (u, v, h) = uvh;
+
+        (
+            (
+                dequantize(u, self.min_ll.0, self.max_ll.0),
+                dequantize(v, self.min_ll.1, self.max_ll.1),
+            ),
+            dequantize(h, self.min_height, self.max_height),
+        )
+    }
+}
+
+fn quantize(value: f64, min: f64, max: f64) -> u16 {
+    if max <= min {
+        return 0;
+    }
+
+    ((value - min) / (max - min) * QUANTIZED_MAX as f64)
+        .round()
+        .clamp(0., QUANTIZED_MAX as f64) as u16
+}
+
+fn dequantize(value: u16, min: f64, max: f64) -> f64 {
+    min + (max - min) * value as f64 / QUANTIZED_MAX as f64
+}
+
+/// Function to encode quantized values of one of u, v and height into the vertex data of quantized-mesh, the zig-zag encoded differences from the previous value
+///
+/// u、v、高さのいずれかの量子化された値を、quantized-meshの頂点データである直前の値との差をジグザグ符号化したものに符号化する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::quantized_mesh::{decode_vertex_data, encode_vertex_data};
+///
+/// let encoded = encode_vertex_data(&[0, 32767, 100]);
+///
+/// assert_eq!(encoded, vec![0, 65534, 65333]);
+/// assert_eq!(decode_vertex_data(&encoded), vec![0, 32767, 100]);
+/// ```
+pub fn encode_vertex_data(values: &[u16]) -> Vec<u16> {
+    let mut prev = 0_i32;
+
+    values
+        .iter()
+        .map(|&value| {
+            let delta = value as i32 - prev;
+            prev = value as i32;
+            ((delta << 1) ^ (delta >> 31)) as u16
+        })
+        .collect()
+}
+
+/// Function to decode the vertex data of quantized-mesh into quantized values, the inverse of `encode_vertex_data`
+///
+/// quantized-meshの頂点データを量子化された値に復号する関数。`encode_vertex_data`の逆変換
+pub fn decode_vertex_data(encoded: &[u16]) -> Vec<u16> {
+    let mut value = 0_i32;
+
+    encoded
+        .iter()
+        .map(|&zigzag| {
+            let zigzag = zigzag as i32;
+            value += (zigzag >> 1) ^ -(zigzag & 1);
+            value as u16
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::pixel_ll::pixel2ll;
+
+    use super::*;
+
+    #[test]
+    fn quantize_maps_tile_edges() {
+        let (tile, zoom) = ((14552, 6451), ZoomLv::Lv14);
+        let bounds = QuantizedMeshBounds::from_tile(tile, zoom, -10., 90.);
+
+        // タイルの北西の角はu = 0、v = 32767となる
+        let north_west = pixel2ll((tile.0 * 256, tile.1 * 256), zoom);
+        assert_eq!(bounds.quantize(north_west, -10.), (0, QUANTIZED_MAX, 0));
+        let south_east = pixel2ll(((tile.0 + 1) * 256, (tile.1 + 1) * 256), zoom);
+        assert_eq!(bounds.quantize(south_east, 90.), (QUANTIZED_MAX, 0, QUANTIZED_MAX));
+
+        // 範囲外は切り詰められる
+        assert_eq!(bounds.quantize((0., 0.), 1000.), (0, 0, QUANTIZED_MAX));
+
+        let ((min_long, min_lat), (max_long, max_lat)) = bounds.ll_bounds();
+        let ll = ((min_long + max_long) / 2., (min_lat + max_lat) / 2.);
+        let ((long, lat), height) = bounds.dequantize(bounds.quantize(ll, 40.));
+        // 量子化の誤差は1段階の半分以内
+        assert_close_to(long, ll.0, 7);
+        assert_close_to(lat, ll.1, 7);
+        assert_close_to(height, 40., 2);
+    }
+
+    #[test]
+    fn vertex_data_round_trips() {
+        let values = [16383, 0, 32767, 32767, 1, 20000];
+        let encoded = encode_vertex_data(&values);
+
+        assert_eq!(encoded[0], 32766);
+        assert_eq!(encoded[3], 0);
+        assert_eq!(decode_vertex_data(&encoded), values);
+    }
+}
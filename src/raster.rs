@@ -0,0 +1,398 @@
+//! Rasterization of vector data into pixel coordinates, e.g. for drawing converted data into tile images.
+//!
+//! タイル画像への描画などのための、ベクタデータのピクセル座標へのラスタ化。
+
+use std::collections::BTreeSet;
+
+use crate::jpr_ll::JprOrigin;
+use crate::interpolation::slerp_ll;
+use crate::pixel_ll::{ll2pixel, ll2pixel_f64, world_size_px, ZoomLv};
+use crate::polygon::{jpr_ring2ll, ll_ring2pixel};
+
+/// Maximum depth of the recursive subdivision of a great circle (up to 2^16 segments)
+///
+/// 大円を再帰的に分割する深さの上限(最大2^16区間)
+const MAX_DEPTH: u32 = 16;
+
+/// Appends the pixels of the segment from `a` to `b` by Bresenham's algorithm, skipping `a` if it is already the last pixel
+///
+/// `a`から`b`までの線分のピクセルをブレゼンハムのアルゴリズムで追加する。`a`が既に最後のピクセルであれば省く
+fn bresenham(a: (u32, u32), b: (u32, u32), pixels: &mut Vec<(u32, u32)>) {
+    let (mut x, mut y) = (a.0 as i64, a.1 as i64);
+    let (x1, y1) = (b.0 as i64, b.1 as i64);
+
+    let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+    let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+    let mut error = dx + dy;
+
+    loop {
+        if pixels.last() != Some(&(x as u32, y as u32)) {
+            pixels.push((x as u32, y as u32));
+        }
+        if (x, y) == (x1, y1) {
+            break;
+        }
+
+        let e2 = 2 * error;
+        if e2 >= dy {
+            error += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Function to rasterize the segment between two points of (longitude, latitude) expressed in the arc degree method into the pixel coordinates it passes through at the Zoom level, by Bresenham's algorithm.
+/// The endpoints follow the rounding of `ll2pixel` and the pixels are 8-connected, from `a` to `b`.
+/// Without `geodesic` the segment is straight in pixel coordinates (a rhumb line); with it, the segment follows the great circle on a sphere, which is densified until it deviates from the straight pieces by at most half a pixel.
+/// The segment does not wrap around the antimeridian, and the endpoints of a great circle must not be antipodal.
+///
+/// 弧度法で表された(経度, 緯度)の2点間の線分を、ブレゼンハムのアルゴリズムで、Zoomレベルにおいて通過するピクセル座標にラスタ化する関数。
+/// 端点は`ll2pixel`の丸めに従い、ピクセルは`a`から`b`の順に8連結となる。
+/// `geodesic`でなければ線分はピクセル座標上の直線(航程線)となり、`geodesic`であれば球面上の大円に沿う。大円は直線に分割したときのずれが半ピクセル以下になるまで高密度化される。
+/// 線分は180度経線で折り返さず、大円の端点は対蹠点であってはならない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::{ll2pixel, ZoomLv};
+/// use coordinate_transformer::raster::rasterize_line;
+///
+/// let a = (139.76_f64.to_radians(), 35.68_f64.to_radians());
+/// let b = (139.77_f64.to_radians(), 35.69_f64.to_radians());
+///
+/// let pixels = rasterize_line(a, b, ZoomLv::Lv16, false);
+///
+/// assert_eq!(pixels.first(), Some(&ll2pixel(a, ZoomLv::Lv16)));
+/// assert_eq!(pixels.last(), Some(&ll2pixel(b, ZoomLv::Lv16)));
+/// ```
+pub fn rasterize_line(a: (f64, f64), b: (f64, f64), zoom: ZoomLv, geodesic: bool) -> Vec<(u32, u32)> {
+    fn subdivide(a: (f64, f64), b: (f64, f64), depth: u32, zoom: ZoomLv, vertices: &mut Vec<(f64, f64)>) {
+        let m = slerp_ll(a, b, 0.5);
+        let (pa, pb, pm) = (ll2pixel_f64(a, zoom), ll2pixel_f64(b, zoom), ll2pixel_f64(m, zoom));
+        let deviation = ((pm.0 - (pa.0 + pb.0) / 2.).powi(2) + (pm.1 - (pa.1 + pb.1) / 2.).powi(2)).sqrt();
+
+        if depth < MAX_DEPTH && deviation > 0.5 {
+            subdivide(a, m, depth + 1, zoom, vertices);
+            vertices.push(m);
+            subdivide(m, b, depth + 1, zoom, vertices);
+        }
+    }
+
+    let mut vertices = vec![a];
+    if geodesic {
+        subdivide(a, b, 0, zoom, &mut vertices);
+    }
+    vertices.push(b);
+
+    let mut pixels = vec![];
+    for segment in vertices.windows(2) {
+        bresenham(ll2pixel(segment[0], zoom), ll2pixel(segment[1], zoom), &mut pixels);
+    }
+
+    pixels
+}
+
+/// Calls `span` with (row, first column, end column (exclusive)) for each run of cells of a grid whose centers are inside the rings by the even-odd rule.
+/// The rings are in the units of the cells and are implicitly closed. A center on a left edge is inside and a center on a right edge is outside; likewise a center on an upper edge is inside and one on a lower edge is outside.
+///
+/// 偶奇規則で環の内側に中心がある格子のセルの連続ごとに、(行, 最初の列, 終わりの列(含まない))で`span`を呼ぶ。
+/// 環はセルの単位で表され、暗黙に閉じている。左の辺上の中心は内側、右の辺上の中心は外側となり、同様に上の辺上の中心は内側、下の辺上の中心は外側となる。
+fn scanline(rings: &[Vec<(f64, f64)>], size: i64, mut span: impl FnMut(i64, i64, i64)) {
+    let edges = rings
+        .iter()
+        .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+        .filter(|(a, b)| a.1 != b.1)
+        .collect::<Vec<_>>();
+
+    let (min_y, max_y) = edges
+        .iter()
+        .flat_map(|(a, b)| [a.1, b.1])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| (min.min(y), max.max(y)));
+    if edges.is_empty() {
+        return;
+    }
+
+    let first_row = ((min_y - 0.5).ceil() as i64).max(0);
+    let end_row = ((max_y - 0.5).ceil() as i64).min(size);
+
+    let mut crossings = vec![];
+    for row in first_row..end_row {
+        let cy = row as f64 + 0.5;
+
+        // 辺は上端を含み下端を含まないため、頂点を通る走査線で交差が重複しない
+        crossings.clear();
+        crossings.extend(edges.iter().filter_map(|&(a, b)| {
+            let (top, bottom) = if a.1 < b.1 { (a, b) } else { (b, a) };
+            (top.1 <= cy && cy < bottom.1).then(|| top.0 + (cy - top.1) * (bottom.0 - top.0) / (bottom.1 - top.1))
+        }));
+        crossings.sort_by(f64::total_cmp);
+
+        for pair in crossings.chunks_exact(2) {
+            let start = ((pair[0] - 0.5).ceil() as i64).max(0);
+            let end = ((pair[1] - 0.5).ceil() as i64).min(size);
+            if start < end {
+                span(row, start, end);
+            }
+        }
+    }
+}
+
+/// Function to rasterize a polygon of rings of (longitude, latitude) expressed in the arc degree method into the pixels it covers at the Zoom level, by scanlines.
+/// The first ring is the exterior and the others are holes, combined by the even-odd rule; the rings may or may not be closed.
+/// Edges, which are straight in longitude and latitude, are densified following `ll_ring2pixel` with `max_deviation_m` (m).
+/// A pixel is covered if its center is inside. A center exactly on an edge is inside for left and upper edges and outside for right and lower edges, so polygons sharing an edge never cover the same pixel.
+/// The pixels are in row-major order from the upper left.
+///
+/// 弧度法で表された(経度, 緯度)の環からなるポリゴンを、走査線によってZoomレベルにおいて覆うピクセルにラスタ化する関数。
+/// 最初の環が外周、それ以外が穴であり、偶奇規則で組み合わされる。環は閉じていてもいなくてもよい。
+/// 緯経度で直線である辺は、`max_deviation_m`(m)で`ll_ring2pixel`に従って高密度化される。
+/// ピクセルは中心が内側にあれば覆われる。中心がちょうど辺上にある場合、左と上の辺では内側、右と下の辺では外側となるため、辺を共有するポリゴンが同じピクセルを覆うことはない。
+/// ピクセルは左上から行優先の順に並ぶ。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::raster::rasterize_polygon;
+///
+/// let ring = vec![
+/// (139.76_f64.to_radians(), 35.68_f64.to_radians()),
+/// (139.77_f64.to_radians(), 35.68_f64.to_radians()),
+/// (139.77_f64.to_radians(), 35.69_f64.to_radians()),
+/// (139.76_f64.to_radians(), 35.69_f64.to_radians()),
+/// ];
+///
+/// let pixels = rasterize_polygon(&[ring], ZoomLv::Lv16, 1.);
+/// ```
+pub fn rasterize_polygon(rings: &[Vec<(f64, f64)>], zoom: ZoomLv, max_deviation_m: f64) -> Vec<(u32, u32)> {
+    let rings = rings
+        .iter()
+        .map(|ring| ll_ring2pixel(ring, zoom, max_deviation_m))
+        .collect::<Vec<_>>();
+
+    let mut pixels = vec![];
+    scanline(&rings, world_size_px(zoom) as i64, |row, start, end| {
+        pixels.extend((start..end).map(|x| (x as u32, row as u32)));
+    });
+
+    pixels
+}
+
+/// Function to rasterize a polygon of rings of plane rectangular coordinates (y, x) into the pixels it covers at the Zoom level, following `rasterize_polygon`.
+/// Edges, which are straight in plane rectangular coordinates, are densified following `jpr_ring2ll` with `max_deviation_m` (m).
+///
+/// `rasterize_polygon`に従って、平面直角座標(y, x)の環からなるポリゴンをZoomレベルにおいて覆うピクセルにラスタ化する関数。
+/// 平面直角座標で直線である辺は、`max_deviation_m`(m)で`jpr_ring2ll`に従って高密度化される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::raster::rasterize_jpr_polygon;
+///
+/// let ring = vec![(0., 0.), (100., 0.), (100., 100.), (0., 100.)];
+///
+/// let pixels = rasterize_jpr_polygon(&[ring], JprOrigin::Nine, ZoomLv::Lv18, 0.1);
+/// ```
+pub fn rasterize_jpr_polygon(
+    rings: &[Vec<(f64, f64)>],
+    origin: JprOrigin,
+    zoom: ZoomLv,
+    max_deviation_m: f64,
+) -> Vec<(u32, u32)> {
+    let rings = rings
+        .iter()
+        .map(|ring| jpr_ring2ll(ring, origin, max_deviation_m))
+        .collect::<Vec<_>>();
+
+    rasterize_polygon(&rings, zoom, max_deviation_m)
+}
+
+/// Function to return the tiles overlapped by a polygon of rings of (longitude, latitude) expressed in the arc degree method at the Zoom level.
+/// Unlike `rasterize_polygon`, a tile is included if any part of it overlaps the polygon, i.e. if an edge passes through it or its center is inside, so that no tile containing covered pixels is missed.
+/// A tile that the polygon only touches at its boundary may be included. The tiles are sorted by (x, y).
+///
+/// 弧度法で表された(経度, 緯度)の環からなるポリゴンが、Zoomレベルにおいて重なるタイルを返す関数。
+/// `rasterize_polygon`と異なり、タイルは一部でもポリゴンと重なれば、すなわち辺が通過するか中心が内側にあれば含まれるため、覆われるピクセルを含むタイルを取りこぼすことはない。
+/// ポリゴンが境界で接するだけのタイルが含まれることがある。タイルは(x, y)の順に並ぶ。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::raster::polygon_tiles;
+///
+/// let ring = vec![
+/// (139_f64.to_radians(), 35_f64.to_radians()),
+/// (140_f64.to_radians(), 35_f64.to_radians()),
+/// (140_f64.to_radians(), 36_f64.to_radians()),
+/// ];
+///
+/// let tiles = polygon_tiles(&[ring], ZoomLv::Lv10, 10.);
+/// ```
+pub fn polygon_tiles(rings: &[Vec<(f64, f64)>], zoom: ZoomLv, max_deviation_m: f64) -> Vec<(u32, u32)> {
+    let size = (world_size_px(zoom) / 256) as i64;
+    let rings = rings
+        .iter()
+        .map(|ring| {
+            ll_ring2pixel(ring, zoom, max_deviation_m)
+                .into_iter()
+                .map(|(x, y)| (x / 256., y / 256.))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut tiles = BTreeSet::new();
+    scanline(&rings, size, |row, start, end| {
+        tiles.extend((start..end).map(|x| (x, row)));
+    });
+
+    // 辺が通過するタイルを格子の走査で加える
+    let clamp = |v: f64| (v.floor() as i64).clamp(0, size - 1);
+    for ring in &rings {
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            let (mut x, mut y) = (clamp(a.0), clamp(a.1));
+            let (end_x, end_y) = (clamp(b.0), clamp(b.1));
+            tiles.insert((x, y));
+
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let (step_x, step_y) = (dx.signum() as i64, dy.signum() as i64);
+            // 次の縦と横の格子線に達するまでの媒介変数
+            let next = |v: i64, step: i64, start: f64, d: f64| {
+                if step == 0 {
+                    f64::INFINITY
+                } else {
+                    ((v + (step > 0) as i64) as f64 - start) / d
+                }
+            };
+
+            while (x, y) != (end_x, end_y) {
+                let (tx, ty) = (next(x, step_x, a.0, dx), next(y, step_y, a.1, dy));
+                if tx > 1. && ty > 1. {
+                    break;
+                }
+                if tx < ty {
+                    x += step_x;
+                } else {
+                    y += step_y;
+                }
+                if !(0..size).contains(&x) || !(0..size).contains(&y) {
+                    break;
+                }
+                tiles.insert((x, y));
+            }
+        }
+    }
+
+    tiles.into_iter().map(|(x, y)| (x as u32, y as u32)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pixel_ll::pixel2ll_f64;
+
+    use super::*;
+
+    #[test]
+    fn rasterize_line_is_connected() {
+        let zoom = ZoomLv::Lv12;
+        let a = pixel2ll_f64((100.5, 200.5), zoom);
+        let b = pixel2ll_f64((110.5, 204.5), zoom);
+
+        let pixels = rasterize_line(a, b, zoom, false);
+        assert_eq!(pixels.len(), 11);
+        assert_eq!(pixels[0], (100, 200));
+        assert_eq!(pixels[10], (110, 204));
+        assert!(pixels.windows(2).all(|w| {
+            let (dx, dy) = (w[0].0.abs_diff(w[1].0), w[0].1.abs_diff(w[1].1));
+            dx <= 1 && dy <= 1 && (dx, dy) != (0, 0)
+        }));
+
+        // 一点の線分は1ピクセルとなる
+        assert_eq!(rasterize_line(a, a, zoom, true), vec![(100, 200)]);
+    }
+
+    #[test]
+    fn geodesic_line_bends_poleward() {
+        let zoom = ZoomLv::Lv6;
+        let a = (-120_f64.to_radians(), 40_f64.to_radians());
+        let b = (-20_f64.to_radians(), 40_f64.to_radians());
+
+        let straight = rasterize_line(a, b, zoom, false);
+        let curved = rasterize_line(a, b, zoom, true);
+
+        assert_eq!(straight.first(), curved.first());
+        assert_eq!(straight.last(), curved.last());
+        assert!(straight.iter().all(|p| p.1 == straight[0].1));
+
+        // 北半球の大円は北に膨らむため、ピクセルのyは小さくなる
+        let min_y = curved.iter().map(|p| p.1).min().unwrap();
+        assert!(min_y + 100 < straight[0].1);
+        assert!(curved.windows(2).all(|w| w[0].0.abs_diff(w[1].0) <= 1 && w[0].1.abs_diff(w[1].1) <= 1));
+    }
+
+    #[test]
+    fn scanline_follows_edge_rules() {
+        let cells = |rings: &[Vec<(f64, f64)>]| {
+            let mut cells = vec![];
+            scanline(rings, 1 << 20, |row, start, end| cells.extend((start..end).map(|x| (x, row))));
+            cells
+        };
+        let square = |x0: f64, y0: f64, x1: f64, y1: f64| vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1)];
+
+        // 中心がちょうど辺上にある4 x 3セルの範囲
+        let left = cells(&[square(10.5, 20.5, 14.5, 23.5)]);
+        assert_eq!(left.len(), 12);
+        assert_eq!(left[0], (10, 20));
+        assert_eq!(left[11], (13, 22));
+
+        // 辺を共有するポリゴンは同じセルを覆わない
+        let right = cells(&[square(14.5, 20.5, 18.5, 23.5)]);
+        assert!(right.iter().all(|p| !left.contains(p)));
+        assert_eq!(right[0], (14, 20));
+
+        // 穴のセルは覆われない
+        let holed = cells(&[square(0., 0., 10., 10.), square(2., 2., 8., 8.)]);
+        assert_eq!(holed.len(), 100 - 36);
+        assert!(!holed.contains(&(5, 5)));
+    }
+
+    #[test]
+    fn rasterize_polygon_works() {
+        let zoom = ZoomLv::Lv12;
+        let ring = [(100.2, 200.2), (110.2, 200.2), (110.2, 205.2), (100.2, 205.2)].map(|p| pixel2ll_f64(p, zoom));
+
+        let pixels = rasterize_polygon(&[ring.to_vec()], zoom, 0.01);
+        assert_eq!(pixels.len(), 50);
+        assert_eq!(pixels[0], (100, 200));
+        assert_eq!(pixels[49], (109, 204));
+    }
+
+    #[test]
+    fn polygon_tiles_cover_all_pixels() {
+        let zoom = ZoomLv::Lv10;
+        let ring = vec![
+            (139_f64.to_radians(), 35_f64.to_radians()),
+            (140.3_f64.to_radians(), 35.2_f64.to_radians()),
+            (139.5_f64.to_radians(), 36.1_f64.to_radians()),
+        ];
+
+        let tiles = polygon_tiles(std::slice::from_ref(&ring), zoom, 10.);
+        let pixels = rasterize_polygon(std::slice::from_ref(&ring), zoom, 10.);
+        assert!(pixels.iter().all(|&(x, y)| tiles.binary_search(&(x / 256, y / 256)).is_ok()));
+
+        // 頂点を含むタイルは、中心が外側でも含まれる
+        let (x, y) = ll2pixel(ring[1], zoom);
+        assert!(tiles.contains(&(x / 256, y / 256)));
+
+        let square = vec![(0., 0.), (100., 0.), (100., 100.), (0., 100.)];
+        let jpr = rasterize_jpr_polygon(&[square], JprOrigin::Nine, ZoomLv::Lv18, 0.1);
+        assert!(!jpr.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! Uniform random sampling of longitude and latitude, enabled by the `rand` feature.
+//! The points are uniform in area on the WGS84 ellipsoid, not in degrees, so that high latitudes are not oversampled.
+//!
+//! `rand`フィーチャで有効になる、経度緯度の一様なランダムサンプリング。
+//! 点は度ではなくWGS84楕円体上の面積に対して一様であるため、高緯度が過剰にサンプリングされることはない。
+
+use rand::{Rng, RngExt};
+
+use crate::{authalic_to_geodetic_lat, geodetic_to_authalic_lat, math, JprOrigin};
+
+/// Size of the rectangle in the authalic coordinates (longitude, sine of the authalic latitude), which is proportional to its area on the ellipsoid
+///
+/// 正積座標(経度, 正積緯度の正弦)における矩形の大きさ。楕円体上の面積に比例する
+fn authalic_extent(min_ll: (f64, f64), max_ll: (f64, f64)) -> ((f64, f64), (f64, f64)) {
+    let min_s = math::sin(geodetic_to_authalic_lat(min_ll.1));
+    let max_s = math::sin(geodetic_to_authalic_lat(max_ll.1));
+
+    ((min_ll.0, min_s), (max_ll.0, max_s))
+}
+
+/// Function to sample (longitude, latitude) expressed in the arc degree method uniformly in area within the rectangle from `min_ll` to `max_ll`
+///
+/// `min_ll`から`max_ll`までの矩形内から、面積に対して一様に弧度法で表された(経度, 緯度)をサンプリングする関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::sampling::sample_ll_in_bounds;
+/// use rand::rngs::SmallRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let (long, lat) = sample_ll_in_bounds(
+/// &mut rng,
+/// (139_f64.to_radians(), 35_f64.to_radians()),
+/// (140_f64.to_radians(), 36_f64.to_radians()),
+/// );
+///
+/// assert!((139_f64.to_radians()..140_f64.to_radians()).contains(&long));
+/// ```
+pub fn sample_ll_in_bounds<R: Rng + ?Sized>(rng: &mut R, min_ll: (f64, f64), max_ll: (f64, f64)) -> (f64, f64) {
+    let ((min_long, min_s), (max_long, max_s)) = authalic_extent(min_ll, max_ll);
+
+    let long = min_long + (max_long - min_long) * rng.random::<f64>();
+    let s = min_s + (max_s - min_s) * rng.random::<f64>();
+
+    (long, authalic_to_geodetic_lat(math::asin(s)))
+}
+
+/// Function to sample (longitude, latitude) expressed in the arc degree method uniformly in area within the areas where the origin applies (`JprOrigin::areas`)
+///
+/// 原点が適用される区域(`JprOrigin::areas`)内から、面積に対して一様に弧度法で表された(経度, 緯度)をサンプリングする関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::sampling::sample_ll_in_zone;
+/// use rand::rngs::SmallRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let points = (0..100).map(|_| sample_ll_in_zone(&mut rng, JprOrigin::Nine)).collect::<Vec<_>>();
+/// ```
+pub fn sample_ll_in_zone<R: Rng + ?Sized>(rng: &mut R, origin: JprOrigin) -> (f64, f64) {
+    let (min_ll, max_ll) = origin.bounds();
+
+    // 区域を囲む矩形内で一様にサンプリングし、区域外の点を棄却する
+    loop {
+        let ll = sample_ll_in_bounds(rng, min_ll, max_ll);
+        if origin.contains(ll) {
+            return ll;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn sampling_is_uniform_in_area() {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        // 赤道から北緯60度までの帯の面積の半分は北緯約26.6度以南にある
+        let min_ll = (0., 0.);
+        let max_ll = (1., 60_f64.to_radians());
+        let half = authalic_to_geodetic_lat(math::asin(math::sin(geodetic_to_authalic_lat(max_ll.1)) / 2.));
+
+        let n = 20000;
+        let below = (0..n)
+            .map(|_| sample_ll_in_bounds(&mut rng, min_ll, max_ll))
+            .inspect(|&(long, lat)| {
+                assert!((0. ..1.).contains(&long));
+                assert!((0. ..=max_ll.1).contains(&lat));
+            })
+            .filter(|&(_, lat)| lat < half)
+            .count();
+
+        assert!((below as f64 / n as f64 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn sample_ll_in_zone_stays_in_areas() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            let ll = sample_ll_in_zone(&mut rng, JprOrigin::Thirteen);
+            assert_eq!(JprOrigin::applicable_zones(ll), vec![JprOrigin::Thirteen]);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Reading and writing of SIMA survey data files (coordinate data).
+//! Only UTF-8 text is handled, so files encoded in Shift_JIS must be decoded beforehand.
+//!
+//! SIMA測量データファイル(座標データ)の読み書き。
+//! UTF-8のテキストのみを扱うため、Shift_JISで符号化されたファイルは事前にデコードしておく必要がある。
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{jpr2ll, ll2jpr, JprOrigin};
+
+/// Error that occurs while parsing a SIMA file
+///
+/// SIMAファイルの解析中に発生するエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimaError {
+    /// The record on the given line (1-based) could not be parsed
+    ///
+    /// 指定された行(1始まり)のレコードを解析できなかった
+    InvalidRecord(usize),
+}
+
+impl Display for SimaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimaError::InvalidRecord(line) => write!(f, "invalid SIMA record at line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for SimaError {}
+
+/// Structure representing a point (A01 record) of a SIMA file
+///
+/// SIMAファイルの点(A01レコード)を表す構造体
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SimaPoint {
+    /// Point number
+    ///
+    /// 点番号
+    pub number: u32,
+    /// Point name
+    ///
+    /// 点名
+    pub name: String,
+    /// Plane rectangular coordinate x (m)
+    ///
+    /// 平面直角座標のx(m)
+    pub x: f64,
+    /// Plane rectangular coordinate y (m)
+    ///
+    /// 平面直角座標のy(m)
+    pub y: f64,
+    /// Height (m)
+    ///
+    /// 標高(m)
+    pub z: Option<f64>,
+}
+
+impl SimaPoint {
+    /// Create a point from (longitude, latitude) expressed in the arc degree method.
+    ///
+    /// 弧度法で表された(経度, 緯度)から点を作成する。
+    pub fn from_ll(number: u32, name: &str, ll: (f64, f64), z: Option<f64>, origin: JprOrigin) -> Self {
+        let (y, x) = ll2jpr(ll, origin);
+
+        Self {
+            number,
+            name: name.to_string(),
+            x,
+            y,
+            z,
+        }
+    }
+
+    /// Returns (longitude, latitude) expressed in the arc degree method.
+    ///
+    /// 弧度法で表された(経度, 緯度)を返す。
+    pub fn to_ll(&self, origin: JprOrigin) -> (f64, f64) {
+        jpr2ll((self.y, self.x), origin)
+    }
+}
+
+/// Structure representing the coordinate data of a SIMA file
+///
+/// SIMAファイルの座標データを表す構造体
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::sima::SimaFile;
+///
+/// let text = "G00,01,工事名,\r\nZ00,座標ﾃﾞｰﾀ,\r\nA00,\r\nA01,1,P1,11573.375,22694.980,10.000,\r\nA99,\r\nA99,END,\r\n";
+///
+/// let file: SimaFile = text.parse().unwrap();
+/// let (long, lat) = file.points[0].to_ll(JprOrigin::Nine);
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct SimaFile {
+    /// Job name of the G00 record
+    ///
+    /// G00レコードの工事名
+    pub job_name: String,
+    /// Points of the A01 records
+    ///
+    /// A01レコードの点
+    pub points: Vec<SimaPoint>,
+}
+
+impl FromStr for SimaFile {
+    type Err = SimaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut file = SimaFile::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let fields = line.trim().split(',').map(str::trim).collect::<Vec<_>>();
+
+            match fields[0] {
+                "G00" => {
+                    file.job_name = fields.get(2).unwrap_or(&"").to_string();
+                }
+                "A01" => {
+                    let err = || SimaError::InvalidRecord(i + 1);
+
+                    let number = fields.get(1).and_then(|v| v.parse().ok()).ok_or_else(err)?;
+                    let name = fields.get(2).ok_or_else(err)?.to_string();
+                    let x = fields.get(3).and_then(|v| v.parse().ok()).ok_or_else(err)?;
+                    let y = fields.get(4).and_then(|v| v.parse().ok()).ok_or_else(err)?;
+                    let z = match fields.get(5) {
+                        Some(v) if !v.is_empty() => Some(v.parse().map_err(|_| err())?),
+                        _ => None,
+                    };
+
+                    file.points.push(SimaPoint { number, name, x, y, z });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+impl Display for SimaFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "G00,01,{},\r\n", self.job_name)?;
+        write!(f, "Z00,座標ﾃﾞｰﾀ,\r\n")?;
+        write!(f, "A00,\r\n")?;
+
+        for point in &self.points {
+            write!(f, "A01,{},{},{:.3},{:.3},", point.number, point.name, point.x, point.y)?;
+            if let Some(z) = point.z {
+                write!(f, "{:.3}", z)?;
+            }
+            write!(f, ",\r\n")?;
+        }
+
+        write!(f, "A99,\r\n")?;
+        write!(f, "A99,END,\r\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sima_works() {
+        let text = "G00,01,テスト,\r\nZ00,座標ﾃﾞｰﾀ,\r\nA00,\r\nA01,1,P1,11573.375,22694.980,10.000,\r\nA01,2,P2,-100.000,200.500,,\r\nA99,\r\nA99,END,\r\n";
+
+        let file: SimaFile = text.parse().unwrap();
+
+        assert_eq!(file.job_name, "テスト");
+        assert_eq!(file.points.len(), 2);
+        assert_eq!(file.points[0].name, "P1");
+        assert_eq!((file.points[0].x, file.points[0].y, file.points[0].z), (11573.375, 22694.980, Some(10.)));
+        assert_eq!(file.points[1].z, None);
+
+        assert_eq!(file.to_string(), text);
+    }
+
+    #[test]
+    fn parse_sima_rejects_invalid_record() {
+        let text = "A00,\r\nA01,1,P1,abc,22694.980,\r\n";
+
+        assert_eq!(text.parse::<SimaFile>(), Err(SimaError::InvalidRecord(2)));
+    }
+}
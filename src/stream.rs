@@ -0,0 +1,128 @@
+//! Adapters that convert a `Stream` of coordinates or records on the fly, e.g. for services receiving coordinates over gRPC or WebSocket.
+//! The conversions of this crate are synchronous, so `convert_stream_chunked` runs them on batches of the items already available, while the concurrent adapters drive asynchronous conversions (e.g. ones run with `spawn_blocking` or needing I/O) with a bound on the number in flight.
+//!
+//! gRPCやWebSocketで座標を受信するサービスなどのための、座標またはレコードの`Stream`を逐次変換するアダプタ。
+//! このクレートの変換は同期的であるため、`convert_stream_chunked`は既に届いている要素のまとまりに対して変換を実行し、並行のアダプタは非同期の変換(`spawn_blocking`で実行するものやI/Oを必要とするものなど)を同時に実行する数に上限を設けて駆動する。
+
+use std::future::Future;
+
+use futures::stream::{self, Stream, StreamExt};
+
+/// Converts the items of a stream in batches of at most `chunk_size` items that are ready without waiting, with a batch conversion such as `ll2jpr_batch`.
+/// The order of the items is kept. Panics if `chunk_size` is 0.
+///
+/// ストリームの要素を、待たずに得られる最大`chunk_size`個のまとまりごとに、`ll2jpr_batch`のような一括変換で変換する。
+/// 要素の順序は保たれる。`chunk_size`が0の場合はパニックする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::batch::ll2jpr_batch;
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::stream::convert_stream_chunked;
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+///
+/// let points = stream::iter(vec![(140_f64.to_radians(), 36_f64.to_radians()); 3]);
+///
+/// let yx = block_on(convert_stream_chunked(points, 1024, |chunk| ll2jpr_batch(chunk, JprOrigin::Nine)).collect::<Vec<_>>());
+///
+/// assert_eq!(yx.len(), 3);
+/// ```
+pub fn convert_stream_chunked<S, F, O>(stream: S, chunk_size: usize, mut f: F) -> impl Stream<Item=O>
+where
+    S: Stream,
+    F: FnMut(&[S::Item]) -> Vec<O>,
+{
+    stream
+        .ready_chunks(chunk_size)
+        .flat_map(move |chunk| stream::iter(f(&chunk)))
+}
+
+/// Converts the items of a stream with an asynchronous conversion, running at most `limit` conversions at the same time.
+/// The results are yielded in the order of the input items. Panics if `limit` is 0.
+///
+/// ストリームの要素を非同期の変換で変換する。同時に実行する変換は最大`limit`個とする。
+/// 結果は入力の要素の順に得られる。`limit`が0の場合はパニックする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::{ll2jpr, JprOrigin};
+/// use coordinate_transformer::stream::convert_stream_concurrent;
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+///
+/// let points = stream::iter(vec![(140_f64.to_radians(), 36_f64.to_radians()); 3]);
+///
+/// let yx = block_on(convert_stream_concurrent(points, 4, |ll| async move { ll2jpr(ll, JprOrigin::Nine) }).collect::<Vec<_>>());
+///
+/// assert_eq!(yx.len(), 3);
+/// ```
+pub fn convert_stream_concurrent<S, F, Fut>(stream: S, limit: usize, f: F) -> impl Stream<Item=Fut::Output>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future,
+{
+    assert!(limit > 0, "limit must be positive");
+    stream.map(f).buffered(limit)
+}
+
+/// Same as `convert_stream_concurrent`, but yields the results as soon as they are ready, regardless of the order of the input items
+///
+/// `convert_stream_concurrent`と同じだが、入力の要素の順序に関わらず、結果が得られ次第返す
+pub fn convert_stream_unordered<S, F, Fut>(stream: S, limit: usize, f: F) -> impl Stream<Item=Fut::Output>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future,
+{
+    assert!(limit > 0, "limit must be positive");
+    stream.map(f).buffer_unordered(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::executor::block_on;
+    use futures::future::ready;
+
+    use crate::batch::ll2jpr_batch;
+    use crate::jpr_ll::{ll2jpr, JprOrigin};
+
+    use super::*;
+
+    #[test]
+    fn convert_stream_chunked_batches_ready_items() {
+        let points = (0..10).map(|i| ((140. + i as f64 * 0.01).to_radians(), 36_f64.to_radians())).collect::<Vec<_>>();
+        let calls = Cell::new(0);
+
+        let yx = block_on(
+            convert_stream_chunked(stream::iter(points.clone()), 4, |chunk| {
+                calls.set(calls.get() + 1);
+                ll2jpr_batch(chunk, JprOrigin::Nine)
+            })
+            .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(yx, ll2jpr_batch(&points, JprOrigin::Nine));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn concurrent_adapters_convert_every_item() {
+        let points = (0..10).map(|i| ((140. + i as f64 * 0.01).to_radians(), 36_f64.to_radians())).collect::<Vec<_>>();
+        let convert = |ll| ready(ll2jpr(ll, JprOrigin::Nine));
+
+        let ordered = block_on(convert_stream_concurrent(stream::iter(points.clone()), 3, convert).collect::<Vec<_>>());
+        assert_eq!(ordered, ll2jpr_batch(&points, JprOrigin::Nine));
+
+        let mut unordered = block_on(convert_stream_unordered(stream::iter(points.clone()), 3, convert).collect::<Vec<_>>());
+        unordered.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = ll2jpr_batch(&points, JprOrigin::Nine);
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(unordered, expected);
+    }
+}
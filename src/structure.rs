@@ -1,15 +1,24 @@
+//! Defines a structure representing each coordinate value.
+//! This reduces the risk of passing wrong arguments to the coordinate conversion function.
+//! It also allows coordinate conversions that must go through latitude and longitude to be done at once.
+//!
+//! 各座標値を表す構造体を定義しています。
+//! これを使用することで、座標変換関数に間違った引数を渡すリスクを減らせます。
+//! また、緯度経度を経由しなくてはいけない座標変換を一度にできます。
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
 #[cfg(feature = "vec-x")]
 use vec_x::VecX;
 
-/// Defines a structure representing each coordinate value.
-/// This reduces the risk of passing wrong arguments to the coordinate conversion function.
-/// It also allows coordinate conversions that must go through latitude and longitude to be done at once.
-///
-/// 各座標値を表す構造体を定義しています。
-/// これを使用することで、座標変換関数に間違った引数を渡すリスクを減らせます。
-/// また、緯度経度を経由しなくてはいけない座標変換を一度にできます。
-
-use crate::{jpr2ll, JprOrigin, ll2jpr, ll2pixel, llz2xyz, pixel2ll, xyz2llz, ZoomLv};
+use crate::dms::{format_dms_ja, parse_dms_ja, DmsParseError};
+use crate::interpolation::{lerp_ll, slerp_ll};
+use crate::maidenhead::{ll2maidenhead, maidenhead2ll, MaidenheadError};
+use crate::pixel_ll::{pixel2ll_f64, pixel_resolution, try_ll2pixel, PixelError};
+use crate::precision::{round_jpr_to_m, round_ll_to_m, round_xyz_to_m};
+use crate::utm::{ll2utm, utm2ll, utm_zone, Hemisphere};
+use crate::voxel_region::VoxelRegion;
+use crate::{jpr2ll, jpr2pixel, JprOrigin, ll2jpr, ll2pixel, llz2xyz, pixel2jpr, pixel2ll, pixel2xyz, xyz2llz, ZoomLv};
 
 /// structure representing latitude and longitude
 ///
@@ -35,6 +44,60 @@ impl LL {
         (self.long, self.lat)
     }
 
+    /// Format in the Japanese notation such as `北緯35度40分52.5秒 東経139度45分53.8秒`, following `format_dms_ja`
+    ///
+    /// `format_dms_ja`に従って`北緯35度40分52.5秒 東経139度45分53.8秒`のような日本語の表記に書式化する
+    pub fn to_dms_ja(&self, decimals: usize) -> String {
+        format_dms_ja(self.to_tuple(), decimals)
+    }
+
+    /// Parse the Japanese notation such as `北緯35度40分52.5秒 東経139度45分53.8秒`, following `parse_dms_ja`
+    ///
+    /// `parse_dms_ja`に従って`北緯35度40分52.5秒 東経139度45分53.8秒`のような日本語の表記を解析する
+    pub fn from_dms_ja(text: &str) -> Result<Self, DmsParseError> {
+        let (long, lat) = parse_dms_ja(text)?;
+        Ok(Self::new(long, lat))
+    }
+
+    /// Encode into a Maidenhead locator of `length` characters (2, 4, 6 or 8), following `ll2maidenhead`
+    ///
+    /// `ll2maidenhead`に従って`length`文字(2, 4, 6, 8)のメイデンヘッドロケーターに符号化する
+    pub fn to_maidenhead(&self, length: usize) -> String {
+        ll2maidenhead(self.to_tuple(), length)
+    }
+
+    /// Decode a Maidenhead locator into the center of its square, following `maidenhead2ll`
+    ///
+    /// `maidenhead2ll`に従ってメイデンヘッドロケーターをそのスクエアの中心に復号する
+    pub fn from_maidenhead(locator: &str) -> Result<Self, MaidenheadError> {
+        let (long, lat) = maidenhead2ll(locator)?;
+        Ok(Self::new(long, lat))
+    }
+
+    /// Returns the midpoint with another point, following `lerp_ll`, so that the longitude takes the shorter way across the antimeridian
+    ///
+    /// `lerp_ll`に従って別の点との中点を返す。経度は180度経線をまたいで短い方をとる
+    pub fn midpoint(&self, other: &LL) -> LL {
+        let (long, lat) = lerp_ll(self.to_tuple(), other.to_tuple(), 0.5);
+        LL::new(long, lat)
+    }
+
+    /// Returns the midpoint with another point along the great circle, following `slerp_ll`
+    ///
+    /// `slerp_ll`に従って別の点との大円に沿った中点を返す
+    pub fn great_circle_midpoint(&self, other: &LL) -> LL {
+        let (long, lat) = slerp_ll(self.to_tuple(), other.to_tuple(), 0.5);
+        LL::new(long, lat)
+    }
+
+    /// Snap to a grid whose spacing on the ground is `precision_m` (m), following `round_ll_to_m`
+    ///
+    /// `round_ll_to_m`に従って、地上での間隔が`precision_m`(m)の格子に丸める
+    pub fn round_to_m(&self, precision_m: f64) -> Self {
+        let (long, lat) = round_ll_to_m(self.to_tuple(), precision_m);
+        Self::new(long, lat)
+    }
+
     /// Returns an array of [longitude, latitude]
     ///
     /// [経度, 緯度]の配列を返す
@@ -51,6 +114,14 @@ impl LL {
         JPR::new(y, x, origin)
     }
 
+    /// Convert to a structure representing UTM coordinates of the zone and hemisphere
+    ///
+    /// ゾーンと半球のUTM座標を表す構造体に変換する
+    pub fn to_utm(&self, zone: u8, hemisphere: Hemisphere) -> Utm {
+        let (easting, northing) = ll2utm(self.to_tuple(), zone, hemisphere);
+        Utm::new(easting, northing, zone, hemisphere)
+    }
+
     /// Convert to a structure representing pixel coordinates
     ///
     /// Pixel座標を表す構造体に変換する
@@ -68,6 +139,143 @@ impl LL {
     }
 }
 
+/// Structure representing latitude and longitude as integer nano-degrees (1e-9 degrees, about 0.1 mm)
+/// Unlike `LL`, it implements `Eq`, `Hash` and `Ord`, so it can be used for deduplication and as a key of maps.
+///
+/// 緯度経度を整数のナノ度(1e-9度、約0.1mm)で表す構造体
+/// `LL`と異なり`Eq`、`Hash`、`Ord`を実装するため、重複の除去やマップのキーに使用できる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::structure::{LL, LLFixed};
+///
+/// let fixed = LLFixed::from(LL::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()));
+/// assert_eq!(fixed.to_tuple(), (139764930800, 35681240500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LLFixed {
+    long: i64,
+    lat: i64,
+}
+
+impl LLFixed {
+    /// Create a new latitude and longitude from nano-degrees
+    ///
+    /// ナノ度から緯度経度を新しく作成する
+    pub fn new(long_nano_deg: i64, lat_nano_deg: i64) -> Self {
+        Self {
+            long: long_nano_deg,
+            lat: lat_nano_deg,
+        }
+    }
+
+    /// Returns a tuple of (longitude, latitude) in nano-degrees
+    ///
+    /// ナノ度で表した(経度, 緯度)をタプルで返す
+    pub fn to_tuple(&self) -> (i64, i64) {
+        (self.long, self.lat)
+    }
+
+    /// Convert to a structure representing latitude and longitude
+    ///
+    /// 緯度経度を表す構造体に変換する
+    pub fn to_ll(&self) -> LL {
+        LL::new(
+            (self.long as f64 / 1e9).to_radians(),
+            (self.lat as f64 / 1e9).to_radians(),
+        )
+    }
+}
+
+impl From<LL> for LLFixed {
+    /// Rounds to the nearest nano-degree
+    ///
+    /// 最も近いナノ度に丸める
+    fn from(value: LL) -> Self {
+        let (long, lat) = value.to_tuple();
+        Self::new(
+            (long.to_degrees() * 1e9).round() as i64,
+            (lat.to_degrees() * 1e9).round() as i64,
+        )
+    }
+}
+
+impl From<LLFixed> for LL {
+    fn from(value: LLFixed) -> Self {
+        value.to_ll()
+    }
+}
+
+/// Structure representing latitude and longitude with altitude (m)
+///
+/// 標高(m)付きの緯度経度を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LLZ {
+    long: f64,
+    lat: f64,
+    altitude: f64,
+}
+
+impl LLZ {
+    /// Create a new latitude and longitude with altitude (m)
+    ///
+    /// 標高(m)付きの緯度経度を新しく作成する
+    pub fn new(long: f64, lat: f64, altitude: f64) -> Self {
+        Self { long, lat, altitude }
+    }
+
+    /// Returns a tuple of ((longitude, latitude), altitude)
+    ///
+    /// ((経度, 緯度), 標高)をタプルで返す
+    pub fn to_tuple(&self) -> ((f64, f64), f64) {
+        ((self.long, self.lat), self.altitude)
+    }
+
+    /// Returns an array of [longitude, latitude, altitude]
+    ///
+    /// [経度, 緯度, 標高]の配列を返す
+    #[cfg(feature = "vec-x")]
+    pub fn to_vec3(&self) -> VecX<f64, 3> {
+        VecX::new([self.long, self.lat, self.altitude])
+    }
+
+    /// Returns the altitude (m)
+    ///
+    /// 標高(m)を返す
+    pub fn altitude(&self) -> f64 {
+        self.altitude
+    }
+
+    /// Convert to a structure representing latitude and longitude
+    ///
+    /// 緯度経度を表す構造体に変換する
+    pub fn to_ll(&self) -> LL {
+        LL::new(self.long, self.lat)
+    }
+
+    /// Convert to a structure representing JPR coordinates with altitude (m)
+    ///
+    /// 平面直角座標を表す構造体と標高(m)に変換する
+    pub fn to_jpr_with_altitude(&self, origin: JprOrigin) -> (JPR, f64) {
+        (self.to_ll().to_jpr(origin), self.altitude)
+    }
+
+    /// Convert to a structure representing pixel coordinates with altitude (m)
+    ///
+    /// ピクセル座標を表す構造体と標高(m)に変換する
+    pub fn to_pixel_with_altitude(&self, zoom_lv: ZoomLv) -> (Pixel, f64) {
+        (self.to_ll().to_pixel(zoom_lv), self.altitude)
+    }
+
+    /// Convert to a structure representing Cartesian (EPSG:4979) coordinates
+    ///
+    /// 直交座標系(EPSG:4979)座標を表す構造体に変換する
+    pub fn to_xyz(&self) -> XYZ {
+        self.to_ll().to_xyz(self.altitude)
+    }
+}
+
 /// Convert to a structure representing plane rectangular coordinates
 ///
 /// 平面直角座標を表す構造体に変換する
@@ -82,7 +290,7 @@ impl JPR {
     /// Create a new JPR coordinate
     ///
     /// JPR座標を新しく作成する
-    pub fn new(x: f64, y: f64, origin: JprOrigin) -> Self {
+    pub fn new(y: f64, x: f64, origin: JprOrigin) -> Self {
         Self { y, x, origin }
     }
 
@@ -93,6 +301,14 @@ impl JPR {
         (self.y, self.x)
     }
 
+    /// Round to a multiple of `precision_m` (m)
+    ///
+    /// `precision_m`(m)の倍数に丸める
+    pub fn round_to_m(&self, precision_m: f64) -> Self {
+        let (y, x) = round_jpr_to_m(self.to_tuple(), precision_m);
+        Self::new(y, x, self.origin)
+    }
+
     /// Returns an array of [y, x]
     ///
     /// [y, x]の配列を返す
@@ -113,7 +329,7 @@ impl JPR {
     ///
     /// Pixel座標を表す構造体に変換する
     pub fn to_pixel(&self, zoom_lv: ZoomLv) -> Pixel {
-        let (x, y) = ll2pixel(self.to_ll().to_tuple(), zoom_lv);
+        let (x, y) = jpr2pixel(self.to_tuple(), self.origin, zoom_lv);
         Pixel::new(x, y, zoom_lv)
     }
 
@@ -126,6 +342,82 @@ impl JPR {
     }
 }
 
+/// Structure representing UTM coordinates
+///
+/// UTM座標を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Utm {
+    easting: f64,
+    northing: f64,
+    zone: u8,
+    hemisphere: Hemisphere,
+}
+
+impl Utm {
+    /// Create a new UTM coordinate
+    ///
+    /// UTM座標を新しく作成する
+    pub fn new(easting: f64, northing: f64, zone: u8, hemisphere: Hemisphere) -> Self {
+        Self {
+            easting,
+            northing,
+            zone,
+            hemisphere,
+        }
+    }
+
+    /// Create a UTM coordinate in the zone and hemisphere containing the latitude and longitude
+    ///
+    /// 緯度経度を含むゾーンと半球のUTM座標を作成する
+    pub fn from_ll(ll: &LL) -> Self {
+        let (long, lat) = ll.to_tuple();
+        ll.to_utm(utm_zone(long), Hemisphere::from_lat(lat))
+    }
+
+    /// Returns a tuple of (easting, northing)
+    ///
+    /// (東距, 北距)をタプルで返す
+    pub fn to_tuple(&self) -> (f64, f64) {
+        (self.easting, self.northing)
+    }
+
+    /// Returns the zone
+    ///
+    /// ゾーンを返す
+    pub fn zone(&self) -> u8 {
+        self.zone
+    }
+
+    /// Returns the hemisphere
+    ///
+    /// 半球を返す
+    pub fn hemisphere(&self) -> Hemisphere {
+        self.hemisphere
+    }
+
+    /// Convert to a structure representing latitude and longitude
+    ///
+    /// 緯度経度を表す構造体に変換する
+    pub fn to_ll(&self) -> LL {
+        let (long, lat) = utm2ll(self.to_tuple(), self.zone, self.hemisphere);
+        LL::new(long, lat)
+    }
+
+    /// Convert to a structure representing pixel coordinates
+    ///
+    /// Pixel座標を表す構造体に変換する
+    pub fn to_pixel(&self, zoom_lv: ZoomLv) -> Pixel {
+        self.to_ll().to_pixel(zoom_lv)
+    }
+
+    /// Convert to a structure representing Cartesian (EPSG:4979) coordinates
+    ///
+    /// 直交座標系(EPSG:4979)座標を表す構造体に変換する
+    pub fn to_xyz(&self, altitude: f64) -> XYZ {
+        self.to_ll().to_xyz(altitude)
+    }
+}
+
 /// Structure representing pixel coordinates
 ///
 /// ピクセル座標を表す構造体
@@ -151,6 +443,13 @@ impl Pixel {
         (self.x, self.y)
     }
 
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom
+    }
+
     /// Returns an array of [x, y]
     ///
     /// [x, y]の配列を返す
@@ -171,7 +470,7 @@ impl Pixel {
     ///
     /// 平面直角座標を表す構造体に変換する
     pub fn to_jpr(&self, origin: JprOrigin) -> JPR {
-        let (y, x) = ll2jpr(self.to_ll().to_tuple(), origin);
+        let (y, x) = pixel2jpr(self.to_tuple(), self.zoom, origin);
         JPR::new(y, x, origin)
     }
 
@@ -179,9 +478,111 @@ impl Pixel {
     ///
     /// 直交座標系(EPSG:4979)座標を表す構造体に変換する
     pub fn to_xyz(&self, altitude: f64) -> XYZ {
-        let (x, y, z) = llz2xyz(self.to_ll().to_tuple(), altitude);
+        let (x, y, z) = pixel2xyz(self.to_tuple(), self.zoom, altitude);
         XYZ::new(x, y, z)
     }
+
+    /// Returns the ground length (m) of a side of the pixel, following `pixel_resolution` at the latitude of the pixel center
+    ///
+    /// `pixel_resolution`に従って、ピクセルの中心の緯度におけるピクセルの一辺の地上での長さ(m)を返す
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    /// use coordinate_transformer::structure::Pixel;
+    ///
+    /// let pixel = Pixel::new(0, 131072, ZoomLv::Lv10);
+    ///
+    /// // 赤道のすぐ南のピクセル
+    /// assert!((pixel.resolution() - 156543.04 / 1024.).abs() < 1e-6);
+    /// ```
+    pub fn resolution(&self) -> f64 {
+        let (_, lat) = pixel2ll_f64((self.x as f64 + 0.5, self.y as f64 + 0.5), self.zoom);
+        pixel_resolution(lat, self.zoom)
+    }
+
+    /// Returns the tile containing the pixel
+    ///
+    /// ピクセルを含むタイルを返す
+    pub fn tile(&self) -> Tile {
+        Tile::new(self.x / 256, self.y / 256, self.zoom)
+    }
+
+    /// Returns the position (0 to 255) of the pixel within its tile
+    ///
+    /// タイル内におけるピクセルの位置(0から255)を返す
+    pub fn offset_in_tile(&self) -> (u32, u32) {
+        (self.x % 256, self.y % 256)
+    }
+}
+
+/// Structure representing tile coordinates of 256 x 256 pixel tiles
+///
+/// 256 x 256ピクセルのタイルのタイル座標を表す構造体
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::Pixel;
+///
+/// let pixel = Pixel::new(476868027, 211407949, ZoomLv::Lv21);
+/// let (tile, offset) = (pixel.tile(), pixel.offset_in_tile());
+///
+/// assert_eq!(tile.to_tuple(), (1862765, 825812));
+/// assert_eq!(tile.pixel(offset), pixel);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tile {
+    x: u32,
+    y: u32,
+    zoom: ZoomLv,
+}
+
+impl Tile {
+    /// Create a new tile coordinate
+    ///
+    /// タイル座標を新しく作成する
+    pub fn new(x: u32, y: u32, zoom: ZoomLv) -> Self {
+        Self { x, y, zoom }
+    }
+
+    /// Returns a tuple of (x, y)
+    ///
+    /// (x, y)をタプルで返す
+    pub fn to_tuple(&self) -> (u32, u32) {
+        (self.x, self.y)
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom
+    }
+
+    /// Returns the pixel at the position `offset` (0 to 255) within the tile
+    ///
+    /// タイル内の位置`offset`(0から255)のピクセルを返す
+    pub fn pixel(&self, offset: (u32, u32)) -> Pixel {
+        assert!(offset.0 < 256 && offset.1 < 256, "offset out of tile");
+        Pixel::new(self.x * 256 + offset.0, self.y * 256 + offset.1, self.zoom)
+    }
+
+    /// Returns the upper left pixel of the tile
+    ///
+    /// タイルの左上のピクセルを返す
+    pub fn origin(&self) -> Pixel {
+        self.pixel((0, 0))
+    }
+
+    /// Convert to a structure representing the latitude and longitude of the upper left corner of the tile
+    ///
+    /// タイルの左上の角の緯度経度を表す構造体に変換する
+    pub fn to_ll(&self) -> LL {
+        self.origin().to_ll()
+    }
 }
 
 /// Structure with height information added to pixel coordinates
@@ -206,6 +607,49 @@ impl Voxel {
         Self { x, y, z, resolution, zoom_lv }
     }
 
+    /// Create the voxel containing latitude and longitude with altitude (m) at the Zoom level.
+    /// The vertical resolution is the pixel resolution at the latitude of the center of the tile containing the voxel, as in `ElevationTile::voxel_resolution`, so that the voxels of a tile share the same grid.
+    /// Negative altitudes are clamped to 0 because voxel heights are unsigned.
+    ///
+    /// Zoomレベルにおいて、標高(m)付きの緯度経度を含むボクセルを作成する。
+    /// 鉛直方向の分解能は、`ElevationTile::voxel_resolution`と同様にボクセルを含むタイルの中心の緯度におけるピクセルの分解能であり、タイル内のボクセルは同じ格子を共有する。
+    /// ボクセルの高さは符号なしのため、負の標高は0に丸められる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    /// use coordinate_transformer::structure::{LLZ, Voxel};
+    ///
+    /// let llz = LLZ::new(139.7649308_f64.to_radians(), 35.6812405_f64.to_radians(), 100.);
+    /// let voxel = Voxel::from_llz(&llz, ZoomLv::Lv18);
+    ///
+    /// let (_, altitude) = voxel.to_ll_with_altitude();
+    /// assert!(altitude <= 100. && 100. < altitude + voxel.resolution());
+    /// ```
+    pub fn from_llz(llz: &LLZ, zoom_lv: ZoomLv) -> Self {
+        let (ll, altitude) = llz.to_tuple();
+        let (x, y) = ll2pixel(ll, zoom_lv);
+        let resolution = voxel_resolution((x, y), zoom_lv);
+
+        Self::new(x, y, (altitude / resolution) as u32, resolution, zoom_lv)
+    }
+
+    /// Create the voxel containing JPR coordinates with altitude (m) at the Zoom level, following `from_llz`
+    ///
+    /// `from_llz`に従って、Zoomレベルにおいて標高(m)付きの平面直角座標を含むボクセルを作成する
+    pub fn from_jprz(jprz: (JPR, f64), zoom_lv: ZoomLv) -> Self {
+        let (jpr, altitude) = jprz;
+        Self::from_llz(&LLZ::from((jpr.to_ll(), altitude)), zoom_lv)
+    }
+
+    /// Create the voxel containing Cartesian (EPSG:4979) coordinates at the Zoom level, following `from_llz`
+    ///
+    /// `from_llz`に従って、Zoomレベルにおいて直交座標系(EPSG:4979)座標を含むボクセルを作成する
+    pub fn from_xyz(xyz: &XYZ, zoom_lv: ZoomLv) -> Self {
+        Self::from_llz(&LLZ::from(*xyz), zoom_lv)
+    }
+
     /// Returns a tuple of (x, y, z)
     ///
     /// (x, y, z)をタプルで返す
@@ -213,6 +657,60 @@ impl Voxel {
         (self.x, self.y, self.z)
     }
 
+    /// Returns the vertical resolution (m)
+    ///
+    /// 鉛直方向の分解能(m)を返す
+    pub fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom_lv
+    }
+
+    /// Rescale to the Zoom level `zoom`, following `pixel_at_zoom` for all three axes.
+    /// The vertical resolution changes by the same factor as the horizontal one, so each zoom step halves or doubles the height of a voxel together with its width.
+    /// When zooming out, the voxel containing this voxel is returned; when zooming in, the lowest upper left voxel of the block covering this voxel is returned (see `children_at_zoom`).
+    /// Returns `None` when zooming in would make a coordinate, typically the altitude index z, exceed `u32::MAX`.
+    ///
+    /// 3軸とも`pixel_at_zoom`に従ってZoomレベル`zoom`に変換する。
+    /// 鉛直方向の分解能は水平方向と同じ倍率で変わるため、Zoomレベルが1つ変わるごとにボクセルの高さは幅とともに半分または2倍になる。
+    /// Zoomアウトではこのボクセルを含むボクセルを、Zoomインではこのボクセルを覆うブロックのうち最も低い左上のボクセルを返す(`children_at_zoom`を参照)。
+    /// Zoomインで座標(主に高さ方向のインデックスz)が`u32::MAX`を超える場合は`None`を返す。
+    pub fn at_zoom(&self, zoom: ZoomLv) -> Option<Self> {
+        let rescale = |v: u32| {
+            if zoom >= self.zoom_lv {
+                // 上位のビットが失われる場合は範囲外とする
+                let shift = zoom as u32 - self.zoom_lv as u32;
+                v.checked_shl(shift).filter(|&w| w >> shift == v)
+            } else {
+                Some(v >> (self.zoom_lv as u32 - zoom as u32))
+            }
+        };
+        let scale = 2_f64.powi(self.zoom_lv as i32 - zoom as i32);
+
+        Some(Self::new(rescale(self.x)?, rescale(self.y)?, rescale(self.z)?, self.resolution * scale, zoom))
+    }
+
+    /// Returns the region of the voxels at the finer Zoom level `zoom` that subdivide this voxel, following `at_zoom`.
+    /// Returns `None` if the coordinates of the region exceed `u32::MAX`.
+    ///
+    /// `at_zoom`に従って、このボクセルを分割する、より細かいZoomレベル`zoom`のボクセルの領域を返す。
+    /// 領域の座標が`u32::MAX`を超える場合は`None`を返す。
+    pub fn children_at_zoom(&self, zoom: ZoomLv) -> Option<VoxelRegion> {
+        assert!(zoom >= self.zoom_lv, "target Zoom level must not be coarser");
+
+        // 最小のボクセルの座標は下位のビットが0のため、n - 1を加えても範囲を超えない
+        let min = self.at_zoom(zoom)?;
+        let n = 1 << (zoom as u32 - self.zoom_lv as u32);
+        let (x, y, z) = min.to_tuple();
+
+        Some(VoxelRegion::new((x, y, z), (x + n - 1, y + n - 1, z + n - 1), min.resolution, zoom))
+    }
+
     /// Returns an array of [x, y, z]
     ///
     /// [x, y, z]の配列を返す
@@ -302,6 +800,69 @@ impl Voxel {
         let xyz = ll.to_xyz(altitude);
         (xyz, altitude)
     }
+
+    /// Returns the minimum and maximum corners of the axis-aligned bounding box of the voxel in the Cartesian coordinate system (EPSG:4979).
+    /// The voxel spans its pixel horizontally and from `z * resolution` to `(z + 1) * resolution` (m) vertically, and the box also covers the bulge of the ellipsoid between the corners.
+    ///
+    /// 直交座標系(EPSG:4979)におけるボクセルの軸平行な外接直方体の最小と最大の角を返す。
+    /// ボクセルは水平方向にはそのピクセル、鉛直方向には`z * resolution`から`(z + 1) * resolution`(m)までを占め、直方体は角の間の楕円体の膨らみも覆う。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    /// use coordinate_transformer::structure::Voxel;
+    ///
+    /// let voxel = Voxel::new(476868027, 211407949, 20, 0.5, ZoomLv::Lv21);
+    /// let (min, max) = voxel.xyz_aabb();
+    ///
+    /// let (x, y, z) = voxel.to_xyz().to_tuple();
+    /// assert!(min.to_tuple().0 <= x && x <= max.to_tuple().0);
+    /// ```
+    pub fn xyz_aabb(&self) -> (XYZ, XYZ) {
+        let (min_long, max_lat) = pixel2ll_f64((self.x as f64, self.y as f64), self.zoom_lv);
+        let (max_long, min_lat) = pixel2ll_f64((self.x as f64 + 1., self.y as f64 + 1.), self.zoom_lv);
+        let heights = [self.z as f64 * self.resolution, (self.z as f64 + 1.) * self.resolution];
+
+        // 各座標は経度では象限の境界、緯度では赤道で極値をとり得るため、範囲内にあればそれらも候補とする
+        let mut longs = vec![min_long, max_long];
+        longs.extend(
+            [-PI, -FRAC_PI_2, 0., FRAC_PI_2, PI]
+                .into_iter()
+                .filter(|long| min_long < *long && *long < max_long),
+        );
+        let mut lats = vec![min_lat, max_lat];
+        if min_lat < 0. && 0. < max_lat {
+            lats.push(0.);
+        }
+
+        let mut min = (f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &long in &longs {
+            for &lat in &lats {
+                for &h in &heights {
+                    let (x, y, z) = llz2xyz((long, lat), h);
+                    min = (min.0.min(x), min.1.min(y), min.2.min(z));
+                    max = (max.0.max(x), max.1.max(y), max.2.max(z));
+                }
+            }
+        }
+
+        (XYZ::new(min.0, min.1, min.2), XYZ::new(max.0, max.1, max.2))
+    }
+}
+
+/// Returns the vertical resolution (m) of the voxels of the tile containing the pixel, the pixel resolution at the latitude of the tile center
+///
+/// ピクセルを含むタイルのボクセルの鉛直方向の分解能(m)を返す。これはタイルの中心の緯度におけるピクセルの分解能である
+pub(crate) fn voxel_resolution(pixel: (u32, u32), zoom_lv: ZoomLv) -> f64 {
+    let center = (
+        (pixel.0 / 256 * 256 + 128) as f64,
+        (pixel.1 / 256 * 256 + 128) as f64,
+    );
+    let (_, lat) = pixel2ll_f64(center, zoom_lv);
+
+    pixel_resolution(lat, zoom_lv)
 }
 
 /// Structure representing Cartesian (EPSG:4979) coordinates
@@ -329,6 +890,14 @@ impl XYZ {
         (self.x, self.y, self.z)
     }
 
+    /// Round to a multiple of `precision_m` (m)
+    ///
+    /// `precision_m`(m)の倍数に丸める
+    pub fn round_to_m(&self, precision_m: f64) -> Self {
+        let (x, y, z) = round_xyz_to_m(self.to_tuple(), precision_m);
+        Self::new(x, y, z)
+    }
+
     /// Returns an array of [x, y, z]
     ///
     /// [x, y, z]の配列を返す
@@ -387,3 +956,168 @@ impl XYZ {
     }
 }
 
+impl From<(f64, f64)> for LL {
+    /// Create from a tuple of (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)のタプルから作成する
+    fn from(value: (f64, f64)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl From<LL> for (f64, f64) {
+    fn from(value: LL) -> Self {
+        value.to_tuple()
+    }
+}
+
+impl From<(LL, f64)> for LLZ {
+    /// Create from latitude and longitude and the altitude (m)
+    ///
+    /// 緯度経度と標高(m)から作成する
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::structure::{LL, LLZ};
+    ///
+    /// let llz = LLZ::from((LL::new(140_f64.to_radians(), 36_f64.to_radians()), 100.));
+    /// assert_eq!(llz.altitude(), 100.);
+    /// ```
+    fn from(value: (LL, f64)) -> Self {
+        let ((long, lat), altitude) = (value.0.to_tuple(), value.1);
+        Self::new(long, lat, altitude)
+    }
+}
+
+impl From<LLZ> for (LL, f64) {
+    fn from(value: LLZ) -> Self {
+        (value.to_ll(), value.altitude)
+    }
+}
+
+impl From<LLZ> for LL {
+    /// Drops the altitude
+    ///
+    /// 標高を取り除く
+    fn from(value: LLZ) -> Self {
+        value.to_ll()
+    }
+}
+
+impl From<LLZ> for XYZ {
+    fn from(value: LLZ) -> Self {
+        value.to_xyz()
+    }
+}
+
+impl From<XYZ> for LLZ {
+    fn from(value: XYZ) -> Self {
+        let (ll, altitude) = value.to_ll_with_altitude();
+        Self::from((ll, altitude))
+    }
+}
+
+impl From<JPR> for LL {
+    fn from(value: JPR) -> Self {
+        value.to_ll()
+    }
+}
+
+impl From<Utm> for LL {
+    fn from(value: Utm) -> Self {
+        value.to_ll()
+    }
+}
+
+impl From<LL> for Utm {
+    /// Converts in the zone and hemisphere containing the latitude and longitude, following `Utm::from_ll`
+    ///
+    /// `Utm::from_ll`に従って、緯度経度を含むゾーンと半球で変換する
+    fn from(value: LL) -> Self {
+        Self::from_ll(&value)
+    }
+}
+
+impl TryFrom<(LL, ZoomLv)> for Pixel {
+    type Error = PixelError;
+
+    /// Converts latitude and longitude to the pixel at the Zoom level, following `try_ll2pixel`.
+    /// Returns `Err` if the latitude and longitude are outside the range of Web Mercator.
+    ///
+    /// `try_ll2pixel`に従って、緯度経度をZoomレベルのピクセルに変換する。
+    /// 緯度経度がWebメルカトルの範囲外の場合は`Err`を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    /// use coordinate_transformer::structure::{LL, Pixel};
+    ///
+    /// assert!(Pixel::try_from((LL::new(0., 0.), ZoomLv::Lv10)).is_ok());
+    /// assert!(Pixel::try_from((LL::new(0., 89_f64.to_radians()), ZoomLv::Lv10)).is_err());
+    /// ```
+    fn try_from(value: (LL, ZoomLv)) -> Result<Self, Self::Error> {
+        let (ll, zoom) = value;
+        let (x, y) = try_ll2pixel(ll.to_tuple(), zoom)?;
+        Ok(Self::new(x, y, zoom))
+    }
+}
+
+impl From<Pixel> for LL {
+    fn from(value: Pixel) -> Self {
+        value.to_ll()
+    }
+}
+
+impl From<Pixel> for Tile {
+    /// Returns the tile containing the pixel
+    ///
+    /// ピクセルを含むタイルを返す
+    fn from(value: Pixel) -> Self {
+        value.tile()
+    }
+}
+
+impl From<Tile> for Pixel {
+    /// Returns the upper left pixel of the tile
+    ///
+    /// タイルの左上のピクセルを返す
+    fn from(value: Tile) -> Self {
+        value.origin()
+    }
+}
+
+impl From<Voxel> for Pixel {
+    /// Drops the height
+    ///
+    /// 高さを取り除く
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coordinate_transformer::pixel_ll::ZoomLv;
+    /// use coordinate_transformer::structure::{Pixel, Voxel};
+    ///
+    /// let pixel = Pixel::from(Voxel::new(10, 20, 30, 1., ZoomLv::Lv10));
+    /// assert_eq!(pixel, Pixel::new(10, 20, ZoomLv::Lv10));
+    /// ```
+    fn from(value: Voxel) -> Self {
+        value.to_pixel()
+    }
+}
+
+impl From<Voxel> for LLZ {
+    /// Converts the upper left corner of the pixel and the altitude of the bottom of the voxel
+    ///
+    /// ピクセルの左上の角とボクセルの底面の標高を変換する
+    fn from(value: Voxel) -> Self {
+        Self::from(value.to_ll_with_altitude())
+    }
+}
+
+impl From<Voxel> for XYZ {
+    fn from(value: Voxel) -> Self {
+        value.to_xyz()
+    }
+}
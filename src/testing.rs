@@ -0,0 +1,105 @@
+//! `proptest` strategies for the types of this crate, enabled by the `testing` feature.
+//! Downstream crates can property-test their own pipelines with e.g. `any::<LL>()` without writing generators for these types.
+//!
+//! `testing`フィーチャで有効になる、このクレートの型に対する`proptest`のストラテジ。
+//! 下流のクレートは、これらの型の生成器を書くことなく`any::<LL>()`などで自身の処理をプロパティテストできる。
+//!
+//! # Examples
+//!
+//! ```
+//! use coordinate_transformer::pixel_ll::{pixel2ll, ll2pixel};
+//! use coordinate_transformer::structure::Pixel;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(pixel in any::<Pixel>())| {
+//!     let ll = pixel2ll(pixel.to_tuple(), pixel.zoom());
+//!     prop_assert!(ll.1.abs() <= 90_f64.to_radians());
+//! });
+//! ```
+
+use std::f64::consts::PI;
+
+use proptest::prelude::*;
+
+use crate::{world_size_px, JprOrigin, Pixel, ZoomLv, LL, MAX_MERCATOR_LAT, XYZ};
+
+/// Longitude and latitude within the domain of Web Mercator, so that every conversion of this crate is defined
+///
+/// このクレートの全ての変換が定義されるよう、Webメルカトルの定義域内の経度緯度
+impl Arbitrary for LL {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (-PI..PI, -MAX_MERCATOR_LAT..=MAX_MERCATOR_LAT)
+            .prop_map(|(long, lat)| LL::new(long, lat))
+            .boxed()
+    }
+}
+
+impl Arbitrary for JprOrigin {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (1_u8..=19).prop_map(|n| JprOrigin::parse(n).unwrap()).boxed()
+    }
+}
+
+impl Arbitrary for ZoomLv {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (0_u8..=24).prop_map(|n| ZoomLv::parse(n).unwrap()).boxed()
+    }
+}
+
+/// Pixel coordinates within the world at an arbitrary Zoom level
+///
+/// 任意のZoomレベルにおける世界の範囲内のピクセル座標
+impl Arbitrary for Pixel {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<ZoomLv>()
+            .prop_flat_map(|zoom| {
+                let max = (world_size_px(zoom) - 1) as u32;
+                (0..=max, 0..=max).prop_map(move |(x, y)| Pixel::new(x, y, zoom))
+            })
+            .boxed()
+    }
+}
+
+/// Cartesian coordinates of points between 1000 m below and 10000 m above the ellipsoid
+///
+/// 楕円体の1000m下から10000m上までの点の直交座標
+impl Arbitrary for XYZ {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<LL>(), -1000_f64..10000.)
+            .prop_map(|(ll, altitude)| ll.to_xyz(altitude))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn arbitrary_values_are_in_domain(pixel in any::<Pixel>(), xyz in any::<XYZ>()) {
+            let (x, y) = pixel.to_tuple();
+            prop_assert!((x as u64) < world_size_px(pixel.zoom()));
+            prop_assert!((y as u64) < world_size_px(pixel.zoom()));
+
+            let (x, y, z) = xyz.to_tuple();
+            let r = (x * x + y * y + z * z).sqrt();
+            prop_assert!((6_355_000. ..6_390_000.).contains(&r));
+        }
+    }
+}
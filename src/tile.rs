@@ -0,0 +1,711 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::pixel_ll::{ll2pixel_f64, pixel2ll_f64};
+use crate::{ll2pixel, pixel2tile, pixel_resolution, world_size_px, ZoomLv};
+
+/// Structure representing a tile grid with the tile size in pixels.
+/// The default tile size is 256 pixels, which is what the free functions of this crate assume.
+/// Pixel coordinates of the grid are `u64`, since the world size `tile_px * 2^zoom` exceeds the range of `u32` for tiles larger than 256 pixels at Zoom level 24.
+///
+/// タイルの大きさ(ピクセル)を持つタイルグリッドを表す構造体。
+/// 既定のタイルの大きさは256ピクセルであり、このクレートの関数はこれを前提としている。
+/// 256ピクセルより大きいタイルではZoomレベル24の世界の大きさ`tile_px * 2^zoom`が`u32`の範囲を超えるため、グリッドのピクセル座標は`u64`とする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::TileGrid;
+///
+/// let grid = TileGrid::new(512);
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+///
+/// let (x, y) = grid.ll2pixel(ll, ZoomLv::Lv10);
+/// let (tile_x, tile_y) = grid.pixel2tile((x, y));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TileGrid {
+    tile_px: u32,
+}
+
+impl Default for TileGrid {
+    fn default() -> Self {
+        Self { tile_px: 256 }
+    }
+}
+
+impl TileGrid {
+    /// Create a new tile grid with the tile size in pixels (e.g. 256, 512 or 1024)
+    ///
+    /// タイルの大きさ(ピクセル、256、512、1024など)を指定してタイルグリッドを新しく作成する
+    pub fn new(tile_px: u32) -> Self {
+        assert!(tile_px > 0, "tile size must be positive");
+        Self { tile_px }
+    }
+
+    /// Returns the tile size in pixels
+    ///
+    /// タイルの大きさ(ピクセル)を返す
+    pub fn tile_px(&self) -> u32 {
+        self.tile_px
+    }
+
+    /// Ratio of the tile size to 256 pixels
+    ///
+    /// 256ピクセルに対するタイルの大きさの比
+    fn scale(&self) -> f64 {
+        self.tile_px as f64 / 256.
+    }
+
+    /// Returns the width (= height) of the world in pixels at the Zoom level
+    ///
+    /// Zoomレベルにおける世界の幅(=高さ)をピクセル数で返す
+    pub fn world_size_px(&self, zoom: ZoomLv) -> u64 {
+        (self.tile_px as u64) << zoom as u32
+    }
+
+    /// Converts (longitude, latitude) expressed in the arc degree method to pixel coordinates (x, y) of the grid
+    ///
+    /// 弧度法で表された(経度, 緯度)をグリッドのピクセル座標(x, y)に変換する
+    pub fn ll2pixel(&self, ll: (f64, f64), zoom: ZoomLv) -> (u64, u64) {
+        let (x, y) = ll2pixel_f64(ll, zoom);
+        ((x * self.scale()) as u64, (y * self.scale()) as u64)
+    }
+
+    /// Converts pixel coordinates (x, y) of the grid to (longitude, latitude) expressed in the arc degree method
+    ///
+    /// グリッドのピクセル座標(x, y)を弧度法で表された(経度, 緯度)に変換する
+    pub fn pixel2ll(&self, pixel: (u64, u64), zoom: ZoomLv) -> (f64, f64) {
+        let (x, y) = pixel;
+        pixel2ll_f64((x as f64 / self.scale(), y as f64 / self.scale()), zoom)
+    }
+
+    /// Converts pixel coordinates of the grid to tile coordinates
+    ///
+    /// グリッドのピクセル座標をタイル座標に変換する
+    pub fn pixel2tile(&self, pixel: (u64, u64)) -> (u32, u32) {
+        let (x, y) = pixel;
+        let tile_px = self.tile_px as u64;
+
+        // タイル数はZoomレベル24でも2^24であり、u32に収まる
+        ((x / tile_px) as u32, (y / tile_px) as u32)
+    }
+
+    /// Converts (longitude, latitude) expressed in the arc degree method to tile coordinates
+    ///
+    /// 弧度法で表された(経度, 緯度)をタイル座標に変換する
+    pub fn ll2tile(&self, ll: (f64, f64), zoom: ZoomLv) -> (u32, u32) {
+        self.pixel2tile(self.ll2pixel(ll, zoom))
+    }
+
+    /// Returns the length per pixel (m) of the grid according to the latitude expressed in the arc degree method and the Zoom level
+    ///
+    /// 弧度法で表された緯度とZoomレベルに応じたグリッドの1ピクセルあたりの長さ(m)を返す
+    pub fn pixel_resolution(&self, lat: f64, zoom: ZoomLv) -> f64 {
+        pixel_resolution(lat, zoom) / self.scale()
+    }
+}
+
+/// Structure representing tile coordinates that may lie outside the world in the x direction, as produced by panning a world map across the antimeridian.
+/// The x coordinate is normalized modulo the number of tiles of the world, while the y coordinate is not wrapped.
+///
+/// 日付変更線をまたいで世界地図を移動させた場合のように、x方向に世界の範囲外となりうるタイル座標を表す構造体。
+/// x座標は世界のタイル数を法として正規化されるが、y座標は折り返されない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::WrappedTile;
+///
+/// let tile = WrappedTile::new(-1, 0, ZoomLv::Lv2);
+///
+/// assert_eq!(tile.normalize(), Some((3, 0)));
+/// assert_eq!(tile.world_copy(), -1);
+/// assert_eq!(WrappedTile::new(0, 4, ZoomLv::Lv2).normalize(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WrappedTile {
+    /// Tile coordinate x, which may be outside the world
+    ///
+    /// タイル座標x。世界の範囲外でもよい
+    pub x: i64,
+    /// Tile coordinate y
+    ///
+    /// タイル座標y
+    pub y: i64,
+    /// Zoom level
+    ///
+    /// Zoomレベル
+    pub zoom: ZoomLv,
+}
+
+impl WrappedTile {
+    /// Create new signed tile coordinates
+    ///
+    /// 符号付きのタイル座標を新しく作成する
+    pub fn new(x: i64, y: i64, zoom: ZoomLv) -> Self {
+        Self { x, y, zoom }
+    }
+
+    /// Returns the number of tiles of the world along one axis at the Zoom level
+    ///
+    /// Zoomレベルにおける世界の一辺のタイル数を返す
+    fn tiles(&self) -> i64 {
+        self.zoom.tile_count() as i64
+    }
+
+    /// Returns the tile coordinates with x normalized into the world, or `None` if y is outside the world
+    ///
+    /// xを世界の範囲内に正規化したタイル座標を返す。yが世界の範囲外の場合は`None`を返す
+    pub fn normalize(&self) -> Option<(u32, u32)> {
+        if !(0..self.tiles()).contains(&self.y) {
+            return None;
+        }
+
+        Some((self.x.rem_euclid(self.tiles()) as u32, self.y as u32))
+    }
+
+    /// Returns which copy of the world the tile is in: 0 for the primary world, -1 for the copy to the west, 1 for the copy to the east, and so on
+    ///
+    /// タイルが何番目の世界の複製にあるかを返す。主たる世界は0、西隣の複製は-1、東隣の複製は1などとなる
+    pub fn world_copy(&self) -> i64 {
+        self.x.div_euclid(self.tiles())
+    }
+}
+
+impl From<((u32, u32), ZoomLv)> for WrappedTile {
+    fn from(((x, y), zoom): ((u32, u32), ZoomLv)) -> Self {
+        Self::new(x as i64, y as i64, zoom)
+    }
+}
+
+/// Function to return the tiles adjacent to a tile (up to 8, in the order from the upper left to the lower right).
+/// Neighbours outside the world are omitted; use `tile_neighbors_wrapped` to wrap in the x direction across the antimeridian.
+///
+/// タイルに隣接するタイル(最大8つ、左上から右下の順)を返す関数。
+/// 世界の範囲外の隣接タイルは省かれる。日付変更線をまたいでx方向に折り返すには`tile_neighbors_wrapped`を用いる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::tile_neighbors;
+///
+/// assert_eq!(tile_neighbors((0, 0), ZoomLv::Lv1), vec![(1, 0), (0, 1), (1, 1)]);
+/// ```
+pub fn tile_neighbors(tile: (u32, u32), zoom: ZoomLv) -> Vec<(u32, u32)> {
+    neighbors(tile, zoom, false)
+}
+
+/// Function to return the tiles adjacent to a tile, wrapping in the x direction across the antimeridian and clamping in the y direction.
+/// At low Zoom levels where the same tile is adjacent on both sides, it is returned once, and the tile itself is never returned.
+///
+/// タイルに隣接するタイルを、x方向には日付変更線をまたいで折り返し、y方向には世界の範囲内に制限して返す関数。
+/// 同じタイルが両側で隣接する低いZoomレベルでは1度だけ返され、タイル自身は返されない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::tile_neighbors_wrapped;
+///
+/// let neighbors = tile_neighbors_wrapped((0, 0), ZoomLv::Lv2);
+/// assert!(neighbors.contains(&(3, 0)));
+/// assert_eq!(neighbors.len(), 5);
+/// ```
+pub fn tile_neighbors_wrapped(tile: (u32, u32), zoom: ZoomLv) -> Vec<(u32, u32)> {
+    neighbors(tile, zoom, true)
+}
+
+fn neighbors(tile: (u32, u32), zoom: ZoomLv, wrap: bool) -> Vec<(u32, u32)> {
+    let (x, y) = (tile.0 as i64, tile.1 as i64);
+    let tiles = zoom.tile_count() as i64;
+
+    let mut result = Vec::with_capacity(8);
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (nx, ny) = (x + dx, y + dy);
+            if (dx, dy) == (0, 0) || !(0..tiles).contains(&ny) || (!wrap && !(0..tiles).contains(&nx)) {
+                continue;
+            }
+
+            let Some(neighbor) = WrappedTile::new(nx, ny, zoom).normalize() else {
+                continue;
+            };
+            if neighbor != tile && !result.contains(&neighbor) {
+                result.push(neighbor);
+            }
+        }
+    }
+
+    result
+}
+
+/// Clips the segment from `p0` to `p1` to the rectangle from `min` to `max` with the Liang-Barsky algorithm.
+/// Clipped endpoints are placed exactly on the edge of the rectangle.
+///
+/// Liang-Barskyのアルゴリズムで`p0`から`p1`までの線分を`min`から`max`までの矩形で切り取る。
+/// 切り取られた端点は矩形の辺上に正確に置かれる。
+fn clip_segment(p0: (f64, f64), p1: (f64, f64), min: (f64, f64), max: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (mut t0, mut t1) = (0_f64, 1_f64);
+    let (mut enter, mut exit) = (None, None);
+
+    for (edge, p, q) in [
+        (0, -dx, p0.0 - min.0),
+        (1, dx, max.0 - p0.0),
+        (2, -dy, p0.1 - min.1),
+        (3, dy, max.1 - p0.1),
+    ] {
+        if p == 0. {
+            if q < 0. {
+                return None;
+            }
+        } else if p < 0. {
+            if q / p > t0 {
+                t0 = q / p;
+                enter = Some(edge);
+            }
+        } else if q / p < t1 {
+            t1 = q / p;
+            exit = Some(edge);
+        }
+    }
+
+    if t0 > t1 {
+        return None;
+    }
+
+    let snap = |p: (f64, f64), edge: Option<u8>| match edge {
+        Some(0) => (min.0, p.1),
+        Some(1) => (max.0, p.1),
+        Some(2) => (p.0, min.1),
+        Some(3) => (p.0, max.1),
+        _ => p,
+    };
+
+    Some((
+        snap((p0.0 + t0 * dx, p0.1 + t0 * dy), enter),
+        snap((p0.0 + t1 * dx, p0.1 + t1 * dy), exit),
+    ))
+}
+
+/// Function to clip a line of (longitude, latitude) expressed in the arc degree method to the bounds of a tile, enlarged by `buffer_px` pixels on each side.
+/// The line is straight between vertices in pixel coordinates, so a geodesic should be densified beforehand.
+/// Returns the portions inside the tile as lines in tile-local pixel coordinates, where (0, 0) is the upper left corner and (256, 256) the lower right corner of the tile.
+/// Because the clipping is done in the same pixel coordinates as the tiling, the portions of adjacent tiles meet exactly at the seams.
+///
+/// 弧度法で表された(経度, 緯度)の線を、各辺を`buffer_px`ピクセル広げたタイルの範囲で切り取る関数。
+/// 線は頂点間でピクセル座標上の直線となるため、測地線はあらかじめ細分化しておく必要がある。
+/// タイル内の部分を、(0, 0)がタイルの左上隅、(256, 256)が右下隅となるタイル内のピクセル座標の線として返す。
+/// タイル分割と同じピクセル座標で切り取るため、隣接するタイルの部分は継ぎ目で正確に一致する。
+///
+/// # Examples
+///
+/// Clip a line crossing the tile containing Tokyo Station.
+///
+/// 東京駅を含むタイルを横切る線を切り取る。
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::clip_line_to_tile;
+///
+/// let line = [
+/// (139.74_f64.to_radians(), 35.6812405_f64.to_radians()),
+/// (139.78_f64.to_radians(), 35.6812405_f64.to_radians()),
+/// ];
+///
+/// let parts = clip_line_to_tile(&line, (14552, 6451), ZoomLv::Lv14, 0.);
+///
+/// assert_eq!(parts.len(), 1);
+/// assert_eq!(parts[0][0].0, 0.);
+/// ```
+pub fn clip_line_to_tile(line: &[(f64, f64)], tile: (u32, u32), zoom: ZoomLv, buffer_px: f64) -> Vec<Vec<(f64, f64)>> {
+    let origin = (tile.0 as f64 * 256., tile.1 as f64 * 256.);
+    let min = (-buffer_px, -buffer_px);
+    let max = (256. + buffer_px, 256. + buffer_px);
+
+    let local = line
+        .iter()
+        .map(|&ll| {
+            let (x, y) = ll2pixel_f64(ll, zoom);
+            (x - origin.0, y - origin.1)
+        })
+        .collect::<Vec<_>>();
+
+    let mut parts: Vec<Vec<(f64, f64)>> = vec![];
+    let mut open = false;
+
+    for segment in local.windows(2) {
+        let Some((a, b)) = clip_segment(segment[0], segment[1], min, max) else {
+            open = false;
+            continue;
+        };
+
+        match parts.last_mut() {
+            Some(part) if open && part.last() == Some(&a) => part.push(b),
+            _ => parts.push(vec![a, b]),
+        }
+
+        // 線分の終点が切り取られていなければ、次の線分に続く
+        open = b == segment[1];
+    }
+
+    parts
+}
+
+/// Structure representing the window of pixels of a tile covered by a bounding box.
+/// `offset` is the position of the upper left pixel of the window within the tile (0 to 255) and `size` is its width and height in pixels.
+///
+/// 矩形範囲に覆われるタイルのピクセルの窓を表す構造体。
+/// `offset`はタイル内における窓の左上のピクセルの位置(0から255)、`size`はその幅と高さ(ピクセル)である。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TileWindow {
+    /// Tile coordinates (x, y)
+    ///
+    /// タイル座標(x, y)
+    pub tile: (u32, u32),
+    /// Position (x, y) of the upper left pixel of the window within the tile (0 to 255)
+    ///
+    /// タイル内における窓の左上のピクセルの位置(x, y)(0から255)
+    pub offset: (u32, u32),
+    /// Width and height of the window in pixels
+    ///
+    /// 窓の幅と高さ(ピクセル)
+    pub size: (u32, u32),
+}
+
+/// Function to return each tile intersecting the rectangle from `min_ll` to `max_ll` expressed in the arc degree method at the Zoom level, together with the window of pixels of the rectangle inside that tile.
+/// Every pixel touched by the rectangle is included, so the windows of adjacent tiles neither overlap nor leave gaps, and a degenerate rectangle still covers one pixel.
+/// The rectangle is clamped to the world and must not cross the antimeridian. The tiles are in row-major order from the upper left.
+///
+/// 弧度法で表された`min_ll`から`max_ll`までの矩形と交わるZoomレベルの各タイルを、そのタイル内における矩形のピクセルの窓とともに返す関数。
+/// 矩形がかかる全てのピクセルが含まれるため、隣接するタイルの窓は重なることも隙間を残すこともなく、大きさのない矩形でも1ピクセルを覆う。
+/// 矩形は世界の範囲に収められ、180度経線をまたいではならない。タイルは左上から行優先の順に並ぶ。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::tile_windows;
+///
+/// let windows = tile_windows(
+/// (139.7_f64.to_radians(), 35.6_f64.to_radians()),
+/// (139.8_f64.to_radians(), 35.7_f64.to_radians()),
+/// ZoomLv::Lv12,
+/// );
+///
+/// let pixels = windows.iter().map(|w| w.size.0 as u64 * w.size.1 as u64).sum::<u64>();
+/// ```
+pub fn tile_windows(min_ll: (f64, f64), max_ll: (f64, f64), zoom: ZoomLv) -> Vec<TileWindow> {
+    let world = world_size_px(zoom) as f64;
+
+    // 左上は経度の最小と緯度の最大、右下は経度の最大と緯度の最小となる
+    let (left, top) = ll2pixel_f64((min_ll.0, max_ll.1), zoom);
+    let (right, bottom) = ll2pixel_f64((max_ll.0, min_ll.1), zoom);
+
+    let span = |start: f64, end: f64| {
+        let start = start.floor().clamp(0., world - 1.) as u64;
+        let end = (end.ceil().clamp(0., world) as u64).max(start + 1);
+        (start, end)
+    };
+    let (x0, x1) = span(left, right);
+    let (y0, y1) = span(top, bottom);
+
+    let mut windows = vec![];
+    for ty in y0 / 256..=(y1 - 1) / 256 {
+        for tx in x0 / 256..=(x1 - 1) / 256 {
+            let (ox, oy) = (tx * 256, ty * 256);
+            let (sx, sy) = (x0.max(ox), y0.max(oy));
+            let (ex, ey) = (x1.min(ox + 256), y1.min(oy + 256));
+
+            windows.push(TileWindow {
+                tile: (tx as u32, ty as u32),
+                offset: ((sx - ox) as u32, (sy - oy) as u32),
+                size: ((ex - sx) as u32, (ey - sy) as u32),
+            });
+        }
+    }
+
+    windows
+}
+
+/// Function to group (longitude, latitude) expressed in the arc degree method by the tile containing them at the Zoom level.
+/// Returns the indices of the points in each tile in the order of `points`. The containing tile follows the rounding of `ll2pixel`.
+///
+/// 弧度法で表された(経度, 緯度)を、Zoomレベルにおいてそれを含むタイルごとにまとめる関数。
+/// 各タイルの点のインデックスを`points`の順で返す。含むタイルは`ll2pixel`の丸めに従う。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::bucket_by_tile;
+///
+/// let points = [
+/// (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()),
+/// (135.4959_f64.to_radians(), 34.7024_f64.to_radians()),
+/// (139.7671_f64.to_radians(), 35.6812_f64.to_radians()),
+/// ];
+///
+/// let buckets = bucket_by_tile(&points, ZoomLv::Lv10);
+///
+/// assert_eq!(buckets[&(909, 403)], vec![0, 2]);
+/// ```
+pub fn bucket_by_tile(points: &[(f64, f64)], zoom: ZoomLv) -> HashMap<(u32, u32), Vec<usize>> {
+    let mut buckets = HashMap::<(u32, u32), Vec<usize>>::new();
+
+    for (i, &ll) in points.iter().enumerate() {
+        buckets.entry(pixel2tile(ll2pixel(ll, zoom))).or_default().push(i);
+    }
+
+    buckets
+}
+
+/// Function to simplify a set of tiles, possibly across Zoom levels, into a minimal set covering the same area.
+/// Tiles covered by another tile of the set are removed, and complete sets of four sibling tiles are merged into their parent recursively.
+/// The result is sorted by Zoom level and then by tile coordinates.
+///
+/// Zoomレベルが混在しうるタイルの集合を、同じ範囲を覆う最小のタイルの集合に単純化する関数。
+/// 集合内の他のタイルに覆われるタイルは取り除かれ、4つ揃った兄弟タイルは再帰的に親タイルにまとめられる。
+/// 結果はZoomレベル、タイル座標の順に並べられる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::tile::simplify_tiles;
+///
+/// let tiles = [
+///     ((0, 0), ZoomLv::Lv1),
+///     ((1, 0), ZoomLv::Lv1),
+///     ((0, 1), ZoomLv::Lv1),
+///     ((1, 1), ZoomLv::Lv1),
+///     ((3, 3), ZoomLv::Lv2),
+/// ];
+///
+/// assert_eq!(simplify_tiles(&tiles), vec![((0, 0), ZoomLv::Lv0)]);
+/// ```
+pub fn simplify_tiles(tiles: &[((u32, u32), ZoomLv)]) -> Vec<((u32, u32), ZoomLv)> {
+    let mut set = tiles
+        .iter()
+        .map(|&((x, y), zoom)| (zoom as u8, x, y))
+        .collect::<HashSet<_>>();
+
+    // 祖先のタイルが集合に含まれるタイルを取り除く
+    let covered = set
+        .iter()
+        .filter(|&&(z, x, y)| (1..=z).any(|d| set.contains(&(z - d, x >> d, y >> d))))
+        .copied()
+        .collect::<Vec<_>>();
+    for tile in covered {
+        set.remove(&tile);
+    }
+
+    // 4つ揃った兄弟タイルを親タイルにまとめる
+    for z in (1..=24).rev() {
+        let mut children = HashMap::<(u32, u32), u8>::new();
+        for &(_, x, y) in set.iter().filter(|(tz, _, _)| *tz == z) {
+            *children.entry((x >> 1, y >> 1)).or_default() += 1;
+        }
+
+        for ((px, py), count) in children {
+            if count == 4 {
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    set.remove(&(z, px * 2 + dx, py * 2 + dy));
+                }
+                set.insert((z - 1, px, py));
+            }
+        }
+    }
+
+    let mut result = set.into_iter().collect::<Vec<_>>();
+    result.sort();
+
+    result
+        .into_iter()
+        .map(|(z, x, y)| ((x, y), ZoomLv::parse(z).unwrap()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn bucket_by_tile_matches_ll2pixel() {
+        let zoom = ZoomLv::Lv3;
+        let points = (0..100)
+            .map(|i| ((i as f64 * 0.061 - 3.).sin() * 3., (i as f64 * 0.037).cos() * 1.3))
+            .collect::<Vec<_>>();
+
+        let buckets = bucket_by_tile(&points, zoom);
+        assert_eq!(buckets.values().map(Vec::len).sum::<usize>(), 100);
+
+        for (tile, indices) in &buckets {
+            assert!(indices.windows(2).all(|w| w[0] < w[1]));
+            assert!(indices.iter().all(|&i| pixel2tile(ll2pixel(points[i], zoom)) == *tile));
+        }
+    }
+
+    #[test]
+    fn tile_windows_cover_bbox() {
+        let zoom = ZoomLv::Lv4;
+        let min_ll = pixel2ll_f64((300.5, 700.), zoom);
+        let max_ll = pixel2ll_f64((1000., 100.2), zoom);
+        let windows = tile_windows(min_ll, max_ll, zoom);
+
+        assert_eq!(windows.len(), 3 * 3);
+        assert_eq!(
+            windows[0],
+            TileWindow {
+                tile: (1, 0),
+                offset: (44, 100),
+                size: (212, 156),
+            }
+        );
+        // 右下のタイルの窓は境界のピクセルで終わる
+        assert_eq!(
+            windows[8],
+            TileWindow {
+                tile: (3, 2),
+                offset: (0, 0),
+                size: (232, 188),
+            }
+        );
+
+        let width = windows.iter().filter(|w| w.tile.1 == 0).map(|w| w.size.0).sum::<u32>();
+        assert_eq!(width, 1000 - 300);
+
+        let point = tile_windows(min_ll, min_ll, zoom);
+        assert_eq!(point.len(), 1);
+        assert_eq!(point[0].size, (1, 1));
+    }
+
+    #[test]
+    fn wrapped_tile_works() {
+        let zoom = ZoomLv::Lv3;
+
+        assert_eq!(WrappedTile::new(8, 2, zoom).normalize(), Some((0, 2)));
+        assert_eq!(WrappedTile::new(-9, 2, zoom).normalize(), Some((7, 2)));
+        assert_eq!(WrappedTile::new(-9, 2, zoom).world_copy(), -2);
+        assert_eq!(WrappedTile::new(3, -1, zoom).normalize(), None);
+
+        let tile = WrappedTile::from(((5, 6), zoom));
+        assert_eq!((tile.normalize(), tile.world_copy()), (Some((5, 6)), 0));
+    }
+
+    #[test]
+    fn tile_neighbors_works() {
+        assert_eq!(tile_neighbors((5, 5), ZoomLv::Lv4).len(), 8);
+        assert_eq!(tile_neighbors((0, 5), ZoomLv::Lv4).len(), 5);
+
+        let neighbors = tile_neighbors_wrapped((0, 0), ZoomLv::Lv4);
+        assert_eq!(neighbors, vec![(15, 0), (1, 0), (15, 1), (0, 1), (1, 1)]);
+
+        // Zoomレベル0では自分自身しかない
+        assert!(tile_neighbors_wrapped((0, 0), ZoomLv::Lv0).is_empty());
+        assert_eq!(tile_neighbors_wrapped((0, 0), ZoomLv::Lv1), vec![(1, 0), (1, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn clip_line_to_tile_works() {
+        let zoom = ZoomLv::Lv14;
+        let (x, y) = pixel2tile(ll2pixel((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), zoom));
+        let center = pixel2ll_f64(((x as f64 + 0.5) * 256., (y as f64 + 0.5) * 256.), zoom);
+        let west = pixel2ll_f64(((x as f64 - 0.5) * 256., (y as f64 + 0.5) * 256.), zoom);
+        let east = pixel2ll_f64(((x as f64 + 1.5) * 256., (y as f64 + 0.5) * 256.), zoom);
+
+        // 西隣のタイルから中心を通って東隣のタイルへ向かう線
+        let parts = clip_line_to_tile(&[west, center, east], (x, y), zoom, 0.);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].len(), 3);
+        assert_close_to(parts[0][0].0, 0., 6);
+        assert_close_to(parts[0][1].0, 128., 6);
+        assert_close_to(parts[0][2].0, 256., 6);
+
+        // 隣接するタイルの部分は継ぎ目で一致する
+        let west_parts = clip_line_to_tile(&[west, center, east], (x - 1, y), zoom, 0.);
+        assert_close_to(west_parts[0].last().unwrap().0, 256., 6);
+        assert_close_to(west_parts[0].last().unwrap().1, parts[0][0].1, 9);
+
+        // タイルの外を通る線は切り取られない
+        assert!(clip_line_to_tile(&[west, west], (x, y), zoom, 0.).is_empty());
+        assert_eq!(clip_line_to_tile(&[west, center], (x, y), zoom, 16.)[0][0].0, -16.);
+    }
+
+    #[test]
+    fn tile_grid_works() {
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+
+        let grid = TileGrid::default();
+        let (x, y) = ll2pixel(ll, ZoomLv::Lv17);
+        assert_eq!(grid.ll2pixel(ll, ZoomLv::Lv17), (x as u64, y as u64));
+        assert_eq!(grid.ll2tile(ll, ZoomLv::Lv17), pixel2tile(ll2pixel(ll, ZoomLv::Lv17)));
+
+        // 512ピクセルのタイルはZoomレベルが1つ小さい256ピクセルのタイルと同じ範囲を覆う
+        let retina = TileGrid::new(512);
+        assert_eq!(retina.ll2tile(ll, ZoomLv::Lv16), grid.ll2tile(ll, ZoomLv::Lv16));
+        assert_eq!(retina.world_size_px(ZoomLv::Lv16), grid.world_size_px(ZoomLv::Lv17));
+        assert_close_to(
+            retina.pixel_resolution(ll.1, ZoomLv::Lv16),
+            grid.pixel_resolution(ll.1, ZoomLv::Lv17),
+            9,
+        );
+
+        let (long, lat) = retina.pixel2ll(retina.ll2pixel(ll, ZoomLv::Lv20), ZoomLv::Lv20);
+        assert_close_to(long, ll.0, 6);
+        assert_close_to(lat, ll.1, 6);
+    }
+
+    #[test]
+    fn tile_grid_exceeds_u32_at_lv24() {
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        let retina = TileGrid::new(512);
+
+        // 世界の幅は2^33ピクセルとなり、u32の範囲を超える
+        let (x, y) = retina.ll2pixel(ll, ZoomLv::Lv24);
+        assert!(x > u32::MAX as u64);
+        // タイル番号はタイルの大きさによらない
+        assert_eq!(retina.pixel2tile((x, y)), TileGrid::default().ll2tile(ll, ZoomLv::Lv24));
+        assert_eq!(retina.ll2tile(ll, ZoomLv::Lv24), (14902125, 6606498));
+
+        let (long, lat) = retina.pixel2ll((x, y), ZoomLv::Lv24);
+        assert_close_to(long, ll.0, 8);
+        assert_close_to(lat, ll.1, 8);
+    }
+
+    #[test]
+    fn simplify_tiles_merges_recursively() {
+        let mut tiles = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                tiles.push(((x + 4, y), ZoomLv::Lv3));
+            }
+        }
+        tiles.push(((0, 0), ZoomLv::Lv3));
+
+        assert_eq!(
+            simplify_tiles(&tiles),
+            vec![((1, 0), ZoomLv::Lv1), ((0, 0), ZoomLv::Lv3)]
+        );
+    }
+
+    #[test]
+    fn simplify_tiles_keeps_incomplete_sets() {
+        let tiles = [
+            ((0, 0), ZoomLv::Lv5),
+            ((1, 0), ZoomLv::Lv5),
+            ((0, 1), ZoomLv::Lv5),
+            ((0, 0), ZoomLv::Lv5),
+        ];
+
+        assert_eq!(
+            simplify_tiles(&tiles),
+            vec![((0, 0), ZoomLv::Lv5), ((0, 1), ZoomLv::Lv5), ((1, 0), ZoomLv::Lv5)]
+        );
+    }
+}
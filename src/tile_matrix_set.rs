@@ -0,0 +1,306 @@
+use std::f64::consts::PI;
+
+use crate::consts::WEB_MERCATOR_RADIUS;
+use crate::math;
+
+const R: f64 = WEB_MERCATOR_RADIUS;
+
+/// Size of the standardized rendering pixel defined by WMTS (m)
+///
+/// WMTSで定義される標準化されたピクセルの大きさ(m)
+const STANDARD_PIXEL_SIZE: f64 = 0.28e-3;
+
+/// Enumerated type representing the coordinate reference system of a tile matrix set
+///
+/// タイルマトリクスセットの座標参照系を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TileMatrixCrs {
+    /// Web Mercator (EPSG:3857) in meters
+    ///
+    /// m単位のWebメルカトル(EPSG:3857)
+    WebMercator,
+    /// Longitude and latitude in degrees (e.g. JGD2011 geographic, EPSG:6668)
+    ///
+    /// 度単位の経度緯度(JGD2011の地理座標系 EPSG:6668など)
+    Geographic,
+}
+
+impl TileMatrixCrs {
+    /// Returns the length (m) of one unit of the coordinate reference system as defined by WMTS
+    ///
+    /// WMTSで定義される座標参照系の1単位あたりの長さ(m)を返す
+    pub fn meters_per_unit(&self) -> f64 {
+        match self {
+            TileMatrixCrs::WebMercator => 1.,
+            TileMatrixCrs::Geographic => 2. * PI * R / 360.,
+        }
+    }
+
+    /// Converts (longitude, latitude) expressed in the arc degree method to (x, y) of the coordinate reference system
+    ///
+    /// 弧度法で表された(経度, 緯度)を座標参照系の(x, y)に変換する
+    pub fn project(&self, ll: (f64, f64)) -> (f64, f64) {
+        let (long, lat) = ll;
+
+        match self {
+            TileMatrixCrs::WebMercator => (R * long, R * math::atanh(math::sin(lat))),
+            TileMatrixCrs::Geographic => (long.to_degrees(), lat.to_degrees()),
+        }
+    }
+
+    /// Converts (x, y) of the coordinate reference system to (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 座標参照系の(x, y)を弧度法で表された(経度, 緯度)に変換する
+    pub fn unproject(&self, xy: (f64, f64)) -> (f64, f64) {
+        let (x, y) = xy;
+
+        match self {
+            TileMatrixCrs::WebMercator => (x / R, math::atan(math::sinh(y / R))),
+            TileMatrixCrs::Geographic => (x.to_radians(), y.to_radians()),
+        }
+    }
+}
+
+/// Structure representing one tile matrix (zoom level) of a tile matrix set
+///
+/// タイルマトリクスセットの1つのタイルマトリクス(ズームレベル)を表す構造体
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct TileMatrix {
+    /// Identifier of the tile matrix
+    ///
+    /// タイルマトリクスの識別子
+    pub identifier: String,
+    /// Scale denominator
+    ///
+    /// 縮尺の分母
+    pub scale_denominator: f64,
+    /// (x, y) of the upper left corner in the coordinate reference system
+    ///
+    /// 座標参照系における左上隅の(x, y)
+    pub top_left: (f64, f64),
+    /// Width of a tile in pixels
+    ///
+    /// タイルの幅(ピクセル)
+    pub tile_width: u32,
+    /// Height of a tile in pixels
+    ///
+    /// タイルの高さ(ピクセル)
+    pub tile_height: u32,
+    /// Number of tiles in the x direction
+    ///
+    /// x方向のタイル数
+    pub matrix_width: u32,
+    /// Number of tiles in the y direction
+    ///
+    /// y方向のタイル数
+    pub matrix_height: u32,
+}
+
+impl TileMatrix {
+    /// Returns the size of a pixel in units of the coordinate reference system
+    ///
+    /// 座標参照系の単位でピクセルの大きさを返す
+    pub fn cell_size(&self, crs: TileMatrixCrs) -> f64 {
+        self.scale_denominator * STANDARD_PIXEL_SIZE / crs.meters_per_unit()
+    }
+}
+
+/// Structure representing a WMTS tile matrix set
+///
+/// WMTSのタイルマトリクスセットを表す構造体
+///
+/// # Examples
+///
+/// Define a geographic tile matrix set and convert longitude and latitude to tile coordinates.
+///
+/// 地理座標系のタイルマトリクスセットを定義し、経度緯度をタイル座標に変換する。
+///
+/// ```
+/// use coordinate_transformer::tile_matrix_set::{TileMatrix, TileMatrixCrs, TileMatrixSet};
+///
+/// let set = TileMatrixSet::new(
+/// "JGD2011Geographic",
+/// TileMatrixCrs::Geographic,
+/// vec![TileMatrix {
+/// identifier: "0".to_string(),
+/// scale_denominator: 279541132.0143589,
+/// top_left: (-180., 90.),
+/// tile_width: 256,
+/// tile_height: 256,
+/// matrix_width: 2,
+/// matrix_height: 1,
+/// }],
+/// );
+///
+/// let tile = set.ll2tile((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()), 0);
+///
+/// assert_eq!(tile, Some((1, 0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct TileMatrixSet {
+    /// Identifier of the tile matrix set
+    ///
+    /// タイルマトリクスセットの識別子
+    pub identifier: String,
+    /// Coordinate reference system of the tile matrix set
+    ///
+    /// タイルマトリクスセットの座標参照系
+    pub crs: TileMatrixCrs,
+    /// Tile matrices ordered from the coarsest
+    ///
+    /// 粗いものから順に並べたタイルマトリクス
+    pub matrices: Vec<TileMatrix>,
+}
+
+impl TileMatrixSet {
+    /// Create a new custom tile matrix set
+    ///
+    /// カスタムのタイルマトリクスセットを新しく作成する
+    pub fn new(identifier: &str, crs: TileMatrixCrs, matrices: Vec<TileMatrix>) -> Self {
+        Self {
+            identifier: identifier.to_string(),
+            crs,
+            matrices,
+        }
+    }
+
+    /// Returns the standard GoogleMapsCompatible tile matrix set (levels 0 to 24), which matches the pixel coordinates of this crate
+    ///
+    /// このクレートのピクセル座標と一致する標準のGoogleMapsCompatibleタイルマトリクスセット(レベル0から24)を返す
+    pub fn google_maps_compatible() -> Self {
+        let half = PI * R;
+
+        let matrices = (0..=24)
+            .map(|level| TileMatrix {
+                identifier: level.to_string(),
+                scale_denominator: 2. * half / 256. / STANDARD_PIXEL_SIZE / 2_f64.powi(level),
+                top_left: (-half, half),
+                tile_width: 256,
+                tile_height: 256,
+                matrix_width: 1 << level,
+                matrix_height: 1 << level,
+            })
+            .collect();
+
+        Self::new("GoogleMapsCompatible", TileMatrixCrs::WebMercator, matrices)
+    }
+
+    /// Returns the tile matrix at the index, or `None` if it does not exist
+    ///
+    /// インデックスのタイルマトリクスを返す。存在しない場合は`None`を返す
+    pub fn matrix(&self, level: usize) -> Option<&TileMatrix> {
+        self.matrices.get(level)
+    }
+
+    /// Converts (longitude, latitude) expressed in the arc degree method to pixel coordinates (x, y) of the tile matrix at `level`.
+    /// Returns `None` if the level does not exist or the point is outside the matrix.
+    ///
+    /// 弧度法で表された(経度, 緯度)を`level`のタイルマトリクスのピクセル座標(x, y)に変換する。
+    /// レベルが存在しない場合や地点がマトリクスの範囲外の場合は`None`を返す。
+    pub fn ll2pixel(&self, ll: (f64, f64), level: usize) -> Option<(u32, u32)> {
+        let matrix = self.matrix(level)?;
+        let cell_size = matrix.cell_size(self.crs);
+
+        let (x, y) = self.crs.project(ll);
+        let px = ((x - matrix.top_left.0) / cell_size).floor();
+        let py = ((matrix.top_left.1 - y) / cell_size).floor();
+
+        let width = matrix.tile_width as f64 * matrix.matrix_width as f64;
+        let height = matrix.tile_height as f64 * matrix.matrix_height as f64;
+
+        if (0. ..width).contains(&px) && (0. ..height).contains(&py) {
+            Some((px as u32, py as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Converts pixel coordinates (x, y) of the tile matrix at `level` to (longitude, latitude) expressed in the arc degree method.
+    /// Returns `None` if the level does not exist.
+    ///
+    /// `level`のタイルマトリクスのピクセル座標(x, y)を弧度法で表された(経度, 緯度)に変換する。
+    /// レベルが存在しない場合は`None`を返す。
+    pub fn pixel2ll(&self, pixel: (u32, u32), level: usize) -> Option<(f64, f64)> {
+        let matrix = self.matrix(level)?;
+        let cell_size = matrix.cell_size(self.crs);
+
+        let (px, py) = pixel;
+        let x = matrix.top_left.0 + px as f64 * cell_size;
+        let y = matrix.top_left.1 - py as f64 * cell_size;
+
+        Some(self.crs.unproject((x, y)))
+    }
+
+    /// Converts pixel coordinates of the tile matrix at `level` to tile coordinates (column, row).
+    /// Returns `None` if the level does not exist.
+    ///
+    /// `level`のタイルマトリクスのピクセル座標をタイル座標(列, 行)に変換する。
+    /// レベルが存在しない場合は`None`を返す。
+    pub fn pixel2tile(&self, pixel: (u32, u32), level: usize) -> Option<(u32, u32)> {
+        let matrix = self.matrix(level)?;
+        let (px, py) = pixel;
+
+        Some((px / matrix.tile_width, py / matrix.tile_height))
+    }
+
+    /// Converts (longitude, latitude) expressed in the arc degree method to tile coordinates (column, row) of the tile matrix at `level`.
+    /// Returns `None` if the level does not exist or the point is outside the matrix.
+    ///
+    /// 弧度法で表された(経度, 緯度)を`level`のタイルマトリクスのタイル座標(列, 行)に変換する。
+    /// レベルが存在しない場合や地点がマトリクスの範囲外の場合は`None`を返す。
+    pub fn ll2tile(&self, ll: (f64, f64), level: usize) -> Option<(u32, u32)> {
+        self.pixel2tile(self.ll2pixel(ll, level)?, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::{ll2pixel, pixel2ll, ZoomLv};
+
+    use super::*;
+
+    #[test]
+    fn google_maps_compatible_matches_pixel_coordinates() {
+        let set = TileMatrixSet::google_maps_compatible();
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+
+        assert_eq!(set.ll2pixel(ll, 17), Some(ll2pixel(ll, ZoomLv::Lv17)));
+
+        let (long, lat) = set.pixel2ll((1000, 2000), 10).unwrap();
+        let (expected_long, expected_lat) = pixel2ll((1000, 2000), ZoomLv::Lv10);
+        assert_close_to(long, expected_long, 9);
+        assert_close_to(lat, expected_lat, 9);
+
+        assert_eq!(set.ll2pixel(ll, 25), None);
+    }
+
+    #[test]
+    fn geographic_set_works() {
+        let set = TileMatrixSet::new(
+            "JGD2011Geographic",
+            TileMatrixCrs::Geographic,
+            vec![TileMatrix {
+                identifier: "1".to_string(),
+                scale_denominator: 279541132.0143589 / 2.,
+                top_left: (-180., 90.),
+                tile_width: 256,
+                tile_height: 256,
+                matrix_width: 4,
+                matrix_height: 2,
+            }],
+        );
+
+        // 1ピクセルはおよそ180/512度
+        assert_close_to(set.matrices[0].cell_size(set.crs), 180. / 512., 9);
+
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        assert_eq!(set.ll2tile(ll, 0), Some((3, 0)));
+
+        let pixel = set.ll2pixel(ll, 0).unwrap();
+        let (long, lat) = set.pixel2ll(pixel, 0).unwrap();
+        assert_close_to(long.to_degrees(), 139.7649308, 0);
+        assert_close_to(lat.to_degrees(), 35.6812405, 0);
+    }
+}
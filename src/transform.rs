@@ -0,0 +1,249 @@
+//! Automatic resolution of conversion paths between coordinate systems
+//! The direct conversions of this crate are represented as a graph with costs, and the cheapest path between two coordinate systems is searched with Dijkstra's algorithm.
+//!
+//! 座標系間の変換経路の自動解決
+//! このクレートの直接の変換をコスト付きのグラフとして表し、2つの座標系間で最も安い経路をダイクストラ法で探索する。
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{
+    jpr2ll, ll2jpr, ll2pixel, llz2xyz, pixel2ll, pixel2tile, pixel_at_zoom, tile_at_zoom, xyz2llz, JprOrigin, ZoomLv,
+};
+
+/// Enumerated type representing a coordinate system
+///
+/// 座標系を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoordinateSystem {
+    /// (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)
+    LongLat,
+    /// Plane rectangular coordinates (y, x) of the origin
+    ///
+    /// 原点に対する平面直角座標(y, x)
+    Jpr(JprOrigin),
+    /// Pixel coordinates (x, y) of the Zoom level
+    ///
+    /// Zoomレベルにおけるピクセル座標(x, y)
+    Pixel(ZoomLv),
+    /// Tile coordinates (x, y) of the Zoom level
+    ///
+    /// Zoomレベルにおけるタイル座標(x, y)
+    Tile(ZoomLv),
+    /// Cartesian coordinates (x, y, z) (EPSG:4979)
+    ///
+    /// 直交座標系(EPSG:4979)の座標(x, y, z)
+    Cartesian,
+}
+
+/// Enumerated type representing a coordinate value together with its coordinate system
+///
+/// 座標系付きの座標値を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Coordinate {
+    LongLat((f64, f64)),
+    Jpr((f64, f64), JprOrigin),
+    Pixel((u32, u32), ZoomLv),
+    Tile((u32, u32), ZoomLv),
+    Cartesian((f64, f64, f64)),
+}
+
+impl Coordinate {
+    /// Returns the coordinate system of the value
+    ///
+    /// 座標値の座標系を返す
+    pub fn system(&self) -> CoordinateSystem {
+        match *self {
+            Coordinate::LongLat(_) => CoordinateSystem::LongLat,
+            Coordinate::Jpr(_, origin) => CoordinateSystem::Jpr(origin),
+            Coordinate::Pixel(_, zoom) => CoordinateSystem::Pixel(zoom),
+            Coordinate::Tile(_, zoom) => CoordinateSystem::Tile(zoom),
+            Coordinate::Cartesian(_) => CoordinateSystem::Cartesian,
+        }
+    }
+}
+
+/// Returns the coordinate systems directly reachable from `system` and their costs.
+/// Only the origins and Zoom levels in `params` are considered.
+///
+/// `system`から直接変換できる座標系とそのコストを返す。
+/// `params`に含まれる原点とZoomレベルのみを考慮する。
+fn edges(system: CoordinateSystem, params: &[CoordinateSystem]) -> Vec<(CoordinateSystem, u32)> {
+    let origins = params.iter().filter_map(|p| match p {
+        CoordinateSystem::Jpr(origin) => Some(*origin),
+        _ => None,
+    });
+    let zooms = params.iter().filter_map(|p| match p {
+        CoordinateSystem::Pixel(zoom) | CoordinateSystem::Tile(zoom) => Some(*zoom),
+        _ => None,
+    });
+
+    match system {
+        CoordinateSystem::LongLat => origins
+            .map(|origin| (CoordinateSystem::Jpr(origin), 10))
+            .chain(zooms.map(|zoom| (CoordinateSystem::Pixel(zoom), 2)))
+            .chain([(CoordinateSystem::Cartesian, 3)])
+            .collect(),
+        CoordinateSystem::Jpr(_) => vec![(CoordinateSystem::LongLat, 10)],
+        CoordinateSystem::Pixel(zoom) => zooms
+            .filter(|&z| z != zoom)
+            .map(|z| (CoordinateSystem::Pixel(z), 1))
+            .chain([(CoordinateSystem::LongLat, 2), (CoordinateSystem::Tile(zoom), 1)])
+            .collect(),
+        CoordinateSystem::Tile(zoom) => zooms
+            .filter(|&z| z != zoom)
+            .map(|z| (CoordinateSystem::Tile(z), 1))
+            .chain([(CoordinateSystem::Pixel(zoom), 1)])
+            .collect(),
+        CoordinateSystem::Cartesian => vec![(CoordinateSystem::LongLat, 3)],
+    }
+}
+
+/// Function to return the cheapest conversion path from `from` to `to`, including both ends.
+/// Returns `None` if there is no path.
+///
+/// `from`から`to`への最も安い変換経路を両端を含めて返す関数。
+/// 経路がない場合は`None`を返す。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::transform::{conversion_path, CoordinateSystem};
+///
+/// let path = conversion_path(CoordinateSystem::Jpr(JprOrigin::Nine), CoordinateSystem::Tile(ZoomLv::Lv10));
+/// assert_eq!(
+/// path,
+/// Some(vec![
+/// CoordinateSystem::Jpr(JprOrigin::Nine),
+/// CoordinateSystem::LongLat,
+/// CoordinateSystem::Pixel(ZoomLv::Lv10),
+/// CoordinateSystem::Tile(ZoomLv::Lv10),
+/// ])
+/// );
+/// ```
+pub fn conversion_path(from: CoordinateSystem, to: CoordinateSystem) -> Option<Vec<CoordinateSystem>> {
+    let params = [from, to];
+
+    let mut costs = HashMap::from([(from, 0)]);
+    let mut previous = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((0, from))]);
+
+    while let Some(Reverse((cost, system))) = queue.pop() {
+        if system == to {
+            let mut path = vec![to];
+            while let Some(&p) = previous.get(path.last().unwrap()) {
+                path.push(p);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if costs.get(&system).is_some_and(|&c| c < cost) {
+            continue;
+        }
+
+        for (next, edge_cost) in edges(system, &params) {
+            let next_cost = cost + edge_cost;
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next, next_cost);
+                previous.insert(next, system);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Applies one direct conversion
+///
+/// 直接の変換を1つ適用する
+fn step(coordinate: Coordinate, to: CoordinateSystem) -> Coordinate {
+    match (coordinate, to) {
+        (Coordinate::LongLat(ll), CoordinateSystem::Jpr(origin)) => Coordinate::Jpr(ll2jpr(ll, origin), origin),
+        (Coordinate::LongLat(ll), CoordinateSystem::Pixel(zoom)) => Coordinate::Pixel(ll2pixel(ll, zoom), zoom),
+        (Coordinate::LongLat(ll), CoordinateSystem::Cartesian) => Coordinate::Cartesian(llz2xyz(ll, 0.)),
+        (Coordinate::Jpr(yx, origin), CoordinateSystem::LongLat) => Coordinate::LongLat(jpr2ll(yx, origin)),
+        (Coordinate::Pixel(pixel, zoom), CoordinateSystem::LongLat) => Coordinate::LongLat(pixel2ll(pixel, zoom)),
+        (Coordinate::Pixel(pixel, zoom), CoordinateSystem::Tile(_)) => Coordinate::Tile(pixel2tile(pixel), zoom),
+        (Coordinate::Pixel(pixel, zoom), CoordinateSystem::Pixel(to_zoom)) => {
+            Coordinate::Pixel(pixel_at_zoom(pixel, zoom, to_zoom), to_zoom)
+        }
+        (Coordinate::Tile((x, y), zoom), CoordinateSystem::Pixel(_)) => Coordinate::Pixel((x * 256, y * 256), zoom),
+        (Coordinate::Tile(tile, zoom), CoordinateSystem::Tile(to_zoom)) => {
+            Coordinate::Tile(tile_at_zoom(tile, zoom, to_zoom), to_zoom)
+        }
+        (Coordinate::Cartesian(xyz), CoordinateSystem::LongLat) => Coordinate::LongLat(xyz2llz(xyz).0),
+        _ => unreachable!("no direct conversion from {:?} to {:?}", coordinate.system(), to),
+    }
+}
+
+/// Function to convert a coordinate value to the coordinate system `to` through the cheapest conversion path.
+/// Conversions to Cartesian coordinates use an altitude of 0, conversions from them drop the altitude,
+/// and tiles are represented by their upper left pixel.
+///
+/// 座標値を最も安い変換経路で座標系`to`に変換する関数。
+/// 直交座標への変換では標高を0とし、直交座標からの変換では標高を捨てる。
+/// また、タイルは左上のピクセルで代表する。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::transform::{transform, Coordinate, CoordinateSystem};
+///
+/// let tile = transform(
+/// Coordinate::Jpr((22694.980, 11573.375), JprOrigin::Nine),
+/// CoordinateSystem::Tile(ZoomLv::Lv10),
+/// );
+/// ```
+pub fn transform(coordinate: Coordinate, to: CoordinateSystem) -> Coordinate {
+    let path = conversion_path(coordinate.system(), to).unwrap();
+
+    path.into_iter().skip(1).fold(coordinate, step)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jpr2jpr, ll2pixel};
+
+    use super::*;
+
+    #[test]
+    fn conversion_path_works() {
+        assert_eq!(
+            conversion_path(CoordinateSystem::Pixel(ZoomLv::Lv10), CoordinateSystem::Tile(ZoomLv::Lv12)).map(|p| p.len()),
+            Some(3)
+        );
+        assert_eq!(
+            conversion_path(CoordinateSystem::Jpr(JprOrigin::Nine), CoordinateSystem::Jpr(JprOrigin::Eight)),
+            Some(vec![
+                CoordinateSystem::Jpr(JprOrigin::Nine),
+                CoordinateSystem::LongLat,
+                CoordinateSystem::Jpr(JprOrigin::Eight),
+            ])
+        );
+        assert_eq!(
+            conversion_path(CoordinateSystem::Cartesian, CoordinateSystem::Cartesian),
+            Some(vec![CoordinateSystem::Cartesian])
+        );
+    }
+
+    #[test]
+    fn transform_works() {
+        let yx = (22694.980, 11573.375);
+
+        assert_eq!(
+            transform(Coordinate::Jpr(yx, JprOrigin::Nine), CoordinateSystem::Pixel(ZoomLv::Lv21)),
+            Coordinate::Pixel(ll2pixel(jpr2ll(yx, JprOrigin::Nine), ZoomLv::Lv21), ZoomLv::Lv21)
+        );
+        assert_eq!(
+            transform(Coordinate::Jpr(yx, JprOrigin::Nine), CoordinateSystem::Jpr(JprOrigin::Eight)),
+            Coordinate::Jpr(jpr2jpr(yx, JprOrigin::Nine, JprOrigin::Eight), JprOrigin::Eight)
+        );
+    }
+}
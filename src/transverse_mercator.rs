@@ -0,0 +1,343 @@
+//! Transverse Mercator projection on the GRS80 ellipsoid with a selectable series, e.g. for reprojecting nationwide datasets through a single zone of the plane rectangular coordinate system.
+//! `TmSeries::Krueger` is the series to the fifth order in the third flattening n used by `ll2jpr` and `jpr2ll`.
+//! `TmSeries::Extended` is the series to the eighth order given by Karney (2011), with the latitude recovered from the conformal latitude by Newton's method instead of a series, which keeps its accuracy far from the central meridian where the truncation error of the shorter series grows.
+//! Compared with the exact projection, the error of `TmSeries::Extended` stays below 1 µm up to 60° from the central meridian, where that of `TmSeries::Krueger` reaches about 0.2 mm.
+//!
+//! 選択可能な級数によるGRS80楕円体上の横メルカトル図法。全国のデータセットを平面直角座標系の単一の系で再投影する場合などに用いる。
+//! `TmSeries::Krueger`は`ll2jpr`と`jpr2ll`で用いる第3扁平率nの5次までの級数である。
+//! `TmSeries::Extended`はKarney(2011)による8次までの級数であり、緯度を級数ではなくニュートン法で等角緯度から求めるため、短い級数の打ち切り誤差が大きくなる中央子午線から遠い地点でも精度を保つ。
+//! 厳密な投影と比べると、中央子午線から60度離れた地点でも`TmSeries::Extended`の誤差は1µm未満に留まるが、`TmSeries::Krueger`の誤差は約0.2mmに達する。
+
+use num::complex::Complex64;
+
+use crate::consts::GRS80_ECCENTRICITY;
+use crate::jpr_ll::{clenshaw_complex_sin, ll2tm, tm2ll, JprOrigin, A, LAT0, LONG0, M0, N, S0_ARR};
+use crate::math;
+
+const N2: f64 = N * N;
+const N3: f64 = N2 * N;
+const N4: f64 = N3 * N;
+const N5: f64 = N4 * N;
+const N6: f64 = N5 * N;
+const N7: f64 = N6 * N;
+const N8: f64 = N7 * N;
+
+const E: f64 = GRS80_ECCENTRICITY;
+
+/// Ratio of the rectifying radius to a / (1 + n), to the eighth order
+///
+/// 修正半径とa / (1 + n)の比(8次まで)
+const EXT_A0: f64 = 1. + N2 / 4. + N4 / 64. + N6 / 256. + 25. * N8 / 16384.;
+
+/// Coefficients of the series from the conformal to the projected coordinates, to the eighth order
+///
+/// 等角座標から投影座標への級数の係数(8次まで)
+const EXT_ALPHA_ARR: [f64; 8] = [
+    N / 2. - 2. * N2 / 3. + 5. * N3 / 16. + 41. * N4 / 180. - 127. * N5 / 288.
+        + 7891. * N6 / 37800.
+        + 72161. * N7 / 387072.
+        - 18975107. * N8 / 50803200.,
+    13. * N2 / 48. - 3. * N3 / 5. + 557. * N4 / 1440. + 281. * N5 / 630. - 1983433. * N6 / 1935360.
+        + 13769. * N7 / 28800.
+        + 148003883. * N8 / 174182400.,
+    61. * N3 / 240. - 103. * N4 / 140. + 15061. * N5 / 26880. + 167603. * N6 / 181440.
+        - 67102379. * N7 / 29030400.
+        + 79682431. * N8 / 79833600.,
+    49561. * N4 / 161280. - 179. * N5 / 168. + 6601661. * N6 / 7257600. + 97445. * N7 / 49896.
+        - 40176129013. * N8 / 7664025600.,
+    34729. * N5 / 80640. - 3418889. * N6 / 1995840.
+        + 14644087. * N7 / 9123840.
+        + 2605413599. * N8 / 622702080.,
+    212378941. * N6 / 319334400. - 30705481. * N7 / 10378368. + 175214326799. * N8 / 58118860800.,
+    1522256789. * N7 / 1383782400. - 16759934899. * N8 / 3113510400.,
+    1424729850961. * N8 / 743921418240.,
+];
+
+/// Coefficients of the series from the projected to the conformal coordinates, to the eighth order
+///
+/// 投影座標から等角座標への級数の係数(8次まで)
+const EXT_BETA_ARR: [f64; 8] = [
+    N / 2. - 2. * N2 / 3. + 37. * N3 / 96. - N4 / 360. - 81. * N5 / 512. + 96199. * N6 / 604800.
+        - 5406467. * N7 / 38707200.
+        + 7944359. * N8 / 67737600.,
+    N2 / 48. + N3 / 15. - 437. * N4 / 1440. + 46. * N5 / 105. - 1118711. * N6 / 3870720.
+        + 51841. * N7 / 1209600.
+        + 24749483. * N8 / 348364800.,
+    17. * N3 / 480. - 37. * N4 / 840. - 209. * N5 / 4480.
+        + 5569. * N6 / 90720.
+        + 9261899. * N7 / 58060800.
+        - 6457463. * N8 / 17740800.,
+    4397. * N4 / 161280. - 11. * N5 / 504. - 830251. * N6 / 7257600.
+        + 466511. * N7 / 2494800.
+        + 324154477. * N8 / 7664025600.,
+    4583. * N5 / 161280. - 108847. * N6 / 3991680. - 8005831. * N7 / 63866880.
+        + 22894433. * N8 / 124540416.,
+    20648693. * N6 / 638668800. - 16363163. * N7 / 518918400. - 2204645983. * N8 / 12915302400.,
+    219941297. * N7 / 5535129600. - 497323811. * N8 / 12454041600.,
+    191773887257. * N8 / 3719607091200.,
+];
+
+/// Enumerated type representing the series used by the Transverse Mercator projection
+///
+/// 横メルカトル図法で用いる級数を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TmSeries {
+    /// Series to the fifth order, the same as `ll2jpr` and `jpr2ll`
+    ///
+    /// 5次までの級数。`ll2jpr`及び`jpr2ll`と同じ
+    #[default]
+    Krueger,
+    /// Series to the eighth order with the latitude recovered by Newton's method
+    ///
+    /// 8次までの級数。緯度はニュートン法で求める
+    Extended,
+}
+
+/// Structure representing a Transverse Mercator projection with the origin (longitude, latitude) expressed in the arc degree method, the scale factor on the central meridian and the series
+///
+/// 弧度法で表された原点(経度, 緯度)、中央子午線上の縮尺係数、級数からなる横メルカトル図法を表す構造体
+///
+/// # Examples
+///
+/// Project Minamitorishima, about 1300 km east of the central meridian, through zone IX.
+///
+/// 中央子午線から東に約1300kmの南鳥島をIX系で投影する。
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::transverse_mercator::{TmSeries, TransverseMercator};
+///
+/// let tm = TransverseMercator::from_jpr_origin(JprOrigin::Nine, TmSeries::Extended);
+/// let ll = (153.9806_f64.to_radians(), 24.2867_f64.to_radians());
+///
+/// let (long, lat) = tm.inverse(tm.forward(ll));
+///
+/// assert!((long - ll.0).abs() < 1e-14 && (lat - ll.1).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TransverseMercator {
+    long0: f64,
+    m0: f64,
+    series: TmSeries,
+    s0: f64,
+}
+
+impl TransverseMercator {
+    /// Create a projection with the origin (longitude, latitude) expressed in the arc degree method and the scale factor `m0` on the central meridian
+    ///
+    /// 弧度法で表された原点(経度, 緯度)と中央子午線上の縮尺係数`m0`から投影を作成する
+    pub fn new(origin: (f64, f64), m0: f64, series: TmSeries) -> Self {
+        let (long0, lat0) = origin;
+        let tm = Self {
+            long0,
+            m0,
+            series,
+            s0: 0.,
+        };
+        let (_, s0) = tm.forward((long0, lat0));
+
+        Self { s0, ..tm }
+    }
+
+    /// Create the projection of the origin of the plane rectangular coordinate system.
+    /// With `TmSeries::Krueger` the results are the same as `ll2jpr` and `jpr2ll`.
+    ///
+    /// 平面直角座標系の原点の投影を作成する。
+    /// `TmSeries::Krueger`では結果は`ll2jpr`及び`jpr2ll`と同じになる。
+    pub fn from_jpr_origin(origin: JprOrigin, series: TmSeries) -> Self {
+        let i = origin as usize;
+
+        match series {
+            TmSeries::Krueger => Self {
+                long0: LONG0[i],
+                m0: M0,
+                series,
+                s0: S0_ARR[i],
+            },
+            TmSeries::Extended => Self::new((LONG0[i], LAT0[i]), M0, series),
+        }
+    }
+
+    /// Returns the series
+    ///
+    /// 級数を返す
+    pub fn series(&self) -> TmSeries {
+        self.series
+    }
+
+    /// Convert (longitude, latitude) expressed in the arc degree method to projected coordinates (y, x), where y is east and x is north as in the plane rectangular coordinate system
+    ///
+    /// 弧度法で表された(経度, 緯度)を投影座標(y, x)に変換する。平面直角座標系と同様にyは東方向、xは北方向である
+    pub fn forward(&self, ll: (f64, f64)) -> (f64, f64) {
+        match self.series {
+            TmSeries::Krueger => ll2tm(ll, self.long0, self.m0, self.s0),
+            TmSeries::Extended => ll2tm_extended(ll, self.long0, self.m0, self.s0),
+        }
+    }
+
+    /// Convert projected coordinates (y, x) to (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 投影座標(y, x)を弧度法で表された(経度, 緯度)に変換する
+    pub fn inverse(&self, yx: (f64, f64)) -> (f64, f64) {
+        match self.series {
+            TmSeries::Krueger => tm2ll(yx, self.long0, self.m0, self.s0),
+            TmSeries::Extended => tm2ll_extended(yx, self.long0, self.m0, self.s0),
+        }
+    }
+}
+
+fn ll2tm_extended(ll: (f64, f64), long0: f64, m0: f64, s_: f64) -> (f64, f64) {
+    let (long, lat) = ll;
+
+    let a_ = m0 * A / (1. + N) * EXT_A0;
+
+    let (lambda_s, lambda_c) = math::sin_cos(long - long0);
+    let sin_lat = math::sin(lat);
+
+    // 等角緯度の正接
+    let t = math::sinh(math::atanh(sin_lat) - E * math::atanh(E * sin_lat));
+    let t_ = (1. + t * t).sqrt();
+
+    let xi2 = math::atan2(t, lambda_c);
+    let eta2 = math::atanh(lambda_s / t_);
+
+    let (d_xi, d_eta) = clenshaw_complex_sin(&EXT_ALPHA_ARR, (xi2, eta2));
+
+    let x = a_ * (xi2 + d_xi) - s_;
+    let y = a_ * (eta2 + d_eta);
+
+    (y, x)
+}
+
+fn tm2ll_extended(yx: (f64, f64), long0: f64, m0: f64, s_: f64) -> (f64, f64) {
+    let (y, x) = yx;
+
+    let a_ = m0 * A / (1. + N) * EXT_A0;
+
+    let zeta = Complex64::new((x + s_) / a_, y / a_);
+    let (d_xi, d_eta) = clenshaw_complex_sin(&EXT_BETA_ARR, (zeta.re, zeta.im));
+
+    let xi2 = zeta.re - d_xi;
+    let eta2 = zeta.im - d_eta;
+
+    let (sin_xi, cos_xi) = math::sin_cos(xi2);
+    let sinh_eta = math::sinh(eta2);
+
+    // 等角緯度の正接から緯度の正接をニュートン法で求める
+    let tau_ = sin_xi / (sinh_eta * sinh_eta + cos_xi * cos_xi).sqrt();
+    let mut tau = tau_;
+    for _ in 0..5 {
+        let tau1 = (1. + tau * tau).sqrt();
+        let sigma = math::sinh(E * math::atanh(E * tau / tau1));
+        let tau_i = tau * (1. + sigma * sigma).sqrt() - sigma * tau1;
+        let d_tau = (tau_ - tau_i) / (1. + tau_i * tau_i).sqrt() * (1. + (1. - E * E) * tau * tau)
+            / ((1. - E * E) * tau1);
+        tau += d_tau;
+
+        if d_tau.abs() < 1e-15 * tau1 {
+            break;
+        }
+    }
+
+    let lat = math::atan(tau);
+    let long = long0 + math::atan2(sinh_eta, cos_xi);
+
+    (long, lat)
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use crate::jpr_ll::{jpr2ll, ll2jpr, ALPHA_ARR};
+
+    use super::*;
+
+    #[test]
+    fn extended_series_extends_krueger_series() {
+        // 5次までの項は既存の係数と一致し、6次以降の項はそれより十分小さい
+        for (ext, alpha) in EXT_ALPHA_ARR.iter().zip(ALPHA_ARR.iter()) {
+            assert!((ext - alpha).abs() < 1e-16, "{} {}", ext, alpha);
+        }
+        assert!(EXT_ALPHA_ARR[7].abs() < 1e-21);
+        assert!((EXT_A0 - crate::jpr_ll::A0).abs() < 1e-16);
+    }
+
+    #[test]
+    fn series_agree_within_zone() {
+        let krueger = TransverseMercator::from_jpr_origin(JprOrigin::Nine, TmSeries::Krueger);
+        let extended = TransverseMercator::from_jpr_origin(JprOrigin::Nine, TmSeries::Extended);
+        let ll = (
+            140.085555556_f64.to_radians(),
+            36.104166667_f64.to_radians(),
+        );
+
+        assert_eq!(krueger.forward(ll), ll2jpr(ll, JprOrigin::Nine));
+        assert_eq!(
+            krueger.inverse((22694.980, 11573.375)),
+            jpr2ll((22694.980, 11573.375), JprOrigin::Nine)
+        );
+
+        // 系の範囲内では両者の差は10nmに満たない
+        for &(long, lat) in &[(140.085555556_f64, 36.104166667_f64), (138.4, 35.), (139.8, 35.7), (142., 38.), (138.5, 33.)] {
+            let ll = (long.to_radians(), lat.to_radians());
+            let (y, x) = extended.forward(ll);
+            let (expected_y, expected_x) = krueger.forward(ll);
+            assert_close_to(y, expected_y, 8);
+            assert_close_to(x, expected_x, 8);
+        }
+    }
+
+    #[test]
+    fn extended_series_matches_exact_projection() {
+        // 厳密な横メルカトル図法による値(中央子午線は東経135度、縮尺係数は1)
+        // 子午線弧長を等角緯度の関数として解析接続し、mpmathにより40桁で求めた
+        let reference = [
+            ((165_f64, 0_f64), (3504812.861297372, 0.0)),
+            ((165., 30.), (2959830.261732426, 3731566.523599305)),
+            ((180., 20.), (5112166.798568309, 3020721.877733176)),
+            ((180., 45.), (3509561.102949681, 6071173.921738482)),
+            ((195., 10.), (8095101.814366583, 2166923.285379333)),
+            ((195., 40.), (5101766.208978208, 6573512.085202144)),
+            ((195., 60.), (2964227.090801294, 8205251.234304439)),
+            ((105., -35.), (-2778513.762984792, -4315796.89121356)),
+            ((90., 30.), (-4550702.122803737, 4350515.049990408)),
+            ((75., 50.), (-4012934.690426028, 7464509.825693656)),
+        ];
+
+        let krueger = TransverseMercator::new((135_f64.to_radians(), 0.), 1., TmSeries::Krueger);
+        let extended = TransverseMercator::new((135_f64.to_radians(), 0.), 1., TmSeries::Extended);
+
+        let mut krueger_error: f64 = 0.;
+        for &((long, lat), (y, x)) in &reference {
+            let ll = (long.to_radians(), lat.to_radians());
+
+            // 8次の級数は中央子午線から30〜60度離れても1µm未満の誤差に留まる
+            let (ext_y, ext_x) = extended.forward(ll);
+            assert_close_to(ext_y, y, 6);
+            assert_close_to(ext_x, x, 6);
+
+            let (long2, lat2) = extended.inverse((y, x));
+            assert_close_to(long2, ll.0, 14);
+            assert_close_to(lat2, ll.1, 14);
+
+            let (k_y, k_x) = krueger.forward(ll);
+            krueger_error = krueger_error.max((k_y - y).hypot(k_x - x));
+        }
+
+        // 5次の級数の誤差は0.1mmを超える
+        assert!(krueger_error > 1e-4, "{}", krueger_error);
+    }
+
+    #[test]
+    fn extended_series_round_trips_far_from_central_meridian() {
+        let tm = TransverseMercator::new((135_f64.to_radians(), 0.), 1., TmSeries::Extended);
+
+        for &(long, lat) in &[(175_f64, 10_f64), (160., 60.), (120., -45.), (135., 89.)] {
+            let ll = (long.to_radians(), lat.to_radians());
+            let (long2, lat2) = tm.inverse(tm.forward(ll));
+            assert_close_to(long2, ll.0, 12);
+            assert_close_to(lat2, ll.1, 12);
+        }
+    }
+}
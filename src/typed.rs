@@ -0,0 +1,211 @@
+//! Points whose coordinate reference system is a type parameter.
+//! The coordinate reference system of `Point<C>` can only be changed through a conversion, so passing e.g. JPR coordinates where longitude and latitude are expected becomes a compile error.
+//! The tuple API remains available for those who do not need this.
+//!
+//! 座標参照系を型パラメータとして持つ点。
+//! `Point<C>`の座標参照系は変換によってのみ変更できるため、緯度経度が必要な箇所に平面直角座標を渡すなどの誤りはコンパイルエラーとなる。
+//! これが不要な場合はタプルのAPIを引き続き利用できる。
+//!
+//! # Examples
+//!
+//! ```
+//! use coordinate_transformer::typed::{Jpr, Point, WebMercator, Wgs84};
+//!
+//! let tokyo = Point::<Wgs84>::new((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()));
+//!
+//! let jpr: Point<Jpr<9>> = tokyo.into();
+//! let pixel: Point<WebMercator<21>> = jpr.into();
+//!
+//! assert_eq!(pixel.coords(), (476868027, 211407949));
+//! ```
+//!
+//! Mixing coordinate reference systems does not compile.
+//!
+//! 座標参照系を混在させるとコンパイルできない。
+//!
+//! ```compile_fail
+//! use coordinate_transformer::typed::{Jpr, Point, Wgs84};
+//!
+//! fn needs_ll(_: Point<Wgs84>) {}
+//!
+//! needs_ll(Point::<Jpr<9>>::new((22694.980, 11573.375)));
+//! ```
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::{jpr2ll, ll2jpr, ll2pixel, pixel2ll, pixel_at_zoom, JprOrigin, ZoomLv};
+
+/// Trait implemented by the marker types of the coordinate reference systems
+///
+/// 座標参照系のマーカー型が実装するトレイト
+pub trait Crs {
+    /// Type of the coordinate values
+    ///
+    /// 座標値の型
+    type Coords: Debug + Clone + Copy + PartialEq;
+}
+
+/// Longitude and latitude (long, lat) expressed in the arc degree method
+///
+/// 弧度法で表された経度緯度(long, lat)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wgs84;
+
+impl Crs for Wgs84 {
+    type Coords = (f64, f64);
+}
+
+/// Plane rectangular coordinates (y, x) of the origin `N` (1 to 19)
+///
+/// 原点`N`(1から19)の平面直角座標(y, x)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Jpr<const N: u8>;
+
+impl<const N: u8> Crs for Jpr<N> {
+    type Coords = (f64, f64);
+}
+
+impl<const N: u8> Jpr<N> {
+    const VALID_ORIGIN: () = assert!(N >= 1 && N <= 19, "JPR origin must be between 1 and 19");
+
+    /// Returns the origin of the plane rectangular coordinate system
+    ///
+    /// 平面直角座標系の原点を返す
+    pub fn origin() -> JprOrigin {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_ORIGIN;
+        JprOrigin::parse(N).unwrap()
+    }
+}
+
+/// Pixel coordinates (x, y) at the Zoom level `Z` (24 or less)
+///
+/// Zoomレベル`Z`(24以下)におけるピクセル座標(x, y)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WebMercator<const Z: u8>;
+
+impl<const Z: u8> Crs for WebMercator<Z> {
+    type Coords = (u32, u32);
+}
+
+impl<const Z: u8> WebMercator<Z> {
+    const VALID_ZOOM: () = assert!(Z <= 24, "Zoom level must be 24 or less");
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom() -> ZoomLv {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_ZOOM;
+        ZoomLv::parse(Z).unwrap()
+    }
+}
+
+/// Structure representing a point in the coordinate reference system `C`
+///
+/// 座標参照系`C`における点を表す構造体
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<C: Crs> {
+    coords: C::Coords,
+    crs: PhantomData<C>,
+}
+
+impl<C: Crs> Point<C> {
+    /// Create a new point from the coordinate values
+    ///
+    /// 座標値から点を新しく作成する
+    pub fn new(coords: C::Coords) -> Self {
+        Self {
+            coords,
+            crs: PhantomData,
+        }
+    }
+
+    /// Returns the coordinate values
+    ///
+    /// 座標値を返す
+    pub fn coords(&self) -> C::Coords {
+        self.coords
+    }
+}
+
+impl<const N: u8> Point<Jpr<N>> {
+    /// Convert to the plane rectangular coordinates of another origin `M`
+    ///
+    /// 別の原点`M`の平面直角座標に変換する
+    pub fn to_origin<const M: u8>(&self) -> Point<Jpr<M>> {
+        Point::<Wgs84>::from(*self).into()
+    }
+}
+
+impl<const Z: u8> Point<WebMercator<Z>> {
+    /// Convert to the pixel coordinates at the Zoom level `W`, following `pixel_at_zoom`
+    ///
+    /// `pixel_at_zoom`に従ってZoomレベル`W`のピクセル座標に変換する
+    pub fn at_zoom<const W: u8>(&self) -> Point<WebMercator<W>> {
+        Point::new(pixel_at_zoom(self.coords, WebMercator::<Z>::zoom(), WebMercator::<W>::zoom()))
+    }
+}
+
+impl<const N: u8> From<Point<Wgs84>> for Point<Jpr<N>> {
+    fn from(point: Point<Wgs84>) -> Self {
+        Point::new(ll2jpr(point.coords, Jpr::<N>::origin()))
+    }
+}
+
+impl<const N: u8> From<Point<Jpr<N>>> for Point<Wgs84> {
+    fn from(point: Point<Jpr<N>>) -> Self {
+        Point::new(jpr2ll(point.coords, Jpr::<N>::origin()))
+    }
+}
+
+impl<const Z: u8> From<Point<Wgs84>> for Point<WebMercator<Z>> {
+    fn from(point: Point<Wgs84>) -> Self {
+        Point::new(ll2pixel(point.coords, WebMercator::<Z>::zoom()))
+    }
+}
+
+impl<const Z: u8> From<Point<WebMercator<Z>>> for Point<Wgs84> {
+    fn from(point: Point<WebMercator<Z>>) -> Self {
+        Point::new(pixel2ll(point.coords, WebMercator::<Z>::zoom()))
+    }
+}
+
+impl<const N: u8, const Z: u8> From<Point<Jpr<N>>> for Point<WebMercator<Z>> {
+    fn from(point: Point<Jpr<N>>) -> Self {
+        Point::<Wgs84>::from(point).into()
+    }
+}
+
+impl<const Z: u8, const N: u8> From<Point<WebMercator<Z>>> for Point<Jpr<N>> {
+    fn from(point: Point<WebMercator<Z>>) -> Self {
+        Point::<Wgs84>::from(point).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn typed_points_convert() {
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        let tokyo = Point::<Wgs84>::new(ll);
+
+        let jpr: Point<Jpr<9>> = tokyo.into();
+        assert_eq!(jpr.coords(), ll2jpr(ll, JprOrigin::Nine));
+
+        let back: Point<Wgs84> = jpr.into();
+        assert_close_to(back.coords().0, ll.0, 9);
+        assert_close_to(back.coords().1, ll.1, 9);
+
+        let other = jpr.to_origin::<10>();
+        assert_eq!(other.coords(), ll2jpr(jpr2ll(jpr.coords(), JprOrigin::Nine), JprOrigin::Ten));
+
+        let pixel: Point<WebMercator<21>> = tokyo.into();
+        assert_eq!(pixel.at_zoom::<18>().coords(), (476868027 >> 3, 211407949 >> 3));
+    }
+}
@@ -0,0 +1,129 @@
+//! Conversions accepting and returning `uom` quantities, for codebases that carry units in their types.
+//! Longitude, latitude and bearings are `Angle`, and plane rectangular coordinates, Cartesian coordinates, altitudes and distances are `Length`, so they can be given in any unit such as degrees or kilometers.
+//!
+//! 型で単位を扱うコードベースのための、`uom`の量を受け取り、返す変換。
+//! 経度、緯度、方位角は`Angle`、平面直角座標、直交座標、標高、距離は`Length`であり、度やキロメートルなど任意の単位で指定できる。
+
+use uom::si::angle::radian;
+use uom::si::f64::{Angle, Length};
+use uom::si::length::meter;
+
+use crate::geodesic::destination;
+use crate::jpr_ll::{jpr2ll, ll2jpr, JprOrigin};
+use crate::pixel_ll::{pixel_resolution, ZoomLv};
+use crate::xyz_ll::{llz2xyz, xyz2llz};
+
+/// Function to convert longitude and latitude to plane rectangular coordinates (y, x), following `ll2jpr`
+///
+/// `ll2jpr`に従って、経度と緯度を平面直角座標(y, x)に変換する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::uom_interop::ll2jpr_uom;
+/// use uom::si::angle::degree;
+/// use uom::si::f64::Angle;
+/// use uom::si::length::kilometer;
+///
+/// let (y, _) = ll2jpr_uom(Angle::new::<degree>(140.), Angle::new::<degree>(36.), JprOrigin::Nine);
+///
+/// assert!((y.get::<kilometer>() - 15.).abs() < 0.1);
+/// ```
+pub fn ll2jpr_uom(long: Angle, lat: Angle, origin: JprOrigin) -> (Length, Length) {
+    let (y, x) = ll2jpr((long.get::<radian>(), lat.get::<radian>()), origin);
+    (Length::new::<meter>(y), Length::new::<meter>(x))
+}
+
+/// Function to convert plane rectangular coordinates (y, x) to longitude and latitude, following `jpr2ll`
+///
+/// `jpr2ll`に従って、平面直角座標(y, x)を経度と緯度に変換する関数
+pub fn jpr2ll_uom(y: Length, x: Length, origin: JprOrigin) -> (Angle, Angle) {
+    let (long, lat) = jpr2ll((y.get::<meter>(), x.get::<meter>()), origin);
+    (Angle::new::<radian>(long), Angle::new::<radian>(lat))
+}
+
+/// Function to convert longitude, latitude and altitude to (x, y, z) in the Cartesian coordinate system (EPSG:4979), following `llz2xyz`
+///
+/// `llz2xyz`に従って、経度、緯度、標高を直交座標系(EPSG:4979)の(x, y, z)に変換する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::uom_interop::{llz2xyz_uom, xyz2llz_uom};
+/// use uom::si::angle::degree;
+/// use uom::si::f64::{Angle, Length};
+/// use uom::si::length::{foot, meter};
+///
+/// let altitude = Length::new::<foot>(1000.);
+/// let (x, y, z) = llz2xyz_uom(Angle::new::<degree>(140.), Angle::new::<degree>(36.), altitude);
+/// let (_, _, h) = xyz2llz_uom(x, y, z);
+///
+/// assert!((h.get::<meter>() - 304.8).abs() < 1e-6);
+/// ```
+pub fn llz2xyz_uom(long: Angle, lat: Angle, altitude: Length) -> (Length, Length, Length) {
+    let (x, y, z) = llz2xyz((long.get::<radian>(), lat.get::<radian>()), altitude.get::<meter>());
+    (Length::new::<meter>(x), Length::new::<meter>(y), Length::new::<meter>(z))
+}
+
+/// Function to convert (x, y, z) in the Cartesian coordinate system (EPSG:4979) to longitude, latitude and altitude, following `xyz2llz`
+///
+/// `xyz2llz`に従って、直交座標系(EPSG:4979)の(x, y, z)を経度、緯度、標高に変換する関数
+pub fn xyz2llz_uom(x: Length, y: Length, z: Length) -> (Angle, Angle, Length) {
+    let ((long, lat), altitude) = xyz2llz((x.get::<meter>(), y.get::<meter>(), z.get::<meter>()));
+    (Angle::new::<radian>(long), Angle::new::<radian>(lat), Length::new::<meter>(altitude))
+}
+
+/// Function to return the point reached by travelling `distance` from longitude and latitude along the geodesic with the initial `bearing`, following `destination`
+///
+/// `destination`に従って、経度と緯度から初期方位角`bearing`の測地線に沿って`distance`進んだ地点を返す関数
+pub fn destination_uom(long: Angle, lat: Angle, bearing: Angle, distance: Length) -> (Angle, Angle) {
+    let (long, lat) = destination(
+        (long.get::<radian>(), lat.get::<radian>()),
+        bearing.get::<radian>(),
+        distance.get::<meter>(),
+    );
+    (Angle::new::<radian>(long), Angle::new::<radian>(lat))
+}
+
+/// Function to return the length per pixel at the latitude and Zoom level, following `pixel_resolution`
+///
+/// `pixel_resolution`に従って、緯度とZoomレベルにおける1ピクセルあたりの長さを返す関数
+pub fn pixel_resolution_uom(lat: Angle, zoom: ZoomLv) -> Length {
+    Length::new::<meter>(pixel_resolution(lat.get::<radian>(), zoom))
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+    use uom::si::angle::degree;
+    use uom::si::length::kilometer;
+
+    use super::*;
+
+    #[test]
+    fn uom_conversions_match_raw_functions() {
+        let (long, lat) = (Angle::new::<degree>(139.7649308), Angle::new::<degree>(35.6812405));
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+
+        let (y, x) = ll2jpr_uom(long, lat, JprOrigin::Nine);
+        let expected = ll2jpr(ll, JprOrigin::Nine);
+        assert_close_to(y.get::<meter>(), expected.0, 6);
+        assert_close_to(x.get::<kilometer>(), expected.1 / 1000., 9);
+
+        let (long2, lat2) = jpr2ll_uom(y, x, JprOrigin::Nine);
+        assert_close_to(long2.get::<degree>(), 139.7649308, 9);
+        assert_close_to(lat2.get::<degree>(), 35.6812405, 9);
+
+        let (long3, lat3) = destination_uom(long, lat, Angle::new::<degree>(90.), Length::new::<kilometer>(1.));
+        let expected = destination(ll, 90_f64.to_radians(), 1000.);
+        assert_close_to(long3.get::<radian>(), expected.0, 12);
+        assert_close_to(lat3.get::<radian>(), expected.1, 12);
+
+        assert_close_to(
+            pixel_resolution_uom(lat, ZoomLv::Lv17).get::<meter>(),
+            pixel_resolution(ll.1, ZoomLv::Lv17),
+            9,
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Conversion between longitude and latitude and the Universal Transverse Mercator (UTM) coordinate system on GRS80.
+//! The same transverse Mercator series as the plane rectangular coordinate system is used, with the scale factor 0.9996 and the false easting and northing of UTM.
+//!
+//! 経度緯度とGRS80上のユニバーサル横メルカトル(UTM)座標系との変換。
+//! 平面直角座標系と同じ横メルカトルの級数を、UTMの縮尺係数0.9996と偽東距・偽北距で用いる。
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::jpr_ll::{ll2tm, tm2ll};
+
+/// Scale factor on the central meridian of UTM
+///
+/// UTMの中央経線における縮尺係数
+const K0: f64 = 0.9996;
+
+/// False easting of UTM (m)
+///
+/// UTMの偽東距(m)
+const FALSE_EASTING: f64 = 500000.;
+
+/// False northing of UTM in the southern hemisphere (m)
+///
+/// 南半球におけるUTMの偽北距(m)
+const FALSE_NORTHING_SOUTH: f64 = 10000000.;
+
+/// Enumerated type representing the hemisphere of UTM coordinates
+///
+/// UTM座標の半球を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Hemisphere {
+    /// Northern hemisphere
+    ///
+    /// 北半球
+    North,
+    /// Southern hemisphere, with the false northing of 10,000 km
+    ///
+    /// 南半球。偽北距は10,000km
+    South,
+}
+
+impl Hemisphere {
+    /// Returns the hemisphere containing the latitude expressed in the arc degree method. The equator belongs to the northern hemisphere.
+    ///
+    /// 弧度法で表された緯度を含む半球を返す。赤道は北半球に含まれる。
+    pub fn from_lat(lat: f64) -> Self {
+        if lat < 0. {
+            Self::South
+        } else {
+            Self::North
+        }
+    }
+
+    fn false_northing(self) -> f64 {
+        match self {
+            Self::North => 0.,
+            Self::South => FALSE_NORTHING_SOUTH,
+        }
+    }
+}
+
+impl fmt::Display for Hemisphere {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::North => write!(f, "N"),
+            Self::South => write!(f, "S"),
+        }
+    }
+}
+
+/// Returns the UTM zone (1 to 60) containing the longitude expressed in the arc degree method.
+/// The exceptions of the zones around Norway and Svalbard are not applied.
+///
+/// 弧度法で表された経度を含むUTMのゾーン(1から60)を返す。
+/// ノルウェー及びスバールバル諸島周辺のゾーンの例外は適用しない。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::utm::utm_zone;
+///
+/// assert_eq!(utm_zone(139.7649308_f64.to_radians()), 54);
+/// assert_eq!(utm_zone(-180_f64.to_radians()), 1);
+/// ```
+pub fn utm_zone(long: f64) -> u8 {
+    let long = (long + PI).rem_euclid(2. * PI);
+    ((long / 6_f64.to_radians()).floor() as u8).min(59) + 1
+}
+
+/// Returns the central meridian of the UTM zone expressed in the arc degree method.
+/// Panics if the zone is not in 1 to 60.
+///
+/// UTMのゾーンの中央経線を弧度法で返す。
+/// ゾーンが1から60でない場合はパニックする。
+pub fn utm_central_meridian(zone: u8) -> f64 {
+    assert!((1..=60).contains(&zone), "UTM zone must be in 1 to 60, got {zone}");
+    (zone as f64 * 6. - 183.).to_radians()
+}
+
+/// Convert (longitude, latitude) expressed in arc degree method to UTM coordinates (easting, northing) of the zone and hemisphere.
+/// Panics if the zone is not in 1 to 60.
+///
+/// 弧度法で表現された(経度, 緯度)を、ゾーンと半球のUTM座標(東距, 北距)に変換する。
+/// ゾーンが1から60でない場合はパニックする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::utm::{ll2utm, utm_zone, Hemisphere};
+///
+/// let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+/// let (easting, northing) = ll2utm(ll, utm_zone(ll.0), Hemisphere::North);
+/// ```
+pub fn ll2utm(ll: (f64, f64), zone: u8, hemisphere: Hemisphere) -> (f64, f64) {
+    let (y, x) = ll2tm(ll, utm_central_meridian(zone), K0, 0.);
+
+    (y + FALSE_EASTING, x + hemisphere.false_northing())
+}
+
+/// Convert UTM coordinates (easting, northing) of the zone and hemisphere to (longitude, latitude) expressed in arc degree method.
+/// Panics if the zone is not in 1 to 60.
+///
+/// ゾーンと半球のUTM座標(東距, 北距)を、弧度法で表現された(経度, 緯度)に変換する。
+/// ゾーンが1から60でない場合はパニックする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::utm::{utm2ll, Hemisphere};
+///
+/// let (long, lat) = utm2ll((500000., 0.), 54, Hemisphere::North);
+///
+/// assert!((long.to_degrees() - 141.).abs() < 1e-9);
+/// assert!(lat.abs() < 1e-9);
+/// ```
+pub fn utm2ll(en: (f64, f64), zone: u8, hemisphere: Hemisphere) -> (f64, f64) {
+    let (easting, northing) = en;
+
+    tm2ll(
+        (easting - FALSE_EASTING, northing - hemisphere.false_northing()),
+        utm_central_meridian(zone),
+        K0,
+        0.,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn ll2utm_works() {
+        // 東京駅 (ゾーン54N)
+        let ll = (139.7671248_f64.to_radians(), 35.6812362_f64.to_radians());
+        let zone = utm_zone(ll.0);
+        assert_eq!(zone, 54);
+
+        let (easting, northing) = ll2utm(ll, zone, Hemisphere::North);
+        assert_close_to(easting, 388435.669, 2);
+        assert_close_to(northing, 3949294.000, 2);
+
+        let (long, lat) = utm2ll((easting, northing), zone, Hemisphere::North);
+        assert_close_to(long, ll.0, 12);
+        assert_close_to(lat, ll.1, 12);
+    }
+
+    #[test]
+    fn southern_hemisphere_uses_false_northing() {
+        let ll = (151.2093_f64.to_radians(), -33.8688_f64.to_radians());
+        let zone = utm_zone(ll.0);
+        assert_eq!((zone, Hemisphere::from_lat(ll.1)), (56, Hemisphere::South));
+
+        let (easting, northing) = ll2utm(ll, zone, Hemisphere::South);
+        assert_close_to(easting, 334368.634, 2);
+        assert_close_to(northing, 6250948.345, 2);
+
+        let (long, lat) = utm2ll((easting, northing), zone, Hemisphere::South);
+        assert_close_to(long, ll.0, 12);
+        assert_close_to(lat, ll.1, 12);
+    }
+}
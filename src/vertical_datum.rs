@@ -0,0 +1,165 @@
+//! Vertical datums of heights and conversion of heights between them.
+//! Heights above Tokyo Peil (T.P.) and the local datums based on it are converted to ellipsoidal heights with the geoid height at the point, which is given by the caller because this crate has no geoid model.
+//!
+//! 高さの鉛直基準と、それらの間の高さの変換。
+//! 東京湾平均海面(T.P.)およびそれを基準とする地域の基準面からの高さは、その地点のジオイド高によって楕円体高に変換される。このクレートはジオイドモデルを持たないため、ジオイド高は呼び出し側が与える。
+
+use std::fmt::{Display, Formatter};
+
+use crate::xyz_ll::{llz2xyz, xyz2llz};
+
+/// Enumerated type representing the vertical datum of a height
+///
+/// 高さの鉛直基準を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VerticalDatum {
+    /// Height above the GRS80 ellipsoid
+    ///
+    /// GRS80楕円体からの高さ
+    Ellipsoidal,
+    /// Tokyo Peil, the mean sea level of Tokyo Bay, which is the basis of the elevations of Japan
+    ///
+    /// 日本の標高の基準である東京湾平均海面
+    Tp,
+    /// Osaka Peil, 1.3000 m below T.P., used around the Yodo River and Osaka Bay
+    ///
+    /// 淀川や大阪湾周辺で用いられる、T.P.の1.3000m下の大阪湾最低潮位
+    Op,
+    /// Yedogawa Peil, 0.8402 m below T.P., used around the Edo River and Tone River
+    ///
+    /// 江戸川や利根川周辺で用いられる、T.P.の0.8402m下の江戸川工事基準面
+    Yp,
+    /// Arakawa Peil, 1.1344 m below T.P., used around the Ara River and Sumida River
+    ///
+    /// 荒川や隅田川周辺で用いられる、T.P.の1.1344m下の荒川工事基準面
+    Ap,
+}
+
+impl VerticalDatum {
+    /// Returns the height (m) of T.P. above this datum, or `None` for the ellipsoid, whose offset depends on the geoid height
+    ///
+    /// この基準面から見たT.P.の高さ(m)を返す。楕円体の場合、差はジオイド高によるため`None`を返す
+    pub fn tp_offset(&self) -> Option<f64> {
+        match self {
+            VerticalDatum::Ellipsoidal => None,
+            VerticalDatum::Tp => Some(0.),
+            VerticalDatum::Op => Some(1.3),
+            VerticalDatum::Yp => Some(0.8402),
+            VerticalDatum::Ap => Some(1.1344),
+        }
+    }
+}
+
+impl Display for VerticalDatum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            VerticalDatum::Ellipsoidal => "ellipsoidal",
+            VerticalDatum::Tp => "T.P.",
+            VerticalDatum::Op => "O.P.",
+            VerticalDatum::Yp => "Y.P.",
+            VerticalDatum::Ap => "A.P.",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Function to convert a height (m) above the datum `from` to the height above the datum `to`.
+/// `geoid_height` is the height (m) of the geoid above the ellipsoid at the point, and is used only when converting to or from `VerticalDatum::Ellipsoidal`.
+///
+/// 基準面`from`からの高さ(m)を基準面`to`からの高さに変換する関数。
+/// `geoid_height`はその地点における楕円体からのジオイドの高さ(m)であり、`VerticalDatum::Ellipsoidal`との変換でのみ用いられる。
+///
+/// # Examples
+///
+/// Convert 5 m above T.P. to the height above O.P. and to the ellipsoidal height in Osaka, where the geoid height is about 37 m.
+///
+/// T.P.上5mを、ジオイド高が約37mの大阪においてO.P.からの高さと楕円体高に変換する。
+///
+/// ```
+/// use coordinate_transformer::vertical_datum::{convert_height, VerticalDatum};
+///
+/// assert_eq!(convert_height(5., VerticalDatum::Tp, VerticalDatum::Op, 37.), 6.3);
+/// assert_eq!(convert_height(5., VerticalDatum::Tp, VerticalDatum::Ellipsoidal, 37.), 42.);
+/// ```
+pub fn convert_height(height: f64, from: VerticalDatum, to: VerticalDatum, geoid_height: f64) -> f64 {
+    // T.P.からの高さを経由して変換する
+    let tp_height = match from.tp_offset() {
+        Some(offset) => height - offset,
+        None => height - geoid_height,
+    };
+
+    match to.tp_offset() {
+        Some(offset) => tp_height + offset,
+        None => tp_height + geoid_height,
+    }
+}
+
+/// Function to transform (longitude, latitude) expressed in the arc degree method and a height above `datum` into (x, y, z) in the Cartesian coordinate system (EPSG:4979), following `llz2xyz`.
+/// `geoid_height` is the height (m) of the geoid above the ellipsoid at the point.
+///
+/// `llz2xyz`に従って、弧度法で表された(経度, 緯度)と`datum`からの高さを直交座標系(EPSG:4979)の(x, y, z)に変換する関数。
+/// `geoid_height`はその地点における楕円体からのジオイドの高さ(m)である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::vertical_datum::{llz2xyz_with_datum, VerticalDatum};
+/// use coordinate_transformer::xyz_ll::llz2xyz;
+///
+/// let ll = (135.5_f64.to_radians(), 34.7_f64.to_radians());
+///
+/// assert_eq!(llz2xyz_with_datum(ll, 5., VerticalDatum::Tp, 37.), llz2xyz(ll, 42.));
+/// ```
+pub fn llz2xyz_with_datum(ll: (f64, f64), height: f64, datum: VerticalDatum, geoid_height: f64) -> (f64, f64, f64) {
+    let altitude = convert_height(height, datum, VerticalDatum::Ellipsoidal, geoid_height);
+    llz2xyz(ll, altitude)
+}
+
+/// Function to convert (x, y, z) in the Cartesian coordinate system (EPSG:4979) to ((longitude, latitude), height above `datum`) expressed using the arc degree method, following `xyz2llz`.
+/// `geoid_height` is the height (m) of the geoid above the ellipsoid at the point.
+///
+/// `xyz2llz`に従って、直交座標系(EPSG:4979)の(x, y, z)を弧度法で表された((経度, 緯度), `datum`からの高さ)に変換する関数。
+/// `geoid_height`はその地点における楕円体からのジオイドの高さ(m)である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::vertical_datum::{xyz2llz_with_datum, VerticalDatum};
+///
+/// let xyz = (-3957446.631, 3320692.008, 3728250.454);
+///
+/// let ((long, lat), height) = xyz2llz_with_datum(xyz, VerticalDatum::Yp, 36.7);
+/// ```
+pub fn xyz2llz_with_datum(xyz: (f64, f64, f64), datum: VerticalDatum, geoid_height: f64) -> ((f64, f64), f64) {
+    let (ll, altitude) = xyz2llz(xyz);
+    (ll, convert_height(altitude, VerticalDatum::Ellipsoidal, datum, geoid_height))
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+
+    #[test]
+    fn convert_height_works() {
+        // A.P.+0mはT.P.-1.1344m、Y.P.-0.2942m
+        assert_close_to(convert_height(0., VerticalDatum::Ap, VerticalDatum::Tp, 0.), -1.1344, 9);
+        assert_close_to(convert_height(0., VerticalDatum::Ap, VerticalDatum::Yp, 0.), -0.2942, 9);
+
+        let ellipsoidal = convert_height(3., VerticalDatum::Op, VerticalDatum::Ellipsoidal, 37.);
+        assert_close_to(ellipsoidal, 38.7, 9);
+        assert_close_to(convert_height(ellipsoidal, VerticalDatum::Ellipsoidal, VerticalDatum::Op, 37.), 3., 9);
+    }
+
+    #[test]
+    fn with_datum_round_trips() {
+        let ll = (139.8_f64.to_radians(), 35.7_f64.to_radians());
+        let xyz = llz2xyz_with_datum(ll, 2.5, VerticalDatum::Ap, 36.9);
+
+        let ((long, lat), height) = xyz2llz_with_datum(xyz, VerticalDatum::Ap, 36.9);
+        assert_close_to(long, ll.0, 9);
+        assert_close_to(lat, ll.1, 9);
+        assert_close_to(height, 2.5, 4);
+    }
+}
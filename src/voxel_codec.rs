@@ -0,0 +1,295 @@
+//! Compact binary serialization of voxel collections.
+//!
+//! The format consists of a header and the voxels sorted in Morton (Z-order) order, each stored as the LEB128 varint of the difference from the previous Morton code.
+//! Because neighbouring voxels have close Morton codes, most voxels of a dense region take only one or two bytes.
+//!
+//! | Field      | Size     | Content                          |
+//! |------------|----------|----------------------------------|
+//! | magic      | 4        | `VOXL`                           |
+//! | version    | 1        | `1`                              |
+//! | zoom       | 1        | Zoom level                       |
+//! | resolution | 8        | vertical resolution (m), f64 LE  |
+//! | count      | varint   | number of voxels                 |
+//! | deltas     | varint.. | differences of the Morton codes  |
+//!
+//! ボクセルの集合のコンパクトなバイナリシリアライズ。
+//!
+//! 形式はヘッダと、Morton順(Z順)に並べたボクセルからなり、各ボクセルは直前のMortonコードとの差のLEB128可変長整数として格納される。
+//! 隣接するボクセルのMortonコードは近いため、密な領域のボクセルのほとんどは1〜2バイトしか要しない。
+
+use std::fmt::{Display, Formatter};
+
+use crate::pixel_ll::ZoomLv;
+use crate::structure::Voxel;
+
+const MAGIC: &[u8; 4] = b"VOXL";
+const VERSION: u8 = 1;
+
+/// Enumerated type representing errors of the voxel serialization
+///
+/// ボクセルのシリアライズのエラーを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoxelCodecError {
+    /// The voxels have different Zoom levels
+    ///
+    /// ボクセルのZoomレベルが異なる
+    MixedZoom,
+    /// The voxels have different resolutions
+    ///
+    /// ボクセルの分解能が異なる
+    MixedResolution,
+    /// The data does not start with the magic bytes
+    ///
+    /// データがマジックバイトで始まっていない
+    InvalidMagic,
+    /// The version of the format is not supported
+    ///
+    /// 形式のバージョンに対応していない
+    UnsupportedVersion(u8),
+    /// The Zoom level in the header is invalid
+    ///
+    /// ヘッダのZoomレベルが不正
+    InvalidZoom(u8),
+    /// The data ended unexpectedly
+    ///
+    /// データが途中で終わっている
+    UnexpectedEof,
+    /// A varint or Morton code is out of range
+    ///
+    /// 可変長整数またはMortonコードが範囲外
+    Overflow,
+}
+
+impl Display for VoxelCodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoxelCodecError::MixedZoom => write!(f, "voxels have different zoom levels"),
+            VoxelCodecError::MixedResolution => write!(f, "voxels have different resolutions"),
+            VoxelCodecError::InvalidMagic => write!(f, "invalid magic bytes"),
+            VoxelCodecError::UnsupportedVersion(v) => write!(f, "unsupported version: {}", v),
+            VoxelCodecError::InvalidZoom(z) => write!(f, "invalid zoom level: {}", z),
+            VoxelCodecError::UnexpectedEof => write!(f, "unexpected end of data"),
+            VoxelCodecError::Overflow => write!(f, "value out of range"),
+        }
+    }
+}
+
+impl std::error::Error for VoxelCodecError {}
+
+/// Spreads the 32 bits of `v` so that there are two zero bits between each bit
+///
+/// `v`の32ビットを各ビットの間に2つの0ビットが入るように広げる
+fn spread(v: u32) -> u128 {
+    (0..32).fold(0, |acc, i| acc | (((v as u128 >> i) & 1) << (3 * i)))
+}
+
+/// Inverse of `spread`
+///
+/// `spread`の逆変換
+fn compact(v: u128) -> u32 {
+    (0..32).fold(0, |acc, i| acc | ((((v >> (3 * i)) & 1) as u32) << i))
+}
+
+/// Function to compute the 96-bit Morton code (Z-order curve index) of voxel coordinates (x, y, z)
+///
+/// ボクセル座標(x, y, z)の96ビットのMortonコード(Z階数曲線のインデックス)を計算する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::voxel_codec::{morton_decode, morton_encode};
+///
+/// assert_eq!(morton_encode((1, 1, 1)), 0b111);
+/// assert_eq!(morton_decode(morton_encode((3, 5, 7))), (3, 5, 7));
+/// ```
+pub fn morton_encode(xyz: (u32, u32, u32)) -> u128 {
+    let (x, y, z) = xyz;
+    spread(x) | (spread(y) << 1) | (spread(z) << 2)
+}
+
+/// Function to restore voxel coordinates (x, y, z) from a Morton code
+///
+/// Mortonコードからボクセル座標(x, y, z)を復元する関数
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::voxel_codec::morton_decode;
+///
+/// assert_eq!(morton_decode(0b110), (0, 1, 1));
+/// ```
+pub fn morton_decode(code: u128) -> (u32, u32, u32) {
+    (compact(code), compact(code >> 1), compact(code >> 2))
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut v: u128) {
+    loop {
+        let byte = (v & 0x7f) as u8;
+        v >>= 7;
+        if v == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u128, VoxelCodecError> {
+    let mut v = 0_u128;
+
+    for shift in (0..128).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(VoxelCodecError::UnexpectedEof)?;
+        *bytes = rest;
+
+        let bits = (byte & 0x7f) as u128;
+        // 最後のバイトは残りの2ビットしか持てない
+        if shift == 126 && bits > 0b11 {
+            return Err(VoxelCodecError::Overflow);
+        }
+        v |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(v);
+        }
+    }
+
+    Err(VoxelCodecError::Overflow)
+}
+
+/// Function to serialize voxels sharing the same Zoom level and resolution into the Morton-ordered binary format.
+/// Duplicate voxels are stored once. An empty slice is stored with Zoom level 0 and resolution 0.
+///
+/// 同じZoomレベルと分解能を持つボクセルをMorton順のバイナリ形式にシリアライズする関数。
+/// 重複するボクセルは1つだけ格納される。空のスライスはZoomレベル0、分解能0として格納される。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::Voxel;
+/// use coordinate_transformer::voxel_codec::{decode_voxels, encode_voxels};
+///
+/// let voxels = vec![
+/// Voxel::new(10, 20, 3, 0.5, ZoomLv::Lv18),
+/// Voxel::new(11, 20, 3, 0.5, ZoomLv::Lv18),
+/// ];
+///
+/// let bytes = encode_voxels(&voxels).unwrap();
+/// let decoded = decode_voxels(&bytes).unwrap();
+///
+/// assert_eq!(decoded, voxels);
+/// ```
+pub fn encode_voxels(voxels: &[Voxel]) -> Result<Vec<u8>, VoxelCodecError> {
+    let (zoom, resolution) = voxels
+        .first()
+        .map_or((ZoomLv::Lv0, 0.), |v| (v.zoom(), v.resolution()));
+
+    if voxels.iter().any(|v| v.zoom() != zoom) {
+        return Err(VoxelCodecError::MixedZoom);
+    }
+    if voxels.iter().any(|v| v.resolution().to_bits() != resolution.to_bits()) {
+        return Err(VoxelCodecError::MixedResolution);
+    }
+
+    let mut codes = voxels.iter().map(|v| morton_encode(v.to_tuple())).collect::<Vec<_>>();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let mut buf = Vec::with_capacity(14 + codes.len() * 2);
+    buf.extend_from_slice(MAGIC);
+    buf.push(VERSION);
+    buf.push(zoom as u8);
+    buf.extend_from_slice(&resolution.to_le_bytes());
+    write_varint(&mut buf, codes.len() as u128);
+
+    let mut prev = 0;
+    for code in codes {
+        write_varint(&mut buf, code - prev);
+        prev = code;
+    }
+
+    Ok(buf)
+}
+
+/// Function to deserialize voxels from the Morton-ordered binary format.
+/// The voxels are returned in Morton order.
+///
+/// Morton順のバイナリ形式からボクセルをデシリアライズする関数。
+/// ボクセルはMorton順で返される。
+pub fn decode_voxels(bytes: &[u8]) -> Result<Vec<Voxel>, VoxelCodecError> {
+    let header = bytes.get(..14).ok_or(VoxelCodecError::UnexpectedEof)?;
+    if &header[..4] != MAGIC {
+        return Err(VoxelCodecError::InvalidMagic);
+    }
+    if header[4] != VERSION {
+        return Err(VoxelCodecError::UnsupportedVersion(header[4]));
+    }
+    let zoom = ZoomLv::parse(header[5]).map_err(|_| VoxelCodecError::InvalidZoom(header[5]))?;
+    let resolution = f64::from_le_bytes(header[6..14].try_into().unwrap());
+
+    let mut rest = &bytes[14..];
+    let count = read_varint(&mut rest)?;
+    // 1ボクセルあたり少なくとも1バイトを要するため、残りのバイト数を超える個数は不正
+    if count > rest.len() as u128 {
+        return Err(VoxelCodecError::UnexpectedEof);
+    }
+
+    let mut voxels = Vec::with_capacity(count as usize);
+    let mut code = 0_u128;
+    for _ in 0..count {
+        code = code
+            .checked_add(read_varint(&mut rest)?)
+            .filter(|c| c >> 96 == 0)
+            .ok_or(VoxelCodecError::Overflow)?;
+
+        let (x, y, z) = morton_decode(code);
+        voxels.push(Voxel::new(x, y, z, resolution, zoom));
+    }
+
+    Ok(voxels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voxel_codec_round_trips() {
+        let voxels = (0..8)
+            .flat_map(|x| (0..8).flat_map(move |y| (0..4).map(move |z| (x, y, z))))
+            .map(|(x, y, z)| Voxel::new(232_000 + x, 103_000 + y, 100 + z, 2.38, ZoomLv::Lv15))
+            .chain(std::iter::once(Voxel::new(u32::MAX, u32::MAX, u32::MAX, 2.38, ZoomLv::Lv15)))
+            .collect::<Vec<_>>();
+
+        let bytes = encode_voxels(&voxels).unwrap();
+        // 密な領域では1ボクセルあたりおよそ1バイトになる
+        assert!(bytes.len() < 14 + voxels.len() + 32);
+
+        let mut decoded = decode_voxels(&bytes).unwrap();
+        let mut expected = voxels.clone();
+        let key = |v: &Voxel| morton_encode(v.to_tuple());
+        decoded.sort_by_key(key);
+        expected.sort_by_key(key);
+        assert_eq!(decoded, expected);
+
+        assert_eq!(decode_voxels(&encode_voxels(&[]).unwrap()), Ok(vec![]));
+    }
+
+    #[test]
+    fn voxel_codec_rejects_invalid_input() {
+        let a = Voxel::new(0, 0, 0, 1., ZoomLv::Lv10);
+
+        assert_eq!(
+            encode_voxels(&[a, Voxel::new(0, 0, 0, 1., ZoomLv::Lv11)]),
+            Err(VoxelCodecError::MixedZoom)
+        );
+        assert_eq!(
+            encode_voxels(&[a, Voxel::new(0, 0, 0, 2., ZoomLv::Lv10)]),
+            Err(VoxelCodecError::MixedResolution)
+        );
+
+        let bytes = encode_voxels(&[a]).unwrap();
+        assert_eq!(decode_voxels(&bytes[..bytes.len() - 1]), Err(VoxelCodecError::UnexpectedEof));
+        assert_eq!(decode_voxels(b"XXXX\x01\x00\0\0\0\0\0\0\0\0\0"), Err(VoxelCodecError::InvalidMagic));
+    }
+}
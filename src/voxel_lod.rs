@@ -0,0 +1,117 @@
+//! Resampling sets of voxels between Zoom levels for building level-of-detail pyramids.
+//! The scaling follows `Voxel::at_zoom`: each zoom step halves or doubles the voxels on all three axes, including the vertical resolution.
+//!
+//! 詳細度のピラミッドを構築するための、Zoomレベル間のボクセルの集合の再標本化。
+//! 倍率は`Voxel::at_zoom`に従い、Zoomレベルが1つ変わるごとに鉛直方向の分解能を含む3軸全てでボクセルが半分または2倍になる。
+
+use std::collections::BTreeMap;
+
+use crate::pixel_ll::ZoomLv;
+use crate::structure::Voxel;
+
+/// Function to merge voxels into the voxels at the coarser Zoom level `zoom`.
+/// A coarse voxel is kept when at least `min_children` of the voxels it contains are present, so 1 keeps any occupied voxel and `8^n` for `n` zoom steps keeps only fully occupied ones.
+/// The result is sorted by (x, y, z) and has no duplicates. The voxels are expected to share their Zoom level and resolution.
+///
+/// ボクセルをより粗いZoomレベル`zoom`のボクセルに統合する関数。
+/// 粗いボクセルは、それに含まれるボクセルが`min_children`個以上存在する場合に残される。1なら占有されたボクセルは全て残り、Zoomレベルの差が`n`のとき`8^n`なら完全に占有されたボクセルのみが残る。
+/// 結果は(x, y, z)の順に並び、重複はない。ボクセルはZoomレベルと分解能が共通であることを前提とする。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::Voxel;
+/// use coordinate_transformer::voxel_lod::downscale_voxels;
+///
+/// let voxels = [Voxel::new(4, 6, 2, 1., ZoomLv::Lv18), Voxel::new(5, 7, 3, 1., ZoomLv::Lv18)];
+///
+/// let merged = downscale_voxels(&voxels, ZoomLv::Lv17, 1);
+///
+/// assert_eq!(merged, vec![Voxel::new(2, 3, 1, 2., ZoomLv::Lv17)]);
+/// ```
+pub fn downscale_voxels(voxels: &[Voxel], zoom: ZoomLv, min_children: usize) -> Vec<Voxel> {
+    let mut parents = BTreeMap::<(u32, u32, u32), (Voxel, usize)>::new();
+
+    for voxel in voxels {
+        assert!(zoom <= voxel.zoom(), "target Zoom level must not be finer");
+
+        // Zoomアウトでは座標が範囲を超えないため、常にSomeとなる
+        if let Some(parent) = voxel.at_zoom(zoom) {
+            parents.entry(parent.to_tuple()).or_insert((parent, 0)).1 += 1;
+        }
+    }
+
+    parents
+        .into_values()
+        .filter(|&(_, count)| count >= min_children)
+        .map(|(parent, _)| parent)
+        .collect()
+}
+
+/// Function to subdivide voxels into the voxels at the finer Zoom level `zoom`, following `Voxel::children_at_zoom`.
+/// Voxels whose subdivisions would exceed `u32::MAX` are skipped.
+///
+/// `Voxel::children_at_zoom`に従って、ボクセルをより細かいZoomレベル`zoom`のボクセルに分割する関数。
+/// 分割すると`u32::MAX`を超えるボクセルは読み飛ばす。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::Voxel;
+/// use coordinate_transformer::voxel_lod::upscale_voxels;
+///
+/// let voxels = [Voxel::new(2, 3, 1, 2., ZoomLv::Lv17)];
+///
+/// assert_eq!(upscale_voxels(&voxels, ZoomLv::Lv18).count(), 8);
+/// ```
+pub fn upscale_voxels(voxels: &[Voxel], zoom: ZoomLv) -> impl Iterator<Item=Voxel> + '_ {
+    voxels.iter().filter_map(move |voxel| voxel.children_at_zoom(zoom)).flat_map(|children| children.iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_zoom_scales_all_axes() {
+        let voxel = Voxel::new(11, 13, 7, 0.5, ZoomLv::Lv20);
+
+        let parent = voxel.at_zoom(ZoomLv::Lv18).unwrap();
+        assert_eq!(parent.to_tuple(), (2, 3, 1));
+        assert_eq!(parent.resolution(), 2.);
+
+        let child = parent.at_zoom(ZoomLv::Lv19).unwrap();
+        assert_eq!(child.to_tuple(), (4, 6, 2));
+        assert_eq!(child.resolution(), 1.);
+
+        // 分割したボクセルは全て元のボクセルに戻る
+        let children = parent.children_at_zoom(ZoomLv::Lv20).unwrap();
+        assert_eq!(children.volume(), 64);
+        assert!(children.contains(&voxel));
+        assert!(children.iter().all(|c| c.at_zoom(ZoomLv::Lv18) == Some(parent)));
+
+        // Zoomインで上位のビットが失われる高さは範囲外となる
+        let high = Voxel::new(11, 13, u32::MAX >> 1, 0.5, ZoomLv::Lv20);
+        assert!(high.at_zoom(ZoomLv::Lv21).is_some());
+        assert_eq!(high.at_zoom(ZoomLv::Lv22), None);
+        assert_eq!(high.children_at_zoom(ZoomLv::Lv22), None);
+        assert_eq!(upscale_voxels(&[high], ZoomLv::Lv22).count(), 0);
+    }
+
+    #[test]
+    fn downscale_voxels_applies_min_children() {
+        let full = Voxel::new(0, 0, 0, 1., ZoomLv::Lv18).children_at_zoom(ZoomLv::Lv19).unwrap().iter();
+        let voxels = full.chain([Voxel::new(2, 0, 0, 0.5, ZoomLv::Lv19)]).collect::<Vec<_>>();
+
+        assert_eq!(downscale_voxels(&voxels, ZoomLv::Lv18, 1).len(), 2);
+        assert_eq!(
+            downscale_voxels(&voxels, ZoomLv::Lv18, 8),
+            vec![Voxel::new(0, 0, 0, 1., ZoomLv::Lv18)]
+        );
+
+        let upscaled = upscale_voxels(&downscale_voxels(&voxels, ZoomLv::Lv18, 1), ZoomLv::Lv19).count();
+        assert_eq!(upscaled, 16);
+    }
+}
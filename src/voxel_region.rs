@@ -0,0 +1,249 @@
+use crate::jpr_ll::JprOrigin;
+use crate::jpr_pixel::jpr2pixel;
+use crate::pixel_ll::{ll2pixel, ZoomLv};
+use crate::structure::Voxel;
+
+/// Structure representing an axis-aligned box of voxel indices.
+/// Both the minimum and maximum corners are inclusive.
+///
+/// ボクセルのインデックスの軸平行な直方体を表す構造体。
+/// 最小と最大の角はいずれも範囲に含まれる。
+///
+/// # Examples
+///
+/// Split a region into chunks and count the voxels.
+///
+/// 領域をチャンクに分割し、ボクセルの数を数える。
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::voxel_region::VoxelRegion;
+///
+/// let region = VoxelRegion::new((0, 0, 0), (9, 9, 1), 1., ZoomLv::Lv18);
+///
+/// assert_eq!(region.volume(), 200);
+/// assert_eq!(region.chunks((4, 4, 2)).map(|c| c.volume()).sum::<u64>(), 200);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct VoxelRegion {
+    min: (u32, u32, u32),
+    max: (u32, u32, u32),
+    resolution: f64,
+    zoom_lv: ZoomLv,
+}
+
+impl VoxelRegion {
+    /// Create a new region from two corners. The corners are reordered so that `min` is not greater than `max` on each axis.
+    ///
+    /// 2つの角から領域を新しく作成する。各軸で`min`が`max`を超えないように角は並べ替えられる。
+    pub fn new(min: (u32, u32, u32), max: (u32, u32, u32), resolution: f64, zoom_lv: ZoomLv) -> Self {
+        Self {
+            min: (min.0.min(max.0), min.1.min(max.1), min.2.min(max.2)),
+            max: (min.0.max(max.0), min.1.max(max.1), min.2.max(max.2)),
+            resolution,
+            zoom_lv,
+        }
+    }
+
+    /// Create a region covering the bounding box of (longitude, latitude) expressed in the arc degree method and the height range (m)
+    ///
+    /// 弧度法で表された(経度, 緯度)の範囲と高さの範囲(m)を覆う領域を作成する
+    pub fn from_ll_bounds(
+        min_ll: (f64, f64),
+        max_ll: (f64, f64),
+        height_range: (f64, f64),
+        resolution: f64,
+        zoom_lv: ZoomLv,
+    ) -> Self {
+        let (x1, y1) = ll2pixel(min_ll, zoom_lv);
+        let (x2, y2) = ll2pixel(max_ll, zoom_lv);
+
+        Self::from_pixels(&[(x1, y1), (x2, y2)], height_range, resolution, zoom_lv)
+    }
+
+    /// Create a region covering the bounding box of plane rectangular coordinates (y, x) and the height range (m).
+    /// Because the axes of the plane rectangular coordinate system are not parallel to those of the pixel coordinates, the region covers the four corners of the box.
+    ///
+    /// 平面直角座標(y, x)の範囲と高さの範囲(m)を覆う領域を作成する。
+    /// 平面直角座標系の軸はピクセル座標の軸と平行ではないため、領域は範囲の4隅を覆う。
+    pub fn from_jpr_bounds(
+        min_yx: (f64, f64),
+        max_yx: (f64, f64),
+        origin: JprOrigin,
+        height_range: (f64, f64),
+        resolution: f64,
+        zoom_lv: ZoomLv,
+    ) -> Self {
+        let corners = [
+            (min_yx.0, min_yx.1),
+            (min_yx.0, max_yx.1),
+            (max_yx.0, min_yx.1),
+            (max_yx.0, max_yx.1),
+        ]
+            .map(|yx| jpr2pixel(yx, origin, zoom_lv));
+
+        Self::from_pixels(&corners, height_range, resolution, zoom_lv)
+    }
+
+    fn from_pixels(pixels: &[(u32, u32)], height_range: (f64, f64), resolution: f64, zoom_lv: ZoomLv) -> Self {
+        let min_x = pixels.iter().map(|p| p.0).min().unwrap();
+        let max_x = pixels.iter().map(|p| p.0).max().unwrap();
+        let min_y = pixels.iter().map(|p| p.1).min().unwrap();
+        let max_y = pixels.iter().map(|p| p.1).max().unwrap();
+
+        // ボクセルの高さは符号なしのため、負の高さは0に丸める
+        let z = |h: f64| (h.max(0.) / resolution) as u32;
+
+        Self::new(
+            (min_x, min_y, z(height_range.0)),
+            (max_x, max_y, z(height_range.1)),
+            resolution,
+            zoom_lv,
+        )
+    }
+
+    /// Returns the minimum corner (x, y, z)
+    ///
+    /// 最小の角(x, y, z)を返す
+    pub fn min(&self) -> (u32, u32, u32) {
+        self.min
+    }
+
+    /// Returns the maximum corner (x, y, z)
+    ///
+    /// 最大の角(x, y, z)を返す
+    pub fn max(&self) -> (u32, u32, u32) {
+        self.max
+    }
+
+    /// Returns the number of voxels in the region
+    ///
+    /// 領域内のボクセルの数を返す
+    pub fn volume(&self) -> u64 {
+        let (dx, dy, dz) = self.size();
+        dx * dy * dz
+    }
+
+    /// Returns the number of voxels along each axis
+    ///
+    /// 各軸方向のボクセルの数を返す
+    pub fn size(&self) -> (u64, u64, u64) {
+        (
+            (self.max.0 - self.min.0) as u64 + 1,
+            (self.max.1 - self.min.1) as u64 + 1,
+            (self.max.2 - self.min.2) as u64 + 1,
+        )
+    }
+
+    /// Returns whether the voxel is in the region. Voxels of another Zoom level or resolution are never contained.
+    ///
+    /// ボクセルが領域内にあるかを返す。Zoomレベルまたは分解能が異なるボクセルは含まれない。
+    pub fn contains(&self, voxel: &Voxel) -> bool {
+        let (x, y, z) = voxel.to_tuple();
+
+        voxel.zoom() == self.zoom_lv
+            && voxel.resolution() == self.resolution
+            && (self.min.0..=self.max.0).contains(&x)
+            && (self.min.1..=self.max.1).contains(&y)
+            && (self.min.2..=self.max.2).contains(&z)
+    }
+
+    /// Returns the intersection of two regions, or `None` if they do not overlap or have different Zoom levels or resolutions
+    ///
+    /// 2つの領域の共通部分を返す。重ならない場合やZoomレベルまたは分解能が異なる場合は`None`を返す
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if self.zoom_lv != other.zoom_lv || self.resolution != other.resolution {
+            return None;
+        }
+
+        let min = (
+            self.min.0.max(other.min.0),
+            self.min.1.max(other.min.1),
+            self.min.2.max(other.min.2),
+        );
+        let max = (
+            self.max.0.min(other.max.0),
+            self.max.1.min(other.max.1),
+            self.max.2.min(other.max.2),
+        );
+
+        if min.0 > max.0 || min.1 > max.1 || min.2 > max.2 {
+            return None;
+        }
+
+        Some(Self { min, max, ..*self })
+    }
+
+    /// Returns an iterator over the voxels in the region, with x varying fastest and z slowest
+    ///
+    /// 領域内のボクセルを、xが最も速く、zが最も遅く変化する順に返すイテレータを返す
+    pub fn iter(&self) -> impl Iterator<Item=Voxel> {
+        let Self { min, max, resolution, zoom_lv } = *self;
+
+        (min.2..=max.2).flat_map(move |z| {
+            (min.1..=max.1)
+                .flat_map(move |y| (min.0..=max.0).map(move |x| Voxel::new(x, y, z, resolution, zoom_lv)))
+        })
+    }
+
+    /// Splits the region into chunks of at most `chunk_size` voxels along each axis
+    ///
+    /// 領域を各軸方向に最大`chunk_size`個のボクセルを持つチャンクに分割する
+    pub fn chunks(&self, chunk_size: (u32, u32, u32)) -> impl Iterator<Item=Self> {
+        assert!(chunk_size.0 > 0 && chunk_size.1 > 0 && chunk_size.2 > 0, "chunk size must be positive");
+
+        let region = *self;
+        let starts = |min: u32, max: u32, step: u32| (min..=max).step_by(step as usize);
+
+        starts(region.min.2, region.max.2, chunk_size.2).flat_map(move |z| {
+            starts(region.min.1, region.max.1, chunk_size.1).flat_map(move |y| {
+                starts(region.min.0, region.max.0, chunk_size.0).map(move |x| Self {
+                    min: (x, y, z),
+                    max: (
+                        x.saturating_add(chunk_size.0 - 1).min(region.max.0),
+                        y.saturating_add(chunk_size.1 - 1).min(region.max.1),
+                        z.saturating_add(chunk_size.2 - 1).min(region.max.2),
+                    ),
+                    ..region
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voxel_region_intersection_works() {
+        let a = VoxelRegion::new((0, 0, 0), (9, 9, 9), 1., ZoomLv::Lv18);
+        let b = VoxelRegion::new((15, 15, 5), (5, 5, 15), 1., ZoomLv::Lv18);
+
+        let i = a.intersection(&b).unwrap();
+        assert_eq!((i.min(), i.max()), ((5, 5, 5), (9, 9, 9)));
+        assert_eq!(i.iter().count() as u64, i.volume());
+        assert!(i.iter().all(|v| a.contains(&v) && b.contains(&v)));
+
+        let c = VoxelRegion::new((10, 0, 0), (12, 0, 0), 1., ZoomLv::Lv18);
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.intersection(&VoxelRegion { zoom_lv: ZoomLv::Lv17, ..a }), None);
+    }
+
+    #[test]
+    fn voxel_region_from_bounds_works() {
+        let min_ll = (139.76_f64.to_radians(), 35.68_f64.to_radians());
+        let max_ll = (139.77_f64.to_radians(), 35.69_f64.to_radians());
+        let region = VoxelRegion::from_ll_bounds(min_ll, max_ll, (-10., 50.), 0.5, ZoomLv::Lv16);
+
+        let (min_x, min_y) = ll2pixel((min_ll.0, max_ll.1), ZoomLv::Lv16);
+        let (max_x, max_y) = ll2pixel((max_ll.0, min_ll.1), ZoomLv::Lv16);
+        assert_eq!(region.min(), (min_x, min_y, 0));
+        assert_eq!(region.max(), (max_x, max_y, 100));
+
+        let region = VoxelRegion::from_jpr_bounds((-100., -100.), (100., 100.), JprOrigin::Nine, (0., 10.), 1., ZoomLv::Lv18);
+        let (x, y) = jpr2pixel((0., 0.), JprOrigin::Nine, ZoomLv::Lv18);
+        let center = Voxel::new(x, y, 5, 1., ZoomLv::Lv18);
+        assert!(region.contains(&center));
+    }
+}
@@ -0,0 +1,243 @@
+//! Sets of occupied voxels with set algebra and morphology, e.g. for comparing voxelized as-built and design point clouds.
+//!
+//! 集合演算と膨張・収縮を備えた占有ボクセルの集合。施工後と設計の点群をボクセル化して比較するなどに用いる。
+
+use std::collections::BTreeSet;
+
+use crate::pixel_ll::ZoomLv;
+use crate::structure::Voxel;
+use crate::voxel_codec::{morton_decode, morton_encode};
+
+/// Structure representing a set of voxels of one Zoom level and resolution, ordered by their Morton codes
+///
+/// 1つのZoomレベルと分解能のボクセルの集合を表す構造体。Mortonコードの順に並ぶ
+///
+/// # Examples
+///
+/// Find the voxels occupied by the as-built model but not by the design.
+///
+/// 施工後のモデルが占有し、設計が占有しないボクセルを求める。
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::structure::Voxel;
+/// use coordinate_transformer::voxel_set::VoxelSet;
+///
+/// let mut design = VoxelSet::new(1., ZoomLv::Lv20);
+/// let mut built = VoxelSet::new(1., ZoomLv::Lv20);
+/// design.extend([(0, 0, 0), (1, 0, 0)].map(|(x, y, z)| Voxel::new(x, y, z, 1., ZoomLv::Lv20)));
+/// built.extend([(1, 0, 0), (1, 0, 1)].map(|(x, y, z)| Voxel::new(x, y, z, 1., ZoomLv::Lv20)));
+///
+/// let extra = built.difference(&design);
+///
+/// assert_eq!(extra.iter().map(|v| v.to_tuple()).collect::<Vec<_>>(), vec![(1, 0, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelSet {
+    resolution: f64,
+    zoom_lv: ZoomLv,
+    codes: BTreeSet<u128>,
+}
+
+impl VoxelSet {
+    /// Create a new empty set of voxels with the resolution and Zoom level
+    ///
+    /// 分解能とZoomレベルを持つ空のボクセルの集合を新しく作成する
+    pub fn new(resolution: f64, zoom_lv: ZoomLv) -> Self {
+        Self {
+            resolution,
+            zoom_lv,
+            codes: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the vertical resolution (m)
+    ///
+    /// 鉛直方向の分解能(m)を返す
+    pub fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        self.zoom_lv
+    }
+
+    fn assert_same_grid(&self, voxel_zoom: ZoomLv, voxel_resolution: f64) {
+        assert!(
+            voxel_zoom == self.zoom_lv && voxel_resolution == self.resolution,
+            "voxels must share the Zoom level and resolution of the set"
+        );
+    }
+
+    fn with_codes(&self, codes: BTreeSet<u128>) -> Self {
+        Self {
+            resolution: self.resolution,
+            zoom_lv: self.zoom_lv,
+            codes,
+        }
+    }
+
+    /// Add a voxel, returning whether it was not yet in the set.
+    /// Panics if the Zoom level or resolution of the voxel differs from the set.
+    ///
+    /// ボクセルを追加し、まだ集合に含まれていなかったかどうかを返す。
+    /// ボクセルのZoomレベルまたは分解能が集合と異なる場合はパニックする。
+    pub fn insert(&mut self, voxel: Voxel) -> bool {
+        self.assert_same_grid(voxel.zoom(), voxel.resolution());
+        self.codes.insert(morton_encode(voxel.to_tuple()))
+    }
+
+    /// Remove a voxel, returning whether it was in the set
+    ///
+    /// ボクセルを削除し、集合に含まれていたかどうかを返す
+    pub fn remove(&mut self, voxel: &Voxel) -> bool {
+        self.codes.remove(&morton_encode(voxel.to_tuple()))
+    }
+
+    /// Returns whether the voxel (x, y, z) is in the set
+    ///
+    /// ボクセル(x, y, z)が集合に含まれるかどうかを返す
+    pub fn contains(&self, xyz: (u32, u32, u32)) -> bool {
+        self.codes.contains(&morton_encode(xyz))
+    }
+
+    /// Returns the number of voxels
+    ///
+    /// ボクセルの数を返す
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Returns whether the set is empty
+    ///
+    /// 集合が空かどうかを返す
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Returns the voxels in the order of their Morton codes
+    ///
+    /// ボクセルをMortonコードの順で返す
+    pub fn iter(&self) -> impl Iterator<Item=Voxel> + '_ {
+        self.codes.iter().map(|&code| {
+            let (x, y, z) = morton_decode(code);
+            Voxel::new(x, y, z, self.resolution, self.zoom_lv)
+        })
+    }
+
+    /// Returns the voxels in either set.
+    /// Panics if the Zoom levels or resolutions of the sets differ, as do the other set operations.
+    ///
+    /// いずれかの集合に含まれるボクセルを返す。
+    /// 他の集合演算と同様に、集合のZoomレベルまたは分解能が異なる場合はパニックする。
+    pub fn union(&self, other: &Self) -> Self {
+        self.assert_same_grid(other.zoom_lv, other.resolution);
+        self.with_codes(self.codes.union(&other.codes).copied().collect())
+    }
+
+    /// Returns the voxels in both sets
+    ///
+    /// 両方の集合に含まれるボクセルを返す
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.assert_same_grid(other.zoom_lv, other.resolution);
+        self.with_codes(self.codes.intersection(&other.codes).copied().collect())
+    }
+
+    /// Returns the voxels in this set but not in the other
+    ///
+    /// この集合に含まれ、もう一方の集合に含まれないボクセルを返す
+    pub fn difference(&self, other: &Self) -> Self {
+        self.assert_same_grid(other.zoom_lv, other.resolution);
+        self.with_codes(self.codes.difference(&other.codes).copied().collect())
+    }
+
+    /// Returns the set grown by one voxel, i.e. the voxels in or adjacent (including diagonally, 26-neighborhood) to the set
+    ///
+    /// 1ボクセル膨張させた集合、すなわち集合に含まれるか、それに(斜めを含めて26近傍で)隣接するボクセルを返す
+    pub fn dilate(&self) -> Self {
+        let codes = self
+            .codes
+            .iter()
+            .flat_map(|&code| neighborhood(morton_decode(code)))
+            .map(morton_encode)
+            .collect();
+
+        self.with_codes(codes)
+    }
+
+    /// Returns the set shrunk by one voxel, i.e. the voxels whose 26-neighborhood is entirely in the set.
+    /// The outside of the voxel coordinate range counts as empty.
+    ///
+    /// 1ボクセル収縮させた集合、すなわち26近傍が全て集合に含まれるボクセルを返す。
+    /// ボクセル座標の範囲外は空とみなす。
+    pub fn erode(&self) -> Self {
+        let codes = self
+            .codes
+            .iter()
+            .copied()
+            .filter(|&code| {
+                let xyz = morton_decode(code);
+                let (x, y, z) = xyz;
+                x > 0 && y > 0 && z > 0 && x < u32::MAX && y < u32::MAX && z < u32::MAX
+                    && neighborhood(xyz).all(|n| self.contains(n))
+            })
+            .collect();
+
+        self.with_codes(codes)
+    }
+}
+
+/// Returns the voxel and its 26 neighbors within the voxel coordinate range
+///
+/// ボクセル座標の範囲内にある、ボクセルとその26近傍を返す
+fn neighborhood(xyz: (u32, u32, u32)) -> impl Iterator<Item=(u32, u32, u32)> {
+    let (x, y, z) = xyz;
+    let range = |v: u32| v.saturating_sub(1)..=v.saturating_add(1);
+
+    range(x).flat_map(move |nx| range(y).flat_map(move |ny| range(z).map(move |nz| (nx, ny, nz))))
+}
+
+impl Extend<Voxel> for VoxelSet {
+    fn extend<T: IntoIterator<Item=Voxel>>(&mut self, iter: T) {
+        for voxel in iter {
+            self.insert(voxel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_of(voxels: &[(u32, u32, u32)]) -> VoxelSet {
+        let mut set = VoxelSet::new(0.5, ZoomLv::Lv18);
+        set.extend(voxels.iter().map(|&(x, y, z)| Voxel::new(x, y, z, 0.5, ZoomLv::Lv18)));
+        set
+    }
+
+    #[test]
+    fn boolean_operations_work() {
+        let a = set_of(&[(1, 1, 1), (2, 1, 1), (3, 1, 1)]);
+        let b = set_of(&[(3, 1, 1), (4, 1, 1)]);
+
+        assert_eq!(a.union(&b).len(), 4);
+        assert_eq!(a.intersection(&b), set_of(&[(3, 1, 1)]));
+        assert_eq!(a.difference(&b), set_of(&[(1, 1, 1), (2, 1, 1)]));
+        assert!(!a.difference(&b).contains((3, 1, 1)));
+    }
+
+    #[test]
+    fn dilate_and_erode_work() {
+        let single = set_of(&[(5, 5, 5)]);
+        let cube = single.dilate();
+        assert_eq!(cube.len(), 27);
+        assert_eq!(cube.erode(), single);
+
+        // 座標の範囲の端では近傍が欠ける
+        assert_eq!(set_of(&[(0, 0, 0)]).dilate().len(), 8);
+        assert!(set_of(&[(0, 0, 0)]).dilate().erode().is_empty());
+    }
+}
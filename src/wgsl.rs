@@ -0,0 +1,304 @@
+//! Generation of WGSL compute shaders for batch conversion on the GPU
+//! The shaders are generated from the same coefficient tables as the CPU functions and are dispatched by `GpuBatchConverter` with the `wgpu` feature.
+//! Every shader reads the storage buffer at binding 0 and writes the storage buffer at binding 1 of group 0, both flat `array<f32>` of the given stride, with the entry point `main` and a workgroup size of 64.
+//! Since WGSL has no `f64` and an `f32` has only 24 bits, absolute coordinates cannot be represented on the GPU, e.g. the spacing of `f32` is 0.5 m at 4000 km and 256 px at 2^32 px.
+//! The buffers therefore hold offsets: the inputs are differences from a reference point and the outputs are differences from that point converted on the CPU in `f64`.
+//! The differences are evaluated with half-angle formulas without cancellation, so the error is below 1e-6 of the offsets instead of the absolute values.
+//! The transcendental functions of WGSL are only required to be accurate to an absolute error (e.g. 2^-11 for `sin`), which is too coarse for small arguments, so the shaders evaluate them with their own polynomials.
+//!
+//! GPUで一括変換するためのWGSLコンピュートシェーダの生成
+//! シェーダはCPU版の関数と同じ係数表から生成され、`wgpu`フィーチャの`GpuBatchConverter`で実行される。
+//! どのシェーダもグループ0のバインディング0のストレージバッファを読み、バインディング1のストレージバッファに書き込む。いずれも指定した間隔で値を並べた`array<f32>`であり、エントリポイントは`main`、ワークグループサイズは64である。
+//! WGSLには`f64`がなく`f32`の仮数は24ビットしかないため、GPU上では絶対座標を表せない。例えば`f32`の間隔は4000kmで0.5m、2^32ピクセルで256ピクセルとなる。
+//! そのためバッファには差分を格納する。入力は基準点からの差であり、出力はCPU上で`f64`で変換した基準点からの差である。
+//! 差は桁落ちのない半角の公式で求めるため、誤差は絶対値ではなく差の1e-6未満となる。
+//! WGSLの超越関数は絶対誤差(例えば`sin`では2^-11)までしか精度が要求されておらず、小さな引数には粗すぎるため、シェーダでは独自の多項式で評価する。
+
+use std::f64::consts::PI;
+
+use crate::consts::{WGS84_ECCENTRICITY_SQUARED, WGS84_SEMI_MAJOR_AXIS};
+use crate::jpr_ll::{A, A0, ALPHA_ARR, LAT0, M0, N, S0_ARR};
+use crate::math;
+use crate::{JprOrigin, ZoomLv};
+
+const HEADER: &str = "@group(0) @binding(0) var<storage, read> input: array<f32>;
+@group(0) @binding(1) var<storage, read_write> output: array<f32>;
+";
+
+const CENTER: &str = "@group(0) @binding(2) var<uniform> center: vec2<f32>;
+";
+
+/// Transcendental functions accurate relative to small arguments, by Taylor series after argument reduction
+///
+/// 引数の縮小の後にテイラー級数で求める、小さな引数に対して相対的に正確な超越関数
+const FUNCTIONS: &str = "
+// [-π/2, π/2]に折り返して求める。|v| <= 3π/2
+fn precise_sin(v: f32) -> f32 {
+    var x = v;
+    if (x > 1.5707964) {
+        x = 3.1415927 - x;
+    } else if (x < -1.5707964) {
+        x = -3.1415927 - x;
+    }
+    let x2 = x * x;
+    return x * (1.0 - x2 / 6.0 * (1.0 - x2 / 20.0 * (1.0 - x2 / 42.0 * (1.0 - x2 / 72.0 * (1.0 - x2 / 110.0 * (1.0 - x2 / 156.0))))));
+}
+
+// |x| <= 3π/2
+fn precise_cos(x: f32) -> f32 {
+    return precise_sin(1.5707964 - abs(x));
+}
+
+fn precise_sinh(x: f32) -> f32 {
+    if (abs(x) >= 1.0) {
+        return 0.5 * (exp(x) - exp(-x));
+    }
+    let x2 = x * x;
+    return x * (1.0 + x2 / 6.0 * (1.0 + x2 / 20.0 * (1.0 + x2 / 42.0 * (1.0 + x2 / 72.0 * (1.0 + x2 / 110.0)))));
+}
+
+// |x| <= 0.2となるまで、atanh(x) = 2 atanh(x / (1 + sqrt(1 - x^2)))により引数を縮小する
+fn precise_atanh(v: f32) -> f32 {
+    var x = v;
+    var scale = 1.0;
+    for (var i = 0; i < 8 && abs(x) > 0.2; i++) {
+        x = x / (1.0 + sqrt((1.0 - x) * (1.0 + x)));
+        scale *= 2.0;
+    }
+    let x2 = x * x;
+    return scale * x * (1.0 + x2 * (1.0 / 3.0 + x2 * (1.0 / 5.0 + x2 * (1.0 / 7.0 + x2 * (1.0 / 9.0 + x2 * (1.0 / 11.0 + x2 / 13.0))))));
+}
+
+// |x| <= 0.2となるまで、atan(x) = 2 atan(x / (1 + sqrt(1 + x^2)))により引数を縮小する
+fn precise_atan(v: f32) -> f32 {
+    var x = v;
+    var scale = 1.0;
+    for (var i = 0; i < 8 && abs(x) > 0.2; i++) {
+        x = x / (1.0 + sqrt(1.0 + x * x));
+        scale *= 2.0;
+    }
+    let x2 = x * x;
+    return scale * x * (1.0 - x2 * (1.0 / 3.0 - x2 * (1.0 / 5.0 - x2 * (1.0 / 7.0 - x2 * (1.0 / 9.0 - x2 * (1.0 / 11.0 - x2 / 13.0))))));
+}
+";
+
+/// Generates a WGSL compute shader equivalent to `ll2jpr`.
+/// The input is (longitude, latitude) expressed in the arc degree method minus those of the origin, and the output is plane rectangular coordinates (y, x), both with a stride of 2.
+/// The error is below 1e-6 of the distance from the origin, e.g. about 5 cm at 100 km.
+///
+/// `ll2jpr`と同等のWGSLコンピュートシェーダを生成する。
+/// 入力は弧度法で表された(経度, 緯度)から原点のものを引いた値、出力は平面直角座標(y, x)であり、いずれも間隔は2である。
+/// 誤差は原点からの距離の1e-6未満であり、例えば100kmで約5cmである。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::jpr_ll::JprOrigin;
+/// use coordinate_transformer::wgsl::ll2jpr_wgsl;
+///
+/// let source = ll2jpr_wgsl(JprOrigin::Nine);
+/// ```
+pub fn ll2jpr_wgsl(origin: JprOrigin) -> String {
+    let lat0 = LAT0[origin as usize];
+
+    let a_ = ((M0 * A) / (1. + N)) * A0;
+    let e = (2. * N.sqrt()) / (1. + N);
+
+    // 原点の等角緯度
+    let (sin_lat0, cos_lat0) = math::sin_cos(lat0);
+    let psi0 = math::atanh(sin_lat0) - e * math::atanh(e * sin_lat0);
+    let t0 = math::sinh(psi0);
+    let chi0 = math::atan(t0);
+
+    // 原点のxは0となるよう、級数と子午線弧長の差を補正する
+    let x_correction = a_
+        * (chi0
+            + ALPHA_ARR
+                .iter()
+                .enumerate()
+                .map(|(i, a)| a * math::sin(2. * (i as f64 + 1.) * chi0))
+                .sum::<f64>())
+        - S0_ARR[origin as usize];
+
+    let terms = ALPHA_ARR
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let k = i as f64 + 1.;
+            let k2 = 2. * k;
+            // 引数を[-π, π]に収める
+            let phase = (k2 * chi0 + PI).rem_euclid(2. * PI) - PI;
+            let sin_phase = math::sin(phase);
+            format!(
+                "    x += {a:?} * (2.0 * precise_cos({phase:?} + {k:?} * d_xi) * precise_sin({k:?} * d_xi) * cosh({k2:?} * eta) + {sin_phase:?} * 2.0 * precise_sinh({k:?} * eta) * precise_sinh({k:?} * eta));
+    y += {a:?} * precise_cos({phase:?} + {k2:?} * d_xi) * precise_sinh({k2:?} * eta);
+"
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        "{HEADER}{FUNCTIONS}
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {{
+    let i = id.x;
+    if (i >= arrayLength(&output) / 2u) {{
+        return;
+    }}
+
+    let d_long = input[2u * i];
+    let d_lat = input[2u * i + 1u];
+
+    // 等長緯度の差
+    let h = precise_sin(0.5 * d_lat);
+    let d_sin = 2.0 * precise_cos({lat0:?} + 0.5 * d_lat) * h;
+    let sin_lat = {sin_lat0:?} + d_sin;
+    let d_psi = precise_atanh(d_sin / (2.0 * h * h + precise_cos({lat0:?} + d_lat) * {cos_lat0:?}))
+        - {e:?} * precise_atanh({e:?} * d_sin / (1.0 - {e:?} * {e:?} * sin_lat * {sin_lat0:?}));
+
+    // 等角緯度の正接の差
+    let psi = {psi0:?} + d_psi;
+    let t = precise_sinh(psi);
+    let d_t = 2.0 * cosh({psi0:?} + 0.5 * d_psi) * precise_sinh(0.5 * d_psi);
+
+    let lambda_h = precise_sin(0.5 * d_long);
+    let d_xi = precise_atan((d_t + {t0:?} * 2.0 * lambda_h * lambda_h) / (precise_cos(d_long) + t * {t0:?}));
+    let eta = precise_atanh(precise_sin(d_long) / cosh(psi));
+
+    var x = d_xi;
+    var y = eta;
+{terms}
+    output[2u * i] = {a_:?} * y;
+    output[2u * i + 1u] = {a_:?} * x + {x_correction:?};
+}}
+"
+    )
+}
+
+/// Generates a WGSL compute shader equivalent to `ll2pixel` without rounding.
+/// The input is (longitude, latitude) expressed in the arc degree method minus those of the center in the uniform buffer at binding 2, and the output is fractional pixel coordinates (x, y) minus those of the center, both with a stride of 2.
+/// The error is below 1e-6 of the distance from the center in pixels.
+///
+/// 丸めを行わない`ll2pixel`と同等のWGSLコンピュートシェーダを生成する。
+/// 入力は弧度法で表された(経度, 緯度)からバインディング2のユニフォームバッファにある中心のものを引いた値、出力は小数のピクセル座標(x, y)から中心のものを引いた値であり、いずれも間隔は2である。
+/// 誤差は中心からのピクセル単位の距離の1e-6未満である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::wgsl::ll2pixel_wgsl;
+///
+/// let source = ll2pixel_wgsl(ZoomLv::Lv17);
+/// ```
+pub fn ll2pixel_wgsl(zoom: ZoomLv) -> String {
+    let scale = 2_f64.powi(zoom as i32 + 7) / PI;
+
+    format!(
+        "{HEADER}{CENTER}{FUNCTIONS}
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {{
+    let i = id.x;
+    if (i >= arrayLength(&output) / 2u) {{
+        return;
+    }}
+
+    let d_long = input[2u * i];
+    let d_lat = input[2u * i + 1u];
+
+    // 等長緯度の差
+    let h = precise_sin(0.5 * d_lat);
+    let d_sin = 2.0 * precise_cos(center.y + 0.5 * d_lat) * h;
+    let d_psi = precise_atanh(d_sin / (2.0 * h * h + precise_cos(center.y + d_lat) * precise_cos(center.y)));
+
+    output[2u * i] = {scale:?} * d_long;
+    output[2u * i + 1u] = -{scale:?} * d_psi;
+}}
+"
+    )
+}
+
+/// Generates a WGSL compute shader equivalent to `llz2xyz`.
+/// The input is (longitude, latitude, altitude) with the longitude and latitude expressed in the arc degree method minus those of the center in the uniform buffer at binding 2, and the output is (x, y, z) minus those of the center at altitude 0, both with a stride of 3.
+/// The error is below 1e-6 of the distance from the center.
+///
+/// `llz2xyz`と同等のWGSLコンピュートシェーダを生成する。
+/// 入力は弧度法で表された経度と緯度からバインディング2のユニフォームバッファにある中心のものを引いた(経度, 緯度, 標高)、出力は(x, y, z)から標高0の中心のものを引いた値であり、いずれも間隔は3である。
+/// 誤差は中心からの距離の1e-6未満である。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::wgsl::llz2xyz_wgsl;
+///
+/// let source = llz2xyz_wgsl();
+/// ```
+pub fn llz2xyz_wgsl() -> String {
+    const A: f64 = WGS84_SEMI_MAJOR_AXIS;
+    const E2: f64 = WGS84_ECCENTRICITY_SQUARED;
+
+    format!(
+        "{HEADER}{CENTER}{FUNCTIONS}
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {{
+    let i = id.x;
+    if (i >= arrayLength(&output) / 3u) {{
+        return;
+    }}
+
+    let d_long = input[3u * i];
+    let d_lat = input[3u * i + 1u];
+    let altitude = input[3u * i + 2u];
+
+    let long = center.x + d_long;
+    let lat = center.y + d_lat;
+
+    // 三角関数の差
+    let h_lat = precise_sin(0.5 * d_lat);
+    let h_long = precise_sin(0.5 * d_long);
+    let d_sin_lat = 2.0 * precise_cos(center.y + 0.5 * d_lat) * h_lat;
+    let d_cos_lat = -2.0 * precise_sin(center.y + 0.5 * d_lat) * h_lat;
+    let d_sin_long = 2.0 * precise_cos(center.x + 0.5 * d_long) * h_long;
+    let d_cos_long = -2.0 * precise_sin(center.x + 0.5 * d_long) * h_long;
+
+    let sin_lat = precise_sin(lat);
+    let cos_lat = precise_cos(lat);
+    let sin_long = precise_sin(long);
+    let cos_long = precise_cos(long);
+    let sin_lat_c = precise_sin(center.y);
+    let cos_lat_c = precise_cos(center.y);
+
+    // 卯酉線曲率半径の差
+    let w = sqrt(1.0 - {E2:?} * sin_lat * sin_lat);
+    let w_c = sqrt(1.0 - {E2:?} * sin_lat_c * sin_lat_c);
+    let n_c = {A:?} / w_c;
+    let d_n = {A:?} * {E2:?} * d_sin_lat * (sin_lat + sin_lat_c) / (w * w_c * (w + w_c));
+
+    output[3u * i] = (d_n + altitude) * cos_lat * cos_long + n_c * (d_cos_lat * cos_long + cos_lat_c * d_cos_long);
+    output[3u * i + 1u] = (d_n + altitude) * cos_lat * sin_long + n_c * (d_cos_lat * sin_long + cos_lat_c * d_sin_long);
+    output[3u * i + 2u] = (d_n * (1.0 - {E2:?}) + altitude) * sin_lat + n_c * (1.0 - {E2:?}) * d_sin_lat;
+}}
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) {
+        let module = naga::front::wgsl::parse_str(source).unwrap_or_else(|e| panic!("{}\n{}", e, source));
+        naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+            .validate(&module)
+            .unwrap_or_else(|e| panic!("{:?}\n{}", e, source));
+    }
+
+    #[test]
+    fn shaders_are_valid_wgsl() {
+        validate(&ll2jpr_wgsl(JprOrigin::Nine));
+        validate(&ll2jpr_wgsl(JprOrigin::Nineteen));
+        validate(&ll2pixel_wgsl(ZoomLv::Lv0));
+        validate(&ll2pixel_wgsl(ZoomLv::Lv24));
+        validate(&llz2xyz_wgsl());
+    }
+}
@@ -0,0 +1,176 @@
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+
+use crate::consts::WEB_MERCATOR_RADIUS;
+use crate::pixel_ll::{pixel2ll_f64, world_size_px, ZoomLv};
+
+/// Coordinate reference system of a world file
+///
+/// ワールドファイルの座標参照系
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WorldFileCrs {
+    /// Web Mercator (EPSG:3857) in meters
+    ///
+    /// m単位のWebメルカトル(EPSG:3857)
+    WebMercator,
+    /// Longitude and latitude in degrees.
+    /// The latitude spacing is averaged over the image, because Web Mercator pixels are not uniform in latitude.
+    ///
+    /// 度単位の経度緯度。
+    /// Webメルカトルのピクセルは緯度方向に等間隔ではないため、緯度方向の間隔は画像全体で平均したものとなる。
+    LongLat,
+}
+
+/// Structure representing the six parameters of a world file (.tfw/.pgw)
+///
+/// ワールドファイル(.tfw/.pgw)の6つのパラメータを表す構造体
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct WorldFile {
+    /// Pixel size in the x direction
+    ///
+    /// x方向のピクセルサイズ
+    pub a: f64,
+    /// Rotation about the y axis
+    ///
+    /// y軸周りの回転
+    pub d: f64,
+    /// Rotation about the x axis
+    ///
+    /// x軸周りの回転
+    pub b: f64,
+    /// Pixel size in the y direction (negative)
+    ///
+    /// y方向のピクセルサイズ(負の値)
+    pub e: f64,
+    /// x coordinate of the center of the upper left pixel
+    ///
+    /// 左上のピクセルの中心のx座標
+    pub c: f64,
+    /// y coordinate of the center of the upper left pixel
+    ///
+    /// 左上のピクセルの中心のy座標
+    pub f: f64,
+}
+
+impl WorldFile {
+    /// Returns the GDAL geotransform, which refers to the upper left corner of the upper left pixel.
+    ///
+    /// 左上のピクセルの左上隅を基準とするGDALのジオトランスフォームを返す。
+    pub fn to_geotransform(&self) -> [f64; 6] {
+        [
+            self.c - self.a / 2. - self.b / 2.,
+            self.a,
+            self.b,
+            self.f - self.d / 2. - self.e / 2.,
+            self.d,
+            self.e,
+        ]
+    }
+}
+
+impl Display for WorldFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for value in [self.a, self.d, self.b, self.e, self.c, self.f] {
+            writeln!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Function to return the world file of an image covering the pixel coordinate range from `min` to `max` (both inclusive).
+///
+/// ピクセル座標`min`から`max`(両端を含む)の範囲を覆う画像のワールドファイルを返す関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::world_file::{pixel_world_file, WorldFileCrs};
+///
+/// let world_file = pixel_world_file((0, 0), (255, 255), ZoomLv::Lv0, WorldFileCrs::WebMercator);
+/// let geotransform = world_file.to_geotransform();
+/// ```
+pub fn pixel_world_file(min: (u32, u32), max: (u32, u32), zoom: ZoomLv, crs: WorldFileCrs) -> WorldFile {
+    let (min_x, min_y) = (min.0 as f64, min.1 as f64);
+    let (max_x, max_y) = (max.0 as f64 + 1., max.1 as f64 + 1.);
+
+    match crs {
+        WorldFileCrs::WebMercator => {
+            let resolution = 2. * PI * WEB_MERCATOR_RADIUS / world_size_px(zoom) as f64;
+
+            WorldFile {
+                a: resolution,
+                d: 0.,
+                b: 0.,
+                e: -resolution,
+                c: (min_x + 0.5) * resolution - PI * WEB_MERCATOR_RADIUS,
+                f: PI * WEB_MERCATOR_RADIUS - (min_y + 0.5) * resolution,
+            }
+        }
+        WorldFileCrs::LongLat => {
+            let (west, north) = pixel2ll_f64((min_x, min_y), zoom);
+            let (east, south) = pixel2ll_f64((max_x, max_y), zoom);
+
+            let a = (east - west).to_degrees() / (max_x - min_x);
+            let e = -(north - south).to_degrees() / (max_y - min_y);
+
+            WorldFile {
+                a,
+                d: 0.,
+                b: 0.,
+                e,
+                c: west.to_degrees() + a / 2.,
+                f: north.to_degrees() + e / 2.,
+            }
+        }
+    }
+}
+
+/// Function to return the world file of a 256 px tile.
+///
+/// 256pxのタイルのワールドファイルを返す関数。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::pixel_ll::ZoomLv;
+/// use coordinate_transformer::world_file::{tile_world_file, WorldFileCrs};
+///
+/// let world_file = tile_world_file((14552, 6451), ZoomLv::Lv14, WorldFileCrs::WebMercator);
+/// println!("{}", world_file);
+/// ```
+pub fn tile_world_file(tile: (u32, u32), zoom: ZoomLv, crs: WorldFileCrs) -> WorldFile {
+    let (x, y) = tile;
+    pixel_world_file((x * 256, y * 256), (x * 256 + 255, y * 256 + 255), zoom, crs)
+}
+
+#[cfg(test)]
+mod tests {
+    use close_to::assert_close_to;
+
+    use super::*;
+    use crate::pixel_ll::MAX_MERCATOR_LAT_DEG;
+
+    #[test]
+    fn tile_world_file_works() {
+        let world_file = tile_world_file((0, 0), ZoomLv::Lv0, WorldFileCrs::WebMercator);
+        let geotransform = world_file.to_geotransform();
+
+        assert_close_to(geotransform[0], -20037508.342789244, 6);
+        assert_close_to(geotransform[3], 20037508.342789244, 6);
+        assert_close_to(geotransform[1], 156543.03392804097, 6);
+        assert_close_to(geotransform[5], -156543.03392804097, 6);
+    }
+
+    #[test]
+    fn long_lat_world_file_works() {
+        let world_file = tile_world_file((1, 0), ZoomLv::Lv1, WorldFileCrs::LongLat);
+        let geotransform = world_file.to_geotransform();
+
+        assert_close_to(geotransform[0], 0., 9);
+        assert_close_to(geotransform[1], 180. / 256., 9);
+        assert_close_to(geotransform[3], MAX_MERCATOR_LAT_DEG, 6);
+        assert_close_to(geotransform[5], -MAX_MERCATOR_LAT_DEG / 256., 6);
+        assert_eq!(world_file.to_string().lines().count(), 6);
+    }
+}
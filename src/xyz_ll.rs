@@ -1,3 +1,6 @@
+use crate::consts::{WGS84_ECCENTRICITY_SQUARED, WGS84_FLATTENING, WGS84_SEMI_MAJOR_AXIS};
+use crate::math;
+
 /// Transforms (longitude, latitude) and altitude expressed in the arc degree method into (x, y, z) in the Cartesian coordinate system (EPSG:4979).
 ///
 /// 弧度法で表された(経度,緯度)と標高を直交座標系(EPSG:4979)の(x, y, z)に変換する
@@ -19,15 +22,17 @@
 pub fn llz2xyz(ll: (f64, f64), altitude: f64) -> (f64, f64, f64) {
     let (long, lat) = ll;
 
-    const A: f64 = 6378137.; // wgs84 長半径
-    const F: f64 = 1. / 298.257223563; // wgs84 扁平率
-    const E2: f64 = F * (2. - F); // 第一離心率の二乗
+    const A: f64 = WGS84_SEMI_MAJOR_AXIS;
+    const E2: f64 = WGS84_ECCENTRICITY_SQUARED;
+
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    let (sin_long, cos_long) = math::sin_cos(long);
 
-    let n = A / (1. - E2 * lat.sin().powf(2.)).sqrt(); // 卯酉線曲率半径
+    let n = A / (1. - E2 * sin_lat * sin_lat).sqrt(); // 卯酉線曲率半径
 
-    let x = (n + altitude) * lat.cos() * long.cos();
-    let y = (n + altitude) * lat.cos() * long.sin();
-    let z = (n * (1. - E2) + altitude) * lat.sin();
+    let x = (n + altitude) * cos_lat * cos_long;
+    let y = (n + altitude) * cos_lat * sin_long;
+    let z = (n * (1. - E2) + altitude) * sin_lat;
 
     (x, y, z)
 }
@@ -52,19 +57,19 @@ pub fn llz2xyz(ll: (f64, f64), altitude: f64) -> (f64, f64, f64) {
 pub fn xyz2llz(xyz: (f64, f64, f64)) -> ((f64, f64), f64) {
     let (x, y, z) = xyz;
 
-    const A: f64 = 6378137.; // wgs84 長半径
-    const F: f64 = 1. / 298.257223563; // wgs84 扁平率
-    const E2: f64 = F * (2. - F); // 第一離心率の二乗
+    const A: f64 = WGS84_SEMI_MAJOR_AXIS;
+    const E2: f64 = WGS84_ECCENTRICITY_SQUARED;
 
-    let p = (x.powf(2.) + y.powf(2.)).sqrt();
+    let p = (x * x + y * y).sqrt();
 
 
-    let mut lat = (z / (p * (1. - E2))).atan();
+    let mut lat = math::atan(z / (p * (1. - E2)));
 
     loop {
-        let n = A / (1. - E2 * lat.sin().powf(2.)).sqrt();
+        let (sin_lat, cos_lat) = math::sin_cos(lat);
+        let n = A / (1. - E2 * sin_lat * sin_lat).sqrt();
 
-        let next_lat = (z / (p - E2 * n * lat.cos())).atan();
+        let next_lat = math::atan(z / (p - E2 * n * cos_lat));
 
         if (lat - next_lat).abs() < 1e-12 {
             lat = next_lat;
@@ -73,12 +78,177 @@ pub fn xyz2llz(xyz: (f64, f64, f64)) -> ((f64, f64), f64) {
         lat = next_lat;
     }
 
-    let long = y.atan2(x);
-    let h = p / lat.cos() - A / (1. - E2 * lat.sin().powf(2.)).sqrt();
+    let long = math::atan2(y, x);
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    let h = p / cos_lat - A / (1. - E2 * sin_lat * sin_lat).sqrt();
 
     ((long, lat), h)
 }
 
+const E2_WGS84: f64 = WGS84_ECCENTRICITY_SQUARED;
+
+/// Converts the geodetic latitude expressed in the arc degree method to the geocentric latitude, the angle between the equatorial plane and the line from the center of the earth (WGS84).
+///
+/// 弧度法で表された測地緯度を、赤道面と地球中心からの直線がなす角である地心緯度に変換する(WGS84)。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::xyz_ll::{geocentric_to_geodetic_lat, geodetic_to_geocentric_lat};
+///
+/// let geocentric = geodetic_to_geocentric_lat(45_f64.to_radians());
+/// let geodetic = geocentric_to_geodetic_lat(geocentric);
+/// ```
+pub fn geodetic_to_geocentric_lat(lat: f64) -> f64 {
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    math::atan2((1. - E2_WGS84) * sin_lat, cos_lat)
+}
+
+/// Converts the geocentric latitude expressed in the arc degree method to the geodetic latitude (WGS84).
+///
+/// 弧度法で表された地心緯度を測地緯度に変換する(WGS84)。
+pub fn geocentric_to_geodetic_lat(lat: f64) -> f64 {
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    math::atan2(sin_lat, (1. - E2_WGS84) * cos_lat)
+}
+
+/// Converts the geodetic latitude expressed in the arc degree method to the parametric (reduced) latitude, the latitude on the auxiliary sphere of the equatorial radius (WGS84).
+///
+/// 弧度法で表された測地緯度を、赤道半径の補助球上の緯度である化成緯度に変換する(WGS84)。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::xyz_ll::{geodetic_to_parametric_lat, parametric_to_geodetic_lat};
+///
+/// let parametric = geodetic_to_parametric_lat(45_f64.to_radians());
+/// let geodetic = parametric_to_geodetic_lat(parametric);
+/// ```
+pub fn geodetic_to_parametric_lat(lat: f64) -> f64 {
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    math::atan2((1. - WGS84_FLATTENING) * sin_lat, cos_lat)
+}
+
+/// Converts the parametric (reduced) latitude expressed in the arc degree method to the geodetic latitude (WGS84).
+///
+/// 弧度法で表された化成緯度を測地緯度に変換する(WGS84)。
+pub fn parametric_to_geodetic_lat(lat: f64) -> f64 {
+    let (sin_lat, cos_lat) = math::sin_cos(lat);
+    math::atan2(sin_lat, (1. - WGS84_FLATTENING) * cos_lat)
+}
+
+/// Converts the geodetic latitude expressed in the arc degree method to the isometric latitude, the vertical coordinate of Mercator projection divided by the equatorial radius (WGS84).
+///
+/// 弧度法で表された測地緯度を、メルカトル図法の縦座標を赤道半径で割ったものである等長緯度に変換する(WGS84)。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::xyz_ll::{geodetic_to_isometric_lat, isometric_to_geodetic_lat};
+///
+/// let isometric = geodetic_to_isometric_lat(45_f64.to_radians());
+/// let geodetic = isometric_to_geodetic_lat(isometric);
+/// ```
+pub fn geodetic_to_isometric_lat(lat: f64) -> f64 {
+    let e = E2_WGS84.sqrt();
+    let sin_lat = math::sin(lat);
+
+    math::atanh(sin_lat) - e * math::atanh(e * sin_lat)
+}
+
+/// Converts the isometric latitude to the geodetic latitude expressed in the arc degree method (WGS84).
+///
+/// 等長緯度を弧度法で表された測地緯度に変換する(WGS84)。
+pub fn isometric_to_geodetic_lat(isometric_lat: f64) -> f64 {
+    let e = E2_WGS84.sqrt();
+
+    // 不動点反復(1回ごとに誤差がおよそe^2倍になる)
+    let mut lat = math::asin(math::tanh(isometric_lat));
+    for _ in 0..16 {
+        let next_lat = math::asin(math::tanh(isometric_lat + e * math::atanh(e * math::sin(lat))));
+        if (next_lat - lat).abs() < 1e-15 {
+            return next_lat;
+        }
+        lat = next_lat;
+    }
+
+    lat
+}
+
+/// Converts the geodetic latitude expressed in the arc degree method to the conformal latitude, the latitude on the sphere to which the ellipsoid is mapped conformally (WGS84).
+///
+/// 弧度法で表された測地緯度を、楕円体を等角に写した球上の緯度である等角緯度に変換する(WGS84)。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::xyz_ll::{conformal_to_geodetic_lat, geodetic_to_conformal_lat};
+///
+/// let conformal = geodetic_to_conformal_lat(45_f64.to_radians());
+/// let geodetic = conformal_to_geodetic_lat(conformal);
+/// ```
+pub fn geodetic_to_conformal_lat(lat: f64) -> f64 {
+    math::atan(math::sinh(geodetic_to_isometric_lat(lat)))
+}
+
+/// Converts the conformal latitude expressed in the arc degree method to the geodetic latitude (WGS84).
+///
+/// 弧度法で表された等角緯度を測地緯度に変換する(WGS84)。
+pub fn conformal_to_geodetic_lat(lat: f64) -> f64 {
+    isometric_to_geodetic_lat(math::atanh(math::sin(lat)))
+}
+
+/// q of the authalic latitude (Snyder, Map Projections: A Working Manual, (3-12))
+///
+/// 正積緯度のq(Snyder, Map Projections: A Working Manual, (3-12))
+fn authalic_q(sin_lat: f64) -> f64 {
+    let e = E2_WGS84.sqrt();
+
+    (1. - E2_WGS84) * (sin_lat / (1. - E2_WGS84 * sin_lat * sin_lat) + math::atanh(e * sin_lat) / e)
+}
+
+/// Converts the geodetic latitude expressed in the arc degree method to the authalic latitude, the latitude on the sphere of the same surface area to which the ellipsoid is mapped with areas preserved (WGS84).
+///
+/// 弧度法で表された測地緯度を、楕円体を面積を保って同じ表面積の球に写したときの緯度である正積緯度に変換する(WGS84)。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::xyz_ll::{authalic_to_geodetic_lat, geodetic_to_authalic_lat};
+///
+/// let authalic = geodetic_to_authalic_lat(45_f64.to_radians());
+/// let geodetic = authalic_to_geodetic_lat(authalic);
+/// ```
+pub fn geodetic_to_authalic_lat(lat: f64) -> f64 {
+    math::asin((authalic_q(math::sin(lat)) / authalic_q(1.)).clamp(-1., 1.))
+}
+
+/// Converts the authalic latitude expressed in the arc degree method to the geodetic latitude (WGS84).
+///
+/// 弧度法で表された正積緯度を測地緯度に変換する(WGS84)。
+pub fn authalic_to_geodetic_lat(lat: f64) -> f64 {
+    let q = authalic_q(1.) * math::sin(lat);
+
+    // Snyder (3-16)によるニュートン法
+    let mut phi = lat;
+    for _ in 0..16 {
+        let (sin_phi, cos_phi) = math::sin_cos(phi);
+        if cos_phi.abs() < 1e-12 {
+            return phi;
+        }
+
+        let w = 1. - E2_WGS84 * sin_phi * sin_phi;
+        let next_phi = phi + w * w / (2. * cos_phi) * (q - authalic_q(sin_phi)) / (1. - E2_WGS84);
+
+        if (next_phi - phi).abs() < 1e-15 {
+            return next_phi;
+        }
+        phi = next_phi;
+    }
+
+    phi
+}
+
 #[cfg(test)]
 mod tests {
     use close_to::assert_close_to;
@@ -107,4 +277,49 @@ mod tests {
         assert_close_to(lat.to_degrees(), 36., 3);
         assert_close_to(altitude, 100., 3);
     }
+
+    #[test]
+    fn auxiliary_latitudes_work() {
+        let lat = 45_f64.to_radians();
+
+        assert_close_to(geodetic_to_geocentric_lat(lat).to_degrees(), 44.80757678401804, 9);
+        assert_close_to(geodetic_to_parametric_lat(lat).to_degrees(), 44.903787849420226, 9);
+
+        for deg in [-90_f64, -36., 0., 12.5, 89.9, 90.] {
+            let lat = deg.to_radians();
+            assert_close_to(geocentric_to_geodetic_lat(geodetic_to_geocentric_lat(lat)), lat, 12);
+            assert_close_to(parametric_to_geodetic_lat(geodetic_to_parametric_lat(lat)), lat, 12);
+        }
+
+        // 地心緯度は直交座標から直接求めたものと一致する
+        let (x, y, z) = llz2xyz((0.3, lat), 0.);
+        assert_close_to(geodetic_to_geocentric_lat(lat), z.atan2(x.hypot(y)), 12);
+    }
+
+    #[test]
+    fn conformal_isometric_authalic_latitudes_work() {
+        for deg in [-89.9_f64, -36., 0., 12.5, 45., 89.9] {
+            let lat = deg.to_radians();
+            assert_close_to(isometric_to_geodetic_lat(geodetic_to_isometric_lat(lat)), lat, 12);
+            assert_close_to(conformal_to_geodetic_lat(geodetic_to_conformal_lat(lat)), lat, 12);
+            assert_close_to(authalic_to_geodetic_lat(geodetic_to_authalic_lat(lat)), lat, 12);
+        }
+
+        // 正積緯度: 赤道からの楕円体の面積は、同じ表面積の球における面積と一致する
+        const A: f64 = 6378137.;
+        let lat = 50_f64.to_radians();
+        let steps = 1000;
+        let h = lat / steps as f64;
+        let band = |phi: f64| {
+            let w = 1. - E2_WGS84 * phi.sin().powi(2);
+            // 子午線曲率半径 × 卯酉線曲率半径 × cos(緯度)
+            A * (1. - E2_WGS84) / w.powf(1.5) * A / w.sqrt() * phi.cos()
+        };
+        let area = (0..steps).fold(0., |acc, i| {
+            let (a, b) = (h * i as f64, h * (i + 1) as f64);
+            acc + h / 6. * (band(a) + 4. * band((a + b) / 2.) + band(b))
+        });
+        let rq2 = A * A * authalic_q(1.) / 2.;
+        assert_close_to(area / (rq2 * geodetic_to_authalic_lat(lat).sin()), 1., 9);
+    }
 }
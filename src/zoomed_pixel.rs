@@ -0,0 +1,160 @@
+//! Pixel coordinates whose Zoom level is fixed at compile time
+//!
+//! Zoomレベルをコンパイル時に固定したピクセル座標
+
+use std::fmt::{Display, Formatter};
+
+use crate::{ll2pixel, pixel2ll, pixel_at_zoom, Pixel, ZoomLv, LL};
+
+/// Structure representing pixel coordinates at the Zoom level `Z`.
+/// Mixing pixel coordinates of different Zoom levels becomes a type error.
+/// `Z` must be 24 or less, otherwise the construction fails to compile.
+///
+/// ZoomレベルZにおけるピクセル座標を表す構造体。
+/// 異なるZoomレベルのピクセル座標を混在させると型エラーになる。
+/// Zは24以下でなければならず、そうでない場合は構築時にコンパイルエラーとなる。
+///
+/// # Examples
+///
+/// ```
+/// use coordinate_transformer::zoomed_pixel::ZoomedPixel;
+///
+/// let pixel = ZoomedPixel::<21>::from_ll((139.7649308_f64.to_radians(), 35.6812405_f64.to_radians()));
+/// assert_eq!(pixel.to_tuple(), (476868027, 211407949));
+///
+/// let parent: ZoomedPixel<18> = pixel.at_zoom();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZoomedPixel<const Z: u8> {
+    x: u32,
+    y: u32,
+}
+
+impl<const Z: u8> ZoomedPixel<Z> {
+    const VALID_ZOOM: () = assert!(Z <= 24, "Zoom level must be 24 or less");
+
+    /// Create a new pixel coordinate
+    ///
+    /// ピクセル座標を新しく作成する
+    pub fn new(x: u32, y: u32) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_ZOOM;
+        Self { x, y }
+    }
+
+    /// Returns the Zoom level
+    ///
+    /// Zoomレベルを返す
+    pub fn zoom(&self) -> ZoomLv {
+        ZoomLv::parse(Z).unwrap()
+    }
+
+    /// Create from (longitude, latitude) expressed in the arc degree method
+    ///
+    /// 弧度法で表された(経度, 緯度)から作成する
+    pub fn from_ll(ll: (f64, f64)) -> Self {
+        let zoom = Self::new(0, 0).zoom();
+        let (x, y) = ll2pixel(ll, zoom);
+        Self::new(x, y)
+    }
+
+    /// Returns a tuple of (x, y)
+    ///
+    /// (x, y)をタプルで返す
+    pub fn to_tuple(&self) -> (u32, u32) {
+        (self.x, self.y)
+    }
+
+    /// Convert to a structure representing latitude and longitude
+    ///
+    /// 緯度経度を表す構造体に変換する
+    pub fn to_ll(&self) -> LL {
+        let (long, lat) = pixel2ll(self.to_tuple(), self.zoom());
+        LL::new(long, lat)
+    }
+
+    /// Convert to the pixel coordinates at the Zoom level `W`, following `pixel_at_zoom`
+    ///
+    /// `pixel_at_zoom`に従ってZoomレベルWのピクセル座標に変換する
+    pub fn at_zoom<const W: u8>(&self) -> ZoomedPixel<W> {
+        let to_zoom = ZoomedPixel::<W>::new(0, 0).zoom();
+        let (x, y) = pixel_at_zoom(self.to_tuple(), self.zoom(), to_zoom);
+        ZoomedPixel::new(x, y)
+    }
+}
+
+impl<const Z: u8> From<ZoomedPixel<Z>> for Pixel {
+    fn from(value: ZoomedPixel<Z>) -> Self {
+        Pixel::new(value.x, value.y, value.zoom())
+    }
+}
+
+/// Error returned when the Zoom level of a pixel coordinate does not match the expected one.
+///
+/// ピクセル座標のZoomレベルが期待するものと一致しない場合に返されるエラー。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoomMismatchError {
+    /// Expected Zoom level
+    ///
+    /// 期待するZoomレベル
+    pub expected: ZoomLv,
+    /// Actual Zoom level
+    ///
+    /// 実際のZoomレベル
+    pub actual: ZoomLv,
+}
+
+impl Display for ZoomMismatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected zoom level {}, found {}", self.expected as u8, self.actual as u8)
+    }
+}
+
+impl std::error::Error for ZoomMismatchError {}
+
+impl<const Z: u8> TryFrom<Pixel> for ZoomedPixel<Z> {
+    type Error = ZoomMismatchError;
+
+    fn try_from(value: Pixel) -> Result<Self, Self::Error> {
+        let (x, y) = value.to_tuple();
+        let pixel = Self::new(x, y);
+
+        if value.zoom() == pixel.zoom() {
+            Ok(pixel)
+        } else {
+            Err(ZoomMismatchError {
+                expected: pixel.zoom(),
+                actual: value.zoom(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoomed_pixel_works() {
+        let ll = (139.7649308_f64.to_radians(), 35.6812405_f64.to_radians());
+        let pixel = ZoomedPixel::<21>::from_ll(ll);
+
+        assert_eq!(pixel.to_tuple(), ll2pixel(ll, ZoomLv::Lv21));
+        assert_eq!(pixel.at_zoom::<18>().to_tuple(), ll2pixel(ll, ZoomLv::Lv18));
+        assert_eq!(Pixel::from(pixel), Pixel::new(476868027, 211407949, ZoomLv::Lv21));
+    }
+
+    #[test]
+    fn try_from_pixel_works() {
+        let pixel = Pixel::new(1, 2, ZoomLv::Lv3);
+
+        assert_eq!(ZoomedPixel::<3>::try_from(pixel), Ok(ZoomedPixel::new(1, 2)));
+        assert_eq!(
+            ZoomedPixel::<4>::try_from(pixel),
+            Err(ZoomMismatchError {
+                expected: ZoomLv::Lv4,
+                actual: ZoomLv::Lv3,
+            })
+        );
+    }
+}